@@ -0,0 +1,262 @@
+use crate::decl::{Decl, DefaultExportDecl, ModExport, ModuleExportName, NamedExportDecl};
+use crate::expr::{Lit, StringLit};
+use crate::{Ident, Program, ProgramPart};
+use std::borrow::Cow;
+
+/// A name a module makes available to importers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedName<'a> {
+    /// The binding this refers to, `None` for `export default` of
+    /// an expression or anonymous declaration and for `export * as`.
+    /// For a re-export this is the name in the other module
+    pub local: Option<&'a Ident<'a>>,
+    /// The name importers use, a string name like `export { a as 'b' }`
+    /// has its escapes applied
+    pub exported: Cow<'a, str>,
+    pub is_default: bool,
+    /// The module a re-export comes from
+    pub source: Option<&'a StringLit<'a>>,
+}
+
+/// Every name exported from `program`, in source order.
+///
+/// `export * from 'mod'` can't be listed without reading the other
+/// module so it is skipped, `module_dependencies` will include it
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::exported_names;
+/// // export { a as b };
+/// let program = Program::module(vec![ProgramPart::Decl(Decl::Export(Box::new(
+///     ModExport::Named(NamedExportDecl::Specifier(
+///         vec![ExportSpecifier {
+///             local: Ident::from("a"),
+///             exported: ModuleExportName::ident_from("b"),
+///         }],
+///         None,
+///     )),
+/// )))]);
+/// let names = exported_names(&program);
+/// assert_eq!(names[0].local.unwrap().name, "a");
+/// assert_eq!(names[0].exported, "b");
+/// ```
+pub fn exported_names<'a>(program: &'a Program<'a>) -> Vec<ExportedName<'a>> {
+    let mut names = Vec::new();
+    for part in program.parts() {
+        let export = match part {
+            ProgramPart::Decl(Decl::Export(export)) => export,
+            _ => continue,
+        };
+        match &**export {
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                for local in declared_names(decl) {
+                    names.push(ExportedName {
+                        local: Some(local),
+                        exported: Cow::Borrowed(&local.name),
+                        is_default: false,
+                        source: None,
+                    });
+                }
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                let source = source.as_ref().and_then(|source| string(&source.source));
+                for spec in specs {
+                    if let Some(exported) = export_name(&spec.exported) {
+                        names.push(ExportedName {
+                            local: Some(&spec.local),
+                            is_default: exported == "default",
+                            exported,
+                            source,
+                        });
+                    }
+                }
+            }
+            ModExport::Default(default) => {
+                let local = match default {
+                    DefaultExportDecl::Decl(decl) => declared_names(decl).into_iter().next(),
+                    DefaultExportDecl::Expr(_) => None,
+                };
+                names.push(ExportedName {
+                    local,
+                    exported: Cow::Borrowed("default"),
+                    is_default: true,
+                    source: None,
+                });
+            }
+            ModExport::All(Some(name), source) => {
+                if let Some(exported) = export_name(name) {
+                    names.push(ExportedName {
+                        local: None,
+                        is_default: exported == "default",
+                        exported,
+                        source: string(&source.source),
+                    });
+                }
+            }
+            ModExport::All(None, _) => {}
+        }
+    }
+    names
+}
+
+fn declared_names<'a>(decl: &'a Decl<'a>) -> Vec<&'a Ident<'a>> {
+    match decl {
+        Decl::Var(_, decls) => decls.iter().flat_map(|decl| decl.names()).collect(),
+        Decl::Func(func) => func.id.iter().collect(),
+        Decl::Class(class) => class.id.iter().collect(),
+        Decl::Import(_) | Decl::Export(_) => Vec::new(),
+    }
+}
+
+fn export_name<'a>(name: &'a ModuleExportName<'a>) -> Option<Cow<'a, str>> {
+    match name {
+        ModuleExportName::Ident(ident) => Some(Cow::Borrowed(&ident.name)),
+        ModuleExportName::Str(lit) => string(lit).map(|s| match s {
+            StringLit::Single(raw) | StringLit::Double(raw) if !raw.contains('\\') => {
+                Cow::Borrowed(&**raw)
+            }
+            _ => s.cooked(),
+        }),
+    }
+}
+
+fn string<'a>(lit: &'a Lit<'a>) -> Option<&'a StringLit<'a>> {
+    match lit {
+        Lit::String(s) => Some(s),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decl::{ExportSource, ExportSpecifier, VarDecl};
+    use crate::expr::Expr;
+    use crate::pat::Pat;
+    use crate::{Class, Func, FuncBody, VarKind};
+
+    fn export<'a>(export: ModExport<'a>) -> ProgramPart<'a> {
+        ProgramPart::Decl(Decl::Export(Box::new(export)))
+    }
+
+    fn spec<'a>(local: &'a str, exported: ModuleExportName<'a>) -> ExportSpecifier<'a> {
+        ExportSpecifier {
+            local: Ident::from(local),
+            exported,
+        }
+    }
+
+    fn summary<'a>(program: &'a Program<'a>) -> Vec<(Option<&'a str>, String, bool, bool)> {
+        exported_names(program)
+            .into_iter()
+            .map(|name| {
+                let local = name.local.map(|id| &*id.name);
+                let exported = name.exported.into_owned();
+                (local, exported, name.is_default, name.source.is_some())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_forms() {
+        // export const x = 1, { ...y } = o;
+        // export function f() {}
+        // export { a as b, c as 'string name' };
+        // export { d as default } from 'd';
+        // export * from 'all';
+        // export * as ns from 'ns';
+        // export default class C {}
+        let vars = Decl::Var(
+            VarKind::Const,
+            vec![
+                VarDecl {
+                    id: Pat::ident_from("x"),
+                    init: Some(Expr::Lit(Lit::number_from("1"))),
+                },
+                VarDecl {
+                    id: Pat::Obj(vec![crate::pat::ObjPatPart::Rest(Box::new(Pat::ident_from(
+                        "y",
+                    )))]),
+                    init: Some(Expr::ident_from("o")),
+                },
+            ],
+        );
+        let func = Func {
+            id: Some(Ident::from("f")),
+            params: vec![],
+            body: FuncBody(vec![]),
+            generator: false,
+            is_async: false,
+        };
+        let class = Class {
+            id: Some(Ident::from("C")),
+            super_class: None,
+            body: Default::default(),
+            decorators: vec![],
+        };
+        let program = Program::module(vec![
+            export(ModExport::Named(NamedExportDecl::Decl(vars))),
+            export(ModExport::Named(NamedExportDecl::Decl(Decl::Func(func)))),
+            export(ModExport::Named(NamedExportDecl::Specifier(
+                vec![
+                    spec("a", ModuleExportName::ident_from("b")),
+                    spec("c", ModuleExportName::str_from("string name")),
+                ],
+                None,
+            ))),
+            export(ModExport::Named(NamedExportDecl::Specifier(
+                vec![spec("d", ModuleExportName::ident_from("default"))],
+                Some(ExportSource::new(Lit::single_string_from("d"))),
+            ))),
+            export(ModExport::All(
+                None,
+                ExportSource::new(Lit::single_string_from("all")),
+            )),
+            export(ModExport::All(
+                Some(ModuleExportName::ident_from("ns")),
+                ExportSource::new(Lit::single_string_from("ns")),
+            )),
+            export(ModExport::Default(DefaultExportDecl::Decl(Decl::Class(class)))),
+        ]);
+        assert_eq!(
+            summary(&program),
+            vec![
+                (Some("x"), "x".into(), false, false),
+                (Some("y"), "y".into(), false, false),
+                (Some("f"), "f".into(), false, false),
+                (Some("a"), "b".into(), false, false),
+                (Some("c"), "string name".into(), false, false),
+                (Some("d"), "default".into(), true, true),
+                (None, "ns".into(), false, true),
+                (Some("C"), "default".into(), true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn anonymous_default() {
+        // export default a;
+        let program = Program::module(vec![export(ModExport::Default(DefaultExportDecl::Expr(
+            Expr::ident_from("a"),
+        )))]);
+        assert_eq!(summary(&program), vec![(None, "default".into(), true, false)]);
+    }
+
+    #[test]
+    fn string_names_are_cooked() {
+        // export { a as "b\u0063", d as "\u{64}efault" };
+        let program = Program::module(vec![export(ModExport::Named(NamedExportDecl::Specifier(
+            vec![
+                spec("a", ModuleExportName::str_from("b\\u0063")),
+                spec("d", ModuleExportName::str_from("\\u{64}efault")),
+            ],
+            None,
+        )))]);
+        assert_eq!(
+            summary(&program),
+            vec![
+                (Some("a"), "bc".into(), false, false),
+                (Some("d"), "default".into(), true, false),
+            ]
+        );
+    }
+}
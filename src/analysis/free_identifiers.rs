@@ -0,0 +1,491 @@
+use crate::decl::{Decl, VarDecl};
+use crate::expr::{
+    ArrowFuncBody, ArrowFuncExpr, AssignLeft, Expr, MemberProp, Prop, PropKey, PropValue,
+};
+use crate::pat::{ArrayPatPart, ObjPatPart, Pat};
+use crate::stmt::*;
+use crate::visit::*;
+use crate::{Class, FieldDef, Func, FuncArg, Ident, MethodDef, ProgramPart, VarKind};
+
+/// Find every identifier `expr` refers to that isn't declared
+/// inside of it, each name is only included the first time
+/// it is referenced.
+///
+/// Function and arrow parameters, declarations in function
+/// bodies and blocks, catch parameters, `arguments` and the name
+/// of a function or class expression are all bindings
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::free_identifiers;
+/// // x => x + y
+/// let arrow = Expr::ArrowFunc(ArrowFuncExpr::new(
+///     vec![FuncArg::Pat(Pat::ident_from("x"))],
+///     ArrowFuncBody::Expr(Box::new(Expr::Binary(BinaryExpr::new(
+///         Expr::ident_from("x"),
+///         BinaryOp::Plus,
+///         Expr::ident_from("y"),
+///     )))),
+///     false,
+/// ));
+/// assert_eq!(free_identifiers(&arrow), vec![&Ident::from("y")]);
+/// ```
+pub fn free_identifiers<'a>(expr: &'a Expr<'a>) -> Vec<&'a Ident<'a>> {
+    let mut finder = FreeIdents::default();
+    finder.visit_expr(expr);
+    finder.free
+}
+
+#[derive(Default)]
+struct FreeIdents<'a> {
+    scopes: Vec<Vec<&'a str>>,
+    free: Vec<&'a Ident<'a>>,
+}
+
+impl<'a> FreeIdents<'a> {
+    fn reference(&mut self, ident: &'a Ident<'a>) {
+        let name = &*ident.name;
+        let bound = self.scopes.iter().any(|scope| scope.contains(&name));
+        if !bound && !self.free.iter().any(|free| free.name == name) {
+            self.free.push(ident);
+        }
+    }
+
+    fn in_scope(&mut self, names: Vec<&'a str>, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(names);
+        f(self);
+        self.scopes.pop();
+    }
+
+    /// Visit the expressions in a pattern, the identifiers
+    /// are only references when it is an assignment target
+    fn pat_exprs(&mut self, pat: &'a Pat<'a>, is_target: bool) {
+        match pat {
+            Pat::Ident(ident) => {
+                if is_target {
+                    self.reference(ident)
+                }
+            }
+            Pat::Obj(parts) => {
+                for part in parts {
+                    match part {
+                        ObjPatPart::Assign(prop) => self.pat_prop(prop, is_target),
+                        ObjPatPart::Rest(pat) => self.pat_exprs(pat, is_target),
+                    }
+                }
+            }
+            Pat::Array(parts) => {
+                for part in parts.iter().flatten() {
+                    match part {
+                        ArrayPatPart::Pat(pat) => self.pat_exprs(pat, is_target),
+                        ArrayPatPart::Expr(expr) => self.target_expr(expr, is_target),
+                    }
+                }
+            }
+            Pat::RestElement(pat) => self.pat_exprs(pat, is_target),
+            Pat::Assign(assign) => {
+                self.pat_exprs(&assign.left, is_target);
+                self.visit_expr(&assign.right);
+            }
+        }
+    }
+
+    fn pat_prop(&mut self, prop: &'a Prop<'a>, is_target: bool) {
+        if prop.computed {
+            self.visit_prop_key(&prop.key);
+        }
+        match &prop.value {
+            PropValue::Pat(pat) => self.pat_exprs(pat, is_target),
+            PropValue::Expr(expr) => self.target_expr(expr, is_target),
+            PropValue::None => match &prop.key {
+                PropKey::Pat(pat) => self.pat_exprs(pat, is_target),
+                PropKey::Expr(expr) if !prop.computed => self.target_expr(expr, is_target),
+                _ => {}
+            },
+        }
+    }
+
+    /// An expression in the place of a pattern, a plain
+    /// identifier here is a binding unless it is a target
+    fn target_expr(&mut self, expr: &'a Expr<'a>, is_target: bool) {
+        match expr {
+            Expr::Ident(_) if !is_target => {}
+            _ => self.visit_expr(expr),
+        }
+    }
+
+    fn params(&mut self, params: &'a [FuncArg<'a>]) {
+        for param in params {
+            match param {
+                FuncArg::Pat(pat) => self.pat_exprs(pat, false),
+                FuncArg::Expr(Expr::Ident(_)) => {}
+                FuncArg::Expr(Expr::Assign(assign)) => {
+                    match &assign.left {
+                        AssignLeft::Pat(pat) => self.pat_exprs(pat, false),
+                        AssignLeft::Expr(expr) => self.target_expr(expr, false),
+                    }
+                    self.visit_expr(&assign.right);
+                }
+                FuncArg::Expr(expr) => self.visit_expr(expr),
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for FreeIdents<'a> {
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        match expr {
+            Expr::Ident(ident) => self.reference(ident),
+            _ => walk_expr(self, expr),
+        }
+    }
+    fn visit_member_prop(&mut self, prop: &'a MemberProp<'a>) {
+        if let MemberProp::Computed(expr) = prop {
+            self.visit_expr(expr);
+        }
+    }
+    // a pattern reached through the walker is always a binding,
+    // assignment targets are handled before getting here
+    fn visit_pat(&mut self, pat: &'a Pat<'a>) {
+        self.pat_exprs(pat, false)
+    }
+    fn visit_assign_left(&mut self, left: &'a AssignLeft<'a>) {
+        match left {
+            AssignLeft::Pat(pat) => self.pat_exprs(pat, true),
+            AssignLeft::Expr(expr) => self.visit_expr(expr),
+        }
+    }
+    fn visit_loop_left(&mut self, left: &'a LoopLeft<'a>) {
+        match left {
+            LoopLeft::Pat(pat) => self.pat_exprs(pat, true),
+            _ => walk_loop_left(self, left),
+        }
+    }
+    fn visit_var_decl(&mut self, decl: &'a VarDecl<'a>) {
+        self.pat_exprs(&decl.id, false);
+        if let Some(init) = &decl.init {
+            self.visit_expr(init);
+        }
+    }
+    fn visit_prop(&mut self, prop: &'a Prop<'a>) {
+        for decorator in &prop.decorators {
+            self.visit_decorator(decorator);
+        }
+        if prop.computed {
+            self.visit_prop_key(&prop.key);
+        } else if prop.short_hand && prop.value == PropValue::None {
+            // `{ x }` refers to `x`
+            if let PropKey::Expr(expr) = &prop.key {
+                self.visit_expr(expr);
+            }
+        }
+        self.visit_prop_value(&prop.value);
+    }
+    fn visit_method_def(&mut self, method: &'a MethodDef<'a>) {
+        for decorator in &method.decorators {
+            self.visit_decorator(decorator);
+        }
+        if method.computed {
+            self.visit_prop_key(&method.key);
+        }
+        self.visit_func(&method.value);
+    }
+    fn visit_field_def(&mut self, field: &'a FieldDef<'a>) {
+        for decorator in &field.decorators {
+            self.visit_decorator(decorator);
+        }
+        if field.computed {
+            self.visit_prop_key(&field.key);
+        }
+        if let Some(value) = &field.value {
+            self.visit_expr(value);
+        }
+    }
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        let names = class.id.iter().map(|id| &*id.name).collect();
+        self.in_scope(names, |v| walk_class(v, class))
+    }
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        let mut names = vec!["arguments"];
+        names.extend(func.id.iter().map(|id| &*id.name));
+        param_names(&func.params, &mut names);
+        body_names(&func.body.0, &mut names);
+        self.in_scope(names, |v| {
+            v.params(&func.params);
+            for part in &func.body.0 {
+                v.visit_program_part(part);
+            }
+        })
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        let mut names = Vec::new();
+        param_names(&expr.params, &mut names);
+        if let ArrowFuncBody::FuncBody(body) = &expr.body {
+            body_names(&body.0, &mut names);
+        }
+        self.in_scope(names, |v| {
+            v.params(&expr.params);
+            match &expr.body {
+                ArrowFuncBody::FuncBody(body) => {
+                    for part in &body.0 {
+                        v.visit_program_part(part);
+                    }
+                }
+                ArrowFuncBody::Expr(expr) => v.visit_expr(expr),
+            }
+        })
+    }
+    fn visit_block_stmt(&mut self, block: &'a BlockStmt<'a>) {
+        let mut names = Vec::new();
+        lexical_names(&block.0, &mut names);
+        self.in_scope(names, |v| walk_block_stmt(v, block))
+    }
+    fn visit_switch_stmt(&mut self, stmt: &'a SwitchStmt<'a>) {
+        self.visit_expr(&stmt.discriminant);
+        let mut names = Vec::new();
+        for case in &stmt.cases {
+            lexical_names(&case.consequent, &mut names);
+        }
+        self.in_scope(names, |v| {
+            for case in &stmt.cases {
+                v.visit_switch_case(case);
+            }
+        })
+    }
+    fn visit_catch_clause(&mut self, clause: &'a CatchClause<'a>) {
+        let names = clause
+            .param
+            .iter()
+            .flat_map(Pat::bound_names)
+            .map(|id| &*id.name)
+            .collect();
+        self.in_scope(names, |v| walk_catch_clause(v, clause))
+    }
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt<'a>) {
+        let mut names = Vec::new();
+        if let Some(LoopInit::Variable(kind, decls)) = &stmt.init {
+            if *kind != VarKind::Var {
+                decl_names(decls, &mut names);
+            }
+        }
+        self.in_scope(names, |v| walk_for_stmt(v, stmt))
+    }
+    fn visit_for_in_stmt(&mut self, stmt: &'a ForInStmt<'a>) {
+        let names = loop_left_names(&stmt.left);
+        self.in_scope(names, |v| walk_for_in_stmt(v, stmt))
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &'a ForOfStmt<'a>) {
+        let names = loop_left_names(&stmt.left);
+        self.in_scope(names, |v| walk_for_of_stmt(v, stmt))
+    }
+}
+
+pub(crate) fn param_names<'a>(params: &'a [FuncArg<'a>], names: &mut Vec<&'a str>) {
+    for param in params {
+        match param {
+            FuncArg::Pat(pat) => names.extend(pat.bound_names().into_iter().map(|id| &*id.name)),
+            FuncArg::Expr(Expr::Ident(id)) => names.push(&id.name),
+            FuncArg::Expr(Expr::Assign(assign)) => match &assign.left {
+                AssignLeft::Pat(pat) => {
+                    names.extend(pat.bound_names().into_iter().map(|id| &*id.name))
+                }
+                AssignLeft::Expr(expr) => {
+                    if let Expr::Ident(id) = &**expr {
+                        names.push(&id.name)
+                    }
+                }
+            },
+            FuncArg::Expr(_) => {}
+        }
+    }
+}
+
+pub(crate) fn decl_names<'a>(decls: &'a [VarDecl<'a>], names: &mut Vec<&'a str>) {
+    for decl in decls {
+        names.extend(decl.id.bound_names().into_iter().map(|id| &*id.name));
+    }
+}
+
+pub(crate) fn loop_left_names<'a>(left: &'a LoopLeft<'a>) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    if let LoopLeft::Variable(kind, decl) = left {
+        if *kind != VarKind::Var {
+            decl_names(std::slice::from_ref(decl), &mut names);
+        }
+    }
+    names
+}
+
+/// Everything declared at the top level of a function body,
+/// including any `var` in a nested block
+pub(crate) fn body_names<'a>(parts: &'a [ProgramPart<'a>], names: &mut Vec<&'a str>) {
+    lexical_names(parts, names);
+    for part in parts {
+        match part {
+            ProgramPart::Decl(Decl::Var(VarKind::Var, decls)) => decl_names(decls, names),
+            ProgramPart::Stmt(stmt) => var_names(stmt, names),
+            _ => {}
+        }
+    }
+}
+
+/// The `let`, `const`, `class` and `function` declarations
+/// directly in `parts`
+pub(crate) fn lexical_names<'a>(parts: &'a [ProgramPart<'a>], names: &mut Vec<&'a str>) {
+    for part in parts {
+        match part {
+            ProgramPart::Decl(Decl::Var(kind, decls)) if *kind != VarKind::Var => {
+                decl_names(decls, names)
+            }
+            ProgramPart::Decl(Decl::Func(Func { id: Some(id), .. }))
+            | ProgramPart::Decl(Decl::Class(Class { id: Some(id), .. })) => names.push(&id.name),
+            _ => {}
+        }
+    }
+}
+
+/// The `var` declarations in `stmt` which are hoisted
+/// out of any blocks
+fn var_names<'a>(stmt: &'a Stmt<'a>, names: &mut Vec<&'a str>) {
+    let parts_names = |parts: &'a [ProgramPart<'a>], names: &mut Vec<&'a str>| {
+        for part in parts {
+            match part {
+                ProgramPart::Decl(Decl::Var(VarKind::Var, decls)) => decl_names(decls, names),
+                ProgramPart::Stmt(stmt) => var_names(stmt, names),
+                _ => {}
+            }
+        }
+    };
+    match stmt {
+        Stmt::Var(decls) => decl_names(decls, names),
+        Stmt::Block(block) => parts_names(&block.0, names),
+        Stmt::With(stmt) => var_names(&stmt.body, names),
+        Stmt::Labeled(stmt) => var_names(&stmt.body, names),
+        Stmt::If(stmt) => {
+            var_names(&stmt.consequent, names);
+            if let Some(alt) = &stmt.alternate {
+                var_names(alt, names);
+            }
+        }
+        Stmt::Switch(stmt) => {
+            for case in &stmt.cases {
+                parts_names(&case.consequent, names);
+            }
+        }
+        Stmt::Try(stmt) => {
+            parts_names(&stmt.block.0, names);
+            if let Some(handler) = &stmt.handler {
+                parts_names(&handler.body.0, names);
+            }
+            if let Some(finalizer) = &stmt.finalizer {
+                parts_names(&finalizer.0, names);
+            }
+        }
+        Stmt::While(stmt) => var_names(&stmt.body, names),
+        Stmt::DoWhile(stmt) => var_names(&stmt.body, names),
+        Stmt::For(stmt) => {
+            if let Some(LoopInit::Variable(VarKind::Var, decls)) = &stmt.init {
+                decl_names(decls, names);
+            }
+            var_names(&stmt.body, names);
+        }
+        Stmt::ForIn(ForInStmt { left, body, .. }) | Stmt::ForOf(ForOfStmt { left, body, .. }) => {
+            if let LoopLeft::Variable(VarKind::Var, decl) = left {
+                decl_names(std::slice::from_ref(decl), names);
+            }
+            var_names(body, names);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{BinaryExpr, CallExpr, Lit};
+    use crate::{BinaryOp, FuncBody};
+
+    fn add<'a>(left: &'a str, right: &'a str) -> Expr<'a> {
+        Expr::Binary(BinaryExpr::new(
+            Expr::ident_from(left),
+            BinaryOp::Plus,
+            Expr::ident_from(right),
+        ))
+    }
+
+    fn names<'a>(expr: &'a Expr<'a>) -> Vec<&'a str> {
+        free_identifiers(expr).into_iter().map(|id| &*id.name).collect()
+    }
+
+    #[test]
+    fn arrow_params() {
+        // x => x + y
+        let arrow = Expr::ArrowFunc(ArrowFuncExpr::new(
+            vec![FuncArg::Pat(Pat::ident_from("x"))],
+            ArrowFuncBody::Expr(Box::new(add("x", "y"))),
+            false,
+        ));
+        assert_eq!(names(&arrow), vec!["y"]);
+    }
+
+    #[test]
+    fn shadowing_iife() {
+        // (function (a) { var b = a + c; { let c = 1; c + d; } return b + a; })(a)
+        let block = Stmt::Block(BlockStmt(vec![
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Let,
+                vec![VarDecl {
+                    id: Pat::ident_from("c"),
+                    init: Some(Expr::Lit(Lit::number_from("1"))),
+                }],
+            )),
+            ProgramPart::Stmt(Stmt::Expr(add("c", "d"))),
+        ]));
+        let func = Func::new(
+            None,
+            vec![FuncArg::Pat(Pat::ident_from("a"))],
+            FuncBody(vec![
+                ProgramPart::Decl(Decl::Var(
+                    VarKind::Var,
+                    vec![VarDecl {
+                        id: Pat::ident_from("b"),
+                        init: Some(add("a", "c")),
+                    }],
+                )),
+                ProgramPart::Stmt(block),
+                ProgramPart::Stmt(Stmt::Return(Some(add("b", "a")))),
+            ]),
+            false,
+            false,
+        );
+        let iife = Expr::Call(CallExpr::new(Expr::Func(func), vec![Expr::ident_from("a")]));
+        // the parameter `a` only shadows inside of the function
+        assert_eq!(names(&iife), vec!["c", "d", "a"]);
+    }
+
+    #[test]
+    fn keys_are_not_references() {
+        // ({ a: b, c, [d]: e.f })
+        let prop = |key: &'static str, value: Option<Expr<'static>>, computed| {
+            crate::expr::ObjProp::Prop(Prop {
+                key: PropKey::Expr(Expr::ident_from(key)),
+                short_hand: value.is_none(),
+                value: value.map(PropValue::Expr).unwrap_or(PropValue::None),
+                kind: crate::PropKind::Init,
+                method: false,
+                computed,
+                is_static: false,
+                decorators: vec![],
+            })
+        };
+        let member = crate::expr::MemberExpr::new(
+            Expr::ident_from("e"),
+            MemberProp::ident_from("f"),
+        );
+        let obj = Expr::Obj(vec![
+            prop("a", Some(Expr::ident_from("b")), false),
+            prop("c", None, false),
+            prop("d", Some(Expr::Member(member)), true),
+        ]);
+        assert_eq!(names(&obj), vec!["b", "c", "d", "e"]);
+    }
+}
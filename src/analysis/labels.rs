@@ -0,0 +1,426 @@
+use crate::expr::ArrowFuncExpr;
+use crate::stmt::*;
+use crate::visit::*;
+use crate::{Class, Func, Ident, Program};
+
+/// Which statement made the jump
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumpKind {
+    Break,
+    Continue,
+}
+
+/// Why a jump doesn't have a target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnresolvedReason {
+    /// No enclosing statement has this label
+    /// ```js
+    /// while (true) { break outer; }
+    /// ```
+    UndefinedLabel,
+    /// A `continue` whose label is on a statement
+    /// that isn't a loop
+    /// ```js
+    /// block: { continue block; }
+    /// ```
+    NotALoop,
+    /// An unlabeled `break` outside of a loop or switch,
+    /// or an unlabeled `continue` outside of a loop
+    NoTarget,
+}
+
+/// A `break` or `continue` that doesn't refer to
+/// any enclosing statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedJump<'a> {
+    pub kind: JumpKind,
+    pub label: Option<&'a Ident<'a>>,
+    pub reason: UnresolvedReason,
+}
+
+/// Find every `break` or `continue` in `program` that has no
+/// valid target.
+///
+/// Labels don't cross function boundaries, so a `break` inside of
+/// a function can't target a label outside of it
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::{resolve_labels, UnresolvedReason};
+/// // break outer;
+/// let program = Program::script(vec![
+///     ProgramPart::Stmt(Stmt::Break(Some(Ident::from("outer")))),
+/// ]);
+/// let jumps = resolve_labels(&program);
+/// assert_eq!(jumps[0].reason, UnresolvedReason::UndefinedLabel);
+/// ```
+pub fn resolve_labels<'a>(program: &'a Program<'a>) -> Vec<UnresolvedJump<'a>> {
+    let mut resolver = LabelResolver::default();
+    resolver.visit_program(program);
+    resolver.jumps
+}
+
+/// The jump targets that are in scope, reset at
+/// every function boundary
+#[derive(Default)]
+struct Targets<'a> {
+    /// Each label and if it is on a loop
+    labels: Vec<(&'a str, bool)>,
+    loops: usize,
+    switches: usize,
+}
+
+#[derive(Default)]
+struct LabelResolver<'a> {
+    targets: Targets<'a>,
+    jumps: Vec<UnresolvedJump<'a>>,
+}
+
+impl<'a> LabelResolver<'a> {
+    fn jump(&mut self, kind: JumpKind, label: &'a Option<Ident<'a>>) {
+        let reason = match label {
+            Some(label) => {
+                let target = self.targets.labels.iter().rev().find(|(name, _)| *name == label.name);
+                match target {
+                    None => Some(UnresolvedReason::UndefinedLabel),
+                    Some((_, false)) if kind == JumpKind::Continue => {
+                        Some(UnresolvedReason::NotALoop)
+                    }
+                    Some(_) => None,
+                }
+            }
+            None => {
+                let has_target = match kind {
+                    JumpKind::Break => self.targets.loops + self.targets.switches > 0,
+                    JumpKind::Continue => self.targets.loops > 0,
+                };
+                if has_target {
+                    None
+                } else {
+                    Some(UnresolvedReason::NoTarget)
+                }
+            }
+        };
+        if let Some(reason) = reason {
+            self.jumps.push(UnresolvedJump {
+                kind,
+                label: label.as_ref(),
+                reason,
+            });
+        }
+    }
+
+    fn in_loop(&mut self, f: impl FnOnce(&mut Self)) {
+        self.targets.loops += 1;
+        f(self);
+        self.targets.loops -= 1;
+    }
+
+    fn new_function(&mut self, f: impl FnOnce(&mut Self)) {
+        let outer = std::mem::take(&mut self.targets);
+        f(self);
+        self.targets = outer;
+    }
+}
+
+/// If the statement a label is attached to is a loop,
+/// looking through any other labels
+fn labels_loop(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::Labeled(labeled) => labels_loop(&labeled.body),
+        _ => stmt.is_loop(),
+    }
+}
+
+impl<'a> Visitor<'a> for LabelResolver<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt<'a>) {
+        match stmt {
+            Stmt::Break(label) => self.jump(JumpKind::Break, label),
+            Stmt::Continue(label) => self.jump(JumpKind::Continue, label),
+            _ => walk_stmt(self, stmt),
+        }
+    }
+    fn visit_labeled_stmt(&mut self, stmt: &'a LabeledStmt<'a>) {
+        self.targets.labels.push((&stmt.label.name, labels_loop(&stmt.body)));
+        walk_labeled_stmt(self, stmt);
+        self.targets.labels.pop();
+    }
+    fn visit_switch_stmt(&mut self, stmt: &'a SwitchStmt<'a>) {
+        self.targets.switches += 1;
+        walk_switch_stmt(self, stmt);
+        self.targets.switches -= 1;
+    }
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt<'a>) {
+        self.in_loop(|v| walk_while_stmt(v, stmt))
+    }
+    fn visit_do_while_stmt(&mut self, stmt: &'a DoWhileStmt<'a>) {
+        self.in_loop(|v| walk_do_while_stmt(v, stmt))
+    }
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt<'a>) {
+        self.in_loop(|v| walk_for_stmt(v, stmt))
+    }
+    fn visit_for_in_stmt(&mut self, stmt: &'a ForInStmt<'a>) {
+        self.in_loop(|v| walk_for_in_stmt(v, stmt))
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &'a ForOfStmt<'a>) {
+        self.in_loop(|v| walk_for_of_stmt(v, stmt))
+    }
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        self.new_function(|v| walk_func(v, func))
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        self.new_function(|v| walk_arrow_func_expr(v, expr))
+    }
+    // static blocks are a boundary just like functions
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        self.new_function(|v| walk_class(v, class))
+    }
+}
+
+/// Find every label in `program` that is already the label of
+/// an enclosing statement, which is an early error.
+///
+/// Sibling statements can reuse a label freely and, like jumps,
+/// labels are reset at every function boundary
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::duplicate_labels;
+/// // a: a: ;
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Labeled(LabeledStmt {
+///     label: Ident::from("a"),
+///     body: Box::new(Stmt::Labeled(LabeledStmt {
+///         label: Ident::from("a"),
+///         body: Box::new(Stmt::Empty),
+///     })),
+/// }))]);
+/// assert_eq!(duplicate_labels(&program), vec![&Ident::from("a")]);
+/// ```
+pub fn duplicate_labels<'a>(program: &'a Program<'a>) -> Vec<&'a Ident<'a>> {
+    let mut finder = DuplicateLabels::default();
+    finder.visit_program(program);
+    finder.duplicates
+}
+
+#[derive(Default)]
+struct DuplicateLabels<'a> {
+    /// The labels of every enclosing statement
+    labels: Vec<&'a str>,
+    duplicates: Vec<&'a Ident<'a>>,
+}
+
+impl<'a> DuplicateLabels<'a> {
+    fn new_function(&mut self, f: impl FnOnce(&mut Self)) {
+        let outer = std::mem::take(&mut self.labels);
+        f(self);
+        self.labels = outer;
+    }
+}
+
+impl<'a> Visitor<'a> for DuplicateLabels<'a> {
+    fn visit_labeled_stmt(&mut self, stmt: &'a LabeledStmt<'a>) {
+        if self.labels.contains(&&*stmt.label.name) {
+            self.duplicates.push(&stmt.label);
+        }
+        self.labels.push(&stmt.label.name);
+        walk_labeled_stmt(self, stmt);
+        self.labels.pop();
+    }
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        self.new_function(|v| walk_func(v, func))
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        self.new_function(|v| walk_arrow_func_expr(v, expr))
+    }
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        self.new_function(|v| walk_class(v, class))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::Expr;
+    use crate::{FuncBody, ProgramPart};
+
+    fn while_x<'a>(body: Vec<ProgramPart<'a>>) -> Stmt<'a> {
+        Stmt::While(WhileStmt {
+            test: Expr::ident_from("x"),
+            body: Box::new(Stmt::Block(BlockStmt(body))),
+        })
+    }
+
+    fn labeled<'a>(label: &'a str, body: Stmt<'a>) -> Stmt<'a> {
+        Stmt::Labeled(LabeledStmt {
+            label: Ident::from(label),
+            body: Box::new(body),
+        })
+    }
+
+    fn stmt<'a>(stmt: Stmt<'a>) -> ProgramPart<'a> {
+        ProgramPart::Stmt(stmt)
+    }
+
+    fn label<'a>(name: &'a str) -> Option<Ident<'a>> {
+        Some(Ident::from(name))
+    }
+
+    #[test]
+    fn valid_labeled_loop() {
+        // outer: while (x) {
+        //     inner: while (x) {
+        //         continue outer;
+        //         break inner;
+        //     }
+        //     switch (x) { default: break; }
+        //     continue;
+        // }
+        let program = Program::script(vec![stmt(labeled(
+            "outer",
+            while_x(vec![
+                stmt(labeled(
+                    "inner",
+                    while_x(vec![
+                        stmt(Stmt::Continue(label("outer"))),
+                        stmt(Stmt::Break(label("inner"))),
+                    ]),
+                )),
+                stmt(Stmt::Switch(SwitchStmt {
+                    discriminant: Expr::ident_from("x"),
+                    cases: vec![SwitchCase {
+                        test: None,
+                        consequent: vec![stmt(Stmt::Break(None))],
+                    }],
+                })),
+                stmt(Stmt::Continue(None)),
+            ]),
+        ))]);
+        assert_eq!(resolve_labels(&program), vec![]);
+    }
+
+    #[test]
+    fn dangling_break() {
+        // while (x) { break outer; }
+        let program = Program::script(vec![stmt(while_x(vec![stmt(Stmt::Break(label(
+            "outer",
+        )))]))]);
+        let ident = Ident::from("outer");
+        assert_eq!(
+            resolve_labels(&program),
+            vec![UnresolvedJump {
+                kind: JumpKind::Break,
+                label: Some(&ident),
+                reason: UnresolvedReason::UndefinedLabel,
+            }]
+        );
+    }
+
+    #[test]
+    fn continue_needs_a_loop() {
+        // block: { break block; continue block; }
+        // a: b: while (x) { continue a; }
+        let program = Program::script(vec![
+            stmt(labeled(
+                "block",
+                Stmt::Block(BlockStmt(vec![
+                    stmt(Stmt::Break(label("block"))),
+                    stmt(Stmt::Continue(label("block"))),
+                ])),
+            )),
+            stmt(labeled(
+                "a",
+                labeled("b", while_x(vec![stmt(Stmt::Continue(label("a")))])),
+            )),
+        ]);
+        let jumps = resolve_labels(&program);
+        assert_eq!(jumps.len(), 1);
+        assert_eq!(jumps[0].kind, JumpKind::Continue);
+        assert_eq!(jumps[0].reason, UnresolvedReason::NotALoop);
+    }
+
+    #[test]
+    fn labels_stop_at_functions() {
+        // outer: while (x) {
+        //     function f() { break outer; continue; }
+        // }
+        // break;
+        let func = Func::new(
+            Some(Ident::from("f")),
+            vec![],
+            FuncBody(vec![
+                stmt(Stmt::Break(label("outer"))),
+                stmt(Stmt::Continue(None)),
+            ]),
+            false,
+            false,
+        );
+        let program = Program::script(vec![
+            stmt(labeled(
+                "outer",
+                while_x(vec![ProgramPart::Decl(crate::decl::Decl::Func(func))]),
+            )),
+            stmt(Stmt::Break(None)),
+        ]);
+        let reasons: Vec<_> = resolve_labels(&program)
+            .into_iter()
+            .map(|jump| (jump.kind, jump.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                (JumpKind::Break, UnresolvedReason::UndefinedLabel),
+                (JumpKind::Continue, UnresolvedReason::NoTarget),
+                (JumpKind::Break, UnresolvedReason::NoTarget),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_duplicate_labels() {
+        // a: while (x) {
+        //     b: { a: while (x) { b: ; } }
+        // }
+        let program = Program::script(vec![stmt(labeled(
+            "a",
+            while_x(vec![stmt(labeled(
+                "b",
+                Stmt::Block(BlockStmt(vec![stmt(labeled(
+                    "a",
+                    while_x(vec![stmt(labeled("b", Stmt::Empty))]),
+                ))])),
+            ))]),
+        ))]);
+        let names: Vec<_> = duplicate_labels(&program)
+            .into_iter()
+            .map(|ident| &*ident.name)
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn sibling_labels_can_repeat() {
+        // a: while (x) {}
+        // a: { b: ; b: ; }
+        // a: while (x) { function f() { a: ; } }
+        let func = Func::new(
+            Some(Ident::from("f")),
+            vec![],
+            FuncBody(vec![stmt(labeled("a", Stmt::Empty))]),
+            false,
+            false,
+        );
+        let program = Program::script(vec![
+            stmt(labeled("a", while_x(vec![]))),
+            stmt(labeled(
+                "a",
+                Stmt::Block(BlockStmt(vec![
+                    stmt(labeled("b", Stmt::Empty)),
+                    stmt(labeled("b", Stmt::Empty)),
+                ])),
+            )),
+            stmt(labeled(
+                "a",
+                while_x(vec![ProgramPart::Decl(crate::decl::Decl::Func(func))]),
+            )),
+        ]);
+        assert_eq!(duplicate_labels(&program), Vec::<&Ident>::new());
+    }
+}
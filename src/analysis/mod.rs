@@ -0,0 +1,30 @@
+//! Checks and queries that run over a whole tree
+//!
+//! These are built on top of the `visit` module and only
+//! ever look at a tree, nothing here modifies one.
+mod exported_names;
+mod free_identifiers;
+mod labels;
+mod module_dependencies;
+mod new_target;
+mod spread_positions;
+mod strict_mode;
+mod string_literals;
+mod super_usage;
+mod top_level_await;
+
+pub use exported_names::{exported_names, ExportedName};
+pub use free_identifiers::free_identifiers;
+pub(crate) use free_identifiers::{
+    body_names, decl_names, lexical_names, loop_left_names, param_names,
+};
+pub use labels::{
+    duplicate_labels, resolve_labels, JumpKind, UnresolvedJump, UnresolvedReason,
+};
+pub use module_dependencies::{module_dependencies, DepKind, ModuleDep};
+pub use new_target::{find_new_target, find_stray_new_target};
+pub use spread_positions::{check_spread_positions, SpreadError};
+pub use strict_mode::{find_strict_violations, StrictViolation};
+pub use string_literals::{string_literals, StringLiteralRef, StringPosition, StringSource};
+pub use super_usage::{check_super_usage, SuperError};
+pub use top_level_await::contains_top_level_await;
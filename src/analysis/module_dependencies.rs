@@ -0,0 +1,183 @@
+use crate::decl::{ModExport, ModImport, NamedExportDecl};
+use crate::expr::{Expr, ImportExpr, Lit, StringLit};
+use crate::visit::*;
+use crate::Program;
+
+/// How a module refers to one of its dependencies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepKind {
+    /// An import that binds at least one name
+    /// ```js
+    /// import a, { b } from 'x';
+    /// ```
+    Import,
+    /// An import only evaluated for its side effects
+    /// ```js
+    /// import 'x';
+    /// ```
+    SideEffect,
+    /// Re-exporting specific names
+    /// ```js
+    /// export { a, b as c } from 'x';
+    /// ```
+    ReExport,
+    /// Re-exporting everything, with or without an alias
+    /// ```js
+    /// export * from 'x';
+    /// export * as ns from 'x';
+    /// ```
+    ReExportAll,
+    /// A call to `import()` with a string literal argument
+    /// ```js
+    /// import('x');
+    /// ```
+    Dynamic,
+}
+
+/// A module specifier `program` depends on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleDep<'a> {
+    pub kind: DepKind,
+    pub source: &'a StringLit<'a>,
+}
+
+/// Find every module specifier in `program`, in source order.
+///
+/// A dynamic `import()` is only included when its argument is a
+/// string literal, anything else can't be known ahead of time
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::{module_dependencies, DepKind};
+/// // import('x');
+/// let program = Program::module(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Import(
+///     ImportExpr::new(Expr::Lit(Lit::single_string_from("x"))),
+/// )))]);
+/// let deps = module_dependencies(&program);
+/// assert_eq!(deps[0].kind, DepKind::Dynamic);
+/// assert!(deps[0].source.inner_matches("x"));
+/// ```
+pub fn module_dependencies<'a>(program: &'a Program<'a>) -> Vec<ModuleDep<'a>> {
+    let mut finder = DepFinder::default();
+    finder.visit_program(program);
+    finder.deps
+}
+
+#[derive(Default)]
+struct DepFinder<'a> {
+    deps: Vec<ModuleDep<'a>>,
+}
+
+impl<'a> DepFinder<'a> {
+    fn push(&mut self, kind: DepKind, source: &'a Lit<'a>) {
+        if let Lit::String(source) = source {
+            self.deps.push(ModuleDep { kind, source });
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for DepFinder<'a> {
+    fn visit_mod_import(&mut self, import: &'a ModImport<'a>) {
+        let kind = if import.specifiers.is_empty() {
+            DepKind::SideEffect
+        } else {
+            DepKind::Import
+        };
+        self.push(kind, &import.source);
+        walk_mod_import(self, import)
+    }
+    fn visit_mod_export(&mut self, export: &'a ModExport<'a>) {
+        match export {
+            ModExport::Named(NamedExportDecl::Specifier(_, Some(source))) => {
+                self.push(DepKind::ReExport, &source.source)
+            }
+            ModExport::All(_, source) => self.push(DepKind::ReExportAll, &source.source),
+            _ => {}
+        }
+        walk_mod_export(self, export)
+    }
+    fn visit_import_expr(&mut self, expr: &'a ImportExpr<'a>) {
+        if let Expr::Lit(source) = &*expr.source {
+            self.push(DepKind::Dynamic, source);
+        }
+        walk_import_expr(self, expr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decl::{Decl, DefaultExportDecl, ExportSource, ImportSpecifier};
+    use crate::expr::{ArrowFuncBody, ArrowFuncExpr};
+    use crate::stmt::Stmt;
+    use crate::{Ident, ProgramPart};
+
+    fn import<'a>(specifiers: Vec<ImportSpecifier<'a>>, source: &'a str) -> ProgramPart<'a> {
+        ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+            specifiers,
+            source: Lit::single_string_from(source),
+            attributes: Vec::new(),
+            attributes_keyword: None,
+        })))
+    }
+
+    fn export<'a>(export: ModExport<'a>) -> ProgramPart<'a> {
+        ProgramPart::Decl(Decl::Export(Box::new(export)))
+    }
+
+    fn dynamic<'a>(source: Expr<'a>) -> Expr<'a> {
+        Expr::Import(ImportExpr::new(source))
+    }
+
+    #[test]
+    fn mixed_module() {
+        // import a from 'a';
+        // import 'polyfill';
+        // export { b } from 'b';
+        // export * from 'c';
+        // export * as d from 'd';
+        // export default () => import('lazy');
+        // import(name);
+        let lazy = ArrowFuncExpr::new(
+            vec![],
+            ArrowFuncBody::Expr(Box::new(dynamic(Expr::Lit(Lit::single_string_from(
+                "lazy",
+            ))))),
+            false,
+        );
+        let program = Program::module(vec![
+            import(vec![ImportSpecifier::Default(Ident::from("a"))], "a"),
+            import(vec![], "polyfill"),
+            export(ModExport::Named(NamedExportDecl::Specifier(
+                vec![],
+                Some(ExportSource::new(Lit::single_string_from("b"))),
+            ))),
+            export(ModExport::All(
+                None,
+                ExportSource::new(Lit::single_string_from("c")),
+            )),
+            export(ModExport::All(
+                Some(crate::decl::ModuleExportName::Ident(Ident::from("d"))),
+                ExportSource::new(Lit::single_string_from("d")),
+            )),
+            export(ModExport::Default(DefaultExportDecl::Expr(Expr::ArrowFunc(
+                lazy,
+            )))),
+            ProgramPart::Stmt(Stmt::Expr(dynamic(Expr::ident_from("name")))),
+        ]);
+        let deps: Vec<_> = module_dependencies(&program)
+            .into_iter()
+            .map(|dep| (dep.kind, dep.source.clone_inner()))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                (DepKind::Import, "a".into()),
+                (DepKind::SideEffect, "polyfill".into()),
+                (DepKind::ReExport, "b".into()),
+                (DepKind::ReExportAll, "c".into()),
+                (DepKind::ReExportAll, "d".into()),
+                (DepKind::Dynamic, "lazy".into()),
+            ]
+        );
+    }
+}
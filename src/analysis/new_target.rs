@@ -0,0 +1,134 @@
+use crate::expr::{ArrowFuncExpr, MetaProp};
+use crate::visit::*;
+use crate::{ClassMember, Func, Program};
+
+/// Find every `new.target` that refers to `func`.
+///
+/// Arrow functions don't have their own `new.target` so any
+/// inside of an arrow are attributed to `func`, while nested
+/// functions and class bodies are skipped
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::find_new_target;
+/// // function f() { return () => new.target; }
+/// let arrow = ArrowFuncExpr::new(
+///     vec![],
+///     ArrowFuncBody::Expr(Box::new(Expr::MetaProp(MetaProp::new_target()))),
+///     false,
+/// );
+/// let func = Func::new(
+///     Some(Ident::from("f")),
+///     vec![],
+///     FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::ArrowFunc(arrow))))]),
+///     false,
+///     false,
+/// );
+/// assert_eq!(find_new_target(&func).len(), 1);
+/// ```
+pub fn find_new_target<'a>(func: &'a Func<'a>) -> Vec<&'a MetaProp<'a>> {
+    let mut finder = NewTargetFinder::default();
+    walk_func(&mut finder, func);
+    finder.found
+}
+
+/// Find every `new.target` in `program` that isn't inside of any
+/// non-arrow function, these are always a syntax error
+pub fn find_stray_new_target<'a>(program: &'a Program<'a>) -> Vec<&'a MetaProp<'a>> {
+    let mut finder = NewTargetFinder::default();
+    finder.visit_program(program);
+    finder.found
+}
+
+#[derive(Default)]
+struct NewTargetFinder<'a> {
+    found: Vec<&'a MetaProp<'a>>,
+}
+
+impl<'a> Visitor<'a> for NewTargetFinder<'a> {
+    fn visit_meta_prop(&mut self, meta: &'a MetaProp<'a>) {
+        if meta.is_new_target() {
+            self.found.push(meta);
+        }
+    }
+    fn visit_func(&mut self, _func: &'a Func<'a>) {}
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        walk_arrow_func_expr(self, expr)
+    }
+    // methods, field initializers and static blocks each have
+    // their own `new.target`, only decorators and computed keys
+    // are evaluated in the enclosing function
+    fn visit_class_member(&mut self, member: &'a ClassMember<'a>) {
+        for decorator in member.decorators() {
+            self.visit_decorator(decorator);
+        }
+        if let Some(key) = member.key() {
+            self.visit_prop_key(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decl::Decl;
+    use crate::expr::{ArrowFuncBody, Expr};
+    use crate::stmt::Stmt;
+    use crate::{FuncBody, Ident, ProgramPart};
+
+    fn new_target<'a>() -> Expr<'a> {
+        Expr::MetaProp(MetaProp::new_target())
+    }
+
+    fn arrow<'a>(body: Expr<'a>) -> Expr<'a> {
+        Expr::ArrowFunc(ArrowFuncExpr::new(
+            vec![],
+            ArrowFuncBody::Expr(Box::new(body)),
+            false,
+        ))
+    }
+
+    fn func<'a>(name: &'a str, body: Vec<ProgramPart<'a>>) -> Func<'a> {
+        Func::new(Some(Ident::from(name)), vec![], FuncBody(body), false, false)
+    }
+
+    fn expr_stmt<'a>(expr: Expr<'a>) -> ProgramPart<'a> {
+        ProgramPart::Stmt(Stmt::Expr(expr))
+    }
+
+    #[test]
+    fn nested_arrows_belong_to_the_function() {
+        // function f() {
+        //     new.target;
+        //     () => () => new.target;
+        //     function g() { new.target; }
+        // }
+        let inner = func("g", vec![expr_stmt(new_target())]);
+        let outer = func(
+            "f",
+            vec![
+                expr_stmt(new_target()),
+                expr_stmt(arrow(arrow(new_target()))),
+                ProgramPart::Decl(Decl::Func(inner)),
+            ],
+        );
+        assert_eq!(find_new_target(&outer).len(), 2);
+        if let Some(ProgramPart::Decl(Decl::Func(inner))) = outer.body.0.get(2) {
+            assert_eq!(find_new_target(inner).len(), 1);
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn stray_new_target() {
+        // () => new.target;
+        // function f() { new.target; }
+        // import.meta;
+        let program = Program::script(vec![
+            expr_stmt(arrow(new_target())),
+            ProgramPart::Decl(Decl::Func(func("f", vec![expr_stmt(new_target())]))),
+            expr_stmt(Expr::MetaProp(MetaProp::import_meta())),
+        ]);
+        assert_eq!(find_stray_new_target(&program).len(), 1);
+    }
+}
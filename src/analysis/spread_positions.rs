@@ -0,0 +1,180 @@
+use crate::expr::{CallExpr, Expr, NewExpr};
+use crate::pat::ArrayPatPart;
+use crate::visit::*;
+use crate::{FuncArg, Program};
+
+/// An `Expr::Spread` somewhere a spread isn't allowed
+/// ```js
+/// a = ...b;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadError<'a> {
+    /// The `Expr::Spread` itself
+    pub spread: &'a Expr<'a>,
+}
+
+/// Find every `Expr::Spread` that isn't an element of an array
+/// literal or array pattern, an argument to a call or `new` or a
+/// rest parameter.
+///
+/// The spread properties of object literals have their own
+/// `ObjProp::Spread` so they can't be misplaced
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::check_spread_positions;
+/// // f(...a);
+/// // ...b;
+/// let spread = |name| Expr::Spread(Box::new(Expr::ident_from(name)));
+/// let program = Program::script(vec![
+///     ProgramPart::Stmt(Stmt::Expr(Expr::Call(CallExpr::new(
+///         Expr::ident_from("f"),
+///         vec![spread("a")],
+///     )))),
+///     ProgramPart::Stmt(Stmt::Expr(spread("b"))),
+/// ]);
+/// let errors = check_spread_positions(&program);
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].spread, &spread("b"));
+/// ```
+pub fn check_spread_positions<'a>(program: &'a Program<'a>) -> Vec<SpreadError<'a>> {
+    let mut checker = SpreadChecker { errors: Vec::new() };
+    checker.visit_program(program);
+    checker.errors
+}
+
+struct SpreadChecker<'a> {
+    errors: Vec<SpreadError<'a>>,
+}
+
+impl<'a> SpreadChecker<'a> {
+    /// Visit an expression that is allowed to be a spread
+    fn spreadable(&mut self, expr: &'a Expr<'a>) {
+        match expr {
+            Expr::Spread(arg) => self.visit_expr(arg),
+            _ => self.visit_expr(expr),
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for SpreadChecker<'a> {
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        match expr {
+            Expr::Spread(arg) => {
+                self.errors.push(SpreadError { spread: expr });
+                self.visit_expr(arg)
+            }
+            Expr::Array(elements) => {
+                for el in elements.iter().flatten() {
+                    self.spreadable(el);
+                }
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        self.visit_expr(&expr.callee);
+        for arg in &expr.arguments {
+            self.spreadable(arg);
+        }
+    }
+    fn visit_new_expr(&mut self, expr: &'a NewExpr<'a>) {
+        self.visit_expr(&expr.callee);
+        for arg in expr.args() {
+            self.spreadable(arg);
+        }
+    }
+    fn visit_func_arg(&mut self, arg: &'a FuncArg<'a>) {
+        match arg {
+            FuncArg::Expr(expr) => self.spreadable(expr),
+            FuncArg::Pat(pat) => self.visit_pat(pat),
+        }
+    }
+    fn visit_array_pat_part(&mut self, part: &'a ArrayPatPart<'a>) {
+        match part {
+            ArrayPatPart::Expr(expr) => self.spreadable(expr),
+            ArrayPatPart::Pat(pat) => self.visit_pat(pat),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decl::{Decl, VarDecl};
+    use crate::expr::{AssignExpr, AssignLeft, ObjProp};
+    use crate::pat::Pat;
+    use crate::stmt::Stmt;
+    use crate::{AssignOp, ProgramPart, VarKind};
+
+    fn spread(name: &str) -> Expr<'_> {
+        Expr::Spread(Box::new(Expr::ident_from(name)))
+    }
+
+    fn stmt(expr: Expr) -> ProgramPart {
+        ProgramPart::Stmt(Stmt::Expr(expr))
+    }
+
+    #[test]
+    fn legal_spreads() {
+        // f(...a);
+        // new F(...b);
+        // [...c, [...d]];
+        // ({ ...e });
+        // (...g) => {} before it is converted to an arrow
+        let program = Program::script(vec![
+            stmt(Expr::Call(CallExpr::new(Expr::ident_from("f"), vec![spread("a")]))),
+            stmt(Expr::New(NewExpr::new(Expr::ident_from("F"), vec![spread("b")]))),
+            stmt(Expr::Array(vec![
+                Some(spread("c")),
+                Some(Expr::Array(vec![Some(spread("d"))])),
+            ])),
+            stmt(Expr::Obj(vec![ObjProp::Spread(Expr::ident_from("e"))])),
+            stmt(Expr::ArrowParamPlaceHolder(vec![FuncArg::Expr(spread("g"))], false)),
+        ]);
+        assert_eq!(check_spread_positions(&program), vec![]);
+    }
+
+    #[test]
+    fn illegal_spreads() {
+        // ...a;
+        // const b = ...c;
+        // f(g(...d), await ...e);
+        // x = ...[...y];
+        let nested = spread("e");
+        let program = Program::script(vec![
+            stmt(spread("a")),
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Const,
+                vec![VarDecl {
+                    id: Pat::ident_from("b"),
+                    init: Some(spread("c")),
+                }],
+            )),
+            stmt(Expr::Call(CallExpr::new(
+                Expr::ident_from("f"),
+                vec![
+                    Expr::Call(CallExpr::new(Expr::ident_from("g"), vec![spread("d")])),
+                    Expr::Await(Box::new(nested.clone())),
+                ],
+            ))),
+            stmt(Expr::Assign(AssignExpr {
+                operator: AssignOp::Equal,
+                left: AssignLeft::Expr(Box::new(Expr::ident_from("x"))),
+                right: Box::new(Expr::Spread(Box::new(Expr::Array(vec![Some(spread("y"))])))),
+            })),
+        ]);
+        let errors = check_spread_positions(&program);
+        let names: Vec<_> = errors
+            .iter()
+            .map(|error| match error.spread {
+                Expr::Spread(arg) => match &**arg {
+                    Expr::Ident(ident) => &*ident.name,
+                    _ => "[]",
+                },
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(names, vec!["a", "c", "e", "[]"]);
+        assert_eq!(errors[2].spread, &nested);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::expr::{ArrowFuncBody, ArrowFuncExpr, Lit, NumberLit};
+use crate::stmt::WithStmt;
+use crate::visit::*;
+use crate::{Class, Dir, Func, Program};
+
+/// Something that isn't allowed in strict mode code
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrictViolation<'a> {
+    /// Any `with` statement
+    /// ```js
+    /// 'use strict';
+    /// with (a) {}
+    /// ```
+    With(&'a WithStmt<'a>),
+    /// A legacy octal or leading zero decimal literal
+    /// ```js
+    /// 'use strict';
+    /// 0777;
+    /// 089;
+    /// ```
+    LegacyOctal(&'a NumberLit<'a>),
+}
+
+/// Find everything in `program` that isn't allowed in
+/// strict mode code.
+///
+/// A module or a script starting with `'use strict'` is checked
+/// in full, otherwise only the functions with a `'use strict'`
+/// directive and class bodies are checked
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::{find_strict_violations, StrictViolation};
+/// // 0777;
+/// let program = Program::module(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Lit(
+///     Lit::number_from("0777"),
+/// )))]);
+/// let violations = find_strict_violations(&program);
+/// assert!(matches!(violations[0], StrictViolation::LegacyOctal(_)));
+/// ```
+pub fn find_strict_violations<'a>(program: &'a Program<'a>) -> Vec<StrictViolation<'a>> {
+    let mut finder = StrictFinder {
+        strict: program.is_strict(),
+        violations: Vec::new(),
+    };
+    finder.visit_program(program);
+    finder.violations
+}
+
+struct StrictFinder<'a> {
+    strict: bool,
+    violations: Vec<StrictViolation<'a>>,
+}
+
+impl<'a> StrictFinder<'a> {
+    fn with_strict(&mut self, strict: bool, f: impl FnOnce(&mut Self)) {
+        let outer = self.strict;
+        self.strict |= strict;
+        f(self);
+        self.strict = outer;
+    }
+}
+
+impl<'a> Visitor<'a> for StrictFinder<'a> {
+    fn visit_with_stmt(&mut self, stmt: &'a WithStmt<'a>) {
+        if self.strict {
+            self.violations.push(StrictViolation::With(stmt));
+        }
+        walk_with_stmt(self, stmt)
+    }
+    fn visit_lit(&mut self, lit: &'a Lit<'a>) {
+        match lit {
+            Lit::Number(number) if self.strict && number.is_legacy_octal() => {
+                self.violations.push(StrictViolation::LegacyOctal(number))
+            }
+            _ => walk_lit(self, lit),
+        }
+    }
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        self.with_strict(func.is_strict(), |v| walk_func(v, func))
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        let strict = match &expr.body {
+            ArrowFuncBody::FuncBody(body) => body.directives().any(Dir::is_use_strict),
+            ArrowFuncBody::Expr(_) => false,
+        };
+        self.with_strict(strict, |v| walk_arrow_func_expr(v, expr))
+    }
+    // all of a class is strict mode code, including its name
+    // and `extends` clause
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        self.with_strict(true, |v| walk_class(v, class))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::Expr;
+    use crate::stmt::{BlockStmt, Stmt};
+    use crate::{FuncBody, ProgramPart};
+
+    fn with_a<'a>() -> Stmt<'a> {
+        Stmt::With(WithStmt {
+            object: Expr::ident_from("a"),
+            body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+        })
+    }
+
+    fn use_strict<'a>() -> ProgramPart<'a> {
+        ProgramPart::Dir(Dir::from_str("use strict"))
+    }
+
+    #[test]
+    fn strict_with() {
+        // 'use strict';
+        // with (a) {}
+        let program = Program::script(vec![use_strict(), ProgramPart::Stmt(with_a())]);
+        let violations = find_strict_violations(&program);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(violations[0], StrictViolation::With(_)));
+        // with (a) {}
+        // function f() { 'use strict'; with (a) {} }
+        let func = Func::new(
+            Some(crate::Ident::from("f")),
+            vec![],
+            FuncBody(vec![use_strict(), ProgramPart::Stmt(with_a())]),
+            false,
+            false,
+        );
+        let program = Program::script(vec![
+            ProgramPart::Stmt(with_a()),
+            ProgramPart::Decl(crate::decl::Decl::Func(func)),
+        ]);
+        assert_eq!(find_strict_violations(&program).len(), 1);
+    }
+
+    #[test]
+    fn strict_legacy_octal() {
+        // 010; 089; 0o10; 0; 0.5;
+        let parts: Vec<_> = ["010", "089", "0o10", "0", "0.5"]
+            .iter()
+            .map(|n| ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::number_from(n)))))
+            .collect();
+        let module = Program::module(parts.clone());
+        let octals: Vec<_> = find_strict_violations(&module)
+            .into_iter()
+            .map(|violation| match violation {
+                StrictViolation::LegacyOctal(number) => number.raw(),
+                StrictViolation::With(_) => panic!("unexpected with"),
+            })
+            .collect();
+        assert_eq!(octals, vec!["010", "089"]);
+        assert_eq!(find_strict_violations(&Program::script(parts)), vec![]);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::expr::{
+    CallExpr, Expr, Lit, MemberProp, NewExpr, PropKey, StringLit, TemplateElement,
+};
+use crate::visit::*;
+use crate::Program;
+use std::borrow::Cow;
+
+/// Where a string literal sits in its parent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringPosition<'a> {
+    /// Passed directly as an argument to a call or `new`, `index`
+    /// counts from 0
+    /// ```js
+    /// t('greeting');
+    /// ```
+    CallArg { callee: &'a Expr<'a>, index: usize },
+    /// The key of a computed member access
+    /// ```js
+    /// messages['greeting'];
+    /// ```
+    MemberKey,
+    /// The key of an object property or class member
+    /// ```js
+    /// ({ 'greeting': 'hi' });
+    /// ```
+    PropKey,
+    /// Anywhere else, including every template quasi
+    Other,
+}
+
+/// The literal a `StringLiteralRef` was found in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StringSource<'a> {
+    String(&'a StringLit<'a>),
+    Template(&'a TemplateElement<'a>),
+}
+
+/// A string literal found by `string_literals`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringLiteralRef<'a> {
+    /// The value with escapes applied
+    pub value: Cow<'a, str>,
+    pub source: StringSource<'a>,
+    pub position: StringPosition<'a>,
+}
+
+/// Find every string literal in `program`, in source order.
+///
+/// When `templates` is set the quasis of template literals are
+/// included too, a quasi with an invalid escape has no cooked value
+/// and is skipped. The plain tree doesn't keep any locations, each
+/// literal is returned by reference so it can be matched back up with
+/// the parser's positions
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::{string_literals, StringPosition};
+/// // t('hello\n');
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Call(
+///     CallExpr::new(
+///         Expr::ident_from("t"),
+///         vec![Expr::Lit(Lit::single_string_from(r"hello\n"))],
+///     ),
+/// )))]);
+/// let strings = string_literals(&program, false);
+/// assert_eq!(strings[0].value, "hello\n");
+/// assert!(matches!(strings[0].position, StringPosition::CallArg { index: 0, .. }));
+/// ```
+pub fn string_literals<'a>(
+    program: &'a Program<'a>,
+    templates: bool,
+) -> Vec<StringLiteralRef<'a>> {
+    let mut finder = StringFinder {
+        templates,
+        found: Vec::new(),
+    };
+    finder.visit_program(program);
+    finder.found
+}
+
+struct StringFinder<'a> {
+    templates: bool,
+    found: Vec<StringLiteralRef<'a>>,
+}
+
+impl<'a> StringFinder<'a> {
+    fn push(&mut self, lit: &'a StringLit<'a>, position: StringPosition<'a>) {
+        self.found.push(StringLiteralRef {
+            value: lit.cooked(),
+            source: StringSource::String(lit),
+            position,
+        });
+    }
+
+    fn args(&mut self, callee: &'a Expr<'a>, args: &'a [Expr<'a>]) {
+        self.visit_expr(callee);
+        for (index, arg) in args.iter().enumerate() {
+            match arg {
+                Expr::Lit(Lit::String(lit)) => {
+                    self.push(lit, StringPosition::CallArg { callee, index })
+                }
+                _ => self.visit_expr(arg),
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for StringFinder<'a> {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        self.args(&expr.callee, &expr.arguments)
+    }
+    fn visit_new_expr(&mut self, expr: &'a NewExpr<'a>) {
+        self.args(&expr.callee, expr.args())
+    }
+    fn visit_member_prop(&mut self, prop: &'a MemberProp<'a>) {
+        match prop {
+            MemberProp::Computed(expr) => match &**expr {
+                Expr::Lit(Lit::String(lit)) => self.push(lit, StringPosition::MemberKey),
+                _ => self.visit_expr(expr),
+            },
+            _ => walk_member_prop(self, prop),
+        }
+    }
+    fn visit_prop_key(&mut self, key: &'a PropKey<'a>) {
+        match key {
+            PropKey::Lit(Lit::String(lit)) => self.push(lit, StringPosition::PropKey),
+            _ => walk_prop_key(self, key),
+        }
+    }
+    fn visit_lit(&mut self, lit: &'a Lit<'a>) {
+        match lit {
+            Lit::String(lit) => self.push(lit, StringPosition::Other),
+            _ => walk_lit(self, lit),
+        }
+    }
+    fn visit_template_element(&mut self, element: &'a TemplateElement<'a>) {
+        if !self.templates {
+            return;
+        }
+        if let Some(cooked) = &element.cooked {
+            self.found.push(StringLiteralRef {
+                value: cooked.clone(),
+                source: StringSource::Template(element),
+                position: StringPosition::Other,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::decl::{Decl, VarDecl};
+    use crate::expr::{MemberExpr, ObjProp, Prop, PropValue, TemplateLit};
+    use crate::pat::Pat;
+    use crate::stmt::Stmt;
+    use crate::{Ident, ProgramPart, PropKind, VarKind};
+
+    fn string(value: &str) -> Expr<'_> {
+        Expr::Lit(Lit::single_string_from(value))
+    }
+
+    fn quasi<'a>(raw: &'a str, cooked: &'a str, tail: bool) -> TemplateElement<'a> {
+        TemplateElement {
+            tail,
+            cooked: Some(Cow::Borrowed(cooked)),
+            raw: Cow::Borrowed(raw),
+        }
+    }
+
+    #[test]
+    fn strings_and_templates() {
+        // const greeting = { 'key': "it\'s" };
+        // t('hello', `hi ${name}!`);
+        // messages['title'];
+        let obj = Expr::Obj(vec![ObjProp::Prop(Prop {
+            key: PropKey::Lit(Lit::single_string_from("key")),
+            value: PropValue::Expr(Expr::Lit(Lit::double_string_from(r"it\'s"))),
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand: false,
+            is_static: false,
+            decorators: vec![],
+        })]);
+        let template = Expr::Lit(Lit::Template(TemplateLit {
+            quasis: vec![quasi("`hi ${", "hi ", false), quasi("}!`", "!", true)],
+            expressions: vec![Expr::ident_from("name")],
+        }));
+        let member = MemberExpr::new(
+            Expr::ident_from("messages"),
+            MemberProp::computed(string("title")),
+        );
+        let program = Program::script(vec![
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Const,
+                vec![VarDecl {
+                    id: Pat::Ident(Ident::from("greeting")),
+                    init: Some(obj),
+                }],
+            )),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Call(CallExpr::new(
+                Expr::ident_from("t"),
+                vec![string("hello"), template],
+            )))),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Member(member))),
+        ]);
+        let found = |templates| -> Vec<_> {
+            string_literals(&program, templates)
+                .into_iter()
+                .map(|s| {
+                    let position = match s.position {
+                        StringPosition::CallArg { index, .. } => format!("arg {}", index),
+                        other => format!("{:?}", other),
+                    };
+                    (s.value.into_owned(), position)
+                })
+                .collect()
+        };
+        let strings = vec![
+            ("key".to_string(), "PropKey".to_string()),
+            ("it's".to_string(), "Other".to_string()),
+            ("hello".to_string(), "arg 0".to_string()),
+            ("title".to_string(), "MemberKey".to_string()),
+        ];
+        assert_eq!(found(false), strings);
+        let mut with_templates = strings;
+        with_templates.insert(3, ("hi ".to_string(), "Other".to_string()));
+        with_templates.insert(4, ("!".to_string(), "Other".to_string()));
+        assert_eq!(found(true), with_templates);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::expr::{ArrowFuncExpr, CallExpr, MemberExpr, Prop};
+use crate::visit::*;
+use crate::{Class, ClassMember, Func, MethodKind};
+
+/// A use of `super` that isn't allowed where it appears
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuperError<'a> {
+    /// `super(...)` outside of the constructor of a class
+    /// with an `extends` clause
+    /// ```js
+    /// class A { constructor() { super(); } }
+    /// ```
+    InvalidCall(&'a CallExpr<'a>),
+    /// `super.property` outside of a method, field initializer
+    /// or static block
+    /// ```js
+    /// class A { m() { function f() { super.x; } } }
+    /// ```
+    InvalidMember(&'a MemberExpr<'a>),
+}
+
+/// Find every misplaced `super(...)` or `super.property`
+/// in `class`.
+///
+/// Arrow functions use the `super` of the function they are
+/// defined in while any other function has none, the methods
+/// of an object literal only allow `super.property`. Nested
+/// classes are checked against their own `extends` clause
+/// ```
+/// # use resast::prelude::*;
+/// use resast::analysis::{check_super_usage, SuperError};
+/// // class A { constructor() { super(); } }
+/// let ctor = Func::new(
+///     None,
+///     vec![],
+///     FuncBody(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Call(CallExpr::new(
+///         Expr::Super,
+///         vec![],
+///     ))))]),
+///     false,
+///     false,
+/// );
+/// let class = Class::new(
+///     Some(Ident::from("A")),
+///     None,
+///     vec![ClassMember::Method(MethodDef::new(
+///         PropKey::Expr(Expr::ident_from("constructor")),
+///         ctor,
+///         MethodKind::Constructor,
+///     ))],
+/// );
+/// let errors = check_super_usage(&class);
+/// assert!(matches!(errors[0], SuperError::InvalidCall(_)));
+/// ```
+pub fn check_super_usage<'a>(class: &'a Class<'a>) -> Vec<SuperError<'a>> {
+    let mut checker = SuperChecker {
+        scope: SuperScope::None,
+        errors: Vec::new(),
+    };
+    checker.visit_class(class);
+    checker.errors
+}
+
+/// What `super` can do in the current function
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SuperScope {
+    None,
+    /// `super.property` only
+    Method,
+    /// Both `super.property` and `super(...)`
+    DerivedCtor,
+}
+
+struct SuperChecker<'a> {
+    scope: SuperScope,
+    errors: Vec<SuperError<'a>>,
+}
+
+impl<'a> SuperChecker<'a> {
+    fn in_scope(&mut self, scope: SuperScope, f: impl FnOnce(&mut Self)) {
+        let outer = std::mem::replace(&mut self.scope, scope);
+        f(self);
+        self.scope = outer;
+    }
+}
+
+impl<'a> Visitor<'a> for SuperChecker<'a> {
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        if !expr.is_super() {
+            return walk_call_expr(self, expr);
+        }
+        if self.scope != SuperScope::DerivedCtor {
+            self.errors.push(SuperError::InvalidCall(expr));
+        }
+        for arg in &expr.arguments {
+            self.visit_expr(arg);
+        }
+    }
+    fn visit_member_expr(&mut self, expr: &'a MemberExpr<'a>) {
+        if !expr.is_super() {
+            return walk_member_expr(self, expr);
+        }
+        if self.scope == SuperScope::None {
+            self.errors.push(SuperError::InvalidMember(expr));
+        }
+        self.visit_member_prop(&expr.property);
+    }
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        self.in_scope(SuperScope::None, |v| walk_func(v, func))
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        walk_arrow_func_expr(self, expr)
+    }
+    // object literal methods
+    fn visit_prop(&mut self, prop: &'a Prop<'a>) {
+        let func = match prop.method_func() {
+            Some(func) => func,
+            None => return walk_prop(self, prop),
+        };
+        for decorator in &prop.decorators {
+            self.visit_decorator(decorator);
+        }
+        self.visit_prop_key(&prop.key);
+        self.in_scope(SuperScope::Method, |v| walk_func(v, func))
+    }
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        for decorator in &class.decorators {
+            self.visit_decorator(decorator);
+        }
+        if let Some(super_class) = &class.super_class {
+            self.visit_expr(super_class);
+        }
+        let ctor = if class.super_class.is_some() {
+            SuperScope::DerivedCtor
+        } else {
+            SuperScope::Method
+        };
+        for member in &class.body.0 {
+            for decorator in member.decorators() {
+                self.visit_decorator(decorator);
+            }
+            if let Some(key) = member.key() {
+                self.visit_prop_key(key);
+            }
+            match member {
+                ClassMember::Method(method) => {
+                    let scope = if method.kind == MethodKind::Constructor {
+                        ctor
+                    } else {
+                        SuperScope::Method
+                    };
+                    self.in_scope(scope, |v| walk_func(v, &method.value))
+                }
+                ClassMember::Field(field) => {
+                    if let Some(value) = &field.value {
+                        self.in_scope(SuperScope::Method, |v| v.visit_expr(value))
+                    }
+                }
+                ClassMember::StaticBlock(block) => {
+                    self.in_scope(SuperScope::Method, |v| v.visit_block_stmt(block))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{ArrowFuncBody, Expr, MemberProp, PropKey};
+    use crate::stmt::Stmt;
+    use crate::{FuncBody, Ident, MethodDef, ProgramPart};
+
+    fn super_call<'a>() -> Expr<'a> {
+        Expr::Call(CallExpr::new(Expr::Super, vec![]))
+    }
+
+    fn super_method_call<'a>() -> Expr<'a> {
+        let member = MemberExpr::new(Expr::Super, MemberProp::ident_from("method"));
+        Expr::Call(CallExpr::new(Expr::Member(member), vec![]))
+    }
+
+    fn func<'a>(body: Vec<Expr<'a>>) -> Func<'a> {
+        let body = body
+            .into_iter()
+            .map(|expr| ProgramPart::Stmt(Stmt::Expr(expr)))
+            .collect();
+        Func::new(None, vec![], FuncBody(body), false, false)
+    }
+
+    fn method<'a>(name: &'a str, kind: MethodKind, body: Vec<Expr<'a>>) -> ClassMember<'a> {
+        let key = PropKey::Expr(Expr::ident_from(name));
+        ClassMember::Method(MethodDef::new(key, func(body), kind))
+    }
+
+    #[test]
+    fn valid_super() {
+        // class A extends B {
+        //     constructor() { super(); super.method(); () => super(); }
+        //     m() { super.method(); }
+        // }
+        let arrow = Expr::ArrowFunc(ArrowFuncExpr::new(
+            vec![],
+            ArrowFuncBody::Expr(Box::new(super_call())),
+            false,
+        ));
+        let class = Class::new(
+            Some(Ident::from("A")),
+            Some(Expr::ident_from("B")),
+            vec![
+                method(
+                    "constructor",
+                    MethodKind::Constructor,
+                    vec![super_call(), super_method_call(), arrow],
+                ),
+                method("m", MethodKind::Method, vec![super_method_call()]),
+            ],
+        );
+        assert_eq!(check_super_usage(&class), vec![]);
+    }
+
+    #[test]
+    fn invalid_super() {
+        // class A {
+        //     constructor() { super(); }
+        //     m() { function f() { super(); super.method(); } }
+        // }
+        let plain = func(vec![super_call(), super_method_call()]);
+        let class = Class::new(
+            Some(Ident::from("A")),
+            None,
+            vec![
+                method("constructor", MethodKind::Constructor, vec![super_call()]),
+                method("m", MethodKind::Method, vec![Expr::Func(plain)]),
+            ],
+        );
+        let errors = check_super_usage(&class);
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], SuperError::InvalidCall(_)));
+        assert!(matches!(errors[1], SuperError::InvalidCall(_)));
+        assert!(matches!(errors[2], SuperError::InvalidMember(_)));
+    }
+}
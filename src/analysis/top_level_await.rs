@@ -0,0 +1,114 @@
+use crate::expr::{ArrowFuncExpr, Expr};
+use crate::stmt::ForOfStmt;
+use crate::visit::*;
+use crate::{ClassMember, Func, Program};
+
+/// If `program` uses `await` outside of any function, either
+/// as an `await` expression or a `for await` loop.
+///
+/// Only a module can legally do this, a script is checked the
+/// same way but a well formed one will always return `false`
+pub fn contains_top_level_await(program: &Program) -> bool {
+    let mut finder = AwaitFinder::default();
+    finder.visit_program(program);
+    finder.found
+}
+
+#[derive(Default)]
+struct AwaitFinder {
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for AwaitFinder {
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        if self.found {
+            return;
+        }
+        if let Expr::Await(_) = expr {
+            self.found = true;
+            return;
+        }
+        walk_expr(self, expr)
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &'a ForOfStmt<'a>) {
+        self.found |= stmt.is_await;
+        walk_for_of_stmt(self, stmt)
+    }
+    fn visit_func(&mut self, _func: &'a Func<'a>) {}
+    fn visit_arrow_func_expr(&mut self, _expr: &'a ArrowFuncExpr<'a>) {}
+    // field initializers and static blocks are their own function
+    // scope but decorators and computed keys are evaluated with
+    // the class
+    fn visit_class_member(&mut self, member: &'a ClassMember<'a>) {
+        for decorator in member.decorators() {
+            self.visit_decorator(decorator);
+        }
+        if let Some(key) = member.key() {
+            self.visit_prop_key(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{ImportExpr, Lit, PropKey};
+    use crate::stmt::Stmt;
+    use crate::{Class, FieldDef, FuncArg, FuncBody, ProgramPart};
+
+    fn await_stmt<'a>(argument: Expr<'a>) -> ProgramPart<'a> {
+        ProgramPart::Stmt(Stmt::Expr(Expr::Await(Box::new(argument))))
+    }
+
+    #[test]
+    fn await_import_in_module() {
+        // await import('x');
+        let import = Expr::Import(ImportExpr {
+            source: Box::new(Expr::Lit(Lit::single_string_from("x"))),
+            options: None,
+        });
+        let program = Program::module(vec![await_stmt(import)]);
+        assert!(contains_top_level_await(&program));
+        assert!(program.contains_top_level_await());
+    }
+
+    #[test]
+    fn await_inside_functions() {
+        // async function f() { await x; }
+        // let g = async () => await x;
+        // class C { a = await x; }
+        let body = FuncBody(vec![await_stmt(Expr::ident_from("x"))]);
+        let func = Func::new(Some(crate::Ident::from("f")), vec![], body, false, true);
+        let arrow = Expr::ArrowFunc(ArrowFuncExpr {
+            id: None,
+            params: vec![FuncArg::Pat(crate::pat::Pat::ident_from("y"))],
+            body: crate::expr::ArrowFuncBody::Expr(Box::new(Expr::Await(Box::new(
+                Expr::ident_from("x"),
+            )))),
+            expression: true,
+            is_async: true,
+        });
+        let field = |computed: bool| {
+            let awaited = Expr::Await(Box::new(Expr::ident_from("x")));
+            let (key, value) = if computed {
+                (PropKey::Expr(awaited), None)
+            } else {
+                (PropKey::Expr(Expr::ident_from("a")), Some(awaited))
+            };
+            let field = FieldDef {
+                computed,
+                ..FieldDef::new(key, value)
+            };
+            Expr::Class(Class::new(None, None, vec![ClassMember::Field(field)]))
+        };
+        let program = Program::module(vec![
+            ProgramPart::Decl(crate::decl::Decl::Func(func)),
+            ProgramPart::Stmt(Stmt::Expr(arrow)),
+            ProgramPart::Stmt(Stmt::Expr(field(false))),
+        ]);
+        assert!(!program.contains_top_level_await());
+        // class C { [await x]; }
+        let program = Program::module(vec![ProgramPart::Stmt(Stmt::Expr(field(true)))]);
+        assert!(program.contains_top_level_await());
+    }
+}
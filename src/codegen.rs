@@ -0,0 +1,1843 @@
+//! Turning an AST back into javascript
+//!
+//! With the `codegen` feature enabled `Program`, `ProgramPart`,
+//! `Decl`, `Stmt`, `Expr` and `Pat` implement `Display`. The
+//! output is valid javascript but makes no attempt to match the
+//! original formatting, parentheses are only added where the
+//! structure of the tree requires them.
+//!
+//! `Display` always uses the default `CodegenOptions`, use
+//! `Program::write_with_options` to change the indentation, quotes
+//! or semicolons.
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::parens::*;
+use crate::stmt::*;
+use crate::*;
+
+/// The quotes used for string literals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Keep the quotes each literal was written with
+    Preserve,
+    Single,
+    Double,
+}
+
+/// Controls the formatting of the generated javascript
+/// ```
+/// # use resast::prelude::*;
+/// use resast::codegen::{CodegenOptions, QuoteStyle};
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Call(
+///     CallExpr::new(Expr::ident_from("f"), vec![Expr::Lit(Lit::double_string_from("a"))]),
+/// )))]);
+/// let options = CodegenOptions {
+///     quote: QuoteStyle::Single,
+///     semicolons: false,
+///     ..CodegenOptions::default()
+/// };
+/// let mut out = String::new();
+/// program.write_with_options(&mut out, &options).unwrap();
+/// assert_eq!(out, "f('a')");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodegenOptions {
+    /// Written once per level of nesting at the start of each line
+    pub indent: String,
+    pub quote: QuoteStyle,
+    /// End statements with `;`, when this is off a `;` is only
+    /// written where leaving it out would change the meaning
+    pub semicolons: bool,
+    /// Put everything on one line and drop the optional spaces
+    /// around punctuation, this always writes semicolons
+    pub minify: bool,
+}
+
+impl Default for CodegenOptions {
+    /// The options `Display` uses, 4 space indentation, quotes
+    /// preserved and semicolons
+    fn default() -> Self {
+        Self {
+            indent: "    ".to_string(),
+            quote: QuoteStyle::Preserve,
+            semicolons: true,
+            minify: false,
+        }
+    }
+}
+
+impl<'a> Program<'a> {
+    /// Write this program as javascript formatted with `options`
+    pub fn write_with_options(
+        &self,
+        out: &mut impl Write,
+        options: &CodegenOptions,
+    ) -> fmt::Result {
+        Codegen::new(out, options).program(self)
+    }
+}
+
+struct Codegen<'o, W> {
+    out: W,
+    indent: usize,
+    options: &'o CodegenOptions,
+    /// Set while writing a statement that is followed by more
+    /// of its parent on the same line, like the consequent of
+    /// an `if` with an `else`, which always needs its `;`
+    same_line: bool,
+    /// Set while writing the init of a `for` loop where a bare `in`
+    /// would be read as a `for...in`, any `in` binary expression is
+    /// wrapped in parentheses until this is cleared
+    no_in: bool,
+}
+
+impl<'o, W: Write> Codegen<'o, W> {
+    fn new(out: W, options: &'o CodegenOptions) -> Self {
+        Self {
+            out,
+            indent: 0,
+            options,
+            same_line: false,
+            no_in: false,
+        }
+    }
+
+    fn write(&mut self, s: &str) -> fmt::Result {
+        self.out.write_str(s)
+    }
+
+    /// Write punctuation, the spaces around it are left
+    /// out when minifying
+    fn punct(&mut self, s: &str) -> fmt::Result {
+        if self.options.minify {
+            self.write(s.trim())
+        } else {
+            self.write(s)
+        }
+    }
+
+    fn semicolons(&self) -> bool {
+        self.options.semicolons || self.options.minify
+    }
+
+    /// Write the `;` ending a statement
+    fn semi(&mut self) -> fmt::Result {
+        if self.semicolons() || self.same_line {
+            self.write(";")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn newline(&mut self) -> fmt::Result {
+        if self.options.minify {
+            return Ok(());
+        }
+        self.write("\n")?;
+        for _ in 0..self.indent {
+            self.out.write_str(&self.options.indent)?;
+        }
+        Ok(())
+    }
+
+    fn comma_sep<'i, T: 'i>(
+        &mut self,
+        items: impl IntoIterator<Item = &'i T>,
+        mut f: impl FnMut(&mut Self, &T) -> fmt::Result,
+    ) -> fmt::Result {
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                self.punct(", ")?;
+            }
+            f(self, item)?;
+        }
+        Ok(())
+    }
+
+    fn program(&mut self, program: &Program) -> fmt::Result {
+        if let Some(hashbang) = program.hashbang() {
+            self.write("#!")?;
+            self.write(hashbang)?;
+            self.write("\n")?;
+        }
+        let parts = match program {
+            Program::Mod(parts, _) | Program::Script(parts, _) => parts,
+        };
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                self.newline()?;
+            }
+            self.program_part(part)?;
+        }
+        Ok(())
+    }
+
+    fn program_part(&mut self, part: &ProgramPart) -> fmt::Result {
+        match part {
+            ProgramPart::Dir(dir) => {
+                self.lit(&dir.expr)?;
+                self.semi()
+            }
+            ProgramPart::Decl(decl) => self.decl(decl),
+            ProgramPart::Stmt(stmt) => self.stmt(stmt),
+        }
+    }
+
+    fn parts_block(&mut self, parts: &[ProgramPart]) -> fmt::Result {
+        self.write("{")?;
+        self.indent += 1;
+        let same_line = std::mem::replace(&mut self.same_line, false);
+        for part in parts {
+            self.newline()?;
+            self.program_part(part)?;
+        }
+        self.same_line = same_line;
+        self.indent -= 1;
+        if !parts.is_empty() {
+            self.newline()?;
+        }
+        self.write("}")
+    }
+
+    fn decl(&mut self, decl: &Decl) -> fmt::Result {
+        match decl {
+            Decl::Var(kind, decls) => {
+                self.var_decls(*kind, decls)?;
+                self.semi()
+            }
+            Decl::Func(func) => self.func(func),
+            Decl::Class(class) => self.class(class),
+            Decl::Import(import) => self.import(import),
+            Decl::Export(export) => self.export(export),
+        }
+    }
+
+    fn var_decls(&mut self, kind: VarKind, decls: &[VarDecl]) -> fmt::Result {
+        self.write(kind.as_str())?;
+        self.write(" ")?;
+        self.comma_sep(decls, Self::var_decl)
+    }
+
+    fn var_decl(&mut self, decl: &VarDecl) -> fmt::Result {
+        self.pat(&decl.id)?;
+        if let Some(init) = &decl.init {
+            self.punct(" = ")?;
+            self.expr(init, ASSIGN)?;
+        }
+        Ok(())
+    }
+
+    fn import(&mut self, import: &ModImport) -> fmt::Result {
+        self.write("import ")?;
+        let mut normal = Vec::new();
+        let mut wrote = false;
+        for spec in &import.specifiers {
+            match spec {
+                ImportSpecifier::Default(ident) => {
+                    if wrote {
+                        self.punct(", ")?;
+                    }
+                    self.ident(ident)?;
+                    wrote = true;
+                }
+                ImportSpecifier::Namespace(ident) => {
+                    if wrote {
+                        self.punct(", ")?;
+                    }
+                    self.write("* as ")?;
+                    self.ident(ident)?;
+                    wrote = true;
+                }
+                ImportSpecifier::Normal(spec) => normal.push(spec),
+            }
+        }
+        if !normal.is_empty() {
+            if wrote {
+                self.punct(", ")?;
+            }
+            self.punct("{ ")?;
+            self.comma_sep(&normal, |g, spec| {
+                if let ModuleExportName::Ident(imported) = &spec.imported {
+                    if imported == &spec.local {
+                        return g.ident(&spec.local);
+                    }
+                }
+                g.module_export_name(&spec.imported)?;
+                g.write(" as ")?;
+                g.ident(&spec.local)
+            })?;
+            self.punct(" }")?;
+            wrote = true;
+        }
+        if wrote {
+            self.write(" from ")?;
+        }
+        self.lit(&import.source)?;
+        self.attributes(&import.attributes, import.clause_keyword())?;
+        self.semi()
+    }
+
+    fn attributes(
+        &mut self,
+        attributes: &[ImportAttribute],
+        keyword: Option<AttributesKeyword>,
+    ) -> fmt::Result {
+        let keyword = match keyword {
+            Some(AttributesKeyword::With) => " with ",
+            Some(AttributesKeyword::Assert) => " assert ",
+            None => return Ok(()),
+        };
+        self.write(keyword)?;
+        if attributes.is_empty() {
+            return self.write("{}");
+        }
+        self.punct("{ ")?;
+        self.comma_sep(attributes, |g, attr| {
+            match &attr.key {
+                ImportAttributeKey::Ident(ident) => g.ident(ident)?,
+                ImportAttributeKey::Lit(lit) => g.lit(lit)?,
+            }
+            g.punct(": ")?;
+            g.lit(&attr.value)
+        })?;
+        self.punct(" }")
+    }
+
+    fn module_export_name(&mut self, name: &ModuleExportName) -> fmt::Result {
+        match name {
+            ModuleExportName::Ident(ident) => self.ident(ident),
+            ModuleExportName::Str(lit) => self.lit(lit),
+        }
+    }
+
+    fn export_source(&mut self, source: &ExportSource) -> fmt::Result {
+        self.write(" from ")?;
+        self.lit(&source.source)?;
+        self.attributes(&source.attributes, source.clause_keyword())
+    }
+
+    fn export(&mut self, export: &ModExport) -> fmt::Result {
+        self.write("export ")?;
+        match export {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                self.write("default ")?;
+                self.decl(decl)
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                self.write("default ")?;
+                if starts_ambiguously(expr) {
+                    self.paren_expr(expr)?;
+                } else {
+                    self.expr(expr, ASSIGN)?;
+                }
+                self.semi()
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => self.decl(decl),
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                if specs.is_empty() {
+                    self.write("{}")?;
+                } else {
+                    self.punct("{ ")?;
+                    self.comma_sep(specs, |g, spec| {
+                        g.ident(&spec.local)?;
+                        if let ModuleExportName::Ident(exported) = &spec.exported {
+                            if exported == &spec.local {
+                                return Ok(());
+                            }
+                        }
+                        g.write(" as ")?;
+                        g.module_export_name(&spec.exported)
+                    })?;
+                    self.punct(" }")?;
+                }
+                if let Some(source) = source {
+                    self.export_source(source)?;
+                }
+                self.semi()
+            }
+            ModExport::All(alias, source) => {
+                self.write("*")?;
+                if let Some(alias) = alias {
+                    self.write(" as ")?;
+                    self.module_export_name(alias)?;
+                }
+                self.export_source(source)?;
+                self.semi()
+            }
+        }
+    }
+
+    fn func(&mut self, func: &Func) -> fmt::Result {
+        if func.is_async {
+            self.write("async ")?;
+        }
+        self.write("function")?;
+        if func.generator {
+            self.write("*")?;
+        }
+        if let Some(id) = &func.id {
+            self.write(" ")?;
+            self.ident(id)?;
+        }
+        self.func_params_and_body(func)
+    }
+
+    fn func_params_and_body(&mut self, func: &Func) -> fmt::Result {
+        self.write("(")?;
+        self.comma_sep(&func.params, Self::func_arg)?;
+        self.punct(") ")?;
+        self.parts_block(&func.body.0)
+    }
+
+    fn func_arg(&mut self, arg: &FuncArg) -> fmt::Result {
+        match arg {
+            FuncArg::Expr(expr) => self.expr(expr, ASSIGN),
+            FuncArg::Pat(pat) => self.pat(pat),
+        }
+    }
+
+    fn decorators(&mut self, decorators: &[Decorator]) -> fmt::Result {
+        for decorator in decorators {
+            self.write("@")?;
+            self.expr(&decorator.expression, CALL)?;
+            self.write(" ")?;
+        }
+        Ok(())
+    }
+
+    fn class(&mut self, class: &Class) -> fmt::Result {
+        self.decorators(&class.decorators)?;
+        self.write("class")?;
+        if let Some(id) = &class.id {
+            self.write(" ")?;
+            self.ident(id)?;
+        }
+        if let Some(super_class) = &class.super_class {
+            self.write(" extends ")?;
+            self.callee(super_class)?;
+        }
+        self.punct(" {")?;
+        self.indent += 1;
+        for member in &class.body.0 {
+            self.newline()?;
+            self.class_member(member)?;
+        }
+        self.indent -= 1;
+        if !class.body.0.is_empty() {
+            self.newline()?;
+        }
+        self.write("}")
+    }
+
+    fn prop_key(&mut self, key: &PropKey, computed: bool) -> fmt::Result {
+        if computed {
+            self.write("[")?;
+        }
+        match key {
+            PropKey::Lit(lit) => self.lit(lit)?,
+            PropKey::Expr(expr) => self.expr(expr, ASSIGN)?,
+            PropKey::Pat(pat) => self.pat(pat)?,
+            PropKey::Private(id) => self.private_id(id)?,
+        }
+        if computed {
+            self.write("]")?;
+        }
+        Ok(())
+    }
+
+    /// Write the part of a method after any `static`, `accessor`
+    /// is `get ` or `set ` for accessors and empty otherwise
+    fn method(
+        &mut self,
+        key: &PropKey,
+        computed: bool,
+        accessor: &str,
+        func: &Func,
+    ) -> fmt::Result {
+        self.write(accessor)?;
+        if func.is_async {
+            self.write("async ")?;
+        }
+        if func.generator {
+            self.write("*")?;
+        }
+        self.prop_key(key, computed)?;
+        self.func_params_and_body(func)
+    }
+
+    fn class_member(&mut self, member: &ClassMember) -> fmt::Result {
+        self.decorators(member.decorators())?;
+        if member.is_static() {
+            self.write("static ")?;
+        }
+        match member {
+            ClassMember::Method(method) => {
+                let accessor = match method.kind {
+                    MethodKind::Get => "get ",
+                    MethodKind::Set => "set ",
+                    MethodKind::Constructor | MethodKind::Method => "",
+                };
+                self.method(&method.key, method.computed, accessor, &method.value)
+            }
+            ClassMember::Field(field) => {
+                self.prop_key(&field.key, field.computed)?;
+                if let Some(value) = &field.value {
+                    self.punct(" = ")?;
+                    self.expr(value, ASSIGN)?;
+                }
+                self.write(";")
+            }
+            ClassMember::StaticBlock(block) => self.parts_block(&block.0),
+        }
+    }
+
+    /// Write a property of an object literal or pattern
+    fn prop(&mut self, prop: &Prop) -> fmt::Result {
+        if let Some(func) = prop.method_func() {
+            let accessor = match prop.kind {
+                PropKind::Get => "get ",
+                PropKind::Set => "set ",
+                _ => "",
+            };
+            return self.method(&prop.key, prop.computed, accessor, func);
+        }
+        if prop.short_hand {
+            return match &prop.value {
+                PropValue::Pat(pat @ Pat::Assign(_)) => self.pat(pat),
+                _ => self.prop_key(&prop.key, prop.computed),
+            };
+        }
+        self.prop_key(&prop.key, prop.computed)?;
+        match &prop.value {
+            PropValue::Expr(expr) => {
+                self.punct(": ")?;
+                self.expr(expr, ASSIGN)?;
+            }
+            PropValue::Pat(pat) => {
+                self.punct(": ")?;
+                self.pat(pat)?;
+            }
+            PropValue::None => {}
+        }
+        Ok(())
+    }
+
+    fn sub_stmt(&mut self, stmt: &Stmt) -> fmt::Result {
+        self.write(" ")?;
+        self.stmt(stmt)
+    }
+
+    /// Write a statement and keep it from running into whatever
+    /// its parent writes after it on the same line
+    fn same_line_stmt(&mut self, stmt: &Stmt) -> fmt::Result {
+        let same_line = std::mem::replace(&mut self.same_line, true);
+        self.sub_stmt(stmt)?;
+        self.same_line = same_line;
+        Ok(())
+    }
+
+    fn expr_stmt(&mut self, expr: &Expr) -> fmt::Result {
+        if starts_ambiguously(expr) {
+            self.paren_expr(expr)
+        } else {
+            self.expr(expr, SEQUENCE)
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> fmt::Result {
+        match stmt {
+            Stmt::Expr(expr) if !self.semicolons() => {
+                // without a `;` on the line before, a statement
+                // starting with any of these would continue it
+                let mut line = Codegen::new(String::new(), self.options);
+                line.indent = self.indent;
+                line.expr_stmt(expr)?;
+                if line.out.starts_with(&['(', '[', '`', '+', '-', '/'][..]) {
+                    self.write(";")?;
+                }
+                self.write(&line.out)?;
+                self.semi()
+            }
+            Stmt::Expr(expr) => {
+                self.expr_stmt(expr)?;
+                self.semi()
+            }
+            Stmt::Block(block) => self.parts_block(&block.0),
+            Stmt::Empty => self.write(";"),
+            Stmt::Debugger => self.write("debugger;"),
+            Stmt::With(stmt) => {
+                self.write("with (")?;
+                self.expr(&stmt.object, SEQUENCE)?;
+                self.write(")")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::Return(arg) => {
+                self.write("return")?;
+                if let Some(arg) = arg {
+                    self.write(" ")?;
+                    self.expr(arg, SEQUENCE)?;
+                }
+                self.semi()
+            }
+            Stmt::Labeled(stmt) => {
+                self.ident(&stmt.label)?;
+                self.write(":")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::Break(label) | Stmt::Continue(label) => {
+                self.write(if let Stmt::Break(_) = stmt {
+                    "break"
+                } else {
+                    "continue"
+                })?;
+                if let Some(label) = label {
+                    self.write(" ")?;
+                    self.ident(label)?;
+                }
+                self.semi()
+            }
+            Stmt::If(stmt) => {
+                self.write("if (")?;
+                self.expr(&stmt.test, SEQUENCE)?;
+                self.write(")")?;
+                match &stmt.alternate {
+                    Some(alt) => {
+                        if ends_with_open_if(&stmt.consequent) {
+                            self.write(" ")?;
+                            self.parts_block(&[ProgramPart::Stmt((*stmt.consequent).clone())])?;
+                        } else {
+                            self.same_line_stmt(&stmt.consequent)?;
+                        }
+                        self.punct(" else")?;
+                        self.sub_stmt(alt)
+                    }
+                    None => self.sub_stmt(&stmt.consequent),
+                }
+            }
+            Stmt::Switch(stmt) => {
+                self.write("switch (")?;
+                self.expr(&stmt.discriminant, SEQUENCE)?;
+                self.write(")")?;
+                self.punct(" {")?;
+                self.indent += 1;
+                for case in &stmt.cases {
+                    self.newline()?;
+                    match &case.test {
+                        Some(test) => {
+                            self.write("case ")?;
+                            self.expr(test, SEQUENCE)?;
+                            self.write(":")?;
+                        }
+                        None => self.write("default:")?,
+                    }
+                    self.indent += 1;
+                    for part in &case.consequent {
+                        self.newline()?;
+                        self.program_part(part)?;
+                    }
+                    self.indent -= 1;
+                }
+                self.indent -= 1;
+                if !stmt.cases.is_empty() {
+                    self.newline()?;
+                }
+                self.write("}")
+            }
+            Stmt::Throw(expr) => {
+                self.write("throw ")?;
+                self.expr(expr, SEQUENCE)?;
+                self.semi()
+            }
+            Stmt::Try(stmt) => {
+                self.write("try ")?;
+                self.parts_block(&stmt.block.0)?;
+                if let Some(handler) = &stmt.handler {
+                    self.write(" catch ")?;
+                    if let Some(param) = &handler.param {
+                        self.write("(")?;
+                        self.pat(param)?;
+                        self.punct(") ")?;
+                    }
+                    self.parts_block(&handler.body.0)?;
+                }
+                if let Some(finalizer) = &stmt.finalizer {
+                    self.write(" finally ")?;
+                    self.parts_block(&finalizer.0)?;
+                }
+                Ok(())
+            }
+            Stmt::While(stmt) => {
+                self.write("while (")?;
+                self.expr(&stmt.test, SEQUENCE)?;
+                self.write(")")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::DoWhile(stmt) => {
+                self.write("do")?;
+                self.same_line_stmt(&stmt.body)?;
+                self.write(" while (")?;
+                self.expr(&stmt.test, SEQUENCE)?;
+                self.write(")")?;
+                self.semi()
+            }
+            Stmt::For(stmt) => {
+                self.write("for (")?;
+                self.no_in = true;
+                let init = match &stmt.init {
+                    Some(LoopInit::Variable(kind, decls)) => self.var_decls(*kind, decls),
+                    Some(LoopInit::Expr(expr)) => self.expr(expr, SEQUENCE),
+                    None => Ok(()),
+                };
+                self.no_in = false;
+                init?;
+                self.write(";")?;
+                if let Some(test) = &stmt.test {
+                    self.write(" ")?;
+                    self.expr(test, SEQUENCE)?;
+                }
+                self.write(";")?;
+                if let Some(update) = &stmt.update {
+                    self.write(" ")?;
+                    self.expr(update, SEQUENCE)?;
+                }
+                self.write(")")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::ForIn(stmt) => {
+                self.write("for (")?;
+                self.loop_left(&stmt.left)?;
+                self.write(" in ")?;
+                self.expr(&stmt.right, SEQUENCE)?;
+                self.write(")")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::ForOf(stmt) => {
+                self.write("for ")?;
+                if stmt.is_await {
+                    self.write("await ")?;
+                }
+                self.write("(")?;
+                self.loop_left(&stmt.left)?;
+                self.write(" of ")?;
+                self.expr(&stmt.right, ASSIGN)?;
+                self.write(")")?;
+                self.sub_stmt(&stmt.body)
+            }
+            Stmt::Var(decls) => {
+                self.var_decls(VarKind::Var, decls)?;
+                self.semi()
+            }
+        }
+    }
+
+    fn loop_left(&mut self, left: &LoopLeft) -> fmt::Result {
+        match left {
+            LoopLeft::Expr(expr) => self.expr(expr, CALL),
+            LoopLeft::Variable(kind, decl) => {
+                self.write(kind.as_str())?;
+                self.write(" ")?;
+                self.var_decl(decl)
+            }
+            LoopLeft::Pat(pat) => self.pat(pat),
+        }
+    }
+
+    fn paren_expr(&mut self, expr: &Expr) -> fmt::Result {
+        let no_in = std::mem::replace(&mut self.no_in, false);
+        self.write("(")?;
+        let inner = self.expr(expr, SEQUENCE);
+        self.no_in = no_in;
+        inner?;
+        self.write(")")
+    }
+
+    /// Write an expression, wrapping it in parentheses when it
+    /// binds more loosely than `min`
+    fn expr(&mut self, expr: &Expr, min: u8) -> fmt::Result {
+        let is_in = matches!(expr, Expr::Binary(bin) if bin.operator == BinaryOp::In);
+        if expr_prec(expr) < min || (self.no_in && is_in) {
+            self.paren_expr(expr)
+        } else {
+            self.expr_inner(expr)
+        }
+    }
+
+    /// The object of a member, the callee of a call or the tag of a
+    /// tagged template, an optional chain needs to be wrapped here
+    /// otherwise the chain would continue into the parent. A `new`
+    /// without an argument list would pick up the parent's access
+    /// or arguments as its own
+    fn callee(&mut self, expr: &Expr) -> fmt::Result {
+        match expr {
+            Expr::OptionalChain(_) | Expr::Lit(Lit::Number(_)) => self.paren_expr(expr),
+            Expr::New(new) if !new.has_parens() => self.paren_expr(expr),
+            _ => self.expr(expr, CALL),
+        }
+    }
+
+    fn args(&mut self, args: &[Expr]) -> fmt::Result {
+        self.write("(")?;
+        self.comma_sep(args, |g, arg| g.expr(arg, ASSIGN))?;
+        self.write(")")
+    }
+
+    fn expr_inner(&mut self, expr: &Expr) -> fmt::Result {
+        match expr {
+            Expr::Array(elements) => {
+                self.write("[")?;
+                self.comma_sep(elements, |g, el| match el {
+                    Some(el) => g.expr(el, ASSIGN),
+                    None => Ok(()),
+                })?;
+                if let Some(None) = elements.last() {
+                    self.write(",")?;
+                }
+                self.write("]")
+            }
+            Expr::ArrowFunc(arrow) => {
+                if arrow.is_async {
+                    self.write("async ")?;
+                }
+                self.write("(")?;
+                self.comma_sep(&arrow.params, Self::func_arg)?;
+                self.punct(") => ")?;
+                match &arrow.body {
+                    ArrowFuncBody::FuncBody(body) => self.parts_block(&body.0),
+                    ArrowFuncBody::Expr(expr) => {
+                        if starts_ambiguously(expr) {
+                            self.paren_expr(expr)
+                        } else {
+                            self.expr(expr, ASSIGN)
+                        }
+                    }
+                }
+            }
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                if *is_async {
+                    self.write("async ")?;
+                }
+                self.write("(")?;
+                self.comma_sep(args, Self::func_arg)?;
+                self.write(")")
+            }
+            Expr::Assign(assign) => {
+                match &assign.left {
+                    AssignLeft::Pat(pat) => self.pat(pat)?,
+                    AssignLeft::Expr(expr) => self.expr(expr, CALL)?,
+                }
+                self.punct(" ")?;
+                self.write(assign.operator.as_str())?;
+                self.punct(" ")?;
+                self.expr(&assign.right, ASSIGN)
+            }
+            Expr::Await(arg) => {
+                self.write("await ")?;
+                self.expr(arg, UNARY)
+            }
+            Expr::Binary(bin) => {
+                let prec = bin.operator.precedence();
+                let (left, right) = if bin.operator.associativity() == Associativity::Right {
+                    // `-a ** b` is a syntax error so unary operands
+                    // also need to be wrapped
+                    (POSTFIX, prec)
+                } else {
+                    (prec, prec + 1)
+                };
+                self.expr(&bin.left, left)?;
+                self.write(" ")?;
+                self.write(bin.operator.as_str())?;
+                self.write(" ")?;
+                self.expr(&bin.right, right)
+            }
+            Expr::Class(class) => self.class(class),
+            Expr::Call(call) => {
+                self.callee(&call.callee)?;
+                if call.optional {
+                    self.write("?.")?;
+                }
+                self.args(&call.arguments)
+            }
+            Expr::Conditional(cond) => {
+                self.expr(&cond.test, NULLISH)?;
+                self.punct(" ? ")?;
+                self.expr(&cond.consequent, ASSIGN)?;
+                self.punct(" : ")?;
+                self.expr(&cond.alternate, ASSIGN)
+            }
+            Expr::Func(func) => self.func(func),
+            Expr::Ident(ident) => self.ident(ident),
+            Expr::Import(import) => {
+                self.write("import(")?;
+                self.expr(&import.source, ASSIGN)?;
+                if let Some(options) = &import.options {
+                    self.punct(", ")?;
+                    self.expr(options, ASSIGN)?;
+                }
+                self.write(")")
+            }
+            Expr::Lit(lit) => self.lit(lit),
+            Expr::Logical(logical) => {
+                let prec = logical.operator.precedence();
+                // `??` can't be mixed with `&&` or `||` without parentheses
+                let mixed = |e: &Expr| match e {
+                    Expr::Logical(child) => !child.operator.can_mix_with(logical.operator),
+                    _ => false,
+                };
+                if mixed(&logical.left) {
+                    self.paren_expr(&logical.left)?;
+                } else {
+                    self.expr(&logical.left, prec)?;
+                }
+                self.write(" ")?;
+                self.write(logical.operator.as_str())?;
+                self.write(" ")?;
+                if mixed(&logical.right) {
+                    self.paren_expr(&logical.right)
+                } else {
+                    self.expr(&logical.right, prec + 1)
+                }
+            }
+            Expr::Member(member) => {
+                self.callee(&member.object)?;
+                let dot = if member.optional { "?." } else { "." };
+                match &member.property {
+                    MemberProp::Computed(property) => {
+                        self.write(if member.optional { "?.[" } else { "[" })?;
+                        self.expr(property, SEQUENCE)?;
+                        self.write("]")
+                    }
+                    MemberProp::Ident(ident) => {
+                        self.write(dot)?;
+                        self.ident(ident)
+                    }
+                    MemberProp::Private(id) => {
+                        self.write(dot)?;
+                        self.private_id(id)
+                    }
+                }
+            }
+            Expr::MetaProp(meta) => {
+                self.ident(&meta.meta)?;
+                self.write(".")?;
+                self.ident(&meta.property)
+            }
+            Expr::OptionalChain(expr) => self.expr_inner(expr),
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(expr) => self.paren_expr(expr),
+            Expr::PrivateId(id) => self.private_id(id),
+            Expr::New(new) => {
+                self.write("new ")?;
+                if contains_call(&new.callee) {
+                    self.paren_expr(&new.callee)?;
+                } else {
+                    self.callee(&new.callee)?;
+                }
+                match &new.arguments {
+                    Some(args) => self.args(args),
+                    None => Ok(()),
+                }
+            }
+            Expr::Obj(props) => {
+                if props.is_empty() {
+                    return self.write("{}");
+                }
+                self.punct("{ ")?;
+                self.comma_sep(props, |g, prop| match prop {
+                    ObjProp::Prop(prop) => g.prop(prop),
+                    ObjProp::Spread(expr) => {
+                        g.write("...")?;
+                        g.expr(expr, ASSIGN)
+                    }
+                })?;
+                self.punct(" }")
+            }
+            Expr::Sequence(exprs) => self.comma_sep(exprs, |g, e| g.expr(e, ASSIGN)),
+            Expr::Spread(expr) => {
+                self.write("...")?;
+                self.expr(expr, ASSIGN)
+            }
+            Expr::Super => self.write("super"),
+            Expr::TaggedTemplate(tagged) => {
+                self.callee(&tagged.tag)?;
+                self.template(&tagged.quasi)
+            }
+            Expr::This => self.write("this"),
+            Expr::Unary(unary) => {
+                self.write(unary.operator.as_str())?;
+                // keywords need a space and `- -a` can't be glued into `--a`
+                let glued = match (unary.operator, &*unary.argument) {
+                    (UnaryOp::TypeOf, _) | (UnaryOp::Void, _) | (UnaryOp::Delete, _) => true,
+                    (UnaryOp::Minus, Expr::Unary(UnaryExpr { operator: UnaryOp::Minus, .. }))
+                    | (UnaryOp::Plus, Expr::Unary(UnaryExpr { operator: UnaryOp::Plus, .. })) => {
+                        true
+                    }
+                    (UnaryOp::Minus, Expr::Update(update)) => {
+                        update.prefix && update.operator == UpdateOp::Decrement
+                    }
+                    (UnaryOp::Plus, Expr::Update(update)) => {
+                        update.prefix && update.operator == UpdateOp::Increment
+                    }
+                    _ => false,
+                };
+                if glued {
+                    self.write(" ")?;
+                }
+                self.expr(&unary.argument, UNARY)
+            }
+            Expr::Update(update) => {
+                let op = update.operator.as_str();
+                if update.prefix {
+                    self.write(op)?;
+                    self.expr(&update.argument, UNARY)
+                } else {
+                    self.expr(&update.argument, CALL)?;
+                    self.write(op)
+                }
+            }
+            Expr::Yield(y) => {
+                self.write(if y.delegate { "yield*" } else { "yield" })?;
+                if let Some(arg) = &y.argument {
+                    self.write(" ")?;
+                    self.expr(arg, ASSIGN)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn ident(&mut self, ident: &Ident) -> fmt::Result {
+        self.write(&ident.name)
+    }
+
+    fn private_id(&mut self, id: &PrivateId) -> fmt::Result {
+        self.write("#")?;
+        self.write(&id.name)
+    }
+
+    fn template(&mut self, template: &TemplateLit) -> fmt::Result {
+        // the raw text of each quasi already includes the
+        // backticks and `${`/`}` around it
+        let mut exprs = template.expressions.iter();
+        for quasi in &template.quasis {
+            self.write(&quasi.raw)?;
+            if let Some(expr) = exprs.next() {
+                self.expr(expr, SEQUENCE)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn lit(&mut self, lit: &Lit) -> fmt::Result {
+        match lit {
+            Lit::Null => self.write("null"),
+            Lit::String(string) => self.string(string),
+            Lit::Number(number) => self.write(number.raw()),
+            Lit::BigInt(digits) => write!(self.out, "{}n", digits),
+            Lit::Boolean(b) => write!(self.out, "{}", b),
+            Lit::RegEx(re) => write!(self.out, "/{}/{}", re.pattern, re.flags),
+            Lit::Template(template) => self.template(template),
+        }
+    }
+
+    fn string(&mut self, string: &StringLit) -> fmt::Result {
+        let (from, value) = match string {
+            StringLit::Double(value) => ('"', value),
+            StringLit::Single(value) => ('\'', value),
+        };
+        let to = match self.options.quote {
+            QuoteStyle::Preserve => from,
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Double => '"',
+        };
+        self.out.write_char(to)?;
+        if to == from {
+            self.write(value)?;
+            return self.out.write_char(to);
+        }
+        // the old quote no longer needs escaping and the new one does
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(escaped) if escaped == from => self.out.write_char(escaped)?,
+                    Some(escaped) => {
+                        self.out.write_char('\\')?;
+                        self.out.write_char(escaped)?;
+                    }
+                    None => self.out.write_char('\\')?,
+                },
+                c if c == to => {
+                    self.out.write_char('\\')?;
+                    self.out.write_char(c)?;
+                }
+                c => self.out.write_char(c)?,
+            }
+        }
+        self.out.write_char(to)
+    }
+
+    fn pat(&mut self, pat: &Pat) -> fmt::Result {
+        match pat {
+            Pat::Ident(ident) => self.ident(ident),
+            Pat::Obj(parts) => {
+                if parts.is_empty() {
+                    return self.write("{}");
+                }
+                self.punct("{ ")?;
+                self.comma_sep(parts, |g, part| match part {
+                    ObjPatPart::Assign(prop) => g.prop(prop),
+                    ObjPatPart::Rest(pat) => {
+                        g.write("...")?;
+                        g.pat(pat)
+                    }
+                })?;
+                self.punct(" }")
+            }
+            Pat::Array(parts) => {
+                self.write("[")?;
+                self.comma_sep(parts, |g, part| match part {
+                    Some(ArrayPatPart::Pat(pat)) => g.pat(pat),
+                    Some(ArrayPatPart::Expr(expr)) => g.expr(expr, ASSIGN),
+                    None => Ok(()),
+                })?;
+                if let Some(None) = parts.last() {
+                    self.write(",")?;
+                }
+                self.write("]")
+            }
+            Pat::RestElement(pat) => {
+                self.write("...")?;
+                self.pat(pat)
+            }
+            Pat::Assign(assign) => {
+                self.pat(&assign.left)?;
+                self.punct(" = ")?;
+                self.expr(&assign.right, ASSIGN)
+            }
+        }
+    }
+}
+
+impl<'a> Display for Program<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).program(self)
+    }
+}
+
+impl<'a> Display for ProgramPart<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).program_part(self)
+    }
+}
+
+impl<'a> Display for Decl<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).decl(self)
+    }
+}
+
+impl<'a> Display for Stmt<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).stmt(self)
+    }
+}
+
+impl<'a> Display for Expr<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).expr(self, SEQUENCE)
+    }
+}
+
+impl<'a> Display for Pat<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Codegen::new(f, &CodegenOptions::default()).pat(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ident<'a>(name: &'a str) -> Box<Expr<'a>> {
+        Box::new(Expr::ident_from(name))
+    }
+
+    fn bin<'a>(left: Box<Expr<'a>>, operator: BinaryOp, right: Box<Expr<'a>>) -> Box<Expr<'a>> {
+        Box::new(Expr::Binary(BinaryExpr {
+            operator,
+            left,
+            right,
+        }))
+    }
+
+    fn logical<'a>(
+        left: Box<Expr<'a>>,
+        operator: LogicalOp,
+        right: Box<Expr<'a>>,
+    ) -> Box<Expr<'a>> {
+        Box::new(Expr::Logical(LogicalExpr {
+            operator,
+            left,
+            right,
+        }))
+    }
+
+    #[test]
+    fn precedence_parens() {
+        let sum = bin(ident("a"), BinaryOp::Plus, ident("b"));
+        let product = bin(sum.clone(), BinaryOp::Times, ident("c"));
+        assert_eq!(product.to_string(), "(a + b) * c");
+        let product = bin(ident("c"), BinaryOp::Times, sum.clone());
+        assert_eq!(product.to_string(), "c * (a + b)");
+        let sum = bin(
+            bin(ident("a"), BinaryOp::Times, ident("b")),
+            BinaryOp::Plus,
+            ident("c"),
+        );
+        assert_eq!(sum.to_string(), "a * b + c");
+    }
+
+    #[test]
+    fn associativity() {
+        let left = bin(
+            bin(ident("a"), BinaryOp::Minus, ident("b")),
+            BinaryOp::Minus,
+            ident("c"),
+        );
+        assert_eq!(left.to_string(), "a - b - c");
+        let right = bin(
+            ident("a"),
+            BinaryOp::Minus,
+            bin(ident("b"), BinaryOp::Minus, ident("c")),
+        );
+        assert_eq!(right.to_string(), "a - (b - c)");
+        let pow = bin(
+            ident("a"),
+            BinaryOp::PowerOf,
+            bin(ident("b"), BinaryOp::PowerOf, ident("c")),
+        );
+        assert_eq!(pow.to_string(), "a ** b ** c");
+        let neg = Box::new(Expr::Unary(UnaryExpr {
+            operator: UnaryOp::Minus,
+            prefix: true,
+            argument: ident("a"),
+        }));
+        assert_eq!(bin(neg, BinaryOp::PowerOf, ident("b")).to_string(), "(-a) ** b");
+        let negative = Box::new(Expr::from(-1.0));
+        let two = Box::new(Expr::from(2.0));
+        assert_eq!(bin(negative.clone(), BinaryOp::PowerOf, two).to_string(), "(-1) ** 2");
+        let minus = Expr::Unary(UnaryExpr {
+            operator: UnaryOp::Minus,
+            prefix: true,
+            argument: negative,
+        });
+        assert_eq!(minus.to_string(), "- -1");
+    }
+
+    #[test]
+    fn nullish_mixing() {
+        let or = logical(ident("a"), LogicalOp::Or, ident("b"));
+        let nullish = logical(or, LogicalOp::NullishCoalescing, ident("c"));
+        assert_eq!(nullish.to_string(), "(a || b) ?? c");
+    }
+
+    #[test]
+    fn member_and_call() {
+        let call = Expr::Call(CallExpr {
+            callee: Box::new(Expr::Member(MemberExpr {
+                object: bin(ident("a"), BinaryOp::Plus, ident("b")),
+                property: MemberProp::ident_from("c"),
+                optional: true,
+            })),
+            arguments: vec![Expr::Sequence(SequenceExpr::new(
+                Expr::ident_from("d"),
+                vec![Expr::ident_from("e")],
+            ))],
+            optional: false,
+        });
+        assert_eq!(call.to_string(), "(a + b)?.c((d, e))");
+        let new = Expr::New(NewExpr {
+            callee: Box::new(Expr::Call(CallExpr {
+                callee: ident("f"),
+                arguments: vec![],
+                optional: false,
+            })),
+            arguments: Some(vec![]),
+        });
+        assert_eq!(new.to_string(), "new (f())()");
+    }
+
+    #[test]
+    fn statements() {
+        let program = Program::script(vec![
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Let,
+                vec![VarDecl {
+                    id: Pat::ident_from("x"),
+                    init: Some(Expr::Obj(vec![])),
+                }],
+            )),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Obj(vec![]))),
+            ProgramPart::Stmt(Stmt::If(IfStmt {
+                test: Expr::ident_from("a"),
+                consequent: Box::new(Stmt::If(IfStmt {
+                    test: Expr::ident_from("b"),
+                    consequent: Box::new(Stmt::Return(None)),
+                    alternate: None,
+                })),
+                alternate: Some(Box::new(Stmt::Debugger)),
+            })),
+        ]);
+        assert_eq!(
+            program.to_string(),
+            "let x = {};\n({});\nif (a) {\n    if (b) return;\n} else debugger;"
+        );
+    }
+
+    #[test]
+    fn arrow_object_body() {
+        let arrow = Expr::ArrowFunc(ArrowFuncExpr {
+            id: None,
+            params: vec![],
+            body: ArrowFuncBody::Expr(Box::new(Expr::Obj(vec![]))),
+            expression: true,
+            is_async: false,
+        });
+        assert_eq!(arrow.to_string(), "() => ({})");
+    }
+
+    #[test]
+    fn arrow_bodies() {
+        let arrow = |body| {
+            Expr::ArrowFunc(ArrowFuncExpr {
+                id: None,
+                params: vec![FuncArg::Pat(Pat::ident_from("x"))],
+                expression: matches!(body, ArrowFuncBody::Expr(_)),
+                body,
+                is_async: false,
+            })
+        };
+        // x => x + 1
+        let concise = arrow(ArrowFuncBody::Expr(bin(
+            ident("x"),
+            BinaryOp::Plus,
+            Box::new(Expr::Lit(Lit::number_from("1"))),
+        )));
+        assert_eq!(concise.to_string(), "(x) => x + 1");
+        // x => { return x + 1 }
+        let block = arrow(ArrowFuncBody::FuncBody(FuncBody(vec![ProgramPart::Stmt(
+            Stmt::Return(Some(*bin(
+                ident("x"),
+                BinaryOp::Plus,
+                Box::new(Expr::Lit(Lit::number_from("1"))),
+            ))),
+        )])));
+        assert_eq!(block.to_string(), "(x) => {\n    return x + 1;\n}");
+        // () => ({a: 1})
+        let obj = Expr::Obj(vec![ObjProp::Prop(Prop {
+            key: PropKey::Expr(Expr::ident_from("a")),
+            value: PropValue::Expr(Expr::Lit(Lit::number_from("1"))),
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand: false,
+            is_static: false,
+            decorators: vec![],
+        })]);
+        assert_eq!(
+            arrow(ArrowFuncBody::Expr(Box::new(obj.clone()))).to_string(),
+            "(x) => ({ a: 1 })"
+        );
+        // x => ({a: 1}).a, only the object needs the parens but
+        // wrapping the whole body is just as valid
+        let member = Expr::Member(MemberExpr {
+            object: Box::new(obj),
+            property: MemberProp::ident_from("a"),
+            optional: false,
+        });
+        assert_eq!(
+            arrow(ArrowFuncBody::Expr(Box::new(member))).to_string(),
+            "(x) => ({ a: 1 }.a)"
+        );
+        // x => (x, 1)
+        let seq = Expr::Sequence(SequenceExpr::new(
+            Expr::ident_from("x"),
+            vec![Expr::Lit(Lit::number_from("1"))],
+        ));
+        assert_eq!(
+            arrow(ArrowFuncBody::Expr(Box::new(seq))).to_string(),
+            "(x) => (x, 1)"
+        );
+    }
+
+    #[test]
+    fn object_methods() {
+        let method = |key, kind, computed, func| {
+            ObjProp::Prop(Prop {
+                key: PropKey::Expr(key),
+                value: PropValue::Expr(Expr::Func(func)),
+                kind,
+                method: kind == PropKind::Method,
+                computed,
+                short_hand: false,
+                is_static: false,
+                decorators: vec![],
+            })
+        };
+        let obj = Expr::Obj(vec![
+            method(Expr::ident_from("x"), PropKind::Get, false, Func::builder().build()),
+            method(
+                Expr::ident_from("m"),
+                PropKind::Method,
+                false,
+                Func::builder().async_(true).build(),
+            ),
+            method(
+                Expr::ident_from("k"),
+                PropKind::Method,
+                true,
+                Func::builder().generator(true).build(),
+            ),
+        ]);
+        assert_eq!(obj.to_string(), "{ get x() {}, async m() {}, *[k]() {} }");
+    }
+
+    #[test]
+    fn yield_delegation() {
+        let yields = vec![
+            // yield v
+            YieldExpr {
+                argument: Some(Box::new(Expr::ident_from("v"))),
+                delegate: false,
+            },
+            // yield
+            YieldExpr {
+                argument: None,
+                delegate: false,
+            },
+            // yield* g()
+            YieldExpr {
+                argument: Some(Box::new(Expr::Call(CallExpr {
+                    callee: Box::new(Expr::ident_from("g")),
+                    arguments: vec![],
+                    optional: false,
+                }))),
+                delegate: true,
+            },
+        ];
+        let written: Vec<_> = yields.into_iter().map(|y| Expr::Yield(y).to_string()).collect();
+        assert_eq!(written, vec!["yield v", "yield", "yield* g()"]);
+    }
+
+    #[test]
+    fn member_properties() {
+        let member = |property| Expr::Member(MemberExpr::new(Expr::ident_from("a"), property));
+        let cases = vec![
+            (MemberProp::ident_from("b"), "a.b"),
+            (
+                MemberProp::computed(Expr::Lit(Lit::double_string_from("b"))),
+                "a[\"b\"]",
+            ),
+            (
+                MemberProp::Computed(bin(ident("b"), BinaryOp::Plus, ident("c"))),
+                "a[b + c]",
+            ),
+            (MemberProp::Private(PrivateId::from("priv")), "a.#priv"),
+        ];
+        for (property, expected) in cases {
+            assert_eq!(member(property).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn class_members() {
+        let func = || Func::new(None, vec![], FuncBody(vec![]), false, false);
+        let key = || PropKey::Expr(Expr::ident_from("key"));
+        let class = Expr::Class(Class::new(
+            Some(Ident::from("C")),
+            None,
+            vec![
+                ClassMember::Method(MethodDef::new(
+                    PropKey::Expr(Expr::ident_from("constructor")),
+                    func(),
+                    MethodKind::Constructor,
+                )),
+                ClassMember::Method(MethodDef {
+                    computed: true,
+                    is_static: true,
+                    ..MethodDef::new(key(), func(), MethodKind::Get)
+                }),
+                ClassMember::Field(FieldDef {
+                    computed: true,
+                    is_static: true,
+                    ..FieldDef::new(key(), Some(Expr::Lit(Lit::number_from("1"))))
+                }),
+                ClassMember::Field(FieldDef::new(
+                    PropKey::Private(PrivateId::from("count")),
+                    None,
+                )),
+                ClassMember::StaticBlock(BlockStmt(vec![])),
+            ],
+        ));
+        assert_eq!(
+            class.to_string(),
+            "class C {\n    constructor() {}\n    static get [key]() {}\n    \
+             static [key] = 1;\n    #count;\n    static {}\n}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "preserve-parens")]
+    fn preserved_parens() {
+        // (a) * (b + c)
+        let expr = Expr::Binary(BinaryExpr::new(
+            Expr::Paren(Box::new(Expr::ident_from("a"))),
+            BinaryOp::Times,
+            Expr::Paren(Box::new(Expr::Binary(BinaryExpr::new(
+                Expr::ident_from("b"),
+                BinaryOp::Plus,
+                Expr::ident_from("c"),
+            )))),
+        ));
+        assert_eq!(expr.to_string(), "(a) * (b + c)");
+    }
+
+    #[test]
+    fn default_reexports() {
+        let reexport = |local, exported| {
+            Decl::Export(Box::new(ModExport::Named(NamedExportDecl::Specifier(
+                vec![ExportSpecifier {
+                    local: Ident::from(local),
+                    exported: ModuleExportName::ident_from(exported),
+                }],
+                Some(ExportSource::new(Lit::single_string_from("m"))),
+            ))))
+        };
+        assert_eq!(
+            reexport("default", "Foo").to_string(),
+            "export { default as Foo } from 'm';"
+        );
+        assert_eq!(
+            reexport("Foo", "default").to_string(),
+            "export { Foo as default } from 'm';"
+        );
+    }
+
+    #[test]
+    fn new_parens() {
+        let foo = || Expr::ident_from("foo");
+        let member = |object| Expr::Member(MemberExpr::new(object, MemberProp::ident_from("Bar")));
+        let bare = Expr::New(NewExpr::without_parens(foo()));
+        assert_eq!(bare.to_string(), "new foo");
+        assert_eq!(Expr::New(NewExpr::new(foo(), vec![])).to_string(), "new foo()");
+        let new_member = Expr::New(NewExpr::new(member(foo()), vec![]));
+        assert_eq!(new_member.to_string(), "new foo.Bar()");
+        assert_eq!(member(bare.clone()).to_string(), "(new foo).Bar");
+        let call = Expr::Call(CallExpr::new(bare.clone(), vec![]));
+        assert_eq!(call.to_string(), "(new foo)()");
+        let nested = Expr::New(NewExpr::new(bare, vec![]));
+        assert_eq!(nested.to_string(), "new (new foo)()");
+    }
+
+    #[test]
+    fn unary_and_update_output() {
+        let a = || Expr::ident_from("a");
+        let unary = |op, arg| Expr::Unary(UnaryExpr::new(op, arg));
+        let update = |op, prefix| Expr::Update(UpdateExpr::new(op, a(), prefix));
+        let member = Expr::Member(MemberExpr::new(a(), MemberProp::ident_from("b")));
+        let cases = vec![
+            (unary(UnaryOp::Minus, a()), "-a"),
+            (unary(UnaryOp::Plus, a()), "+a"),
+            (unary(UnaryOp::Not, a()), "!a"),
+            (unary(UnaryOp::Tilde, a()), "~a"),
+            (unary(UnaryOp::TypeOf, a()), "typeof a"),
+            (Expr::Unary(UnaryExpr::void_zero()), "void 0"),
+            (unary(UnaryOp::Delete, member), "delete a.b"),
+            (update(UpdateOp::Increment, true), "++a"),
+            (update(UpdateOp::Increment, false), "a++"),
+            (update(UpdateOp::Decrement, true), "--a"),
+            (update(UpdateOp::Decrement, false), "a--"),
+            (unary(UnaryOp::Minus, update(UpdateOp::Decrement, true)), "- --a"),
+            (unary(UnaryOp::Minus, update(UpdateOp::Decrement, false)), "-a--"),
+            (unary(UnaryOp::Plus, unary(UnaryOp::Plus, a())), "+ +a"),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(expr.to_string(), expected);
+        }
+    }
+
+    fn formatted(program: &Program, options: &CodegenOptions) -> String {
+        let mut out = String::new();
+        program.write_with_options(&mut out, options).unwrap();
+        out
+    }
+
+    #[test]
+    fn codegen_options() {
+        // function f(a, b) {
+        //     if (a) b = { k: "it's" }; else return 'say "hi"';
+        // }
+        // [a, b] = [b, a];
+        // (function () {}());
+        let consequent = Expr::Assign(AssignExpr {
+            operator: AssignOp::Equal,
+            left: AssignLeft::Expr(ident("b")),
+            right: Box::new(Expr::Obj(vec![ObjProp::Prop(Prop {
+                key: PropKey::Expr(Expr::ident_from("k")),
+                value: PropValue::Expr(Expr::Lit(Lit::double_string_from("it's"))),
+                kind: PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: false,
+                is_static: false,
+                decorators: vec![],
+            })])),
+        });
+        let func = Func {
+            id: Some(Ident::from("f")),
+            params: vec![
+                FuncArg::Pat(Pat::ident_from("a")),
+                FuncArg::Pat(Pat::ident_from("b")),
+            ],
+            body: FuncBody(vec![ProgramPart::Stmt(Stmt::If(IfStmt {
+                test: Expr::ident_from("a"),
+                consequent: Box::new(Stmt::Expr(consequent)),
+                alternate: Some(Box::new(Stmt::Return(Some(Expr::Lit(
+                    Lit::single_string_from(r#"say "hi""#),
+                ))))),
+            }))]),
+            generator: false,
+            is_async: false,
+        };
+        let pair = |a, b| vec![Some(Expr::ident_from(a)), Some(Expr::ident_from(b))];
+        let swap = Expr::Assign(AssignExpr {
+            operator: AssignOp::Equal,
+            left: AssignLeft::Expr(Box::new(Expr::Array(pair("a", "b")))),
+            right: Box::new(Expr::Array(pair("b", "a"))),
+        });
+        let iife = Expr::Call(CallExpr::new(
+            Expr::Func(Func {
+                id: None,
+                params: vec![],
+                body: FuncBody(vec![]),
+                generator: false,
+                is_async: false,
+            }),
+            vec![],
+        ));
+        let program = Program::script(vec![
+            ProgramPart::Decl(Decl::Func(func)),
+            ProgramPart::Stmt(Stmt::Expr(swap)),
+            ProgramPart::Stmt(Stmt::Expr(iife)),
+        ]);
+        let default = CodegenOptions::default();
+        assert_eq!(formatted(&program, &default), program.to_string());
+        assert_eq!(
+            program.to_string(),
+            "function f(a, b) {\n    \
+             if (a) b = { k: \"it's\" }; else return 'say \"hi\"';\n\
+             }\n\
+             [a, b] = [b, a];\n\
+             (function() {}());"
+        );
+        let formatter = CodegenOptions {
+            indent: "\t".to_string(),
+            quote: QuoteStyle::Single,
+            semicolons: false,
+            minify: false,
+        };
+        assert_eq!(
+            formatted(&program, &formatter),
+            "function f(a, b) {\n\t\
+             if (a) b = { k: 'it\\'s' }; else return 'say \"hi\"'\n\
+             }\n\
+             ;[a, b] = [b, a]\n\
+             ;(function() {}())"
+        );
+        let minified = CodegenOptions {
+            quote: QuoteStyle::Double,
+            semicolons: false,
+            minify: true,
+            ..CodegenOptions::default()
+        };
+        assert_eq!(
+            formatted(&program, &minified),
+            "function f(a,b){\
+             if (a) b={k:\"it's\"};else return \"say \\\"hi\\\"\";\
+             }\
+             [a,b]=[b,a];\
+             (function(){}());"
+        );
+    }
+
+    #[test]
+    fn import_attributes() {
+        let import = |attributes, attributes_keyword| {
+            Decl::Import(Box::new(ModImport {
+                specifiers: vec![ImportSpecifier::Default(Ident::from("data"))],
+                source: Lit::single_string_from("./data.json"),
+                attributes,
+                attributes_keyword,
+            }))
+        };
+        let json = || {
+            vec![ImportAttribute {
+                key: ImportAttributeKey::Ident(Ident::from("type")),
+                value: Lit::single_string_from("json"),
+            }]
+        };
+        let cases = vec![
+            (json(), Some(AttributesKeyword::With), " with { type: 'json' }"),
+            (json(), Some(AttributesKeyword::Assert), " assert { type: 'json' }"),
+            (json(), None, " with { type: 'json' }"),
+            (vec![], Some(AttributesKeyword::With), " with {}"),
+            (vec![], None, ""),
+        ];
+        for (attributes, keyword, clause) in cases {
+            let expected = format!("import data from './data.json'{};", clause);
+            assert_eq!(import(attributes, keyword).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn in_inside_for_init() {
+        let key_in = || bin(ident("a"), BinaryOp::In, ident("o"));
+        let for_loop = |init| {
+            Stmt::For(ForStmt {
+                init: Some(init),
+                test: Some(*key_in()),
+                update: None,
+                body: Box::new(Stmt::Empty),
+            })
+        };
+        // for (let x = (a in o); a in o;);
+        let decl = for_loop(LoopInit::Variable(
+            VarKind::Let,
+            vec![VarDecl {
+                id: Pat::ident_from("x"),
+                init: Some(*key_in()),
+            }],
+        ));
+        assert_eq!(decl.to_string(), "for (let x = (a in o); a in o;) ;");
+        // for (x = (a in o) || b; a in o;);
+        let assign = Expr::Assign(AssignExpr {
+            operator: AssignOp::Equal,
+            left: AssignLeft::Expr(ident("x")),
+            right: Box::new(*logical(key_in(), LogicalOp::Or, ident("b"))),
+        });
+        let expr = for_loop(LoopInit::Expr(assign));
+        assert_eq!(expr.to_string(), "for (x = (a in o) || b; a in o;) ;");
+    }
+
+    #[test]
+    fn array_holes() {
+        let one = || Some(Expr::Lit(Lit::number_from("1")));
+        let leading = Expr::Array(vec![None, one()]);
+        assert_eq!(leading.to_string(), "[, 1]");
+        let middle = Expr::Array(vec![one(), None, one()]);
+        assert_eq!(middle.to_string(), "[1, , 1]");
+        // a single trailing comma is swallowed, so a trailing
+        // hole needs one more
+        let trailing = Expr::Array(vec![one(), None]);
+        assert_eq!(trailing.to_string(), "[1, ,]");
+        let part = |name| Some(ArrayPatPart::Pat(Pat::ident_from(name)));
+        let pat = Pat::Array(vec![None, part("b"), None]);
+        assert_eq!(pat.to_string(), "[, b, ,]");
+    }
+
+    #[test]
+    fn templates() {
+        // `a${b}c${d}e`
+        let multi = Expr::Lit(Lit::Template(TemplateLit {
+            quasis: vec![
+                TemplateElement::from(false, "a", "`a${"),
+                TemplateElement::from(false, "c", "}c${"),
+                TemplateElement::from(true, "e", "}e`"),
+            ],
+            expressions: vec![Expr::ident_from("b"), Expr::ident_from("d")],
+        }));
+        assert_eq!(multi.to_string(), "`a${b}c${d}e`");
+        // String.raw`\unicode`
+        let tagged = Expr::TaggedTemplate(TaggedTemplateExpr {
+            tag: Box::new(Expr::Member(MemberExpr {
+                object: ident("String"),
+                property: MemberProp::ident_from("raw"),
+                optional: false,
+            })),
+            quasi: TemplateLit {
+                quasis: vec![TemplateElement::invalid(true, "`\\unicode`")],
+                expressions: vec![],
+            },
+        });
+        assert_eq!(tagged.to_string(), "String.raw`\\unicode`");
+    }
+
+    #[test]
+    fn for_await() {
+        let for_of = |is_await| {
+            Stmt::ForOf(ForOfStmt {
+                left: LoopLeft::Variable(
+                    VarKind::Const,
+                    VarDecl {
+                        id: Pat::ident_from("x"),
+                        init: None,
+                    },
+                ),
+                right: Expr::ident_from("stream"),
+                body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+                is_await,
+            })
+        };
+        assert_eq!(for_of(true).to_string(), "for await (const x of stream) {}");
+        assert_eq!(for_of(false).to_string(), "for (const x of stream) {}");
+    }
+
+    #[test]
+    fn object_spread_and_rest() {
+        let spread = Expr::Obj(vec![
+            ObjProp::Spread(Expr::ident_from("a")),
+            ObjProp::Spread(Expr::ident_from("b")),
+        ]);
+        assert_eq!(spread.to_string(), "{ ...a, ...b }");
+        let rest = Pat::Obj(vec![ObjPatPart::Rest(Box::new(Pat::ident_from("rest")))]);
+        assert_eq!(rest.to_string(), "{ ...rest }");
+        let array_spread = Expr::Array(vec![Some(Expr::Spread(Box::new(Expr::ident_from("a"))))]);
+        assert_eq!(array_spread.to_string(), "[...a]");
+    }
+
+    #[test]
+    fn catch_bindings() {
+        let try_catch = |param| {
+            Stmt::Try(TryStmt {
+                block: BlockStmt(vec![]),
+                handler: Some(CatchClause {
+                    param,
+                    body: BlockStmt(vec![]),
+                }),
+                finalizer: None,
+            })
+        };
+        assert_eq!(
+            try_catch(Some(Pat::ident_from("e"))).to_string(),
+            "try {} catch (e) {}"
+        );
+        let message = Pat::Obj(vec![ObjPatPart::Assign(Prop {
+            key: PropKey::Pat(Pat::ident_from("message")),
+            value: PropValue::None,
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand: true,
+            is_static: false,
+            decorators: vec![],
+        })]);
+        assert_eq!(
+            try_catch(Some(message)).to_string(),
+            "try {} catch ({ message }) {}"
+        );
+        assert_eq!(try_catch(None).to_string(), "try {} catch {}");
+    }
+}
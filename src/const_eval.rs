@@ -0,0 +1,411 @@
+//! Evaluating expressions that only depend on literals
+//!
+//! This follows the JS conversion rules for the primitive
+//! types, anything that would need an object, a variable or
+//! a function call is not a constant.
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+
+use crate::expr::{Expr, Lit, StringLit};
+use crate::{BinaryOp, LogicalOp, UnaryOp};
+
+/// The value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+    Undefined,
+}
+
+impl ConstValue {
+    /// The value JS would convert this to with `Number(value)`
+    pub fn to_number(&self) -> f64 {
+        match self {
+            ConstValue::Number(n) => *n,
+            ConstValue::String(s) => string_to_number(s),
+            ConstValue::Bool(b) => *b as u8 as f64,
+            ConstValue::Null => 0.0,
+            ConstValue::Undefined => f64::NAN,
+        }
+    }
+    /// If this value is truthy when used as a condition
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            ConstValue::Number(n) => *n != 0.0 && !n.is_nan(),
+            ConstValue::String(s) => !s.is_empty(),
+            ConstValue::Bool(b) => *b,
+            ConstValue::Null | ConstValue::Undefined => false,
+        }
+    }
+    /// The result of `typeof` for this value
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            ConstValue::Number(_) => "number",
+            ConstValue::String(_) => "string",
+            ConstValue::Bool(_) => "boolean",
+            ConstValue::Null => "object",
+            ConstValue::Undefined => "undefined",
+        }
+    }
+    /// `===`, `NaN` is never equal to anything and `0`
+    /// is equal to `-0`
+    pub fn strict_equals(&self, other: &Self) -> bool {
+        self == other
+    }
+    /// `==`
+    pub fn loose_equals(&self, other: &Self) -> bool {
+        use ConstValue::*;
+        match (self, other) {
+            (Null, Null) | (Undefined, Undefined) | (Null, Undefined) | (Undefined, Null) => true,
+            (Null, _) | (Undefined, _) | (_, Null) | (_, Undefined) => false,
+            (String(a), String(b)) => a == b,
+            _ => self.to_number() == other.to_number(),
+        }
+    }
+}
+
+/// Formats the value the way JS would convert it
+/// to a string, so a string is written without quotes
+/// and `0.1 + 0.2` is `0.30000000000000004`
+impl Display for ConstValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ConstValue::Number(n) => f.write_str(&number_to_string(*n)),
+            ConstValue::String(s) => f.write_str(s),
+            ConstValue::Bool(b) => write!(f, "{}", b),
+            ConstValue::Null => f.write_str("null"),
+            ConstValue::Undefined => f.write_str("undefined"),
+        }
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// If this expression always evaluates to the same
+    /// primitive value, see `const_eval`
+    pub fn is_constant(&self) -> bool {
+        self.const_eval().is_some()
+    }
+    /// Evaluate this expression if it is made up of only literals
+    /// and unary, binary or logical operators.
+    ///
+    /// `NaN`, `Infinity` and `undefined` are treated as constants,
+    /// the caller needs to check they haven't been shadowed. String
+    /// literals with escapes are not evaluated
+    /// ```
+    /// # use resast::prelude::*;
+    /// let sum = Expr::Binary(BinaryExpr::new(
+    ///     Expr::Lit(Lit::number_from("0.1")),
+    ///     BinaryOp::Plus,
+    ///     Expr::Lit(Lit::number_from("0.2")),
+    /// ));
+    /// assert_eq!(sum.const_eval().unwrap().to_string(), "0.30000000000000004");
+    /// ```
+    pub fn const_eval(&self) -> Option<ConstValue> {
+        let ret = match self {
+            Expr::Lit(lit) => return lit_value(lit),
+            Expr::Ident(ident) => match &*ident.name {
+                "NaN" => ConstValue::Number(f64::NAN),
+                "Infinity" => ConstValue::Number(f64::INFINITY),
+                "undefined" => ConstValue::Undefined,
+                _ => return None,
+            },
+            Expr::Unary(unary) => {
+                let arg = unary.argument.const_eval()?;
+                match unary.operator {
+                    UnaryOp::Minus => ConstValue::Number(-arg.to_number()),
+                    UnaryOp::Plus => ConstValue::Number(arg.to_number()),
+                    UnaryOp::Not => ConstValue::Bool(!arg.is_truthy()),
+                    UnaryOp::Tilde => ConstValue::Number(!to_int32(arg.to_number()) as f64),
+                    UnaryOp::TypeOf => ConstValue::String(arg.type_of().to_string()),
+                    UnaryOp::Void => ConstValue::Undefined,
+                    UnaryOp::Delete => return None,
+                }
+            }
+            Expr::Binary(bin) => {
+                let left = bin.left.const_eval()?;
+                let right = bin.right.const_eval()?;
+                return binary(bin.operator, &left, &right);
+            }
+            Expr::Logical(logical) => {
+                let left = logical.left.const_eval()?;
+                let short_circuits = match logical.operator {
+                    LogicalOp::And => !left.is_truthy(),
+                    LogicalOp::Or => left.is_truthy(),
+                    LogicalOp::NullishCoalescing => {
+                        !matches!(left, ConstValue::Null | ConstValue::Undefined)
+                    }
+                };
+                if short_circuits {
+                    left
+                } else {
+                    logical.right.const_eval()?
+                }
+            }
+            _ => return None,
+        };
+        Some(ret)
+    }
+}
+
+fn lit_value(lit: &Lit) -> Option<ConstValue> {
+    let ret = match lit {
+        Lit::Null => ConstValue::Null,
+        Lit::Boolean(b) => ConstValue::Bool(*b),
+        Lit::Number(n) => ConstValue::Number(n.as_f64()?),
+        Lit::String(StringLit::Double(s)) | Lit::String(StringLit::Single(s)) => {
+            if s.contains('\\') {
+                return None;
+            }
+            ConstValue::String(s.to_string())
+        }
+        _ => return None,
+    };
+    Some(ret)
+}
+
+fn binary(op: BinaryOp, left: &ConstValue, right: &ConstValue) -> Option<ConstValue> {
+    use BinaryOp::*;
+    let number =
+        |f: fn(f64, f64) -> f64| ConstValue::Number(f(left.to_number(), right.to_number()));
+    let ret = match op {
+        Plus => match (left, right) {
+            (ConstValue::String(_), _) | (_, ConstValue::String(_)) => {
+                ConstValue::String(format!("{}{}", left, right))
+            }
+            _ => number(|l, r| l + r),
+        },
+        Minus => number(|l, r| l - r),
+        Times => number(|l, r| l * r),
+        Over => number(|l, r| l / r),
+        Mod => number(|l, r| l % r),
+        PowerOf => number(power),
+        LeftShift => number(|l, r| to_int32(l).wrapping_shl(to_uint32(r)) as f64),
+        RightShift => number(|l, r| (to_int32(l) >> (to_uint32(r) & 31)) as f64),
+        UnsignedRightShift => number(|l, r| (to_uint32(l) >> (to_uint32(r) & 31)) as f64),
+        And => number(|l, r| (to_int32(l) & to_int32(r)) as f64),
+        Or => number(|l, r| (to_int32(l) | to_int32(r)) as f64),
+        XOr => number(|l, r| (to_int32(l) ^ to_int32(r)) as f64),
+        Equal => ConstValue::Bool(left.loose_equals(right)),
+        NotEqual => ConstValue::Bool(!left.loose_equals(right)),
+        StrictEqual => ConstValue::Bool(left.strict_equals(right)),
+        StrictNotEqual => ConstValue::Bool(!left.strict_equals(right)),
+        LessThan => ConstValue::Bool(compare(left, right) == Some(Ordering::Less)),
+        GreaterThan => ConstValue::Bool(compare(left, right) == Some(Ordering::Greater)),
+        LessThanEqual => ConstValue::Bool(matches!(
+            compare(left, right),
+            Some(Ordering::Less) | Some(Ordering::Equal)
+        )),
+        GreaterThanEqual => ConstValue::Bool(matches!(
+            compare(left, right),
+            Some(Ordering::Greater) | Some(Ordering::Equal)
+        )),
+        In | InstanceOf => return None,
+    };
+    Some(ret)
+}
+
+/// `**` differs from `powf` when the base is `1` or `-1`
+fn power(base: f64, exponent: f64) -> f64 {
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        f64::NAN
+    } else {
+        base.powf(exponent)
+    }
+}
+
+/// Two strings compare by their UTF-16 code units, anything
+/// else is compared as a number. `None` if either is `NaN`
+fn compare(left: &ConstValue, right: &ConstValue) -> Option<Ordering> {
+    match (left, right) {
+        (ConstValue::String(l), ConstValue::String(r)) => {
+            Some(l.encode_utf16().cmp(r.encode_utf16()))
+        }
+        _ => left.to_number().partial_cmp(&right.to_number()),
+    }
+}
+
+fn to_uint32(n: f64) -> u32 {
+    if !n.is_finite() {
+        return 0;
+    }
+    n.trunc().rem_euclid(4_294_967_296.0) as u32
+}
+
+fn to_int32(n: f64) -> i32 {
+    to_uint32(n) as i32
+}
+
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+    if s.is_empty() {
+        return 0.0;
+    }
+    match s {
+        "Infinity" | "+Infinity" => return f64::INFINITY,
+        "-Infinity" => return f64::NEG_INFINITY,
+        _ => {}
+    }
+    let radix = match s.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &s[2..];
+        if digits.is_empty() {
+            return f64::NAN;
+        }
+        return digits
+            .chars()
+            .try_fold(0f64, |value, c| {
+                c.to_digit(radix).map(|d| value * radix as f64 + d as f64)
+            })
+            .unwrap_or(f64::NAN);
+    }
+    // rust accepts `inf` and `nan` which JS doesn't
+    if !s.bytes().all(|b| b.is_ascii_digit() || b"+-.eE".contains(&b)) {
+        return f64::NAN;
+    }
+    s.parse().unwrap_or(f64::NAN)
+}
+
+/// `Number.prototype.toString` for base 10
+fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n < 0.0 {
+        return format!("-{}", number_to_string(-n));
+    }
+    // the shortest digits that round trip, as `d.ddde-x`
+    let sci = format!("{:e}", n);
+    let (mantissa, exponent) = sci.split_at(sci.find('e').unwrap_or(sci.len()));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent[1..].parse().unwrap_or(0);
+    let k = digits.len() as i32;
+    // where the decimal point goes relative to the digits
+    let point = exponent + 1;
+    if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        let (int, fract) = digits.split_at(point as usize);
+        format!("{}.{}", int, fract)
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let sign = if point - 1 < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        let rest = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{}", rest)
+        };
+        format!("{}{}e{}{}", first, rest, sign, (point - 1).abs())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{BinaryExpr, UnaryExpr};
+
+    fn num<'a>(text: &'a str) -> Expr<'a> {
+        Expr::Lit(Lit::number_from(text))
+    }
+
+    fn string<'a>(text: &'a str) -> Expr<'a> {
+        Expr::Lit(Lit::double_string_from(text))
+    }
+
+    fn bin<'a>(left: Expr<'a>, op: BinaryOp, right: Expr<'a>) -> Expr<'a> {
+        Expr::Binary(BinaryExpr::new(left, op, right))
+    }
+
+    #[test]
+    fn constant_expressions() {
+        // 1 + 2
+        let sum = bin(num("1"), BinaryOp::Plus, num("2"));
+        assert_eq!(sum.const_eval(), Some(ConstValue::Number(3.0)));
+        // "a" + "b"
+        let concat = bin(string("a"), BinaryOp::Plus, string("b"));
+        assert_eq!(concat.const_eval(), Some(ConstValue::String("ab".to_string())));
+        // "1" + 2
+        let mixed = bin(string("1"), BinaryOp::Plus, num("2"));
+        assert_eq!(mixed.const_eval(), Some(ConstValue::String("12".to_string())));
+        // !true
+        let not = Expr::Unary(UnaryExpr::new(UnaryOp::Not, Expr::Lit(Lit::Boolean(true))));
+        assert_eq!(not.const_eval(), Some(ConstValue::Bool(false)));
+        // x + 1
+        let var = bin(Expr::ident_from("x"), BinaryOp::Plus, num("1"));
+        assert!(!var.is_constant());
+        assert_eq!(var.const_eval(), None);
+    }
+
+    #[test]
+    fn number_edge_cases() {
+        let sum = bin(num("0.1"), BinaryOp::Plus, num("0.2"));
+        let value = sum.const_eval().unwrap();
+        assert_ne!(value, ConstValue::Number(0.3));
+        assert_eq!(value.to_string(), "0.30000000000000004");
+        // NaN === NaN
+        let nan = bin(Expr::ident_from("NaN"), BinaryOp::StrictEqual, Expr::ident_from("NaN"));
+        assert_eq!(nan.const_eval(), Some(ConstValue::Bool(false)));
+        // 1 / 0
+        let inf = bin(num("1"), BinaryOp::Over, num("0"));
+        assert_eq!(inf.const_eval(), Some(ConstValue::Number(f64::INFINITY)));
+        assert_eq!(inf.const_eval().unwrap().to_string(), "Infinity");
+        // "" + -Infinity
+        let neg = Expr::Unary(UnaryExpr::new(UnaryOp::Minus, Expr::ident_from("Infinity")));
+        let text = bin(string(""), BinaryOp::Plus, neg);
+        assert_eq!(text.const_eval(), Some(ConstValue::String("-Infinity".to_string())));
+        // "abc" * 2
+        let product = bin(string("abc"), BinaryOp::Times, num("2"));
+        assert!(product.const_eval().unwrap().to_number().is_nan());
+        // "nan" isn't a number in JS
+        assert!(string_to_number("nan").is_nan());
+        assert_eq!(string_to_number(" 0x10 "), 16.0);
+        assert_eq!(string_to_number(""), 0.0);
+    }
+
+    #[test]
+    fn numbers_to_strings() {
+        let cases = [
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (123.456, "123.456"),
+            (0.000001, "0.000001"),
+            (1e-7, "1e-7"),
+            (1.5e-7, "1.5e-7"),
+            (-2.5, "-2.5"),
+            (-0.0, "0"),
+        ];
+        for (n, expected) in cases.iter() {
+            assert_eq!(number_to_string(*n), *expected);
+        }
+    }
+
+    #[test]
+    fn comparisons() {
+        let cases = vec![
+            (bin(string("10"), BinaryOp::LessThan, string("9")), true),
+            (bin(string("10"), BinaryOp::LessThan, num("9")), false),
+            (bin(Expr::Lit(Lit::Null), BinaryOp::Equal, Expr::ident_from("undefined")), true),
+            (bin(Expr::Lit(Lit::Null), BinaryOp::Equal, num("0")), false),
+            (bin(string("1"), BinaryOp::Equal, Expr::Lit(Lit::Boolean(true))), true),
+            (bin(string("1"), BinaryOp::StrictEqual, num("1")), false),
+            (bin(Expr::ident_from("NaN"), BinaryOp::GreaterThanEqual, num("0")), false),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(expr.const_eval(), Some(ConstValue::Bool(expected)), "{:?}", expr);
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::VarKind;
 use crate::{Class, Func, Ident};
 
 /// The declaration of a variable, function, class, import or export
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -41,8 +41,92 @@ pub enum Decl<'a> {
     Export(Box<ModExport<'a>>),
 }
 
+impl<'a> Decl<'a> {
+    /// The kind and declarators of a variable declaration
+    pub fn as_var(&self) -> Option<(VarKind, &[VarDecl<'a>])> {
+        match self {
+            Decl::Var(kind, decls) => Some((*kind, decls)),
+            _ => None,
+        }
+    }
+    /// The declarators of a variable declaration, `a = 1`, `b`
+    /// and `c = 3` for `var a = 1, b, c = 3`
+    pub fn var_decls(&self) -> Option<&[VarDecl<'a>]> {
+        self.as_var().map(|(_, decls)| decls)
+    }
+    pub fn as_func(&self) -> Option<&Func<'a>> {
+        match self {
+            Decl::Func(func) => Some(func),
+            _ => None,
+        }
+    }
+    pub fn as_class(&self) -> Option<&Class<'a>> {
+        match self {
+            Decl::Class(class) => Some(class),
+            _ => None,
+        }
+    }
+    pub fn as_import(&self) -> Option<&ModImport<'a>> {
+        match self {
+            Decl::Import(import) => Some(import),
+            _ => None,
+        }
+    }
+    pub fn as_export(&self) -> Option<&ModExport<'a>> {
+        match self {
+            Decl::Export(export) => Some(export),
+            _ => None,
+        }
+    }
+    /// Check that a function or class declaration has a name.
+    ///
+    /// `Class` and `Func` are shared with their expression forms
+    /// where the name is optional. As a declaration the name is
+    /// required, except directly after `export default`
+    /// ```js
+    /// class A {} // Ok
+    /// export class {} // Err(AnonymousClass)
+    /// export default class {} // Ok
+    /// ```
+    pub fn validate(&self) -> Result<(), DeclError> {
+        match self {
+            Decl::Func(func) if func.id.is_none() => Err(DeclError::AnonymousFunc),
+            Decl::Class(class) if class.id.is_none() => Err(DeclError::AnonymousClass),
+            Decl::Export(export) => match &**export {
+                ModExport::Named(NamedExportDecl::Decl(decl)) => decl.validate(),
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The reasons a declaration is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclError {
+    /// ```js
+    /// function () {}
+    /// ```
+    AnonymousFunc,
+    /// ```js
+    /// class {}
+    /// ```
+    AnonymousClass,
+}
+
+impl std::fmt::Display for DeclError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DeclError::AnonymousFunc => write!(f, "function declaration without a name"),
+            DeclError::AnonymousClass => write!(f, "class declaration without a name"),
+        }
+    }
+}
+
+impl std::error::Error for DeclError {}
+
 /// The identifier and optional value of a variable declaration
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -53,11 +137,25 @@ pub struct VarDecl<'a> {
     pub init: Option<Expr<'a>>,
 }
 
+impl<'a> VarDecl<'a> {
+    /// Every name this declarator binds, see `Pat::bound_names`
+    /// ```js
+    /// let { a, b: [c] } = o; // a, c
+    /// ```
+    pub fn names(&self) -> Vec<&Ident<'a>> {
+        self.id.bound_names()
+    }
+}
+
 /// A module declaration, This would only be available
 /// in an ES Mod, it would be either an import or
 /// export at the top level
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub enum ModDecl<'a> {
     Import(ModImport<'a>),
     Export(ModExport<'a>),
@@ -69,15 +167,92 @@ pub enum ModDecl<'a> {
 /// ```js
 /// import {Thing} from './stuff.js';
 /// ```
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct ModImport<'a> {
     pub specifiers: Vec<ImportSpecifier<'a>>,
     pub source: Lit<'a>,
+    /// Any import attributes
+    /// ```js
+    /// import data from './data.json' with { type: 'json' };
+    /// ```
+    pub attributes: Vec<ImportAttribute<'a>>,
+    /// The keyword that introduced the `attributes`,
+    /// `None` if there was no attributes clause
+    pub attributes_keyword: Option<AttributesKeyword>,
+}
+
+impl<'a> ModImport<'a> {
+    /// The keyword to write before the `attributes`, `None`
+    /// if this import has no attributes clause. A non-empty
+    /// list of `attributes` without an `attributes_keyword`
+    /// is treated as a `with` clause
+    pub fn clause_keyword(&self) -> Option<AttributesKeyword> {
+        AttributesKeyword::for_clause(self.attributes_keyword, &self.attributes)
+    }
+}
+
+/// A single key/value pair from an import attributes clause
+/// ```js
+/// import data from './data.json' with { type: 'json' };
+/// //                                    ^^^^^^^^^^^^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct ImportAttribute<'a> {
+    pub key: ImportAttributeKey<'a>,
+    pub value: Lit<'a>,
+}
+
+/// The key of an `ImportAttribute`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
+pub enum ImportAttributeKey<'a> {
+    /// `{ type: 'json' }`
+    Ident(Ident<'a>),
+    /// `{ 'type': 'json' }`
+    Lit(Lit<'a>),
+}
+
+/// The keyword used to introduce import attributes
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub enum AttributesKeyword {
+    /// `with { type: 'json' }`
+    With,
+    /// The legacy `assert { type: 'json' }`
+    Assert,
+}
+
+impl AttributesKeyword {
+    fn for_clause(keyword: Option<Self>, attributes: &[ImportAttribute]) -> Option<Self> {
+        keyword.or(if attributes.is_empty() {
+            None
+        } else {
+            Some(AttributesKeyword::With)
+        })
+    }
 }
 
 /// The name of the thing being imported
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -107,15 +282,47 @@ pub enum ImportSpecifier<'a> {
     /// ```
     Namespace(Ident<'a>),
 }
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct NormalImportSpec<'a> {
     pub local: Ident<'a>,
-    pub imported: Ident<'a>,
+    pub imported: ModuleExportName<'a>,
+}
+
+/// The name of a binding as another module sees it,
+/// this can be a string literal that is not a valid
+/// identifier
+/// ```js
+/// import {"a-b" as ab} from 'mod';
+/// export {ab as "a-b"};
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
+pub enum ModuleExportName<'a> {
+    Ident(Ident<'a>),
+    Str(Lit<'a>),
+}
+
+impl<'a> ModuleExportName<'a> {
+    pub fn ident_from(s: &'a str) -> Self {
+        ModuleExportName::Ident(Ident::from(s))
+    }
+    pub fn str_from(s: &'a str) -> Self {
+        ModuleExportName::Str(Lit::double_string_from(s))
+    }
 }
 
 /// Something exported from this module
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -139,10 +346,85 @@ pub enum ModExport<'a> {
     /// }
     /// ```
     Named(NamedExportDecl<'a>),
+    /// A re-export of everything from another module,
+    /// optionally as a namespace
     /// ```js
     /// export * from 'mod';
+    /// //or
+    /// export * as ns from 'mod';
     /// ```
-    All(Lit<'a>),
+    All(Option<ModuleExportName<'a>>, ExportSource<'a>),
+}
+
+impl<'a> ModExport<'a> {
+    /// The declaration of an `export default` that exports a
+    /// function or class declaration
+    pub fn default_decl(&self) -> Option<&Decl<'a>> {
+        match self {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => Some(decl),
+            _ => None,
+        }
+    }
+    /// The value of an `export default` that exports an expression
+    pub fn default_expr(&self) -> Option<&Expr<'a>> {
+        match self {
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => Some(expr),
+            _ => None,
+        }
+    }
+    /// The name of a default exported function or class, `None`
+    /// if it is anonymous or this isn't a default export
+    /// ```js
+    /// export default class Foo {} // Some(Foo)
+    /// export default function () {} // None
+    /// ```
+    pub fn default_name(&self) -> Option<&Ident<'a>> {
+        let id = match self.default_decl()? {
+            Decl::Func(func) => &func.id,
+            Decl::Class(class) => &class.id,
+            _ => return None,
+        };
+        id.as_ref()
+    }
+}
+
+/// The module a re-export is coming from
+/// ```js
+/// export * from 'mod';
+/// export {foo} from 'mod' with { type: 'json' };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct ExportSource<'a> {
+    pub source: Lit<'a>,
+    /// Any import attributes
+    pub attributes: Vec<ImportAttribute<'a>>,
+    /// The keyword that introduced the `attributes`,
+    /// `None` if there was no attributes clause
+    pub attributes_keyword: Option<AttributesKeyword>,
+}
+
+impl<'a> ExportSource<'a> {
+    /// A source without any import attributes
+    pub fn new(source: Lit<'a>) -> Self {
+        ExportSource {
+            source,
+            attributes: Vec::new(),
+            attributes_keyword: None,
+        }
+    }
+
+    /// The keyword to write before the `attributes`, `None`
+    /// if this source has no attributes clause. A non-empty
+    /// list of `attributes` without an `attributes_keyword`
+    /// is treated as a `with` clause
+    pub fn clause_keyword(&self) -> Option<AttributesKeyword> {
+        AttributesKeyword::for_clause(self.attributes_keyword, &self.attributes)
+    }
 }
 
 // pub struct NamedExportDecl<'a> {
@@ -154,18 +436,22 @@ pub enum ModExport<'a> {
 /// ```js
 /// export function thing() {}
 /// export {stuff} from 'place';
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub enum NamedExportDecl<'a> {
     Decl(Decl<'a>),
-    Specifier(Vec<ExportSpecifier<'a>>, Option<Lit<'a>>),
+    Specifier(Vec<ExportSpecifier<'a>>, Option<ExportSource<'a>>),
 }
 
 /// A default export
 /// ```js
 /// export default class Thing {}
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -184,7 +470,14 @@ pub enum DefaultExportDecl<'a> {
 /// //aliased
 /// export {Stuff as NewThing} from 'place'
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+/// `default` is a valid name on either side, a `local` of
+/// `default` re-exports another module's default export and an
+/// `exported` of `default` makes a binding this module's default
+/// ```js
+/// export {default as Thing} from 'place';
+/// export {Thing as default} from 'place';
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -192,5 +485,275 @@ pub enum DefaultExportDecl<'a> {
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct ExportSpecifier<'a> {
     pub local: Ident<'a>,
-    pub exported: Ident<'a>,
+    pub exported: ModuleExportName<'a>,
+}
+
+impl<'a> ExportSpecifier<'a> {
+    /// If this refers to the default export of the source
+    /// module, `default as Thing`
+    pub fn is_local_default(&self) -> bool {
+        self.local.name == "default"
+    }
+    /// If this becomes the default export of this
+    /// module, `Thing as default`
+    pub fn is_exported_default(&self) -> bool {
+        match &self.exported {
+            ModuleExportName::Ident(ident) => ident.name == "default",
+            ModuleExportName::Str(Lit::String(s)) => s.inner_matches("default"),
+            ModuleExportName::Str(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{Prop, PropKey, PropValue};
+    use crate::pat::{ArrayPatPart, ObjPatPart};
+    use crate::{ClassBody, PropKind};
+
+    #[test]
+    fn export_all_alias() {
+        // export * from 'm'
+        let plain = ModExport::All(None, ExportSource::new(Lit::single_string_from("m")));
+        // export * as x from 'm'
+        let aliased = ModExport::All(
+            Some(ModuleExportName::ident_from("x")),
+            ExportSource::new(Lit::single_string_from("m")),
+        );
+        let ModExport::All(alias, source) = plain else {
+            panic!("expected an export all, found {:?}", plain)
+        };
+        assert!(alias.is_none());
+        assert_eq!(source.source, Lit::single_string_from("m"));
+        let ModExport::All(alias, source) = aliased else {
+            panic!("expected an export all, found {:?}", aliased)
+        };
+        assert_eq!(alias, Some(ModuleExportName::ident_from("x")));
+        assert_eq!(source.source, Lit::single_string_from("m"));
+    }
+
+    #[test]
+    fn string_specifier_names() {
+        // import {"a-b" as x, y} from 'm'
+        let import = ModImport {
+            specifiers: vec![
+                ImportSpecifier::Normal(NormalImportSpec {
+                    local: Ident::from("x"),
+                    imported: ModuleExportName::str_from("a-b"),
+                }),
+                ImportSpecifier::Normal(NormalImportSpec {
+                    local: Ident::from("y"),
+                    imported: ModuleExportName::ident_from("y"),
+                }),
+            ],
+            source: Lit::single_string_from("m"),
+            attributes: vec![],
+            attributes_keyword: None,
+        };
+        // export {x as "a-b", y}
+        let export = NamedExportDecl::Specifier(
+            vec![
+                ExportSpecifier {
+                    local: Ident::from("x"),
+                    exported: ModuleExportName::str_from("a-b"),
+                },
+                ExportSpecifier {
+                    local: Ident::from("y"),
+                    exported: ModuleExportName::ident_from("y"),
+                },
+            ],
+            None,
+        );
+        let imported: Vec<_> = import
+            .specifiers
+            .iter()
+            .map(|spec| match spec {
+                ImportSpecifier::Normal(spec) => &spec.imported,
+                other => panic!("expected a normal specifier, found {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            imported,
+            vec![
+                &ModuleExportName::Str(Lit::double_string_from("a-b")),
+                &ModuleExportName::Ident(Ident::from("y")),
+            ]
+        );
+        let NamedExportDecl::Specifier(specs, _) = export else {
+            panic!("expected export specifiers, found {:?}", export)
+        };
+        assert_eq!(specs[0].exported, ModuleExportName::Str(Lit::double_string_from("a-b")));
+        assert_eq!(specs[1].exported, ModuleExportName::Ident(Ident::from("y")));
+    }
+
+    #[test]
+    fn default_exports() {
+        // export default function () {}
+        let anon = ModExport::Default(DefaultExportDecl::Decl(Decl::Func(Func::new(
+            None,
+            vec![],
+            crate::FuncBody(vec![]),
+            false,
+            false,
+        ))));
+        assert!(anon.default_decl().and_then(Decl::as_func).is_some());
+        assert_eq!(anon.default_expr(), None);
+        assert_eq!(anon.default_name(), None);
+        // export default class Foo {}
+        let class = ModExport::Default(DefaultExportDecl::Decl(Decl::Class(Class::new(
+            Some(Ident::from("Foo")),
+            None,
+            vec![],
+        ))));
+        assert!(class.default_decl().and_then(Decl::as_class).is_some());
+        assert_eq!(class.default_name(), Some(&Ident::from("Foo")));
+        // export default 42
+        let value = ModExport::Default(DefaultExportDecl::Expr(Expr::Lit(Lit::number_from("42"))));
+        assert_eq!(value.default_decl(), None);
+        assert_eq!(value.default_expr(), Some(&Expr::Lit(Lit::number_from("42"))));
+        assert_eq!(value.default_name(), None);
+        // export * from 'm'
+        let all = ModExport::All(None, ExportSource::new(Lit::single_string_from("m")));
+        assert_eq!(all.default_decl(), None);
+        assert_eq!(all.default_expr(), None);
+    }
+
+    #[test]
+    fn default_reexports() {
+        // export { default as Foo } from 'm';
+        let spec = ExportSpecifier {
+            local: Ident::from("default"),
+            exported: ModuleExportName::ident_from("Foo"),
+        };
+        assert!(spec.is_local_default() && !spec.is_exported_default());
+        // export { Foo as default } from 'm';
+        let spec = ExportSpecifier {
+            local: Ident::from("Foo"),
+            exported: ModuleExportName::ident_from("default"),
+        };
+        assert!(!spec.is_local_default() && spec.is_exported_default());
+        // export { Foo as "default" } from 'm';
+        let spec = ExportSpecifier {
+            local: Ident::from("Foo"),
+            exported: ModuleExportName::str_from("default"),
+        };
+        assert!(spec.is_exported_default());
+    }
+
+    #[test]
+    fn var_decl_names() {
+        let names = |decl: &VarDecl<'static>| -> Vec<String> {
+            decl.names().iter().map(|id| id.name.to_string()).collect()
+        };
+        // var a = 1, b, c = 3;
+        let one = Expr::Lit(Lit::number_from("1"));
+        let three = Expr::Lit(Lit::number_from("3"));
+        let var = Decl::Var(
+            VarKind::Var,
+            vec![
+                VarDecl {
+                    id: Pat::ident_from("a"),
+                    init: Some(one),
+                },
+                VarDecl {
+                    id: Pat::ident_from("b"),
+                    init: None,
+                },
+                VarDecl {
+                    id: Pat::ident_from("c"),
+                    init: Some(three),
+                },
+            ],
+        );
+        let decls = var.var_decls().unwrap();
+        assert_eq!(decls.len(), 3);
+        let all: Vec<_> = decls.iter().flat_map(names).collect();
+        assert_eq!(all, ["a", "b", "c"]);
+        assert!(decls[1].init.is_none());
+        // let { x, y: [z, ...rest] } = o;
+        let array = Pat::Array(vec![
+            Some(ArrayPatPart::Pat(Pat::ident_from("z"))),
+            Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(Pat::ident_from(
+                "rest",
+            ))))),
+        ]);
+        let obj = Pat::Obj(vec![
+            ObjPatPart::Assign(Prop {
+                key: PropKey::Pat(Pat::ident_from("x")),
+                value: PropValue::None,
+                kind: PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: true,
+                is_static: false,
+                decorators: vec![],
+            }),
+            ObjPatPart::Assign(Prop {
+                key: PropKey::Pat(Pat::ident_from("y")),
+                value: PropValue::Pat(array),
+                kind: PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: false,
+                is_static: false,
+                decorators: vec![],
+            }),
+        ]);
+        let destructure = Decl::Var(
+            VarKind::Let,
+            vec![VarDecl {
+                id: obj,
+                init: Some(Expr::ident_from("o")),
+            }],
+        );
+        let decls = destructure.var_decls().unwrap();
+        assert_eq!(names(&decls[0]), ["x", "z", "rest"]);
+    }
+
+    #[test]
+    fn class_names() {
+        let class = |name: Option<&'static str>| Class {
+            id: name.map(Ident::from),
+            super_class: None,
+            body: ClassBody(vec![]),
+            decorators: vec![],
+        };
+        // class C {}
+        assert_eq!(Decl::Class(class(Some("C"))).validate(), Ok(()));
+        // class {}
+        let anonymous = Decl::Class(class(None));
+        assert_eq!(anonymous.validate(), Err(DeclError::AnonymousClass));
+        // export class {}
+        let export = |export| Decl::Export(Box::new(export));
+        let named = export(ModExport::Named(NamedExportDecl::Decl(anonymous.clone())));
+        assert_eq!(named.validate(), Err(DeclError::AnonymousClass));
+        // export default class {}
+        let default = export(ModExport::Default(DefaultExportDecl::Decl(anonymous)));
+        assert_eq!(default.validate(), Ok(()));
+        // const x = class {}, y = class Y {};
+        let vars = Decl::Var(
+            VarKind::Const,
+            vec![
+                VarDecl {
+                    id: Pat::ident_from("x"),
+                    init: Some(Expr::Class(class(None))),
+                },
+                VarDecl {
+                    id: Pat::ident_from("y"),
+                    init: Some(Expr::Class(class(Some("Y")))),
+                },
+            ],
+        );
+        assert_eq!(vars.validate(), Ok(()));
+        // function () {}
+        let func = Decl::Func(crate::Func {
+            id: None,
+            params: vec![],
+            body: crate::FuncBody(vec![]),
+            generator: false,
+            is_async: false,
+        });
+        assert_eq!(func.validate(), Err(DeclError::AnonymousFunc));
+    }
 }
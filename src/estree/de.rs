@@ -0,0 +1,969 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use serde_json::Value;
+
+use super::ResastError;
+use crate::prelude::*;
+
+type Res<T> = Result<T, ResastError>;
+
+fn node_type(node: &Value) -> Res<&str> {
+    match node.get("type") {
+        Some(Value::String(kind)) => Ok(kind),
+        Some(Value::Null) | None => Err(missing(node, "type")),
+        Some(_) => Err(invalid(node, "type")),
+    }
+}
+
+fn type_name(node: &Value) -> String {
+    node.get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// The position of `node` from either acorn's `start` and `end`
+/// or esprima's `range`
+fn range(node: &Value) -> Option<Range<usize>> {
+    let offset = |value: &Value| value.as_u64().map(|n| n as usize);
+    match (node.get("start"), node.get("end"), node.get("range")) {
+        (Some(start), Some(end), _) => Some(offset(start)?..offset(end)?),
+        (_, _, Some(Value::Array(range))) if range.len() == 2 => {
+            Some(offset(&range[0])?..offset(&range[1])?)
+        }
+        _ => None,
+    }
+}
+
+fn unknown(node: &Value) -> ResastError {
+    ResastError::UnknownNodeType {
+        type_name: type_name(node),
+        range: range(node),
+    }
+}
+
+fn missing(node: &Value, field: &'static str) -> ResastError {
+    ResastError::MissingField {
+        node: type_name(node),
+        field,
+        range: range(node),
+    }
+}
+
+fn invalid(node: &Value, field: &'static str) -> ResastError {
+    ResastError::InvalidField {
+        node: type_name(node),
+        field,
+        range: range(node),
+    }
+}
+
+fn unexpected(expected: &'static str, node: &Value) -> ResastError {
+    ResastError::Unexpected {
+        expected,
+        found: type_name(node),
+        range: range(node),
+    }
+}
+
+fn invalid_operator(node: &Value, operator: &str) -> ResastError {
+    ResastError::InvalidOperator {
+        node: type_name(node),
+        operator: operator.to_string(),
+        range: range(node),
+    }
+}
+
+fn field<'v>(node: &'v Value, name: &'static str) -> Res<&'v Value> {
+    match node.get(name) {
+        Some(Value::Null) | None => Err(missing(node, name)),
+        Some(value) => Ok(value),
+    }
+}
+
+/// The `operator` of a unary, update, binary, logical or
+/// assignment expression
+fn operator(node: &Value) -> Res<&str> {
+    field(node, "operator")?
+        .as_str()
+        .ok_or_else(|| invalid(node, "operator"))
+}
+
+/// A field that may be missing or `null`
+fn opt_field<'v>(node: &'v Value, name: &'static str) -> Option<&'v Value> {
+    match node.get(name) {
+        Some(Value::Null) | None => None,
+        Some(value) => Some(value),
+    }
+}
+
+fn array<'v>(node: &'v Value, name: &'static str) -> Res<&'v Vec<Value>> {
+    field(node, name)?
+        .as_array()
+        .ok_or_else(|| invalid(node, name))
+}
+
+/// An array field that may be missing, this is treated as empty
+fn opt_array<'v>(node: &'v Value, name: &'static str) -> Res<&'v [Value]> {
+    match opt_field(node, name) {
+        Some(value) => value
+            .as_array()
+            .map(|a| a.as_slice())
+            .ok_or_else(|| invalid(node, name)),
+        None => Ok(&[]),
+    }
+}
+
+fn string(node: &Value, name: &'static str) -> Res<String> {
+    field(node, name)?
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| invalid(node, name))
+}
+
+/// A boolean field, a missing field is `false`
+fn flag(node: &Value, name: &'static str) -> Res<bool> {
+    match opt_field(node, name) {
+        Some(value) => value.as_bool().ok_or_else(|| invalid(node, name)),
+        None => Ok(false),
+    }
+}
+
+fn all<T>(values: &[Value], f: impl Fn(&Value) -> Res<T>) -> Res<Vec<T>> {
+    values.iter().map(f).collect()
+}
+
+pub(crate) fn program(node: &Value) -> Res<Program<'static>> {
+    let kind = node_type(node)?;
+    if kind != "Program" {
+        return Err(unexpected("Program", node));
+    }
+    let body = all(array(node, "body")?, part)?;
+    let hashbang = match opt_field(node, "hashbang") {
+        Some(value) => Some(Cow::Owned(
+            value
+                .as_str()
+                .ok_or_else(|| invalid(node, "hashbang"))?
+                .to_string(),
+        )),
+        None => None,
+    };
+    match opt_field(node, "sourceType").and_then(Value::as_str) {
+        Some("module") => Ok(Program::Mod(body, hashbang)),
+        Some("script") | None => Ok(Program::Script(body, hashbang)),
+        Some(_) => Err(invalid(node, "sourceType")),
+    }
+}
+
+fn part(node: &Value) -> Res<ProgramPart<'static>> {
+    let ret = match node_type(node)? {
+        "ExpressionStatement" if opt_field(node, "directive").is_some() => {
+            let expr = lit(field(node, "expression")?)?;
+            ProgramPart::Dir(Dir {
+                expr,
+                dir: Cow::Owned(string(node, "directive")?),
+            })
+        }
+        "VariableDeclaration" => {
+            let (kind, decls) = var_decl(node)?;
+            ProgramPart::Decl(Decl::Var(kind, decls))
+        }
+        "FunctionDeclaration" => ProgramPart::Decl(Decl::Func(func(node)?)),
+        "ClassDeclaration" => ProgramPart::Decl(Decl::Class(class(node)?)),
+        "ImportDeclaration" => ProgramPart::Decl(Decl::Import(Box::new(import(node)?))),
+        "ExportNamedDeclaration" | "ExportDefaultDeclaration" | "ExportAllDeclaration" => {
+            ProgramPart::Decl(Decl::Export(Box::new(export(node)?)))
+        }
+        _ => ProgramPart::Stmt(stmt(node)?),
+    };
+    Ok(ret)
+}
+
+fn parts(node: &Value, name: &'static str) -> Res<Vec<ProgramPart<'static>>> {
+    all(array(node, name)?, part)
+}
+
+fn var_kind(node: &Value) -> Res<VarKind> {
+    match field(node, "kind")?.as_str() {
+        Some("var") => Ok(VarKind::Var),
+        Some("let") => Ok(VarKind::Let),
+        Some("const") => Ok(VarKind::Const),
+        _ => Err(invalid(node, "kind")),
+    }
+}
+
+fn var_decl(node: &Value) -> Res<(VarKind, Vec<VarDecl<'static>>)> {
+    let kind = var_kind(node)?;
+    let decls = all(array(node, "declarations")?, declarator)?;
+    Ok((kind, decls))
+}
+
+fn declarator(node: &Value) -> Res<VarDecl<'static>> {
+    if node_type(node)? != "VariableDeclarator" {
+        return Err(unexpected("VariableDeclarator", node));
+    }
+    Ok(VarDecl {
+        id: pat(field(node, "id")?)?,
+        init: opt_field(node, "init").map(expr).transpose()?,
+    })
+}
+
+fn ident(node: &Value) -> Res<Ident<'static>> {
+    if node_type(node)? != "Identifier" {
+        return Err(unexpected("Identifier", node));
+    }
+    Ok(Ident::new(string(node, "name")?))
+}
+
+fn private_id(node: &Value) -> Res<PrivateId<'static>> {
+    Ok(PrivateId {
+        name: Cow::Owned(string(node, "name")?),
+    })
+}
+
+fn member_prop(node: &Value) -> Res<MemberProp<'static>> {
+    let property = field(node, "property")?;
+    if flag(node, "computed")? {
+        return Ok(MemberProp::Computed(Box::new(expr(property)?)));
+    }
+    match node_type(property)? {
+        "PrivateIdentifier" => Ok(MemberProp::Private(private_id(property)?)),
+        _ => Ok(MemberProp::Ident(ident(property)?)),
+    }
+}
+
+fn func(node: &Value) -> Res<Func<'static>> {
+    let body = field(node, "body")?;
+    Ok(Func {
+        id: opt_field(node, "id").map(ident).transpose()?,
+        params: all(array(node, "params")?, func_arg)?,
+        body: FuncBody(parts(body, "body")?),
+        generator: flag(node, "generator")?,
+        is_async: flag(node, "async")?,
+    })
+}
+
+fn func_arg(node: &Value) -> Res<FuncArg<'static>> {
+    Ok(FuncArg::Pat(pat(node)?))
+}
+
+fn decorators(node: &Value) -> Res<Vec<Decorator<'static>>> {
+    all(opt_array(node, "decorators")?, |d| {
+        Ok(Decorator {
+            expression: expr(field(d, "expression")?)?,
+        })
+    })
+}
+
+fn class(node: &Value) -> Res<Class<'static>> {
+    let body = field(node, "body")?;
+    Ok(Class {
+        id: opt_field(node, "id").map(ident).transpose()?,
+        super_class: opt_field(node, "superClass")
+            .map(|e| expr(e).map(Box::new))
+            .transpose()?,
+        body: ClassBody(all(array(body, "body")?, class_member)?),
+        decorators: decorators(node)?,
+    })
+}
+
+fn class_member(node: &Value) -> Res<ClassMember<'static>> {
+    let ret = match node_type(node)? {
+        "StaticBlock" => ClassMember::StaticBlock(BlockStmt(parts(node, "body")?)),
+        "MethodDefinition" => {
+            let kind = match field(node, "kind")?.as_str() {
+                Some("constructor") => MethodKind::Constructor,
+                Some("method") => MethodKind::Method,
+                Some("get") => MethodKind::Get,
+                Some("set") => MethodKind::Set,
+                _ => return Err(invalid(node, "kind")),
+            };
+            ClassMember::Method(MethodDef {
+                key: prop_key(field(node, "key")?)?,
+                value: func(field(node, "value")?)?,
+                kind,
+                computed: flag(node, "computed")?,
+                is_static: flag(node, "static")?,
+                decorators: decorators(node)?,
+            })
+        }
+        "PropertyDefinition" => ClassMember::Field(FieldDef {
+            key: prop_key(field(node, "key")?)?,
+            value: opt_field(node, "value").map(expr).transpose()?,
+            computed: flag(node, "computed")?,
+            is_static: flag(node, "static")?,
+            decorators: decorators(node)?,
+        }),
+        _ => return Err(unexpected("class member", node)),
+    };
+    Ok(ret)
+}
+
+fn prop_key(node: &Value) -> Res<PropKey<'static>> {
+    let ret = match node_type(node)? {
+        "Literal" => PropKey::Lit(lit(node)?),
+        "PrivateIdentifier" => PropKey::Private(private_id(node)?),
+        _ => PropKey::Expr(expr(node)?),
+    };
+    Ok(ret)
+}
+
+fn prop_kind(node: &Value) -> Res<PropKind> {
+    match opt_field(node, "kind").and_then(Value::as_str) {
+        Some("init") | None => Ok(PropKind::Init),
+        Some("get") => Ok(PropKind::Get),
+        Some("set") => Ok(PropKind::Set),
+        Some(_) => Err(invalid(node, "kind")),
+    }
+}
+
+/// A `Property` in an object literal
+fn prop(node: &Value) -> Res<Prop<'static>> {
+    let short_hand = flag(node, "shorthand")?;
+    let value = if short_hand {
+        PropValue::None
+    } else {
+        PropValue::Expr(expr(field(node, "value")?)?)
+    };
+    let method = flag(node, "method")?;
+    let kind = match prop_kind(node)? {
+        PropKind::Init if method => PropKind::Method,
+        kind => kind,
+    };
+    Ok(Prop {
+        key: prop_key(field(node, "key")?)?,
+        value,
+        kind,
+        method,
+        computed: flag(node, "computed")?,
+        short_hand,
+        is_static: false,
+        decorators: vec![],
+    })
+}
+
+/// A `Property` in an object pattern
+fn pat_prop(node: &Value) -> Res<Prop<'static>> {
+    let short_hand = flag(node, "shorthand")?;
+    let value = field(node, "value")?;
+    let (key, value) = if short_hand {
+        let value = match node_type(value)? {
+            "AssignmentPattern" => PropValue::Pat(pat(value)?),
+            _ => PropValue::None,
+        };
+        (PropKey::Pat(pat(field(node, "key")?)?), value)
+    } else {
+        (prop_key(field(node, "key")?)?, PropValue::Pat(pat(value)?))
+    };
+    Ok(Prop {
+        key,
+        value,
+        kind: PropKind::Init,
+        method: false,
+        computed: flag(node, "computed")?,
+        short_hand,
+        is_static: false,
+        decorators: vec![],
+    })
+}
+
+fn source_lit(node: &Value) -> Res<Lit<'static>> {
+    let source = field(node, "source")?;
+    match lit(source)? {
+        lit @ Lit::String(_) => Ok(lit),
+        _ => Err(invalid(node, "source")),
+    }
+}
+
+/// The standard `attributes` or the legacy `assertions` written
+/// for `assert { ... }`
+fn attributes(node: &Value) -> Res<(Vec<ImportAttribute<'static>>, Option<AttributesKeyword>)> {
+    let (attrs, keyword) = match opt_array(node, "attributes")? {
+        [] => (opt_array(node, "assertions")?, AttributesKeyword::Assert),
+        attrs => (attrs, AttributesKeyword::With),
+    };
+    if attrs.is_empty() {
+        return Ok((vec![], None));
+    }
+    let attrs = all(attrs, |attr| {
+        let key = field(attr, "key")?;
+        let key = match node_type(key)? {
+            "Identifier" => ImportAttributeKey::Ident(ident(key)?),
+            _ => ImportAttributeKey::Lit(lit(key)?),
+        };
+        Ok(ImportAttribute {
+            key,
+            value: lit(field(attr, "value")?)?,
+        })
+    })?;
+    Ok((attrs, Some(keyword)))
+}
+
+fn module_export_name(node: &Value) -> Res<ModuleExportName<'static>> {
+    let ret = match node_type(node)? {
+        "Identifier" => ModuleExportName::Ident(ident(node)?),
+        "Literal" => ModuleExportName::Str(lit(node)?),
+        _ => return Err(unexpected("Identifier or Literal", node)),
+    };
+    Ok(ret)
+}
+
+fn import(node: &Value) -> Res<ModImport<'static>> {
+    let specifiers = all(array(node, "specifiers")?, |spec| {
+        let local = ident(field(spec, "local")?)?;
+        let ret = match node_type(spec)? {
+            "ImportSpecifier" => ImportSpecifier::Normal(NormalImportSpec {
+                local,
+                imported: module_export_name(field(spec, "imported")?)?,
+            }),
+            "ImportDefaultSpecifier" => ImportSpecifier::Default(local),
+            "ImportNamespaceSpecifier" => ImportSpecifier::Namespace(local),
+            _ => return Err(unexpected("import specifier", spec)),
+        };
+        Ok(ret)
+    })?;
+    let (attributes, attributes_keyword) = attributes(node)?;
+    Ok(ModImport {
+        specifiers,
+        source: source_lit(node)?,
+        attributes,
+        attributes_keyword,
+    })
+}
+
+fn export_source(node: &Value) -> Res<ExportSource<'static>> {
+    let (attributes, attributes_keyword) = attributes(node)?;
+    Ok(ExportSource {
+        source: source_lit(node)?,
+        attributes,
+        attributes_keyword,
+    })
+}
+
+fn decl(node: &Value) -> Res<Decl<'static>> {
+    match part(node)? {
+        ProgramPart::Decl(decl) => Ok(decl),
+        _ => Err(unexpected("declaration", node)),
+    }
+}
+
+fn export(node: &Value) -> Res<ModExport<'static>> {
+    let ret = match node_type(node)? {
+        "ExportAllDeclaration" => ModExport::All(
+            opt_field(node, "exported")
+                .map(module_export_name)
+                .transpose()?,
+            export_source(node)?,
+        ),
+        "ExportDefaultDeclaration" => {
+            let declaration = field(node, "declaration")?;
+            let ret = match node_type(declaration)? {
+                "FunctionDeclaration" | "ClassDeclaration" => {
+                    DefaultExportDecl::Decl(decl(declaration)?)
+                }
+                _ => DefaultExportDecl::Expr(expr(declaration)?),
+            };
+            ModExport::Default(ret)
+        }
+        _ => {
+            if let Some(declaration) = opt_field(node, "declaration") {
+                ModExport::Named(NamedExportDecl::Decl(decl(declaration)?))
+            } else {
+                let specs = all(array(node, "specifiers")?, |spec| {
+                    let local = field(spec, "local")?;
+                    Ok(ExportSpecifier {
+                        local: ident(local)?,
+                        exported: module_export_name(field(spec, "exported")?)?,
+                    })
+                })?;
+                let source = match opt_field(node, "source") {
+                    Some(_) => Some(export_source(node)?),
+                    None => None,
+                };
+                ModExport::Named(NamedExportDecl::Specifier(specs, source))
+            }
+        }
+    };
+    Ok(ret)
+}
+
+fn block(node: &Value) -> Res<BlockStmt<'static>> {
+    if node_type(node)? != "BlockStatement" {
+        return Err(unexpected("BlockStatement", node));
+    }
+    Ok(BlockStmt(parts(node, "body")?))
+}
+
+fn boxed_stmt(node: &Value) -> Res<Box<Stmt<'static>>> {
+    stmt(node).map(Box::new)
+}
+
+fn opt_label(node: &Value) -> Res<Option<Ident<'static>>> {
+    opt_field(node, "label").map(ident).transpose()
+}
+
+fn stmt(node: &Value) -> Res<Stmt<'static>> {
+    let ret = match node_type(node)? {
+        "ExpressionStatement" => Stmt::Expr(expr(field(node, "expression")?)?),
+        "BlockStatement" => Stmt::Block(block(node)?),
+        "EmptyStatement" => Stmt::Empty,
+        "DebuggerStatement" => Stmt::Debugger,
+        "WithStatement" => Stmt::With(WithStmt {
+            object: expr(field(node, "object")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+        }),
+        "ReturnStatement" => Stmt::Return(opt_field(node, "argument").map(expr).transpose()?),
+        "LabeledStatement" => Stmt::Labeled(LabeledStmt {
+            label: ident(field(node, "label")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+        }),
+        "BreakStatement" => Stmt::Break(opt_label(node)?),
+        "ContinueStatement" => Stmt::Continue(opt_label(node)?),
+        "IfStatement" => Stmt::If(IfStmt {
+            test: expr(field(node, "test")?)?,
+            consequent: boxed_stmt(field(node, "consequent")?)?,
+            alternate: opt_field(node, "alternate").map(boxed_stmt).transpose()?,
+        }),
+        "SwitchStatement" => Stmt::Switch(SwitchStmt {
+            discriminant: expr(field(node, "discriminant")?)?,
+            cases: all(array(node, "cases")?, |case| {
+                Ok(SwitchCase {
+                    test: opt_field(case, "test").map(expr).transpose()?,
+                    consequent: parts(case, "consequent")?,
+                })
+            })?,
+        }),
+        "ThrowStatement" => Stmt::Throw(expr(field(node, "argument")?)?),
+        "TryStatement" => Stmt::Try(TryStmt {
+            block: block(field(node, "block")?)?,
+            handler: opt_field(node, "handler").map(catch).transpose()?,
+            finalizer: opt_field(node, "finalizer").map(block).transpose()?,
+        }),
+        "WhileStatement" => Stmt::While(WhileStmt {
+            test: expr(field(node, "test")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+        }),
+        "DoWhileStatement" => Stmt::DoWhile(DoWhileStmt {
+            test: expr(field(node, "test")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+        }),
+        "ForStatement" => {
+            let init = match opt_field(node, "init") {
+                Some(init) if node_type(init)? == "VariableDeclaration" => {
+                    let (kind, decls) = var_decl(init)?;
+                    Some(LoopInit::Variable(kind, decls))
+                }
+                Some(init) => Some(LoopInit::Expr(expr(init)?)),
+                None => None,
+            };
+            Stmt::For(ForStmt {
+                init,
+                test: opt_field(node, "test").map(expr).transpose()?,
+                update: opt_field(node, "update").map(expr).transpose()?,
+                body: boxed_stmt(field(node, "body")?)?,
+            })
+        }
+        "ForInStatement" => Stmt::ForIn(ForInStmt {
+            left: loop_left(field(node, "left")?)?,
+            right: expr(field(node, "right")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+        }),
+        "ForOfStatement" => Stmt::ForOf(ForOfStmt {
+            left: loop_left(field(node, "left")?)?,
+            right: expr(field(node, "right")?)?,
+            body: boxed_stmt(field(node, "body")?)?,
+            is_await: flag(node, "await")?,
+        }),
+        "VariableDeclaration" => match var_decl(node)? {
+            (VarKind::Var, decls) => Stmt::Var(decls),
+            _ => return Err(unexpected("statement", node)),
+        },
+        "FunctionDeclaration" | "ClassDeclaration" | "ImportDeclaration"
+        | "ExportNamedDeclaration" | "ExportDefaultDeclaration" | "ExportAllDeclaration" => {
+            return Err(unexpected("statement", node))
+        }
+        _ => return Err(unknown(node)),
+    };
+    Ok(ret)
+}
+
+fn catch(node: &Value) -> Res<CatchClause<'static>> {
+    Ok(CatchClause {
+        param: opt_field(node, "param").map(pat).transpose()?,
+        body: block(field(node, "body")?)?,
+    })
+}
+
+fn loop_left(node: &Value) -> Res<LoopLeft<'static>> {
+    let ret = match node_type(node)? {
+        "VariableDeclaration" => {
+            let (kind, mut decls) = var_decl(node)?;
+            if decls.len() != 1 {
+                return Err(invalid(node, "declarations"));
+            }
+            LoopLeft::Variable(kind, decls.remove(0))
+        }
+        "ObjectPattern" | "ArrayPattern" => LoopLeft::Pat(pat(node)?),
+        _ => LoopLeft::Expr(expr(node)?),
+    };
+    Ok(ret)
+}
+
+fn lit(node: &Value) -> Res<Lit<'static>> {
+    match node_type(node)? {
+        "Literal" => {}
+        "TemplateLiteral" => return Ok(Lit::Template(template(node)?)),
+        _ => return Err(unexpected("Literal", node)),
+    }
+    if let Some(regex) = opt_field(node, "regex") {
+        return Ok(Lit::RegEx(RegEx {
+            pattern: Cow::Owned(string(regex, "pattern")?),
+            flags: Cow::Owned(string(regex, "flags")?),
+        }));
+    }
+    if let Some(bigint) = opt_field(node, "bigint") {
+        let digits = bigint.as_str().ok_or_else(|| invalid(node, "bigint"))?;
+        return Ok(Lit::BigInt(Cow::Owned(digits.to_string())));
+    }
+    let raw = opt_field(node, "raw").and_then(Value::as_str);
+    let ret = match opt_field(node, "value") {
+        None => Lit::Null,
+        Some(Value::Bool(b)) => Lit::Boolean(*b),
+        Some(Value::Number(n)) => {
+            let text = raw.map(String::from).unwrap_or_else(|| n.to_string());
+            Lit::Number(NumberLit(Cow::Owned(text)))
+        }
+        Some(Value::String(s)) => match raw {
+            Some(raw) if raw.len() >= 2 && raw.starts_with('\'') => {
+                Lit::String(StringLit::Single(Cow::Owned(raw[1..raw.len() - 1].to_string())))
+            }
+            Some(raw) if raw.len() >= 2 && raw.starts_with('"') => {
+                Lit::String(StringLit::Double(Cow::Owned(raw[1..raw.len() - 1].to_string())))
+            }
+            _ => Lit::String(string_source(s)),
+        },
+        Some(_) => return Err(invalid(node, "value")),
+    };
+    Ok(ret)
+}
+
+/// The source text of a string literal whose `raw` is missing, with
+/// the quote that appears in `value` less often and the characters a
+/// string literal can't hold as is escaped
+fn string_source(value: &str) -> StringLit<'static> {
+    let double = value.matches('"').count() <= value.matches('\'').count();
+    let quote = if double { '"' } else { '\'' };
+    let mut text = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => text.push_str("\\\\"),
+            '\n' => text.push_str("\\n"),
+            '\r' => text.push_str("\\r"),
+            '\u{2028}' => text.push_str("\\u2028"),
+            '\u{2029}' => text.push_str("\\u2029"),
+            c if c == quote => {
+                text.push('\\');
+                text.push(c);
+            }
+            c => text.push(c),
+        }
+    }
+    if double {
+        StringLit::Double(Cow::Owned(text))
+    } else {
+        StringLit::Single(Cow::Owned(text))
+    }
+}
+
+fn template(node: &Value) -> Res<TemplateLit<'static>> {
+    let quasis = array(node, "quasis")?;
+    let quasis = quasis
+        .iter()
+        .enumerate()
+        .map(|(i, quasi)| {
+            let value = field(quasi, "value")?;
+            let tail = flag(quasi, "tail")?;
+            let raw = format!(
+                "{}{}{}",
+                if i == 0 { "`" } else { "}" },
+                string(value, "raw")?,
+                if tail { "`" } else { "${" },
+            );
+            let cooked = match opt_field(value, "cooked") {
+                Some(cooked) => Some(Cow::Owned(
+                    cooked
+                        .as_str()
+                        .ok_or_else(|| invalid(quasi, "cooked"))?
+                        .to_string(),
+                )),
+                None => None,
+            };
+            Ok(TemplateElement {
+                tail,
+                cooked,
+                raw: Cow::Owned(raw),
+            })
+        })
+        .collect::<Res<_>>()?;
+    Ok(TemplateLit {
+        quasis,
+        expressions: all(array(node, "expressions")?, expr)?,
+    })
+}
+
+fn boxed_expr(node: &Value, name: &'static str) -> Res<Box<Expr<'static>>> {
+    expr(field(node, name)?).map(Box::new)
+}
+
+fn exprs(node: &Value, name: &'static str) -> Res<Vec<Expr<'static>>> {
+    all(array(node, name)?, expr)
+}
+
+fn expr(node: &Value) -> Res<Expr<'static>> {
+    let ret = match node_type(node)? {
+        "Identifier" => Expr::Ident(ident(node)?),
+        "PrivateIdentifier" => Expr::PrivateId(private_id(node)?),
+        "Literal" | "TemplateLiteral" => Expr::Lit(lit(node)?),
+        "ThisExpression" => Expr::This,
+        "Super" => Expr::Super,
+        "ArrayExpression" => Expr::Array(
+            array(node, "elements")?
+                .iter()
+                .map(|el| match el {
+                    Value::Null => Ok(None),
+                    el => expr(el).map(Some),
+                })
+                .collect::<Res<_>>()?,
+        ),
+        "ObjectExpression" => Expr::Obj(all(array(node, "properties")?, |p| {
+            match node_type(p)? {
+                "SpreadElement" => Ok(ObjProp::Spread(expr(field(p, "argument")?)?)),
+                "Property" => Ok(ObjProp::Prop(prop(p)?)),
+                _ => Err(unexpected("Property", p)),
+            }
+        })?),
+        "FunctionExpression" => Expr::Func(func(node)?),
+        "ArrowFunctionExpression" => {
+            let body = field(node, "body")?;
+            let expression = flag(node, "expression")?;
+            let body = if node_type(body)? == "BlockStatement" && !expression {
+                ArrowFuncBody::FuncBody(FuncBody(parts(body, "body")?))
+            } else {
+                ArrowFuncBody::Expr(Box::new(expr(body)?))
+            };
+            Expr::ArrowFunc(ArrowFuncExpr {
+                id: None,
+                params: all(array(node, "params")?, func_arg)?,
+                body,
+                expression,
+                is_async: flag(node, "async")?,
+            })
+        }
+        "ClassExpression" => Expr::Class(class(node)?),
+        "UnaryExpression" => Expr::Unary(UnaryExpr {
+            operator: unary_op(node)?,
+            prefix: true,
+            argument: boxed_expr(node, "argument")?,
+        }),
+        "UpdateExpression" => Expr::Update(UpdateExpr {
+            operator: match operator(node)? {
+                "++" => UpdateOp::Increment,
+                "--" => UpdateOp::Decrement,
+                op => return Err(invalid_operator(node, op)),
+            },
+            argument: boxed_expr(node, "argument")?,
+            prefix: flag(node, "prefix")?,
+        }),
+        "BinaryExpression" => Expr::Binary(BinaryExpr {
+            operator: binary_op(node)?,
+            left: boxed_expr(node, "left")?,
+            right: boxed_expr(node, "right")?,
+        }),
+        "LogicalExpression" => Expr::Logical(LogicalExpr {
+            operator: match operator(node)? {
+                "||" => LogicalOp::Or,
+                "&&" => LogicalOp::And,
+                "??" => LogicalOp::NullishCoalescing,
+                op => return Err(invalid_operator(node, op)),
+            },
+            left: boxed_expr(node, "left")?,
+            right: boxed_expr(node, "right")?,
+        }),
+        "AssignmentExpression" => {
+            let left = field(node, "left")?;
+            let left = match node_type(left)? {
+                "ObjectPattern" | "ArrayPattern" => AssignLeft::Pat(pat(left)?),
+                _ => AssignLeft::Expr(Box::new(expr(left)?)),
+            };
+            Expr::Assign(AssignExpr {
+                operator: assign_op(node)?,
+                left,
+                right: boxed_expr(node, "right")?,
+            })
+        }
+        "MemberExpression" => Expr::Member(MemberExpr {
+            object: boxed_expr(node, "object")?,
+            property: member_prop(node)?,
+            optional: flag(node, "optional")?,
+        }),
+        "ChainExpression" => Expr::OptionalChain(boxed_expr(node, "expression")?),
+        "ConditionalExpression" => Expr::Conditional(ConditionalExpr {
+            test: boxed_expr(node, "test")?,
+            alternate: boxed_expr(node, "alternate")?,
+            consequent: boxed_expr(node, "consequent")?,
+        }),
+        "CallExpression" => Expr::Call(CallExpr {
+            callee: boxed_expr(node, "callee")?,
+            arguments: exprs(node, "arguments")?,
+            optional: flag(node, "optional")?,
+        }),
+        "NewExpression" => Expr::New(NewExpr {
+            callee: boxed_expr(node, "callee")?,
+            arguments: Some(exprs(node, "arguments")?),
+        }),
+        "SequenceExpression" => Expr::Sequence(
+            SequenceExpr::from_vec(exprs(node, "expressions")?)
+                .ok_or_else(|| invalid(node, "expressions"))?,
+        ),
+        "SpreadElement" => Expr::Spread(boxed_expr(node, "argument")?),
+        "YieldExpression" => Expr::Yield(YieldExpr {
+            argument: opt_field(node, "argument")
+                .map(|e| expr(e).map(Box::new))
+                .transpose()?,
+            delegate: flag(node, "delegate")?,
+        }),
+        "AwaitExpression" => Expr::Await(boxed_expr(node, "argument")?),
+        "TaggedTemplateExpression" => Expr::TaggedTemplate(TaggedTemplateExpr {
+            tag: boxed_expr(node, "tag")?,
+            quasi: template(field(node, "quasi")?)?,
+        }),
+        "MetaProperty" => Expr::MetaProp(MetaProp {
+            meta: ident(field(node, "meta")?)?,
+            property: ident(field(node, "property")?)?,
+        }),
+        "ImportExpression" => Expr::Import(ImportExpr {
+            source: boxed_expr(node, "source")?,
+            options: opt_field(node, "options")
+                .map(|e| expr(e).map(Box::new))
+                .transpose()?,
+        }),
+        // the parentheses don't change the meaning of the
+        // expression so they are dropped unless asked for
+        #[cfg(not(feature = "preserve-parens"))]
+        "ParenthesizedExpression" => expr(field(node, "expression")?)?,
+        #[cfg(feature = "preserve-parens")]
+        "ParenthesizedExpression" => Expr::Paren(boxed_expr(node, "expression")?),
+        _ if is_statement(node) => return Err(unexpected("expression", node)),
+        _ => return Err(unknown(node)),
+    };
+    Ok(ret)
+}
+
+fn is_statement(node: &Value) -> bool {
+    node_type(node)
+        .map(|t| t.ends_with("Statement") || t.ends_with("Declaration"))
+        .unwrap_or(false)
+}
+
+fn pat(node: &Value) -> Res<Pat<'static>> {
+    let ret = match node_type(node)? {
+        "Identifier" => Pat::Ident(ident(node)?),
+        "ObjectPattern" => Pat::Obj(all(array(node, "properties")?, |p| {
+            match node_type(p)? {
+                "RestElement" => Ok(ObjPatPart::Rest(Box::new(pat(field(p, "argument")?)?))),
+                "Property" => Ok(ObjPatPart::Assign(pat_prop(p)?)),
+                _ => Err(unexpected("Property", p)),
+            }
+        })?),
+        "ArrayPattern" => Pat::Array(
+            array(node, "elements")?
+                .iter()
+                .map(|el| match el {
+                    Value::Null => Ok(None),
+                    el => match node_type(el)? {
+                        "MemberExpression" => expr(el).map(|e| Some(ArrayPatPart::Expr(e))),
+                        _ => pat(el).map(|p| Some(ArrayPatPart::Pat(p))),
+                    },
+                })
+                .collect::<Res<_>>()?,
+        ),
+        "RestElement" => Pat::RestElement(Box::new(pat(field(node, "argument")?)?)),
+        "AssignmentPattern" => Pat::Assign(AssignPat {
+            left: Box::new(pat(field(node, "left")?)?),
+            right: boxed_expr(node, "right")?,
+        }),
+        "MemberExpression" => return Err(unexpected("pattern", node)),
+        other if is_statement(node) || other.ends_with("Expression") => {
+            return Err(unexpected("pattern", node))
+        }
+        _ => return Err(unknown(node)),
+    };
+    Ok(ret)
+}
+
+fn unary_op(node: &Value) -> Res<UnaryOp> {
+    let op = match operator(node)? {
+        "-" => UnaryOp::Minus,
+        "+" => UnaryOp::Plus,
+        "!" => UnaryOp::Not,
+        "~" => UnaryOp::Tilde,
+        "typeof" => UnaryOp::TypeOf,
+        "void" => UnaryOp::Void,
+        "delete" => UnaryOp::Delete,
+        op => return Err(invalid_operator(node, op)),
+    };
+    Ok(op)
+}
+
+fn binary_op(node: &Value) -> Res<BinaryOp> {
+    let op = match operator(node)? {
+        "==" => BinaryOp::Equal,
+        "!=" => BinaryOp::NotEqual,
+        "===" => BinaryOp::StrictEqual,
+        "!==" => BinaryOp::StrictNotEqual,
+        "<" => BinaryOp::LessThan,
+        ">" => BinaryOp::GreaterThan,
+        "<=" => BinaryOp::LessThanEqual,
+        ">=" => BinaryOp::GreaterThanEqual,
+        "<<" => BinaryOp::LeftShift,
+        ">>" => BinaryOp::RightShift,
+        ">>>" => BinaryOp::UnsignedRightShift,
+        "+" => BinaryOp::Plus,
+        "-" => BinaryOp::Minus,
+        "*" => BinaryOp::Times,
+        "/" => BinaryOp::Over,
+        "%" => BinaryOp::Mod,
+        "|" => BinaryOp::Or,
+        "^" => BinaryOp::XOr,
+        "&" => BinaryOp::And,
+        "in" => BinaryOp::In,
+        "instanceof" => BinaryOp::InstanceOf,
+        "**" => BinaryOp::PowerOf,
+        op => return Err(invalid_operator(node, op)),
+    };
+    Ok(op)
+}
+
+fn assign_op(node: &Value) -> Res<AssignOp> {
+    let op = match operator(node)? {
+        "=" => AssignOp::Equal,
+        "+=" => AssignOp::PlusEqual,
+        "-=" => AssignOp::MinusEqual,
+        "*=" => AssignOp::TimesEqual,
+        "/=" => AssignOp::DivEqual,
+        "%=" => AssignOp::ModEqual,
+        "<<=" => AssignOp::LeftShiftEqual,
+        ">>=" => AssignOp::RightShiftEqual,
+        ">>>=" => AssignOp::UnsignedRightShiftEqual,
+        "|=" => AssignOp::OrEqual,
+        "^=" => AssignOp::XOrEqual,
+        "&=" => AssignOp::AndEqual,
+        "**=" => AssignOp::PowerOfEqual,
+        "&&=" => AssignOp::LogicalAndEqual,
+        "||=" => AssignOp::LogicalOrEqual,
+        "??=" => AssignOp::NullishEqual,
+        op => return Err(invalid_operator(node, op)),
+    };
+    Ok(op)
+}
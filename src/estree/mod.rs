@@ -0,0 +1,741 @@
+//! Conversions between this crate's AST and ESTree shaped JSON
+//!
+//! `from_estree_json` reads ESTree into a `Program` and
+//! `to_estree_json` writes one back out, with each node
+//! tagged by its ESTree `type`.
+//!
+//! Unlike the `esprima` feature, which replaces the serde
+//! implementations of the AST with ones that match esprima's
+//! output, this module works with plain ESTree as produced by
+//! tools like acorn. The default serde derives are left untouched.
+//!
+//! Node position information (`start`, `end`, `loc`, `range`) is
+//! ignored and so are any fields not listed in the ESTree spec.
+//! `ParenthesizedExpression` nodes are unwrapped into their inner
+//! expression unless the `preserve-parens` feature is enabled.
+//!
+//! The following are not supported and will produce a
+//! `ResastError::UnknownNodeType`
+//! - JSX nodes (`JSXElement`, `JSXFragment`, etc)
+//! - TypeScript and Flow nodes and type annotations
+//! - `AccessorProperty` class members
+//! - Any node `type` that isn't part of ESTree
+use std::fmt;
+use std::ops::Range;
+
+mod de;
+mod ser;
+pub(crate) mod unescape;
+
+/// The reasons converting ESTree JSON can fail.
+///
+/// Other than `Json` each variant names the `type` of the node
+/// that couldn't be converted, `"unknown"` if it didn't have one,
+/// and the node's position in the source when the JSON includes
+/// `start` and `end` or `range`
+#[derive(Debug)]
+pub enum ResastError {
+    /// The input wasn't valid JSON
+    Json(serde_json::Error),
+    /// A node `type` this crate can't represent
+    UnknownNodeType {
+        type_name: String,
+        range: Option<Range<usize>>,
+    },
+    /// A required field was missing or `null`
+    MissingField {
+        node: String,
+        field: &'static str,
+        range: Option<Range<usize>>,
+    },
+    /// A field had the wrong shape, e.g. a string where a list
+    /// was expected
+    InvalidField {
+        node: String,
+        field: &'static str,
+        range: Option<Range<usize>>,
+    },
+    /// An `operator` that isn't valid for the node
+    InvalidOperator {
+        node: String,
+        operator: String,
+        range: Option<Range<usize>>,
+    },
+    /// A node showed up somewhere it isn't allowed,
+    /// e.g. a `FunctionDeclaration` as the body of an `if`
+    Unexpected {
+        expected: &'static str,
+        found: String,
+        range: Option<Range<usize>>,
+    },
+}
+
+/// The original name of `ResastError`
+pub type Error = ResastError;
+
+impl ResastError {
+    /// The `type` of the node that failed to convert
+    pub fn node_type(&self) -> Option<&str> {
+        match self {
+            ResastError::Json(_) => None,
+            ResastError::UnknownNodeType { type_name: node, .. }
+            | ResastError::MissingField { node, .. }
+            | ResastError::InvalidField { node, .. }
+            | ResastError::InvalidOperator { node, .. }
+            | ResastError::Unexpected { found: node, .. } => Some(node),
+        }
+    }
+    /// Where the node that failed to convert is in the source
+    pub fn range(&self) -> Option<&Range<usize>> {
+        match self {
+            ResastError::Json(_) => None,
+            ResastError::UnknownNodeType { range, .. }
+            | ResastError::MissingField { range, .. }
+            | ResastError::InvalidField { range, .. }
+            | ResastError::InvalidOperator { range, .. }
+            | ResastError::Unexpected { range, .. } => range.as_ref(),
+        }
+    }
+}
+
+impl fmt::Display for ResastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResastError::Json(e) => return write!(f, "invalid json: {}", e),
+            ResastError::UnknownNodeType { type_name, .. } => {
+                write!(f, "unsupported ESTree node type `{}`", type_name)?
+            }
+            ResastError::MissingField { node, field, .. } => {
+                write!(f, "missing field `{}` on `{}`", field, node)?
+            }
+            ResastError::InvalidField { node, field, .. } => {
+                write!(f, "invalid field `{}` on `{}`", field, node)?
+            }
+            ResastError::InvalidOperator { node, operator, .. } => {
+                write!(f, "invalid operator `{}` on `{}`", operator, node)?
+            }
+            ResastError::Unexpected { expected, found, .. } => {
+                write!(f, "expected {} found `{}`", expected, found)?
+            }
+        }
+        match self.range() {
+            Some(range) => write!(f, " at {}..{}", range.start, range.end),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ResastError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ResastError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ResastError {
+    fn from(other: serde_json::Error) -> Self {
+        ResastError::Json(other)
+    }
+}
+
+/// Parse the ESTree JSON for a `Program` node
+/// ```
+/// # use resast::prelude::*;
+/// let json = r#"{
+///     "type": "Program",
+///     "sourceType": "script",
+///     "body": [{
+///         "type": "ExpressionStatement",
+///         "expression": { "type": "Identifier", "name": "a" }
+///     }]
+/// }"#;
+/// let program = resast::estree::from_estree_json(json).unwrap();
+/// assert_eq!(
+///     program,
+///     Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))])
+/// );
+/// ```
+pub fn from_estree_json(json: &str) -> Result<crate::Program<'static>, ResastError> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    from_estree_value(&value)
+}
+
+/// Convert an already parsed ESTree `Program` node
+pub fn from_estree_value(
+    value: &serde_json::Value,
+) -> Result<crate::Program<'static>, ResastError> {
+    de::program(value)
+}
+
+/// Write a `Program` as ESTree JSON
+/// ```
+/// # use resast::prelude::*;
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))]);
+/// let json = resast::estree::to_estree_json(&program);
+/// assert_eq!(resast::estree::from_estree_json(&json).unwrap(), program);
+/// ```
+pub fn to_estree_json(program: &crate::Program) -> String {
+    to_estree_value(program).to_string()
+}
+
+/// Convert a `Program` into an ESTree `Program` node
+pub fn to_estree_value(program: &crate::Program) -> serde_json::Value {
+    ser::program(program)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    // A module in the shape acorn writes, with `start` and `end`
+    // offsets, written by hand for
+    // ```js
+    // import { readFile } from "fs";
+    // export const answer = 42;
+    // export default function add(a, b = 1) {
+    //   return a + b;
+    // }
+    // ```
+    const ACORN_MODULE: &str = r#"{
+        "type": "Program", "start": 0, "end": 115, "sourceType": "module",
+        "body": [
+            {
+                "type": "ImportDeclaration", "start": 0, "end": 30,
+                "specifiers": [{
+                    "type": "ImportSpecifier", "start": 9, "end": 17,
+                    "imported": { "type": "Identifier", "start": 9, "end": 17, "name": "readFile" },
+                    "local": { "type": "Identifier", "start": 9, "end": 17, "name": "readFile" }
+                }],
+                "source": { "type": "Literal", "start": 25, "end": 29, "value": "fs", "raw": "\"fs\"" }
+            },
+            {
+                "type": "ExportNamedDeclaration", "start": 31, "end": 56,
+                "declaration": {
+                    "type": "VariableDeclaration", "start": 38, "end": 56, "kind": "const",
+                    "declarations": [{
+                        "type": "VariableDeclarator", "start": 44, "end": 55,
+                        "id": { "type": "Identifier", "start": 44, "end": 50, "name": "answer" },
+                        "init": { "type": "Literal", "start": 53, "end": 55, "value": 42, "raw": "42" }
+                    }]
+                },
+                "specifiers": [],
+                "source": null
+            },
+            {
+                "type": "ExportDefaultDeclaration", "start": 57, "end": 114,
+                "declaration": {
+                    "type": "FunctionDeclaration", "start": 72, "end": 114,
+                    "id": { "type": "Identifier", "start": 81, "end": 84, "name": "add" },
+                    "expression": false, "generator": false, "async": false,
+                    "params": [
+                        { "type": "Identifier", "start": 85, "end": 86, "name": "a" },
+                        {
+                            "type": "AssignmentPattern", "start": 88, "end": 93,
+                            "left": { "type": "Identifier", "start": 88, "end": 89, "name": "b" },
+                            "right": { "type": "Literal", "start": 92, "end": 93, "value": 1, "raw": "1" }
+                        }
+                    ],
+                    "body": {
+                        "type": "BlockStatement", "start": 95, "end": 114,
+                        "body": [{
+                            "type": "ReturnStatement", "start": 99, "end": 112,
+                            "argument": {
+                                "type": "BinaryExpression", "start": 106, "end": 111,
+                                "left": { "type": "Identifier", "start": 106, "end": 107, "name": "a" },
+                                "operator": "+",
+                                "right": { "type": "Identifier", "start": 110, "end": 111, "name": "b" }
+                            }
+                        }]
+                    }
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn acorn_module() {
+        let program = from_estree_json(ACORN_MODULE).expect("failed to convert acorn style module");
+        let expectation = Program::module(vec![
+            ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+                specifiers: vec![ImportSpecifier::Normal(NormalImportSpec {
+                    local: Ident::from("readFile"),
+                    imported: ModuleExportName::ident_from("readFile"),
+                })],
+                source: Lit::double_string_from("fs"),
+                attributes: vec![],
+                attributes_keyword: None,
+            }))),
+            ProgramPart::Decl(Decl::Export(Box::new(ModExport::Named(NamedExportDecl::Decl(
+                Decl::Var(
+                    VarKind::Const,
+                    vec![VarDecl {
+                        id: Pat::ident_from("answer"),
+                        init: Some(Expr::Lit(Lit::number_from("42"))),
+                    }],
+                ),
+            ))))),
+            ProgramPart::Decl(Decl::Export(Box::new(ModExport::Default(
+                DefaultExportDecl::Decl(Decl::Func(Func {
+                    id: Some(Ident::from("add")),
+                    params: vec![
+                        FuncArg::Pat(Pat::ident_from("a")),
+                        FuncArg::Pat(Pat::Assign(AssignPat {
+                            left: Box::new(Pat::ident_from("b")),
+                            right: Box::new(Expr::Lit(Lit::number_from("1"))),
+                        })),
+                    ],
+                    body: FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::Binary(
+                        BinaryExpr {
+                            operator: BinaryOp::Plus,
+                            left: Box::new(Expr::ident_from("a")),
+                            right: Box::new(Expr::ident_from("b")),
+                        },
+                    ))))]),
+                    generator: false,
+                    is_async: false,
+                })),
+            )))),
+        ]);
+        assert_eq!(program, expectation);
+    }
+
+    #[test]
+    fn directives_and_templates() {
+        let json = r#"{
+            "type": "Program", "sourceType": "script",
+            "body": [
+                {
+                    "type": "ExpressionStatement",
+                    "expression": { "type": "Literal", "value": "use strict", "raw": "'use strict'" },
+                    "directive": "use strict"
+                },
+                {
+                    "type": "ExpressionStatement",
+                    "expression": {
+                        "type": "TemplateLiteral",
+                        "expressions": [{ "type": "Identifier", "name": "b" }],
+                        "quasis": [
+                            { "type": "TemplateElement", "tail": false, "value": { "raw": "a\\n", "cooked": "a\n" } },
+                            { "type": "TemplateElement", "tail": true, "value": { "raw": "", "cooked": "" } }
+                        ]
+                    }
+                }
+            ]
+        }"#;
+        let program = from_estree_json(json).expect("failed to convert template");
+        let expectation = Program::script(vec![
+            ProgramPart::Dir(Dir {
+                expr: Lit::single_string_from("use strict"),
+                dir: "use strict".into(),
+            }),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::Template(TemplateLit {
+                quasis: vec![
+                    TemplateElement::from(false, "a\n", "`a\\n${"),
+                    TemplateElement::from(true, "", "}`"),
+                ],
+                expressions: vec![Expr::ident_from("b")],
+            })))),
+        ]);
+        assert_eq!(program, expectation);
+    }
+
+    #[test]
+    fn unsupported_nodes() {
+        let json = r#"{
+            "type": "Program", "sourceType": "script",
+            "body": [{
+                "type": "ExpressionStatement",
+                "expression": { "type": "JSXElement", "children": [] }
+            }]
+        }"#;
+        match from_estree_json(json) {
+            Err(ResastError::UnknownNodeType { type_name, .. }) => {
+                assert_eq!(type_name, "JSXElement")
+            }
+            other => panic!("expected an unsupported error, found {:?}", other),
+        }
+        let missing = r#"{ "type": "Program", "body": [{ "type": "ReturnStatement" }, { "type": "IfStatement" }] }"#;
+        match from_estree_json(missing) {
+            Err(ResastError::MissingField { node, field, .. }) => {
+                assert_eq!(node, "IfStatement");
+                assert_eq!(field, "test");
+            }
+            other => panic!("expected a missing field error, found {:?}", other),
+        }
+        assert!(matches!(from_estree_json("{"), Err(ResastError::Json(_))));
+    }
+
+    // The ESTree for the following, without positions
+    // ```js
+    // const { a, ...rest } = obj;
+    // let f = async (x, [, y]) => x + y;
+    // obj = { a, ...rest, [k]: `t${a}`, [1, , 2]: new C() };
+    // ```
+    const EXPRESSIONS: &str = r#"{
+        "type": "Program", "sourceType": "script",
+        "body": [
+            {
+                "type": "VariableDeclaration", "kind": "const",
+                "declarations": [{
+                    "type": "VariableDeclarator",
+                    "id": {
+                        "type": "ObjectPattern",
+                        "properties": [
+                            {
+                                "type": "Property", "kind": "init",
+                                "method": false, "shorthand": true, "computed": false,
+                                "key": { "type": "Identifier", "name": "a" },
+                                "value": { "type": "Identifier", "name": "a" }
+                            },
+                            { "type": "RestElement", "argument": { "type": "Identifier", "name": "rest" } }
+                        ]
+                    },
+                    "init": { "type": "Identifier", "name": "obj" }
+                }]
+            },
+            {
+                "type": "VariableDeclaration", "kind": "let",
+                "declarations": [{
+                    "type": "VariableDeclarator",
+                    "id": { "type": "Identifier", "name": "f" },
+                    "init": {
+                        "type": "ArrowFunctionExpression", "id": null,
+                        "expression": true, "generator": false, "async": true,
+                        "params": [
+                            { "type": "Identifier", "name": "x" },
+                            { "type": "ArrayPattern", "elements": [null, { "type": "Identifier", "name": "y" }] }
+                        ],
+                        "body": {
+                            "type": "BinaryExpression", "operator": "+",
+                            "left": { "type": "Identifier", "name": "x" },
+                            "right": { "type": "Identifier", "name": "y" }
+                        }
+                    }
+                }]
+            },
+            {
+                "type": "ExpressionStatement",
+                "expression": {
+                    "type": "AssignmentExpression", "operator": "=",
+                    "left": { "type": "Identifier", "name": "obj" },
+                    "right": {
+                        "type": "ObjectExpression",
+                        "properties": [
+                            {
+                                "type": "Property", "kind": "init",
+                                "method": false, "shorthand": true, "computed": false,
+                                "key": { "type": "Identifier", "name": "a" },
+                                "value": { "type": "Identifier", "name": "a" }
+                            },
+                            { "type": "SpreadElement", "argument": { "type": "Identifier", "name": "rest" } },
+                            {
+                                "type": "Property", "kind": "init",
+                                "method": false, "shorthand": false, "computed": true,
+                                "key": { "type": "Identifier", "name": "k" },
+                                "value": {
+                                    "type": "TemplateLiteral",
+                                    "expressions": [{ "type": "Identifier", "name": "a" }],
+                                    "quasis": [
+                                        { "type": "TemplateElement", "tail": false, "value": { "raw": "t", "cooked": "t" } },
+                                        { "type": "TemplateElement", "tail": true, "value": { "raw": "", "cooked": "" } }
+                                    ]
+                                }
+                            },
+                            {
+                                "type": "Property", "kind": "init",
+                                "method": false, "shorthand": false, "computed": true,
+                                "key": {
+                                    "type": "ArrayExpression",
+                                    "elements": [
+                                        { "type": "Literal", "value": 1, "raw": "1" },
+                                        null,
+                                        { "type": "Literal", "value": 2, "raw": "2" }
+                                    ]
+                                },
+                                "value": {
+                                    "type": "NewExpression",
+                                    "callee": { "type": "Identifier", "name": "C" },
+                                    "arguments": []
+                                }
+                            }
+                        ]
+                    }
+                }
+            }
+        ]
+    }"#;
+
+    // The ESTree for the following, without positions
+    // ```js
+    // for await (const item of list) { if (item) continue; else break; }
+    // try { f(a.b, c[0]); } catch (e) { throw e; } finally {}
+    // switch (a) { case 'b': b(); default: }
+    // ```
+    const STATEMENTS: &str = r#"{
+        "type": "Program", "sourceType": "module",
+        "body": [
+            {
+                "type": "ForOfStatement", "await": true,
+                "left": {
+                    "type": "VariableDeclaration", "kind": "const",
+                    "declarations": [{
+                        "type": "VariableDeclarator",
+                        "id": { "type": "Identifier", "name": "item" },
+                        "init": null
+                    }]
+                },
+                "right": { "type": "Identifier", "name": "list" },
+                "body": {
+                    "type": "BlockStatement",
+                    "body": [{
+                        "type": "IfStatement",
+                        "test": { "type": "Identifier", "name": "item" },
+                        "consequent": { "type": "ContinueStatement", "label": null },
+                        "alternate": { "type": "BreakStatement", "label": null }
+                    }]
+                }
+            },
+            {
+                "type": "TryStatement",
+                "block": {
+                    "type": "BlockStatement",
+                    "body": [{
+                        "type": "ExpressionStatement",
+                        "expression": {
+                            "type": "CallExpression", "optional": false,
+                            "callee": { "type": "Identifier", "name": "f" },
+                            "arguments": [
+                                {
+                                    "type": "MemberExpression", "computed": false, "optional": false,
+                                    "object": { "type": "Identifier", "name": "a" },
+                                    "property": { "type": "Identifier", "name": "b" }
+                                },
+                                {
+                                    "type": "MemberExpression", "computed": true, "optional": false,
+                                    "object": { "type": "Identifier", "name": "c" },
+                                    "property": { "type": "Literal", "value": 0, "raw": "0" }
+                                }
+                            ]
+                        }
+                    }]
+                },
+                "handler": {
+                    "type": "CatchClause",
+                    "param": { "type": "Identifier", "name": "e" },
+                    "body": {
+                        "type": "BlockStatement",
+                        "body": [{ "type": "ThrowStatement", "argument": { "type": "Identifier", "name": "e" } }]
+                    }
+                },
+                "finalizer": { "type": "BlockStatement", "body": [] }
+            },
+            {
+                "type": "SwitchStatement",
+                "discriminant": { "type": "Identifier", "name": "a" },
+                "cases": [
+                    {
+                        "type": "SwitchCase",
+                        "test": { "type": "Literal", "value": "b", "raw": "'b'" },
+                        "consequent": [{
+                            "type": "ExpressionStatement",
+                            "expression": {
+                                "type": "CallExpression", "optional": false,
+                                "callee": { "type": "Identifier", "name": "b" },
+                                "arguments": []
+                            }
+                        }]
+                    },
+                    { "type": "SwitchCase", "test": null, "consequent": [] }
+                ]
+            }
+        ]
+    }"#;
+
+    fn round_trip(json: &str) {
+        let fixture: serde_json::Value = serde_json::from_str(json).unwrap();
+        let program = from_estree_value(&fixture).expect("failed to convert fixture");
+        assert_eq!(to_estree_value(&program), fixture);
+        let written = to_estree_json(&program);
+        assert_eq!(from_estree_json(&written).unwrap(), program);
+    }
+
+    #[test]
+    fn round_trip_expressions() {
+        round_trip(EXPRESSIONS);
+    }
+
+    #[test]
+    fn round_trip_statements() {
+        round_trip(STATEMENTS);
+    }
+
+    #[test]
+    fn write_types() {
+        let program = Program::script(vec![
+            ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::number_from("0x10")))),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::RegEx(RegEx::from("a", "g"))))),
+            ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::String(StringLit::double_from(
+                "\\u0041",
+            ))))),
+        ]);
+        let value = to_estree_value(&program);
+        let expressions: Vec<_> = value["body"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|part| &part["expression"])
+            .collect();
+        assert_eq!(expressions[0]["value"], 16);
+        assert_eq!(expressions[0]["raw"], "0x10");
+        assert_eq!(expressions[1]["regex"]["flags"], "g");
+        assert!(expressions[1]["value"].is_null());
+        assert_eq!(expressions[2]["value"], "A");
+        assert_eq!(expressions[2]["raw"], "\"\\u0041\"");
+    }
+
+    #[test]
+    #[cfg(feature = "preserve-parens")]
+    fn parenthesized_expressions() {
+        // (a);
+        let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Paren(Box::new(
+            Expr::ident_from("a"),
+        ))))]);
+        let json = to_estree_value(&program);
+        assert_eq!(
+            json["body"][0]["expression"],
+            serde_json::json!({
+                "type": "ParenthesizedExpression",
+                "expression": { "type": "Identifier", "name": "a" },
+            })
+        );
+        assert_eq!(from_estree_value(&json).unwrap(), program);
+    }
+
+    #[test]
+    fn malformed_nodes() {
+        let program = |expression: &str| {
+            format!(
+                r#"{{
+                    "type": "Program", "sourceType": "script", "start": 0, "end": 20,
+                    "body": [{{
+                        "type": "ExpressionStatement", "start": 0, "end": 20,
+                        "expression": {}
+                    }}]
+                }}"#,
+                expression
+            )
+        };
+        let unknown = program(r#"{ "type": "MadeUpExpression", "start": 4, "end": 9 }"#);
+        match from_estree_json(&unknown) {
+            Err(e @ ResastError::UnknownNodeType { .. }) => {
+                assert_eq!(e.node_type(), Some("MadeUpExpression"));
+                assert_eq!(e.range(), Some(&(4..9)));
+                let message = "unsupported ESTree node type `MadeUpExpression` at 4..9";
+                assert_eq!(e.to_string(), message);
+            }
+            other => panic!("expected an unknown node error, found {:?}", other),
+        }
+        let no_operator = program(
+            r#"{
+                "type": "BinaryExpression", "range": [0, 5],
+                "left": { "type": "Identifier", "name": "a" },
+                "right": { "type": "Identifier", "name": "b" }
+            }"#,
+        );
+        match from_estree_json(&no_operator) {
+            Err(ResastError::MissingField { node, field, range }) => {
+                assert_eq!((node.as_str(), field), ("BinaryExpression", "operator"));
+                assert_eq!(range, Some(0..5));
+            }
+            other => panic!("expected a missing field error, found {:?}", other),
+        }
+        let bad_operator = program(
+            r#"{
+                "type": "UnaryExpression", "operator": "++", "prefix": true,
+                "argument": { "type": "Identifier", "name": "a" }
+            }"#,
+        );
+        match from_estree_json(&bad_operator) {
+            Err(ResastError::InvalidOperator { node, operator, range }) => {
+                assert_eq!((node.as_str(), operator.as_str()), ("UnaryExpression", "++"));
+                assert_eq!(range, None);
+            }
+            other => panic!("expected an invalid operator error, found {:?}", other),
+        }
+        let no_type = program(r#"{ "name": "a" }"#);
+        match from_estree_json(&no_type) {
+            Err(ResastError::MissingField { node, field, .. }) => {
+                assert_eq!((node.as_str(), field), ("unknown", "type"));
+            }
+            other => panic!("expected a missing type error, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn strings_without_raw() {
+        let expression = |value: &str| {
+            let json = serde_json::json!({
+                "type": "Program", "sourceType": "script",
+                "body": [{
+                    "type": "ExpressionStatement",
+                    "expression": { "type": "Literal", "value": value }
+                }]
+            });
+            match from_estree_value(&json).expect("failed to convert literal") {
+                Program::Script(mut parts, _) => match parts.remove(0) {
+                    ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::String(s)))) => s,
+                    other => panic!("expected a string literal, found {:?}", other),
+                },
+                other => panic!("expected a script, found {:?}", other),
+            }
+        };
+        let both = expression("a'b\"c\nd\\");
+        assert_eq!(both, StringLit::double_from(r#"a'b\"c\nd\\"#));
+        // the quote that isn't in the text is picked
+        assert_eq!(expression("say \"hi\""), StringLit::single_from("say \"hi\""));
+        assert_eq!(expression("it's"), StringLit::double_from("it's"));
+    }
+
+    #[test]
+    fn attribute_keywords() {
+        // import data from './data.json' with { type: 'json' };
+        // export * from './data.json' assert { type: 'json' };
+        let attributes = || {
+            vec![ImportAttribute {
+                key: ImportAttributeKey::Ident(Ident::from("type")),
+                value: Lit::single_string_from("json"),
+            }]
+        };
+        let import = |keyword| {
+            ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+                specifiers: vec![ImportSpecifier::Default(Ident::from("data"))],
+                source: Lit::single_string_from("./data.json"),
+                attributes: attributes(),
+                attributes_keyword: keyword,
+            })))
+        };
+        let export = |keyword| {
+            let source = ExportSource {
+                source: Lit::single_string_from("./data.json"),
+                attributes: attributes(),
+                attributes_keyword: keyword,
+            };
+            ProgramPart::Decl(Decl::Export(Box::new(ModExport::All(None, source))))
+        };
+        for keyword in &[AttributesKeyword::With, AttributesKeyword::Assert] {
+            let program = Program::module(vec![import(Some(*keyword)), export(Some(*keyword))]);
+            let written = to_estree_json(&program);
+            assert_eq!(from_estree_json(&written).unwrap(), program);
+        }
+        let (with, assert) = (Some(AttributesKeyword::With), Some(AttributesKeyword::Assert));
+        let with = to_estree_value(&Program::module(vec![import(with), export(with)]));
+        let assert = to_estree_value(&Program::module(vec![import(assert)]));
+        assert_ne!(with, assert);
+        assert_eq!(with["body"][0]["attributes"], assert["body"][0]["assertions"]);
+        let unset = to_estree_value(&Program::module(vec![import(None), export(None)]));
+        assert_eq!(unset, with);
+    }
+}
@@ -0,0 +1,700 @@
+use serde_json::{json, Map, Value};
+
+use super::unescape::unescaper;
+use crate::prelude::*;
+
+fn opt<T>(value: &Option<T>, f: impl Fn(&T) -> Value) -> Value {
+    value.as_ref().map(f).unwrap_or(Value::Null)
+}
+
+fn all<T>(values: &[T], f: impl Fn(&T) -> Value) -> Value {
+    Value::Array(values.iter().map(f).collect())
+}
+
+pub(crate) fn program(program: &Program) -> Value {
+    let (source_type, body) = match program {
+        Program::Mod(body, _) => ("module", body),
+        Program::Script(body, _) => ("script", body),
+    };
+    let mut ret = json!({
+        "type": "Program",
+        "sourceType": source_type,
+        "body": all(body, part),
+    });
+    if let Some(hashbang) = program.hashbang() {
+        ret["hashbang"] = json!(hashbang);
+    }
+    ret
+}
+
+fn part(part: &ProgramPart) -> Value {
+    match part {
+        ProgramPart::Dir(dir) => json!({
+            "type": "ExpressionStatement",
+            "expression": lit(&dir.expr),
+            "directive": dir.dir,
+        }),
+        ProgramPart::Decl(d) => decl(d),
+        ProgramPart::Stmt(s) => stmt(s),
+    }
+}
+
+fn var_kind(kind: VarKind) -> &'static str {
+    match kind {
+        VarKind::Var => "var",
+        VarKind::Let => "let",
+        VarKind::Const => "const",
+    }
+}
+
+fn var_decls(kind: VarKind, decls: &[VarDecl]) -> Value {
+    json!({
+        "type": "VariableDeclaration",
+        "kind": var_kind(kind),
+        "declarations": all(decls, |d| json!({
+            "type": "VariableDeclarator",
+            "id": pat(&d.id),
+            "init": opt(&d.init, expr),
+        })),
+    })
+}
+
+fn decl(decl: &Decl) -> Value {
+    match decl {
+        Decl::Var(kind, decls) => var_decls(*kind, decls),
+        Decl::Func(f) => func(f, "FunctionDeclaration"),
+        Decl::Class(c) => class(c, "ClassDeclaration"),
+        Decl::Import(import) => {
+            let mut ret = json!({
+                "type": "ImportDeclaration",
+                "specifiers": all(&import.specifiers, import_specifier),
+                "source": lit(&import.source),
+            });
+            if let Some(keyword) = import.clause_keyword() {
+                ret[attributes_key(keyword)] = all(&import.attributes, import_attribute);
+            }
+            ret
+        }
+        Decl::Export(export) => mod_export(export),
+    }
+}
+
+fn import_specifier(spec: &ImportSpecifier) -> Value {
+    match spec {
+        ImportSpecifier::Normal(spec) => json!({
+            "type": "ImportSpecifier",
+            "imported": module_export_name(&spec.imported),
+            "local": ident(&spec.local),
+        }),
+        ImportSpecifier::Default(local) => json!({
+            "type": "ImportDefaultSpecifier",
+            "local": ident(local),
+        }),
+        ImportSpecifier::Namespace(local) => json!({
+            "type": "ImportNamespaceSpecifier",
+            "local": ident(local),
+        }),
+    }
+}
+
+fn import_attribute(attr: &ImportAttribute) -> Value {
+    let key = match &attr.key {
+        ImportAttributeKey::Ident(i) => ident(i),
+        ImportAttributeKey::Lit(l) => lit(l),
+    };
+    json!({
+        "type": "ImportAttribute",
+        "key": key,
+        "value": lit(&attr.value),
+    })
+}
+
+/// `with` is the standard `attributes`, the legacy `assert` keeps
+/// the `assertions` name acorn and babel used for it
+fn attributes_key(keyword: AttributesKeyword) -> &'static str {
+    match keyword {
+        AttributesKeyword::With => "attributes",
+        AttributesKeyword::Assert => "assertions",
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> Value {
+    match name {
+        ModuleExportName::Ident(i) => ident(i),
+        ModuleExportName::Str(l) => lit(l),
+    }
+}
+
+/// Add the `source` and, if there are any, attributes of a re-export
+fn export_source(ret: &mut Value, source: Option<&ExportSource>) {
+    ret["source"] = source.map(|s| lit(&s.source)).unwrap_or(Value::Null);
+    if let Some(source) = source {
+        if let Some(keyword) = source.clause_keyword() {
+            ret[attributes_key(keyword)] = all(&source.attributes, import_attribute);
+        }
+    }
+}
+
+fn mod_export(export: &ModExport) -> Value {
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(d)) => json!({
+            "type": "ExportDefaultDeclaration",
+            "declaration": decl(d),
+        }),
+        ModExport::Default(DefaultExportDecl::Expr(e)) => json!({
+            "type": "ExportDefaultDeclaration",
+            "declaration": expr(e),
+        }),
+        ModExport::Named(NamedExportDecl::Decl(d)) => json!({
+            "type": "ExportNamedDeclaration",
+            "declaration": decl(d),
+            "specifiers": [],
+            "source": null,
+        }),
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            let mut ret = json!({
+                "type": "ExportNamedDeclaration",
+                "declaration": null,
+                "specifiers": all(specs, |spec| json!({
+                    "type": "ExportSpecifier",
+                    "local": ident(&spec.local),
+                    "exported": module_export_name(&spec.exported),
+                })),
+            });
+            export_source(&mut ret, source.as_ref());
+            ret
+        }
+        ModExport::All(alias, source) => {
+            let mut ret = json!({
+                "type": "ExportAllDeclaration",
+                "exported": opt(alias, module_export_name),
+            });
+            export_source(&mut ret, Some(source));
+            ret
+        }
+    }
+}
+
+fn ident(ident: &Ident) -> Value {
+    let name = unescaper(&ident.name).unwrap_or_else(|| ident.name.to_string());
+    json!({ "type": "Identifier", "name": name })
+}
+
+fn private_id(id: &PrivateId) -> Value {
+    json!({ "type": "PrivateIdentifier", "name": id.name })
+}
+
+fn block(parts: &[ProgramPart]) -> Value {
+    json!({ "type": "BlockStatement", "body": all(parts, part) })
+}
+
+fn func(f: &Func, kind: &str) -> Value {
+    json!({
+        "type": kind,
+        "id": opt(&f.id, ident),
+        "expression": false,
+        "generator": f.generator,
+        "async": f.is_async,
+        "params": all(&f.params, func_arg),
+        "body": block(&f.body.0),
+    })
+}
+
+fn func_arg(arg: &FuncArg) -> Value {
+    match arg {
+        FuncArg::Expr(e) => expr(e),
+        FuncArg::Pat(p) => pat(p),
+    }
+}
+
+fn with_decorators(mut node: Value, decorators: &[Decorator]) -> Value {
+    if !decorators.is_empty() {
+        node["decorators"] = all(decorators, |d| {
+            json!({ "type": "Decorator", "expression": expr(&d.expression) })
+        });
+    }
+    node
+}
+
+fn class(c: &Class, kind: &str) -> Value {
+    let ret = json!({
+        "type": kind,
+        "id": opt(&c.id, ident),
+        "superClass": opt(&c.super_class, |e| expr(e)),
+        "body": {
+            "type": "ClassBody",
+            "body": all(&c.body.0, class_member),
+        },
+    });
+    with_decorators(ret, &c.decorators)
+}
+
+fn class_member(member: &ClassMember) -> Value {
+    let ret = match member {
+        ClassMember::StaticBlock(b) => {
+            return json!({ "type": "StaticBlock", "body": all(&b.0, part) })
+        }
+        ClassMember::Field(field) => json!({
+            "type": "PropertyDefinition",
+            "key": prop_key(&field.key),
+            "value": opt(&field.value, expr),
+            "computed": field.computed,
+            "static": field.is_static,
+        }),
+        ClassMember::Method(method) => {
+            let kind = match method.kind {
+                MethodKind::Constructor => "constructor",
+                MethodKind::Method => "method",
+                MethodKind::Get => "get",
+                MethodKind::Set => "set",
+            };
+            json!({
+                "type": "MethodDefinition",
+                "key": prop_key(&method.key),
+                "value": func(&method.value, "FunctionExpression"),
+                "kind": kind,
+                "computed": method.computed,
+                "static": method.is_static,
+            })
+        }
+    };
+    with_decorators(ret, member.decorators())
+}
+
+fn prop_key(key: &PropKey) -> Value {
+    match key {
+        PropKey::Lit(l) => lit(l),
+        PropKey::Expr(e) => expr(e),
+        PropKey::Pat(p) => pat(p),
+        PropKey::Private(p) => private_id(p),
+    }
+}
+
+fn prop_value(value: &PropValue) -> Option<Value> {
+    match value {
+        PropValue::Expr(e) => Some(expr(e)),
+        PropValue::Pat(p) => Some(pat(p)),
+        PropValue::None => None,
+    }
+}
+
+/// A `Property` from either an object literal or pattern, a
+/// shorthand property's value is a copy of its key
+fn prop(prop: &Prop) -> Value {
+    let key = prop_key(&prop.key);
+    let value = prop_value(&prop.value).unwrap_or_else(|| key.clone());
+    let kind = match prop.kind {
+        PropKind::Get => "get",
+        PropKind::Set => "set",
+        _ => "init",
+    };
+    json!({
+        "type": "Property",
+        "key": key,
+        "value": value,
+        "kind": kind,
+        "method": prop.method,
+        "shorthand": prop.short_hand,
+        "computed": prop.computed,
+    })
+}
+
+fn stmt(node: &Stmt) -> Value {
+    match node {
+        Stmt::Expr(e) => json!({ "type": "ExpressionStatement", "expression": expr(e) }),
+        Stmt::Block(b) => block(&b.0),
+        Stmt::Empty => json!({ "type": "EmptyStatement" }),
+        Stmt::Debugger => json!({ "type": "DebuggerStatement" }),
+        Stmt::With(w) => json!({
+            "type": "WithStatement",
+            "object": expr(&w.object),
+            "body": stmt(&w.body),
+        }),
+        Stmt::Return(arg) => json!({ "type": "ReturnStatement", "argument": opt(arg, expr) }),
+        Stmt::Labeled(l) => json!({
+            "type": "LabeledStatement",
+            "label": ident(&l.label),
+            "body": stmt(&l.body),
+        }),
+        Stmt::Break(label) => json!({ "type": "BreakStatement", "label": opt(label, ident) }),
+        Stmt::Continue(label) => json!({ "type": "ContinueStatement", "label": opt(label, ident) }),
+        Stmt::If(i) => json!({
+            "type": "IfStatement",
+            "test": expr(&i.test),
+            "consequent": stmt(&i.consequent),
+            "alternate": opt(&i.alternate, |s| stmt(s)),
+        }),
+        Stmt::Switch(s) => json!({
+            "type": "SwitchStatement",
+            "discriminant": expr(&s.discriminant),
+            "cases": all(&s.cases, |case| json!({
+                "type": "SwitchCase",
+                "test": opt(&case.test, expr),
+                "consequent": all(&case.consequent, part),
+            })),
+        }),
+        Stmt::Throw(e) => json!({ "type": "ThrowStatement", "argument": expr(e) }),
+        Stmt::Try(t) => json!({
+            "type": "TryStatement",
+            "block": block(&t.block.0),
+            "handler": opt(&t.handler, |h| json!({
+                "type": "CatchClause",
+                "param": opt(&h.param, pat),
+                "body": block(&h.body.0),
+            })),
+            "finalizer": opt(&t.finalizer, |f| block(&f.0)),
+        }),
+        Stmt::While(w) => json!({
+            "type": "WhileStatement",
+            "test": expr(&w.test),
+            "body": stmt(&w.body),
+        }),
+        Stmt::DoWhile(d) => json!({
+            "type": "DoWhileStatement",
+            "body": stmt(&d.body),
+            "test": expr(&d.test),
+        }),
+        Stmt::For(f) => json!({
+            "type": "ForStatement",
+            "init": opt(&f.init, |init| match init {
+                LoopInit::Variable(kind, decls) => var_decls(*kind, decls),
+                LoopInit::Expr(e) => expr(e),
+            }),
+            "test": opt(&f.test, expr),
+            "update": opt(&f.update, expr),
+            "body": stmt(&f.body),
+        }),
+        Stmt::ForIn(f) => json!({
+            "type": "ForInStatement",
+            "left": loop_left(&f.left),
+            "right": expr(&f.right),
+            "body": stmt(&f.body),
+        }),
+        Stmt::ForOf(f) => json!({
+            "type": "ForOfStatement",
+            "await": f.is_await,
+            "left": loop_left(&f.left),
+            "right": expr(&f.right),
+            "body": stmt(&f.body),
+        }),
+        Stmt::Var(decls) => var_decls(VarKind::Var, decls),
+    }
+}
+
+fn loop_left(left: &LoopLeft) -> Value {
+    match left {
+        LoopLeft::Expr(e) => expr(e),
+        LoopLeft::Variable(kind, decl) => var_decls(*kind, std::slice::from_ref(decl)),
+        LoopLeft::Pat(p) => pat(p),
+    }
+}
+
+fn lit(lit: &Lit) -> Value {
+    match lit {
+        Lit::Null => json!({ "type": "Literal", "value": null, "raw": "null" }),
+        Lit::Boolean(b) => json!({ "type": "Literal", "value": b, "raw": b.to_string() }),
+        Lit::Number(n) => json!({
+            "type": "Literal",
+            "value": number_value(n),
+            "raw": n.raw(),
+        }),
+        Lit::BigInt(digits) => json!({
+            "type": "Literal",
+            "value": null,
+            "raw": format!("{}n", digits),
+            "bigint": digits,
+        }),
+        Lit::String(s) => {
+            let (quote, inner) = match s {
+                StringLit::Double(s) => ('"', s),
+                StringLit::Single(s) => ('\'', s),
+            };
+            let raw = format!("{0}{1}{0}", quote, inner);
+            let value = unescaper(&raw)
+                .map(|s| s[1..s.len() - 1].to_string())
+                .unwrap_or_else(|| inner.to_string());
+            json!({ "type": "Literal", "value": value, "raw": raw })
+        }
+        Lit::RegEx(re) => json!({
+            "type": "Literal",
+            "value": null,
+            "raw": format!("/{}/{}", re.pattern, re.flags),
+            "regex": { "pattern": re.pattern, "flags": re.flags },
+        }),
+        Lit::Template(t) => template(t),
+    }
+}
+
+/// The numeric value of a number literal, non-finite
+/// values can't be represented in JSON so they are `null`
+fn number_value(n: &NumberLit) -> Value {
+    match n.as_f64() {
+        // integers are written without a fractional part to match
+        // what JSON.stringify would produce
+        Some(v) if v.fract() == 0.0 && v.abs() < 9_007_199_254_740_992.0 => json!(v as i64),
+        Some(v) => serde_json::Number::from_f64(v)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        None => Value::Null,
+    }
+}
+
+fn template(t: &TemplateLit) -> Value {
+    json!({
+        "type": "TemplateLiteral",
+        "expressions": all(&t.expressions, expr),
+        "quasis": all(&t.quasis, template_element),
+    })
+}
+
+fn template_element(el: &TemplateElement) -> Value {
+    let raw: &str = &el.raw;
+    let raw = raw
+        .strip_prefix('`')
+        .or_else(|| raw.strip_prefix('}'))
+        .unwrap_or(raw);
+    let raw = raw
+        .strip_suffix("${")
+        .or_else(|| raw.strip_suffix('`'))
+        .unwrap_or(raw);
+    let mut value = Map::new();
+    value.insert("raw".to_string(), json!(raw));
+    value.insert(
+        "cooked".to_string(),
+        el.cooked.as_ref().map(|c| json!(c)).unwrap_or(Value::Null),
+    );
+    json!({
+        "type": "TemplateElement",
+        "tail": el.tail,
+        "value": value,
+    })
+}
+
+fn unary_op(op: UnaryOp) -> &'static str {
+    match op {
+        UnaryOp::Minus => "-",
+        UnaryOp::Plus => "+",
+        UnaryOp::Not => "!",
+        UnaryOp::Tilde => "~",
+        UnaryOp::TypeOf => "typeof",
+        UnaryOp::Void => "void",
+        UnaryOp::Delete => "delete",
+    }
+}
+
+fn binary_op(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::StrictEqual => "===",
+        BinaryOp::StrictNotEqual => "!==",
+        BinaryOp::LessThan => "<",
+        BinaryOp::GreaterThan => ">",
+        BinaryOp::LessThanEqual => "<=",
+        BinaryOp::GreaterThanEqual => ">=",
+        BinaryOp::LeftShift => "<<",
+        BinaryOp::RightShift => ">>",
+        BinaryOp::UnsignedRightShift => ">>>",
+        BinaryOp::Plus => "+",
+        BinaryOp::Minus => "-",
+        BinaryOp::Times => "*",
+        BinaryOp::Over => "/",
+        BinaryOp::Mod => "%",
+        BinaryOp::Or => "|",
+        BinaryOp::XOr => "^",
+        BinaryOp::And => "&",
+        BinaryOp::In => "in",
+        BinaryOp::InstanceOf => "instanceof",
+        BinaryOp::PowerOf => "**",
+    }
+}
+
+fn assign_op(op: AssignOp) -> &'static str {
+    match op {
+        AssignOp::Equal => "=",
+        AssignOp::PlusEqual => "+=",
+        AssignOp::MinusEqual => "-=",
+        AssignOp::TimesEqual => "*=",
+        AssignOp::DivEqual => "/=",
+        AssignOp::ModEqual => "%=",
+        AssignOp::LeftShiftEqual => "<<=",
+        AssignOp::RightShiftEqual => ">>=",
+        AssignOp::UnsignedRightShiftEqual => ">>>=",
+        AssignOp::OrEqual => "|=",
+        AssignOp::XOrEqual => "^=",
+        AssignOp::AndEqual => "&=",
+        AssignOp::PowerOfEqual => "**=",
+        AssignOp::LogicalAndEqual => "&&=",
+        AssignOp::LogicalOrEqual => "||=",
+        AssignOp::NullishEqual => "??=",
+    }
+}
+
+fn logical_op(op: LogicalOp) -> &'static str {
+    match op {
+        LogicalOp::Or => "||",
+        LogicalOp::And => "&&",
+        LogicalOp::NullishCoalescing => "??",
+    }
+}
+
+pub(crate) fn expr(node: &Expr) -> Value {
+    match node {
+        Expr::Array(elements) => json!({
+            "type": "ArrayExpression",
+            "elements": all(elements, |el| opt(el, expr)),
+        }),
+        Expr::ArrowFunc(arrow) => {
+            let body = match &arrow.body {
+                ArrowFuncBody::FuncBody(body) => block(&body.0),
+                ArrowFuncBody::Expr(e) => expr(e),
+            };
+            json!({
+                "type": "ArrowFunctionExpression",
+                "id": null,
+                "expression": arrow.expression,
+                "generator": false,
+                "async": arrow.is_async,
+                "params": all(&arrow.params, func_arg),
+                "body": body,
+            })
+        }
+        // only ever produced part way through parsing
+        Expr::ArrowParamPlaceHolder(_, _) => Value::Null,
+        Expr::Assign(a) => {
+            let left = match &a.left {
+                AssignLeft::Pat(p) => pat(p),
+                AssignLeft::Expr(e) => expr(e),
+            };
+            json!({
+                "type": "AssignmentExpression",
+                "operator": assign_op(a.operator),
+                "left": left,
+                "right": expr(&a.right),
+            })
+        }
+        Expr::Await(e) => json!({ "type": "AwaitExpression", "argument": expr(e) }),
+        Expr::Binary(b) => json!({
+            "type": "BinaryExpression",
+            "left": expr(&b.left),
+            "operator": binary_op(b.operator),
+            "right": expr(&b.right),
+        }),
+        Expr::Class(c) => class(c, "ClassExpression"),
+        Expr::Call(c) => json!({
+            "type": "CallExpression",
+            "callee": expr(&c.callee),
+            "arguments": all(&c.arguments, expr),
+            "optional": c.optional,
+        }),
+        Expr::Conditional(c) => json!({
+            "type": "ConditionalExpression",
+            "test": expr(&c.test),
+            "consequent": expr(&c.consequent),
+            "alternate": expr(&c.alternate),
+        }),
+        Expr::Func(f) => func(f, "FunctionExpression"),
+        Expr::Ident(i) => ident(i),
+        Expr::Import(i) => {
+            let mut ret = json!({ "type": "ImportExpression", "source": expr(&i.source) });
+            if let Some(options) = &i.options {
+                ret["options"] = expr(options);
+            }
+            ret
+        }
+        Expr::Lit(l) => lit(l),
+        Expr::Logical(l) => json!({
+            "type": "LogicalExpression",
+            "left": expr(&l.left),
+            "operator": logical_op(l.operator),
+            "right": expr(&l.right),
+        }),
+        Expr::Member(m) => json!({
+            "type": "MemberExpression",
+            "object": expr(&m.object),
+            "property": match &m.property {
+                MemberProp::Ident(id) => ident(id),
+                MemberProp::Private(id) => private_id(id),
+                MemberProp::Computed(e) => expr(e),
+            },
+            "computed": m.is_computed(),
+            "optional": m.optional,
+        }),
+        Expr::MetaProp(m) => json!({
+            "type": "MetaProperty",
+            "meta": ident(&m.meta),
+            "property": ident(&m.property),
+        }),
+        Expr::PrivateId(p) => private_id(p),
+        Expr::OptionalChain(e) => json!({ "type": "ChainExpression", "expression": expr(e) }),
+        #[cfg(feature = "preserve-parens")]
+        Expr::Paren(e) => json!({ "type": "ParenthesizedExpression", "expression": expr(e) }),
+        Expr::New(n) => json!({
+            "type": "NewExpression",
+            "callee": expr(&n.callee),
+            "arguments": all(n.args(), expr),
+        }),
+        Expr::Obj(props) => json!({
+            "type": "ObjectExpression",
+            "properties": all(props, |p| match p {
+                ObjProp::Prop(p) => prop(p),
+                ObjProp::Spread(e) => json!({ "type": "SpreadElement", "argument": expr(e) }),
+            }),
+        }),
+        Expr::Sequence(seq) => json!({
+            "type": "SequenceExpression",
+            "expressions": seq.iter().map(expr).collect::<Vec<_>>(),
+        }),
+        Expr::Spread(e) => json!({ "type": "SpreadElement", "argument": expr(e) }),
+        Expr::Super => json!({ "type": "Super" }),
+        Expr::TaggedTemplate(t) => json!({
+            "type": "TaggedTemplateExpression",
+            "tag": expr(&t.tag),
+            "quasi": template(&t.quasi),
+        }),
+        Expr::This => json!({ "type": "ThisExpression" }),
+        Expr::Unary(u) => json!({
+            "type": "UnaryExpression",
+            "operator": unary_op(u.operator),
+            "prefix": true,
+            "argument": expr(&u.argument),
+        }),
+        Expr::Update(u) => json!({
+            "type": "UpdateExpression",
+            "operator": if u.operator == UpdateOp::Increment { "++" } else { "--" },
+            "prefix": u.prefix,
+            "argument": expr(&u.argument),
+        }),
+        Expr::Yield(y) => json!({
+            "type": "YieldExpression",
+            "argument": opt(&y.argument, |e| expr(e)),
+            "delegate": y.delegate,
+        }),
+    }
+}
+
+fn pat(node: &Pat) -> Value {
+    match node {
+        Pat::Ident(i) => ident(i),
+        Pat::Obj(parts) => json!({
+            "type": "ObjectPattern",
+            "properties": all(parts, |part| match part {
+                ObjPatPart::Assign(p) => prop(p),
+                ObjPatPart::Rest(r) => json!({ "type": "RestElement", "argument": pat(r) }),
+            }),
+        }),
+        Pat::Array(parts) => json!({
+            "type": "ArrayPattern",
+            "elements": all(parts, |part| opt(part, |part| match part {
+                ArrayPatPart::Pat(p) => pat(p),
+                ArrayPatPart::Expr(e) => expr(e),
+            })),
+        }),
+        Pat::RestElement(r) => json!({ "type": "RestElement", "argument": pat(r) }),
+        Pat::Assign(a) => json!({
+            "type": "AssignmentPattern",
+            "left": pat(&a.left),
+            "right": expr(&a.right),
+        }),
+    }
+}
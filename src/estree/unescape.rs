@@ -0,0 +1,183 @@
+//! Undo the escapes in javascript string and identifier text
+use std::collections::VecDeque;
+
+pub(crate) fn unescaper(s: &str) -> Option<String> {
+    let mut queue: VecDeque<_> = String::from(s).chars().collect();
+    let mut s = String::new();
+
+    while let Some(c) = queue.pop_front() {
+        if c != '\\' {
+            s.push(c);
+            continue;
+        }
+
+        match queue.pop_front() {
+            Some('b') => s.push('\u{0008}'),
+            Some('f') => s.push('\u{000C}'),
+            Some('n') => s.push('\n'),
+            Some('r') => s.push('\r'),
+            Some('t') => s.push('\t'),
+            Some('v') => s.push('\u{000b}'),
+            Some('\'') => s.push('\''),
+            Some('\"') => s.push('\"'),
+            Some('\\') => s.push('\\'),
+            Some('u') => {
+                if let Some(x) = unescape_unicode(&mut queue) {
+                    s.push(x);
+                } else {
+                    return None;
+                }
+            }
+            Some('x') => {
+                if let Some(x) = unescape_byte(&mut queue) {
+                    s.push(x)
+                } else {
+                    return None;
+                }
+            }
+            Some('\0') => s.push('\0'),
+            Some(c) => {
+                if c.is_digit(8) {
+                    if let Some(x) = unescape_octal(c, &mut queue) {
+                        s.push(x);
+                    } else {
+                        return None;
+                    }
+                } else {
+                    s.push(c)
+                }
+            }
+            _ => return None,
+        };
+    }
+
+    Some(s)
+}
+
+fn unescape_unicode(queue: &mut VecDeque<char>) -> Option<char> {
+    let ret = hex_char_code(queue)?;
+    ::std::char::from_u32(ret)
+}
+
+fn hex_char_code(queue: &mut VecDeque<char>) -> Option<u32> {
+    if let Some(c) = queue.pop_front() {
+        if c == '{' {
+            let mut x = 0;
+            while let Some(c) = queue.pop_front() {
+                if c == '}' {
+                    break;
+                }
+                x = x * 16 + c.to_digit(16)?;
+            }
+            Some(x)
+        } else {
+            let mut x = c.to_digit(16)?;
+            for _ in 0..3 {
+                if let Some(u) = queue.pop_front() {
+                    x = x * 16 + u.to_digit(16)?;
+                }
+            }
+            if (0xD800..=0xDBFF).contains(&x) {
+                debug_assert!(queue.pop_front() == Some('\\'));
+                debug_assert!(queue.pop_front() == Some('u'));
+                let high = (x - 0xD800) * 0x400;
+                let low = hex_char_code(queue)? - 0xDC00;
+                x = 0x10000 + high + low;
+            }
+            Some(x)
+        }
+    } else {
+        None
+    }
+}
+
+fn unescape_byte(queue: &mut VecDeque<char>) -> Option<char> {
+    let mut s = String::new();
+
+    for _ in 0..2 {
+        if let Some(c) = queue.pop_front() {
+            s.push(c)
+        } else {
+            return None;
+        }
+    }
+    match u32::from_str_radix(&s, 16) {
+        Ok(u) => ::std::char::from_u32(u),
+        Err(e) => {
+            panic!("{}", e);
+        }
+    }
+}
+
+fn unescape_octal(c: char, queue: &mut VecDeque<char>) -> Option<char> {
+    let (ret, ct) = if let Some(next) = queue.front() {
+        if !next.is_digit(8) {
+            let d = c.to_digit(8)?;
+            return std::char::from_u32(d);
+        } else if ('0'..'4').contains(&c) {
+            let s = if let Some(third) = queue.get(1) {
+                if !third.is_digit(8) {
+                    format!("{}{}", c, next)
+                } else {
+                    format!("{}{}{}", c, next, third)
+                }
+            } else {
+                format!("{}{}", c, next)
+            };
+            let ct = s.len().saturating_sub(1);
+            match u32::from_str_radix(&s, 8) {
+                Ok(r) => (::std::char::from_u32(r), ct),
+                Err(e) => panic!("{}", e),
+            }
+        } else {
+            match u32::from_str_radix(&format!("{}{}", c, next), 8) {
+                Ok(r) => (::std::char::from_u32(r), 1),
+                Err(e) => panic!("{}", e),
+            }
+        }
+    } else {
+        (Some(c), 0)
+    };
+    for _ in 0..ct {
+        let _ = queue.pop_front();
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn four_hundred() {
+        let js = r#""\1\00\400\000\""#;
+        let expectation = "\"\u{1}\u{0} 0\u{0}\"";
+        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    }
+    #[test]
+    fn escape_lots() {
+        let js = "\"\\'\\\"\\\\\\b\\f\\n\\r\\t\\v\\0\"";
+        let expectation = "\"'\"\\\u{8}\u{c}\n\r\t\u{b}\u{0}\"";
+        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    }
+
+    #[test]
+    fn escaped_new_line() {
+        let js = r#""\\\n""#;
+        let expectation = "\"\\\n\"";
+        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    }
+
+    #[test]
+    fn unicode_ident() {
+        let js = "φ";
+        let expectation = "φ";
+        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    }
+
+    #[test]
+    fn unicode_string() {
+        let js = r#""\uD834\uDF06\u2603\u03C6 \u{0000001F4a9}\u{1D306}\u{2603}\u{3c6} 𝌆☃φ""#;
+        let expectation = "\"𝌆☃φ 💩𝌆☃φ 𝌆☃φ\"";
+        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    }
+}
@@ -1,9 +1,12 @@
-use crate::pat::Pat;
+use crate::pat::{ArrayPatPart, ObjPatPart, Pat};
 use crate::{AssignOp, BinaryOp, LogicalOp, PropKind, UnaryOp, UpdateOp};
-use crate::{Class, Func, FuncArg, FuncBody, Ident};
+use crate::{Class, Decorator, Func, FuncArg, FuncBody, Ident};
 use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::iter::Peekable;
+use std::str::Chars;
 /// A slightly more granular program part that a statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -44,6 +47,8 @@ pub enum Expr<'a> {
     Func(Func<'a>),
     /// An identifier
     Ident(Ident<'a>),
+    /// A dynamic import, see `ImportExpr`
+    Import(ImportExpr<'a>),
     /// A literal value, see `Literal`
     Lit(Lit<'a>),
     /// A specialized `BinaryExpr` for logical evaluation
@@ -58,8 +63,32 @@ pub enum Expr<'a> {
     /// c.stuff;
     /// ```
     Member(MemberExpr<'a>),
-    /// currently just `new.target`
+    /// `new.target` or `import.meta`
     MetaProp(MetaProp<'a>),
+    /// The name of a private class member on the left
+    /// side of an `in` brand check, as the property of a
+    /// `MemberExpr` this is a `MemberProp::Private`
+    /// ```js
+    /// #count in obj;
+    /// ```
+    PrivateId(PrivateId<'a>),
+    /// The boundary of an optional chain, everything inside
+    /// of this expression will short circuit when an optional
+    /// link (`?.`) evaluates to `null` or `undefined`
+    /// ```js
+    /// a?.b.c;
+    /// //vs
+    /// (a?.b).c;
+    /// ```
+    OptionalChain(Box<Expr<'a>>),
+    /// An expression wrapped in parentheses, this is only
+    /// available with the `preserve-parens` feature for tools
+    /// that need to keep the parentheses an author wrote
+    /// ```js
+    /// (a + b) * c;
+    /// ```
+    #[cfg(feature = "preserve-parens")]
+    Paren(Box<Expr<'a>>),
     /// ```js
     /// var a = true ? 'stuff' : 'things';
     /// ```
@@ -71,7 +100,9 @@ pub enum Expr<'a> {
     Sequence(SequenceExpr<'a>),
     /// `...` followed by an `Expr`
     Spread(Box<Expr<'a>>),
-    /// `super`
+    /// `super`, this is only ever valid as the callee of a
+    /// `CallExpr` (`super()`) or the object of a `MemberExpr`
+    /// (`super.thing`)
     Super,
     /// A template literal preceded by a tag function identifier
     TaggedTemplate(TaggedTemplateExpr<'a>),
@@ -97,23 +128,133 @@ impl<'a> Expr<'a> {
     pub fn ident_from(s: &'a str) -> Self {
         Expr::Ident(Ident::from(s))
     }
+    /// The expression form of a destructuring pattern, the
+    /// reverse of `Pat::try_from_expr`
+    pub fn from_pat(pat: Pat<'a>) -> Self {
+        Expr::from(pat)
+    }
+    /// Compare the shape and values of two expressions, see
+    /// `Program::structural_eq`
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+    /// The identifiers this expression refers to that aren't
+    /// declared inside of it. See `analysis::free_identifiers`
+    pub fn free_identifiers(&'a self) -> Vec<&'a Ident<'a>> {
+        crate::analysis::free_identifiers(self)
+    }
+    /// If this is the literal `null`
+    pub fn is_null_literal(&self) -> bool {
+        matches!(self, Expr::Lit(Lit::Null))
+    }
+    /// If this is the identifier `undefined`.
+    ///
+    /// Unlike `null`, `undefined` isn't a literal, it is a global
+    /// variable and can be shadowed (`let undefined = 1`), this only
+    /// looks at the shape of the expression and doesn't check what
+    /// the name resolves to. `void 0` always evaluates to
+    /// `undefined` but isn't an identifier, see
+    /// `UnaryExpr::is_void_zero`
+    pub fn is_undefined_identifier(&self) -> bool {
+        matches!(self, Expr::Ident(id) if id.name == "undefined")
+    }
+}
+
+impl<'a> From<Ident<'a>> for Expr<'a> {
+    fn from(ident: Ident<'a>) -> Self {
+        Expr::Ident(ident)
+    }
 }
 
 /// `[a, b, c]`
+///
+/// A `None` entry is a hole (elision) like the middle of `[a,,b]`,
+/// holes still count toward the array's `length`.
+/// `ArrayExpr::default()` is `[]`
 pub type ArrayExpr<'a> = Vec<Option<Expr<'a>>>;
-/// `{a: 'b', c, ...d}`
+/// `{a: 'b', c, ...d}`, `ObjExpr::default()` is `{}`
 pub type ObjExpr<'a> = Vec<ObjProp<'a>>;
 /// A single part of an object literal
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum ObjProp<'a> {
     Prop(Prop<'a>),
+    /// `{...a}`, the value is the expression
+    /// being spread and not an `Expr::Spread`
     Spread(Expr<'a>),
 }
 
+/// Checks for an `ObjExpr`, which is a plain `Vec` so they
+/// can't be inherent methods
+pub trait ObjExprExt {
+    /// Fails if more than one property sets the prototype with
+    /// `__proto__: value` or `"__proto__": value`. Computed keys,
+    /// shorthand properties and methods named `__proto__` define a
+    /// normal property so any number of them are allowed
+    /// ```
+    /// # use resast::prelude::*;
+    /// let proto = || ObjProp::Prop(Prop {
+    ///     key: PropKey::Expr(Expr::ident_from("__proto__")),
+    ///     value: PropValue::Expr(Expr::Lit(Lit::Null)),
+    ///     kind: PropKind::Init,
+    ///     method: false,
+    ///     computed: false,
+    ///     short_hand: false,
+    ///     is_static: false,
+    ///     decorators: vec![],
+    /// });
+    /// assert_eq!(vec![proto()].validate_proto(), Ok(()));
+    /// assert_eq!(vec![proto(), proto()].validate_proto(), Err(ObjError::DuplicateProto));
+    /// ```
+    fn validate_proto(&self) -> Result<(), ObjError>;
+}
+
+impl<'a> ObjExprExt for ObjExpr<'a> {
+    fn validate_proto(&self) -> Result<(), ObjError> {
+        let mut found = false;
+        for prop in self {
+            let prop = match prop {
+                ObjProp::Prop(prop) => prop,
+                ObjProp::Spread(_) => continue,
+            };
+            if !prop.sets_proto() {
+                continue;
+            }
+            if found {
+                return Err(ObjError::DuplicateProto);
+            }
+            found = true;
+        }
+        Ok(())
+    }
+}
+
+/// The reasons an object literal is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjError {
+    /// ```js
+    /// ({ __proto__: a, "__proto__": b })
+    /// ```
+    DuplicateProto,
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ObjError::DuplicateProto => write!(f, "duplicate __proto__ property"),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
 /// A single part of an object literal or class
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -127,20 +268,119 @@ pub struct Prop<'a> {
     pub computed: bool,
     pub short_hand: bool,
     pub is_static: bool,
+    /// Any decorators applied to this property, this
+    /// will always be empty outside of a class body
+    /// ```js
+    /// class Thing {
+    ///     @logged
+    ///     get stuff() {}
+    /// }
+    /// ```
+    pub decorators: Vec<Decorator<'a>>,
+}
+
+impl<'a> Prop<'a> {
+    /// The function of a method, getter, setter or constructor.
+    ///
+    /// `async` and generator methods don't have their own flags
+    /// here, they are read from this function so the two can't
+    /// disagree
+    pub fn method_func(&self) -> Option<&Func<'a>> {
+        match (&self.value, self.is_method()) {
+            (PropValue::Expr(Expr::Func(func)), true) => Some(func),
+            _ => None,
+        }
+    }
+    /// If this was written with method syntax, including
+    /// `get`, `set` and class constructors
+    /// ```js
+    /// ({ m() {}, get x() {}, set x(v) {} })
+    /// ```
+    pub fn is_method(&self) -> bool {
+        self.method
+            || matches!(
+                self.kind,
+                PropKind::Get | PropKind::Set | PropKind::Ctor | PropKind::Method
+            )
+    }
+    /// If this is a `get` or `set` accessor
+    pub fn is_accessor(&self) -> bool {
+        self.is_getter() || self.is_setter()
+    }
+    pub fn is_getter(&self) -> bool {
+        self.kind == PropKind::Get
+    }
+    pub fn is_setter(&self) -> bool {
+        self.kind == PropKind::Set
+    }
+    /// If this is an `async` method, `{ a: async () => {} }`
+    /// is not an async method
+    pub fn is_async(&self) -> bool {
+        self.method_func().map(|f| f.is_async).unwrap_or(false)
+    }
+    /// If this is a generator method `{ *g() {} }`
+    pub fn is_generator(&self) -> bool {
+        self.method_func().map(|f| f.generator).unwrap_or(false)
+    }
+    /// If this is an object literal's `__proto__: value`, which
+    /// sets the prototype instead of defining a property
+    fn sets_proto(&self) -> bool {
+        if self.computed || self.short_hand || self.is_method() || self.kind != PropKind::Init {
+            return false;
+        }
+        match &self.key {
+            PropKey::Expr(Expr::Ident(id)) | PropKey::Pat(Pat::Ident(id)) => id.name == "__proto__",
+            PropKey::Lit(Lit::String(s)) => s.cooked() == "__proto__",
+            _ => false,
+        }
+    }
 }
 
 /// An object literal or class property identifier
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum PropKey<'a> {
     Lit(Lit<'a>),
     Expr(Expr<'a>),
     Pat(Pat<'a>),
+    /// A private class member name
+    /// ```js
+    /// class Thing {
+    ///     #stuff = 0;
+    ///     #things() {}
+    /// }
+    /// ```
+    Private(PrivateId<'a>),
+}
+
+/// The name of a private class member, this does
+/// not include the leading `#`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct PrivateId<'a> {
+    pub name: Cow<'a, str>,
+}
+
+impl<'a> PrivateId<'a> {
+    pub fn new(s: String) -> Self {
+        PrivateId {
+            name: Cow::Owned(s),
+        }
+    }
+    pub fn from(s: &'a str) -> Self {
+        PrivateId {
+            name: Cow::Borrowed(s),
+        }
+    }
 }
 
 /// The value of an object literal or class property
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum PropValue<'a> {
@@ -150,7 +390,7 @@ pub enum PropValue<'a> {
 }
 
 /// An operation that takes one argument
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct UnaryExpr<'a> {
     pub operator: UnaryOp,
@@ -158,8 +398,28 @@ pub struct UnaryExpr<'a> {
     pub argument: Box<Expr<'a>>,
 }
 
+impl<'a> UnaryExpr<'a> {
+    /// Every unary operator is a prefix so `prefix` is always `true`
+    pub fn new(operator: UnaryOp, argument: Expr<'a>) -> Self {
+        Self {
+            operator,
+            prefix: true,
+            argument: Box::new(argument),
+        }
+    }
+    /// `void 0`, a shorter `undefined` that can't be shadowed
+    pub fn void_zero() -> Self {
+        Self::new(UnaryOp::Void, Expr::Lit(Lit::number_from("0")))
+    }
+    /// If this is `void` applied to a number literal, which always
+    /// evaluates to `undefined`
+    pub fn is_void_zero(&self) -> bool {
+        self.operator == UnaryOp::Void && matches!(&*self.argument, Expr::Lit(Lit::Number(_)))
+    }
+}
+
 /// Increment or decrementing a value
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct UpdateExpr<'a> {
     pub operator: UpdateOp,
@@ -167,8 +427,19 @@ pub struct UpdateExpr<'a> {
     pub prefix: bool,
 }
 
+impl<'a> UpdateExpr<'a> {
+    /// `++a` when `prefix` is true, `a++` otherwise
+    pub fn new(operator: UpdateOp, argument: Expr<'a>, prefix: bool) -> Self {
+        Self {
+            operator,
+            argument: Box::new(argument),
+            prefix,
+        }
+    }
+}
+
 /// An operation that requires 2 arguments
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct BinaryExpr<'a> {
     pub operator: BinaryOp,
@@ -176,8 +447,18 @@ pub struct BinaryExpr<'a> {
     pub right: Box<Expr<'a>>,
 }
 
+impl<'a> BinaryExpr<'a> {
+    pub fn new(left: Expr<'a>, operator: BinaryOp, right: Expr<'a>) -> Self {
+        Self {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
 /// An assignment or update + assignment operation
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct AssignExpr<'a> {
     pub operator: AssignOp,
@@ -185,8 +466,140 @@ pub struct AssignExpr<'a> {
     pub right: Box<Expr<'a>>,
 }
 
+impl<'a> AssignExpr<'a> {
+    pub fn new(left: AssignLeft<'a>, operator: AssignOp, right: Expr<'a>) -> Self {
+        Self {
+            operator,
+            left,
+            right: Box::new(right),
+        }
+    }
+
+    /// Check that the left side can be assigned to, an
+    /// identifier, a member expression or, when the operator
+    /// is `=`, a destructuring pattern.
+    ///
+    /// Inside of a pattern, defaults (`[a = 1] = x`) and rest
+    /// elements are allowed. The tree doesn't keep parentheses
+    /// so `(a.b) = x` is checked the same as `a.b = x`
+    /// ```
+    /// # use resast::prelude::*;
+    /// use resast::expr::AssignError;
+    /// // 1 = x
+    /// let assign = AssignExpr::new(
+    ///     AssignLeft::Expr(Box::new(Expr::Lit(Lit::number_from("1")))),
+    ///     AssignOp::Equal,
+    ///     Expr::ident_from("x"),
+    /// );
+    /// assert_eq!(assign.validate_target(), Err(AssignError::InvalidTarget));
+    /// ```
+    pub fn validate_target(&self) -> Result<(), AssignError> {
+        let is_pattern = match &self.left {
+            AssignLeft::Pat(Pat::Ident(_)) => return Ok(()),
+            AssignLeft::Pat(pat @ Pat::Obj(_)) | AssignLeft::Pat(pat @ Pat::Array(_)) => {
+                pat_target(pat)
+            }
+            AssignLeft::Pat(_) => return Err(AssignError::InvalidTarget),
+            AssignLeft::Expr(expr) => match &**expr {
+                Expr::Obj(_) | Expr::Array(_) => expr_target(expr),
+                _ if simple_target(expr) => return Ok(()),
+                _ => return Err(AssignError::InvalidTarget),
+            },
+        };
+        if self.operator != AssignOp::Equal {
+            return Err(AssignError::CompoundDestructure);
+        }
+        if !is_pattern {
+            return Err(AssignError::InvalidTarget);
+        }
+        Ok(())
+    }
+}
+
+/// An identifier or a non-optional member expression
+fn simple_target(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(_) => true,
+        Expr::Member(member) => !member.optional,
+        _ => false,
+    }
+}
+
+/// If an expression is valid inside of a destructuring assignment
+fn expr_target(expr: &Expr) -> bool {
+    match expr {
+        Expr::Array(elements) => elements.iter().flatten().all(|el| match el {
+            Expr::Spread(inner) => expr_target(inner),
+            _ => expr_target(el),
+        }),
+        Expr::Obj(props) => props.iter().all(|prop| match prop {
+            ObjProp::Prop(prop) => match &prop.value {
+                PropValue::Expr(expr) => expr_target(expr),
+                PropValue::Pat(pat) => pat_target(pat),
+                PropValue::None => true,
+            },
+            ObjProp::Spread(expr) => expr_target(expr),
+        }),
+        Expr::Assign(assign) if assign.operator == AssignOp::Equal => match &assign.left {
+            AssignLeft::Pat(pat) => pat_target(pat),
+            AssignLeft::Expr(expr) => expr_target(expr),
+        },
+        _ => simple_target(expr),
+    }
+}
+
+/// If a pattern is valid inside of a destructuring assignment
+fn pat_target(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(_) => true,
+        Pat::Obj(parts) => parts.iter().all(|part| match part {
+            ObjPatPart::Assign(prop) => match &prop.value {
+                PropValue::Expr(expr) => expr_target(expr),
+                PropValue::Pat(pat) => pat_target(pat),
+                PropValue::None => true,
+            },
+            ObjPatPart::Rest(pat) => pat_target(pat),
+        }),
+        Pat::Array(parts) => parts.iter().flatten().all(|part| match part {
+            ArrayPatPart::Pat(pat) => pat_target(pat),
+            ArrayPatPart::Expr(expr) => expr_target(expr),
+        }),
+        Pat::RestElement(pat) => pat_target(pat),
+        Pat::Assign(assign) => pat_target(&assign.left),
+    }
+}
+
+/// The reasons the left side of an `AssignExpr` is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignError {
+    /// The target isn't an identifier, member
+    /// expression or pattern
+    /// ```js
+    /// f() = x;
+    /// ```
+    InvalidTarget,
+    /// A pattern used with an operator other than `=`
+    /// ```js
+    /// [a] += x;
+    /// ```
+    CompoundDestructure,
+}
+
+impl std::fmt::Display for AssignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AssignError::InvalidTarget => write!(f, "invalid assignment target"),
+            AssignError::CompoundDestructure => {
+                write!(f, "only `=` can assign to a destructuring pattern")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssignError {}
+
 /// The value being assigned to
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum AssignLeft<'a> {
@@ -199,7 +612,7 @@ pub enum AssignLeft<'a> {
 /// true && true
 /// false || true
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct LogicalExpr<'a> {
     pub operator: LogicalOp,
@@ -207,24 +620,113 @@ pub struct LogicalExpr<'a> {
     pub right: Box<Expr<'a>>,
 }
 
+impl<'a> LogicalExpr<'a> {
+    pub fn new(left: Expr<'a>, operator: LogicalOp, right: Expr<'a>) -> Self {
+        Self {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+}
+
 /// Accessing the member of a value
 /// ```js
 /// b['thing'];
 /// c.stuff;
+/// d?.things;
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+///
+/// An optional member (`?.`) is always nested inside of
+/// an `Expr::OptionalChain` that marks where the short
+/// circuit ends
+///
+/// More fields may be added, outside of this crate use
+/// `MemberExpr::new` to build one
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct MemberExpr<'a> {
     pub object: Box<Expr<'a>>,
-    pub property: Box<Expr<'a>>,
-    pub computed: bool,
+    pub property: MemberProp<'a>,
+    /// If this access uses `?.`
+    pub optional: bool,
+}
+
+impl<'a> MemberExpr<'a> {
+    /// `object.property` or `object[property]` depending on the
+    /// kind of `property`. The access is not optional
+    pub fn new(object: Expr<'a>, property: MemberProp<'a>) -> Self {
+        Self {
+            object: Box::new(object),
+            property,
+            optional: false,
+        }
+    }
+    /// If this uses `[]`
+    pub fn is_computed(&self) -> bool {
+        self.property.is_computed()
+    }
+    /// If this is `super.property` or `super[property]`
+    pub fn is_super(&self) -> bool {
+        matches!(*self.object, Expr::Super)
+    }
+}
+
+/// The property of a `MemberExpr`
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub enum MemberProp<'a> {
+    /// `a.b`
+    Ident(Ident<'a>),
+    /// `a.#b`
+    Private(PrivateId<'a>),
+    /// `a['b']` or `a[b]`, the property is an expression even
+    /// when it is a string literal
+    Computed(Box<Expr<'a>>),
+}
+
+impl<'a> MemberProp<'a> {
+    /// `.name`
+    pub fn ident_from(name: &'a str) -> Self {
+        MemberProp::Ident(Ident::from(name))
+    }
+    /// `[expr]`
+    pub fn computed(expr: Expr<'a>) -> Self {
+        MemberProp::Computed(Box::new(expr))
+    }
+    pub fn is_computed(&self) -> bool {
+        matches!(self, MemberProp::Computed(_))
+    }
+    pub fn as_ident(&self) -> Option<&Ident<'a>> {
+        match self {
+            MemberProp::Ident(ident) => Some(ident),
+            _ => None,
+        }
+    }
+    pub fn as_private(&self) -> Option<&PrivateId<'a>> {
+        match self {
+            MemberProp::Private(id) => Some(id),
+            _ => None,
+        }
+    }
+    pub fn as_computed(&self) -> Option<&Expr<'a>> {
+        match self {
+            MemberProp::Computed(expr) => Some(expr),
+            _ => None,
+        }
+    }
 }
 
 /// A ternery expression
 /// ```js
 /// var a = true ? 'stuff' : 'things';
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct ConditionalExpr<'a> {
     pub test: Box<Expr<'a>>,
@@ -232,30 +734,269 @@ pub struct ConditionalExpr<'a> {
     pub consequent: Box<Expr<'a>>,
 }
 
+impl<'a> ConditionalExpr<'a> {
+    /// `test ? consequent : alternate`
+    pub fn new(test: Expr<'a>, consequent: Expr<'a>, alternate: Expr<'a>) -> Self {
+        Self {
+            test: Box::new(test),
+            alternate: Box::new(alternate),
+            consequent: Box::new(consequent),
+        }
+    }
+    /// If the alternate is another conditional, `a ? b : c ? d : e`.
+    /// The operator is right associative so only the alternate
+    /// continues a chain, a conditional as the test or consequent
+    /// had to be wrapped in parentheses
+    pub fn is_chained(&self) -> bool {
+        matches!(*self.alternate, Expr::Conditional(_))
+    }
+    /// Each `test ? consequent` pair in a chain of conditionals
+    /// followed by the final alternate, `a ? b : c ? d : e` is
+    /// `([(a, b), (c, d)], e)`
+    /// ```
+    /// # use resast::prelude::*;
+    /// let chain = ConditionalExpr::new(
+    ///     Expr::ident_from("a"),
+    ///     Expr::ident_from("b"),
+    ///     Expr::ident_from("c"),
+    /// );
+    /// let (pairs, last) = chain.flatten_chain();
+    /// assert_eq!(pairs.len(), 1);
+    /// assert_eq!(last, &Expr::ident_from("c"));
+    /// ```
+    pub fn flatten_chain(&self) -> (Vec<(&Expr<'a>, &Expr<'a>)>, &Expr<'a>) {
+        let mut pairs = vec![(&*self.test, &*self.consequent)];
+        let mut last = &*self.alternate;
+        while let Expr::Conditional(next) = last {
+            pairs.push((&next.test, &next.consequent));
+            last = &next.alternate;
+        }
+        (pairs, last)
+    }
+}
+
 /// Calling a function or method
 /// ```js
 /// Math.random()
+/// maybe?.()
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+/// More fields may be added, outside of this crate use
+/// `CallExpr::new` to build one
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct CallExpr<'a> {
     pub callee: Box<Expr<'a>>,
     pub arguments: Vec<Expr<'a>>,
+    /// If this call uses `?.`
+    pub optional: bool,
+}
+
+impl<'a> CallExpr<'a> {
+    /// A call that is not optional
+    pub fn new(callee: Expr<'a>, arguments: Vec<Expr<'a>>) -> Self {
+        Self {
+            callee: Box::new(callee),
+            arguments,
+            optional: false,
+        }
+    }
+    /// If this is a `super(...)` call
+    pub fn is_super(&self) -> bool {
+        matches!(*self.callee, Expr::Super)
+    }
+}
+
+/// A dynamic import
+/// ```js
+/// import('./stuff.js');
+/// import(place, { with: { type: 'json' } });
+/// ```
+/// More fields may be added, outside of this crate use
+/// `ImportExpr::new` to build one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+#[non_exhaustive]
+pub struct ImportExpr<'a> {
+    /// The module specifier
+    pub source: Box<Expr<'a>>,
+    /// The optional second argument, holding
+    /// any import attributes
+    pub options: Option<Box<Expr<'a>>>,
+}
+
+impl<'a> ImportExpr<'a> {
+    /// An import without any options
+    pub fn new(source: Expr<'a>) -> Self {
+        Self {
+            source: Box::new(source),
+            options: None,
+        }
+    }
 }
 
 /// Calling a constructor
 /// ```js
 /// new Uint8Array(32);
+/// new Date;
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+/// `arguments` is `None` when the argument list was left off
+/// entirely, `new Foo`, and `Some` of an empty list for `new Foo()`.
+///
+/// A member access binds to the callee before the `new` does, so
+/// `new Foo.Bar()` constructs `Foo.Bar` and the call arguments belong
+/// to the `new`. To construct `Foo` and then read `Bar` the `new` has
+/// to be wrapped, `(new Foo).Bar`, and the same goes for any call in
+/// the callee, `new (foo())()`
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct NewExpr<'a> {
     pub callee: Box<Expr<'a>>,
-    pub arguments: Vec<Expr<'a>>,
+    pub arguments: Option<Vec<Expr<'a>>>,
+}
+
+impl<'a> NewExpr<'a> {
+    pub fn new(callee: Expr<'a>, arguments: Vec<Expr<'a>>) -> Self {
+        Self {
+            callee: Box::new(callee),
+            arguments: Some(arguments),
+        }
+    }
+    /// A constructor call without an argument list, `new Foo`
+    pub fn without_parens(callee: Expr<'a>) -> Self {
+        Self {
+            callee: Box::new(callee),
+            arguments: None,
+        }
+    }
+    /// If the argument list was written out, `new Foo()` but not
+    /// `new Foo`
+    pub fn has_parens(&self) -> bool {
+        self.arguments.is_some()
+    }
+    /// The arguments passed to the constructor, empty for both
+    /// `new Foo` and `new Foo()`
+    pub fn args(&self) -> &[Expr<'a>] {
+        self.arguments.as_deref().unwrap_or_default()
+    }
 }
 
 /// A collection of `Exprs` separated by commas
-pub type SequenceExpr<'a> = Vec<Expr<'a>>;
+/// ```js
+/// a, b, c
+/// ```
+/// There is always at least one expression, `head`, so an empty
+/// sequence can't be built. The serialized form is still a list
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize),
+    serde(try_from = "Vec<Expr<'a>>", into = "Vec<Expr<'a>>")
+)]
+#[cfg_attr(
+    all(feature = "serde", feature = "esprima"),
+    derive(Deserialize),
+    serde(try_from = "Vec<Expr<'a>>")
+)]
+pub struct SequenceExpr<'a> {
+    pub head: Box<Expr<'a>>,
+    pub tail: Vec<Expr<'a>>,
+}
+
+impl<'a> SequenceExpr<'a> {
+    pub fn new(head: Expr<'a>, tail: Vec<Expr<'a>>) -> Self {
+        Self {
+            head: Box::new(head),
+            tail,
+        }
+    }
+    /// `None` if `exprs` is empty
+    pub fn from_vec(mut exprs: Vec<Expr<'a>>) -> Option<Self> {
+        if exprs.is_empty() {
+            return None;
+        }
+        let tail = exprs.split_off(1);
+        let head = exprs.pop()?;
+        Some(Self::new(head, tail))
+    }
+    /// The number of expressions, never 0
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+    pub fn first(&self) -> &Expr<'a> {
+        &self.head
+    }
+    /// The expression whose value is the value of the sequence
+    pub fn last(&self) -> &Expr<'a> {
+        self.tail.last().unwrap_or(&self.head)
+    }
+    pub fn push(&mut self, expr: Expr<'a>) {
+        self.tail.push(expr);
+    }
+    pub fn iter(&self) -> SequenceIter<'_, 'a> {
+        std::iter::once(&*self.head).chain(self.tail.iter())
+    }
+    pub fn iter_mut(&mut self) -> SequenceIterMut<'_, 'a> {
+        std::iter::once(&mut *self.head).chain(self.tail.iter_mut())
+    }
+    pub fn into_vec(self) -> Vec<Expr<'a>> {
+        self.into()
+    }
+}
+
+pub type SequenceIter<'s, 'a> =
+    std::iter::Chain<std::iter::Once<&'s Expr<'a>>, std::slice::Iter<'s, Expr<'a>>>;
+pub type SequenceIterMut<'s, 'a> =
+    std::iter::Chain<std::iter::Once<&'s mut Expr<'a>>, std::slice::IterMut<'s, Expr<'a>>>;
+
+impl<'s, 'a> IntoIterator for &'s SequenceExpr<'a> {
+    type Item = &'s Expr<'a>;
+    type IntoIter = SequenceIter<'s, 'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s mut SequenceExpr<'a> {
+    type Item = &'s mut Expr<'a>;
+    type IntoIter = SequenceIterMut<'s, 'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a> From<SequenceExpr<'a>> for Vec<Expr<'a>> {
+    fn from(seq: SequenceExpr<'a>) -> Self {
+        let mut exprs = Vec::with_capacity(seq.len());
+        exprs.push(*seq.head);
+        exprs.extend(seq.tail);
+        exprs
+    }
+}
+
+impl<'a> TryFrom<Vec<Expr<'a>>> for SequenceExpr<'a> {
+    type Error = EmptySequence;
+    fn try_from(exprs: Vec<Expr<'a>>) -> Result<Self, Self::Error> {
+        Self::from_vec(exprs).ok_or(EmptySequence)
+    }
+}
+
+/// The error when building a `SequenceExpr` from an empty list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptySequence;
+
+impl std::fmt::Display for EmptySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a sequence expression needs at least one expression")
+    }
+}
+
+impl std::error::Error for EmptySequence {}
 
 /// An arrow function
 /// ```js
@@ -264,22 +1005,94 @@ pub type SequenceExpr<'a> = Vec<Expr<'a>>;
 ///     return x + 1;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+/// Arrow functions can be `async` but never generators, so unlike
+/// `Func` there is no `generator` flag to set
+/// ```compile_fail
+/// # use resast::prelude::*;
+/// // async* () => {}
+/// let arrow = ArrowFuncExpr {
+///     id: None,
+///     params: vec![],
+///     body: ArrowFuncBody::FuncBody(FuncBody(vec![])),
+///     expression: false,
+///     generator: true,
+///     is_async: true,
+/// };
+/// ```
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct ArrowFuncExpr<'a> {
     pub id: Option<Ident<'a>>,
     pub params: Vec<FuncArg<'a>>,
     pub body: ArrowFuncBody<'a>,
     pub expression: bool,
-    pub generator: bool,
     pub is_async: bool,
 }
 
+impl<'a> ArrowFuncExpr<'a> {
+    /// An anonymous arrow function, `expression` is set
+    /// to match the kind of `body`
+    pub fn new(params: Vec<FuncArg<'a>>, body: ArrowFuncBody<'a>, is_async: bool) -> Self {
+        Self {
+            id: None,
+            params,
+            expression: matches!(body, ArrowFuncBody::Expr(_)),
+            body,
+            is_async,
+        }
+    }
+    /// Check the parameter list, see `ParamError`
+    pub fn validate_params(&self) -> Result<(), crate::ParamError> {
+        crate::validate_params(&self.params)
+    }
+    /// If this is an `async` arrow function
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+    /// Always `false`, arrow functions can't be generators
+    pub fn is_generator(&self) -> bool {
+        false
+    }
+    /// Always `true`, see `Func::is_arrow`
+    pub fn is_arrow(&self) -> bool {
+        true
+    }
+    /// If the body is a single expression (`x => x + 1`)
+    /// rather than a block (`x => { return x + 1 }`)
+    pub fn is_expression_body(&self) -> bool {
+        matches!(self.body, ArrowFuncBody::Expr(_))
+    }
+    /// The expression of a concise body
+    pub fn body_expr(&self) -> Option<&Expr<'a>> {
+        match &self.body {
+            ArrowFuncBody::Expr(e) => Some(e),
+            ArrowFuncBody::FuncBody(_) => None,
+        }
+    }
+    /// The statements of a block body
+    pub fn body_block(&self) -> Option<&FuncBody<'a>> {
+        match &self.body {
+            ArrowFuncBody::FuncBody(body) => Some(body),
+            ArrowFuncBody::Expr(_) => None,
+        }
+    }
+    /// If the block body starts with a `'use strict'` directive,
+    /// a concise body can never be strict on its own
+    pub fn is_strict(&self) -> bool {
+        self.body_block()
+            .map(|body| body.directives().any(crate::Dir::is_use_strict))
+            .unwrap_or(false)
+    }
+}
+
 /// The body portion of an arrow function can be either an expression or a block of statements
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub enum ArrowFuncBody<'a> {
+    /// A block body `x => { return x + 1 }`
     FuncBody(FuncBody<'a>),
+    /// A concise body `x => x + 1`, when written out a body
+    /// that starts with an object literal is wrapped in parens
     Expr(Box<Expr<'a>>),
 }
 
@@ -291,27 +1104,62 @@ pub enum ArrowFuncBody<'a> {
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct YieldExpr<'a> {
     pub argument: Option<Box<Expr<'a>>>,
+    /// If this is `yield* g()`, which yields every value
+    /// produced by `g()` rather than `g()` itself
     pub delegate: bool,
 }
 
+impl<'a> YieldExpr<'a> {
+    /// A `yield` that isn't delegating
+    pub fn new(argument: Option<Expr<'a>>) -> Self {
+        Self {
+            argument: argument.map(Box::new),
+            delegate: false,
+        }
+    }
+    /// `yield* argument`
+    pub fn delegate(argument: Expr<'a>) -> Self {
+        Self {
+            argument: Some(Box::new(argument)),
+            delegate: true,
+        }
+    }
+}
+
 /// A Template literal preceded by a function identifier
 /// see [MDN](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Template_literals#Tagged_templates) for more details
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct TaggedTemplateExpr<'a> {
     pub tag: Box<Expr<'a>>,
     pub quasi: TemplateLit<'a>,
 }
 
+impl<'a> TaggedTemplateExpr<'a> {
+    pub fn new(tag: Expr<'a>, quasi: TemplateLit<'a>) -> Self {
+        Self {
+            tag: Box::new(tag),
+            quasi,
+        }
+    }
+    /// The expression before the template, `tag` in `` tag`a` ``
+    pub fn tag(&self) -> &Expr<'a> {
+        &self.tag
+    }
+    pub fn quasi(&self) -> &TemplateLit<'a> {
+        &self.quasi
+    }
+}
+
 /// A template string literal
 /// ```js
 /// `I own ${0} birds`;
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -322,8 +1170,46 @@ pub struct TemplateLit<'a> {
     pub expressions: Vec<Expr<'a>>,
 }
 
+impl<'a> TemplateLit<'a> {
+    /// Rebuild the source text of this template from the raw text of
+    /// each quasi. A substitution that is a template literal is rebuilt
+    /// as well, any other expression is written as `...`
+    /// ```
+    /// # use resast::prelude::*;
+    /// // `a${b}c`
+    /// let template = TemplateLit {
+    ///     quasis: vec![
+    ///         TemplateElement::from(false, "a", "`a${"),
+    ///         TemplateElement::from(true, "c", "}c`"),
+    ///     ],
+    ///     expressions: vec![Expr::ident_from("b")],
+    /// };
+    /// assert_eq!(template.reconstruct_source(), "`a${...}c`");
+    /// ```
+    pub fn reconstruct_source(&self) -> String {
+        let mut source = String::new();
+        self.write_source(&mut source);
+        source
+    }
+
+    fn write_source(&self, source: &mut String) {
+        let mut expressions = self.expressions.iter();
+        for quasi in &self.quasis {
+            source.push_str(&quasi.raw);
+            if quasi.tail {
+                break;
+            }
+            match expressions.next() {
+                Some(Expr::Lit(Lit::Template(nested))) => nested.write_source(source),
+                Some(_) => source.push_str("..."),
+                None => {}
+            }
+        }
+    }
+}
+
 /// The text part of a `TemplateLiteral`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -331,9 +1217,12 @@ pub struct TemplateLit<'a> {
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct TemplateElement<'a> {
     pub tail: bool,
-    /// The non-quoted version
-    pub cooked: Cow<'a, str>,
-    /// The quoted version
+    /// The value with escapes applied, this will be `None`
+    /// when a tagged template contains an escape that isn't
+    /// valid (e.g. `` tag`\unicode` ``)
+    pub cooked: Option<Cow<'a, str>>,
+    /// The source text, including the surrounding
+    /// `` ` ``, `}` or `${`
     pub raw: Cow<'a, str>,
 }
 
@@ -341,13 +1230,22 @@ impl<'a> TemplateElement<'a> {
     pub fn from(tail: bool, cooked: &'a str, raw: &'a str) -> TemplateElement<'a> {
         Self {
             tail,
-            cooked: Cow::Borrowed(cooked),
+            cooked: Some(Cow::Borrowed(cooked)),
+            raw: Cow::Borrowed(raw),
+        }
+    }
+    /// An element of a tagged template that contains an
+    /// invalid escape, only the raw text is available
+    pub fn invalid(tail: bool, raw: &'a str) -> TemplateElement<'a> {
+        Self {
+            tail,
+            cooked: None,
             raw: Cow::Borrowed(raw),
         }
     }
 }
 
-/// pretty much just `new.target`
+/// Either `new.target` or `import.meta`
 /// ```js
 /// function Thing(one, two) {
 ///     if (!new.target) {
@@ -356,16 +1254,42 @@ impl<'a> TemplateElement<'a> {
 ///     this.one = one;
 ///     this.two = two;
 /// }
+/// console.log(import.meta.url);
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct MetaProp<'a> {
     pub meta: Ident<'a>,
     pub property: Ident<'a>,
 }
 
+impl<'a> MetaProp<'a> {
+    /// `new.target`
+    pub fn new_target() -> Self {
+        MetaProp {
+            meta: Ident::from("new"),
+            property: Ident::from("target"),
+        }
+    }
+    /// `import.meta`
+    pub fn import_meta() -> Self {
+        MetaProp {
+            meta: Ident::from("import"),
+            property: Ident::from("meta"),
+        }
+    }
+    /// If this is `new.target`
+    pub fn is_new_target(&self) -> bool {
+        self.meta.name == "new" && self.property.name == "target"
+    }
+    /// If this is `import.meta`
+    pub fn is_import_meta(&self) -> bool {
+        self.meta.name == "import" && self.property.name == "meta"
+    }
+}
+
 /// A literal value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -385,7 +1309,15 @@ pub enum Lit<'a> {
     /// `0xf`
     /// `0o7`
     /// `0b1`
-    Number(Cow<'a, str>),
+    /// `1_000`
+    Number(NumberLit<'a>),
+    /// A BigInt literal, this is the source text without
+    /// the trailing `n`, any radix prefix is kept as is
+    /// `0n`
+    /// `0x1fn`
+    /// `0o7n`
+    /// `0b1n`
+    BigInt(Cow<'a, str>),
     /// `true`
     /// `false`
     Boolean(bool),
@@ -397,9 +1329,102 @@ pub enum Lit<'a> {
     Template(TemplateLit<'a>),
 }
 
+/// A double quoted string literal, the text is used as is
+/// so it should not include the quotes
+impl<'a> From<&'a str> for Lit<'a> {
+    fn from(s: &'a str) -> Self {
+        Lit::double_string_from(s)
+    }
+}
+
+/// A number literal from its shortest round tripping text.
+///
+/// A javascript numeric literal is never negative and `NaN` or
+/// `Infinity` are global identifiers, not literals, so those values
+/// are rejected, `Expr::from` will build them instead
+impl<'a> TryFrom<f64> for Lit<'a> {
+    type Error = NotANumberLit;
+    fn try_from(n: f64) -> Result<Self, Self::Error> {
+        if !n.is_finite() || n.is_sign_negative() {
+            return Err(NotANumberLit(n));
+        }
+        Ok(Lit::Number(NumberLit(Cow::Owned(n.to_string()))))
+    }
+}
+
+/// The error when a `f64` can't be written as a number literal,
+/// it is negative (including `-0`), `NaN` or infinite
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotANumberLit(pub f64);
+
+impl std::fmt::Display for NotANumberLit {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} can't be written as a number literal", self.0)
+    }
+}
+
+impl std::error::Error for NotANumberLit {}
+
+/// The expression that evaluates to `n`, a number literal if possible
+/// otherwise the `NaN` or `Infinity` identifiers with a leading `-`
+/// unary expression for negative values
+/// ```js
+/// 1.5
+/// -1.5
+/// NaN
+/// -Infinity
+/// ```
+impl<'a> From<f64> for Expr<'a> {
+    fn from(n: f64) -> Self {
+        if n.is_nan() {
+            return Expr::ident_from("NaN");
+        }
+        let abs = if n.is_infinite() {
+            Expr::ident_from("Infinity")
+        } else {
+            Expr::Lit(Lit::Number(NumberLit(Cow::Owned(n.abs().to_string()))))
+        };
+        if n.is_sign_negative() {
+            Expr::Unary(UnaryExpr {
+                operator: UnaryOp::Minus,
+                prefix: true,
+                argument: Box::new(abs),
+            })
+        } else {
+            abs
+        }
+    }
+}
+
+impl<'a> From<bool> for Lit<'a> {
+    fn from(b: bool) -> Self {
+        Lit::Boolean(b)
+    }
+}
+
 impl<'a> Lit<'a> {
     pub fn number_from(s: &'a str) -> Self {
-        Lit::Number(Cow::Borrowed(s))
+        Lit::Number(NumberLit::from(s))
+    }
+    /// Create a BigInt literal from the source text
+    /// without the trailing `n`
+    pub fn bigint_from(s: &'a str) -> Self {
+        Lit::BigInt(Cow::Borrowed(s))
+    }
+    /// The radix of a BigInt literal, determined
+    /// by the prefix of its source text
+    pub fn bigint_radix(&self) -> Option<u32> {
+        if let Lit::BigInt(ref n) = self {
+            let radix = match n.get(..2) {
+                Some("0x") | Some("0X") => 16,
+                Some("0o") | Some("0O") => 8,
+                Some("0b") | Some("0B") => 2,
+                _ => 10,
+            };
+            Some(radix)
+        } else {
+            None
+        }
     }
     pub fn single_string_from(s: &'a str) -> Self {
         Lit::String(StringLit::single_from(s))
@@ -409,7 +1434,93 @@ impl<'a> Lit<'a> {
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+/// A number literal, this holds the text exactly as
+/// it appeared in the source, including any numeric
+/// separators (`_`) so `1_000` and `1000` are not equal.
+///
+/// Keeping the text instead of an `f64` is also what lets
+/// `Expr` implement `Eq` and `Hash`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct NumberLit<'a>(pub Cow<'a, str>);
+
+impl<'a> NumberLit<'a> {
+    pub fn from(s: &'a str) -> Self {
+        NumberLit(Cow::Borrowed(s))
+    }
+    /// The source text of this literal
+    pub fn raw(&self) -> &str {
+        &self.0
+    }
+    /// The source text of this literal with any
+    /// numeric separators removed
+    pub fn without_separators(&self) -> Cow<'_, str> {
+        if self.0.contains('_') {
+            Cow::Owned(self.0.replace('_', ""))
+        } else {
+            Cow::Borrowed(&self.0)
+        }
+    }
+    /// The base this literal is written in, a legacy octal
+    /// literal like `0777` is 8 while a leading zero decimal
+    /// like `089` is 10
+    pub fn radix(&self) -> u32 {
+        let text = self.without_separators();
+        let mut chars = text.chars();
+        if chars.next() != Some('0') {
+            return 10;
+        }
+        match chars.next() {
+            Some('x') | Some('X') => 16,
+            Some('o') | Some('O') => 8,
+            Some('b') | Some('B') => 2,
+            Some(c) if c.is_digit(8) && text[1..].chars().all(|c| c.is_digit(8)) => 8,
+            _ => 10,
+        }
+    }
+    /// If this is a legacy octal literal like `0777` or a decimal
+    /// with a leading zero like `089`, neither is allowed in
+    /// strict mode code
+    pub fn is_legacy_octal(&self) -> bool {
+        let mut chars = self.0.chars();
+        chars.next() == Some('0') && matches!(chars.next(), Some(c) if c.is_ascii_digit())
+    }
+    /// The numeric value of this literal or `None` if the text
+    /// isn't a valid number. A decimal too large for an `f64`,
+    /// like `1e400`, is infinite just as it would be in JS
+    pub fn as_f64(&self) -> Option<f64> {
+        let text = self.without_separators();
+        let radix = self.radix();
+        if radix == 10 {
+            return text.parse().ok();
+        }
+        // skip the prefix, a legacy octal only has the `0`
+        let digits = if text.len() > 1 && text.as_bytes()[1].is_ascii_digit() {
+            &text[1..]
+        } else {
+            &text[2..]
+        };
+        if digits.is_empty() {
+            return None;
+        }
+        digits.chars().try_fold(0f64, |value, c| {
+            c.to_digit(radix).map(|d| value * radix as f64 + d as f64)
+        })
+    }
+    /// If the value of this literal is a finite whole number,
+    /// this includes literals like `1.0` or `1e3`
+    pub fn is_integer(&self) -> bool {
+        self.as_f64()
+            .map(|v| v.is_finite() && v.fract() == 0.0)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub enum StringLit<'a> {
     Double(Cow<'a, str>),
@@ -435,9 +1546,113 @@ impl<'a> StringLit<'a> {
             StringLit::Double(ref d) => d == o,
         }
     }
+    /// The value of the string with its escapes applied and any
+    /// line continuations removed, this only allocates when the
+    /// source text contains a `\`.
+    ///
+    /// An escape that doesn't decode to a character, like a lone
+    /// surrogate `'\uD800'`, becomes `U+FFFD`
+    /// ```
+    /// # use resast::prelude::*;
+    /// let lit = StringLit::single_from(r"tab\tand \u{1F600}");
+    /// assert_eq!(lit.cooked(), "tab\tand \u{1F600}");
+    /// ```
+    pub fn cooked(&self) -> Cow<'a, str> {
+        let raw = self.clone_inner();
+        if !raw.contains('\\') {
+            return raw;
+        }
+        let mut cooked = String::with_capacity(raw.len());
+        let mut chars = raw.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                cooked.push(c);
+                continue;
+            }
+            let c = match chars.next() {
+                Some(c) => c,
+                None => break,
+            };
+            let code = match c {
+                'n' => '\n' as u32,
+                'r' => '\r' as u32,
+                't' => '\t' as u32,
+                'b' => 0x8,
+                'f' => 0xC,
+                'v' => 0xB,
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    continue;
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => continue,
+                '0'..='7' => {
+                    // legacy octal escapes stop before going over 255
+                    let extra = if c <= '3' { 2 } else { 1 };
+                    let mut value = c as u32 - '0' as u32;
+                    for _ in 0..extra {
+                        match chars.peek().and_then(|c| c.to_digit(8)) {
+                            Some(digit) => value = value * 8 + digit,
+                            None => break,
+                        }
+                        chars.next();
+                    }
+                    value
+                }
+                'x' => hex_escape(&mut chars, 2).unwrap_or(0xFFFD),
+                'u' => {
+                    let code = unicode_escape(&mut chars).unwrap_or(0xFFFD);
+                    if (0xD800..0xDC00).contains(&code) {
+                        let mut ahead = chars.clone();
+                        let low = match (ahead.next(), ahead.next()) {
+                            (Some('\\'), Some('u')) => unicode_escape(&mut ahead),
+                            _ => None,
+                        };
+                        match low {
+                            Some(low) if (0xDC00..0xE000).contains(&low) => {
+                                chars = ahead;
+                                0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00)
+                            }
+                            _ => code,
+                        }
+                    } else {
+                        code
+                    }
+                }
+                c => c as u32,
+            };
+            cooked.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+        }
+        Cow::Owned(cooked)
+    }
+}
+
+/// `len` hex digits from the escape `\xHH` or `\uHHHH`
+fn hex_escape(chars: &mut Peekable<Chars>, len: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..len {
+        value = value * 16 + chars.next()?.to_digit(16)?;
+    }
+    Some(value)
+}
+
+/// The code point of a `\u` escape, either `\uHHHH` or `\u{H+}`
+fn unicode_escape(chars: &mut Peekable<Chars>) -> Option<u32> {
+    if chars.peek() != Some(&'{') {
+        return hex_escape(chars, 4);
+    }
+    chars.next();
+    let mut value: u32 = 0;
+    loop {
+        match chars.next()? {
+            '}' => return Some(value),
+            c => value = value.checked_mul(16)?.checked_add(c.to_digit(16)?)?,
+        }
+    }
 }
 /// A regular expression literal
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 #[cfg_attr(all(feature = "serialization"), serde(rename_all = "camelCase"))]
 pub struct RegEx<'a> {
@@ -452,4 +1667,888 @@ impl<'a> RegEx<'a> {
             flags: Cow::Borrowed(f),
         }
     }
+    /// Parse the flag string, this will fail if a flag is
+    /// unknown, repeated or if both `u` and `v` are set
+    pub fn parse_flags(&self) -> Result<RegExFlags, RegExFlagError> {
+        RegExFlags::parse(&self.flags)
+    }
+}
+
+/// The set of flags that follow a regular expression
+/// literal's closing `/`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RegExFlags(u8);
+
+impl RegExFlags {
+    /// `d`
+    pub const HAS_INDICES: RegExFlags = RegExFlags(1);
+    /// `g`
+    pub const GLOBAL: RegExFlags = RegExFlags(1 << 1);
+    /// `i`
+    pub const IGNORE_CASE: RegExFlags = RegExFlags(1 << 2);
+    /// `m`
+    pub const MULTILINE: RegExFlags = RegExFlags(1 << 3);
+    /// `s`
+    pub const DOT_ALL: RegExFlags = RegExFlags(1 << 4);
+    /// `u`
+    pub const UNICODE: RegExFlags = RegExFlags(1 << 5);
+    /// `v`
+    pub const UNICODE_SETS: RegExFlags = RegExFlags(1 << 6);
+    /// `y`
+    pub const STICKY: RegExFlags = RegExFlags(1 << 7);
+
+    /// No flags set
+    pub fn empty() -> Self {
+        RegExFlags(0)
+    }
+
+    /// The flag for a single character of a flag string
+    pub fn from_char(c: char) -> Option<Self> {
+        let flag = match c {
+            'd' => Self::HAS_INDICES,
+            'g' => Self::GLOBAL,
+            'i' => Self::IGNORE_CASE,
+            'm' => Self::MULTILINE,
+            's' => Self::DOT_ALL,
+            'u' => Self::UNICODE,
+            'v' => Self::UNICODE_SETS,
+            'y' => Self::STICKY,
+            _ => return None,
+        };
+        Some(flag)
+    }
+
+    /// Parse a flag string like `"gimsuy"`
+    pub fn parse(flags: &str) -> Result<Self, RegExFlagError> {
+        let mut ret = Self::empty();
+        for c in flags.chars() {
+            let flag = Self::from_char(c).ok_or(RegExFlagError::Unknown(c))?;
+            if ret.contains(flag) {
+                return Err(RegExFlagError::Duplicate(c));
+            }
+            ret.insert(flag);
+        }
+        if ret.has_unicode() && ret.has_unicode_sets() {
+            return Err(RegExFlagError::UnicodeAndUnicodeSets);
+        }
+        Ok(ret)
+    }
+
+    /// If every flag in `other` is also set here
+    pub fn contains(self, other: RegExFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+    pub fn insert(&mut self, other: RegExFlags) {
+        self.0 |= other.0;
+    }
+    pub fn remove(&mut self, other: RegExFlags) {
+        self.0 &= !other.0;
+    }
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+    pub fn has_indices(self) -> bool {
+        self.contains(Self::HAS_INDICES)
+    }
+    pub fn has_global(self) -> bool {
+        self.contains(Self::GLOBAL)
+    }
+    pub fn has_ignore_case(self) -> bool {
+        self.contains(Self::IGNORE_CASE)
+    }
+    pub fn has_multiline(self) -> bool {
+        self.contains(Self::MULTILINE)
+    }
+    pub fn has_dotall(self) -> bool {
+        self.contains(Self::DOT_ALL)
+    }
+    pub fn has_unicode(self) -> bool {
+        self.contains(Self::UNICODE)
+    }
+    pub fn has_unicode_sets(self) -> bool {
+        self.contains(Self::UNICODE_SETS)
+    }
+    pub fn has_sticky(self) -> bool {
+        self.contains(Self::STICKY)
+    }
+}
+
+impl std::ops::BitOr for RegExFlags {
+    type Output = RegExFlags;
+    fn bitor(self, other: RegExFlags) -> RegExFlags {
+        RegExFlags(self.0 | other.0)
+    }
+}
+
+impl std::fmt::Display for RegExFlags {
+    /// The flags in their canonical order (`dgimsuvy`)
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for c in "dgimsuvy".chars() {
+            if let Some(flag) = Self::from_char(c) {
+                if self.contains(flag) {
+                    write!(f, "{}", c)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The reason a regular expression's flags are invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegExFlagError {
+    /// A character that isn't a flag
+    Unknown(char),
+    /// A flag that appears more than once
+    Duplicate(char),
+    /// `u` and `v` can't be used together
+    UnicodeAndUnicodeSets,
+}
+
+impl std::fmt::Display for RegExFlagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RegExFlagError::Unknown(c) => write!(f, "unknown regular expression flag `{}`", c),
+            RegExFlagError::Duplicate(c) => write!(f, "duplicate regular expression flag `{}`", c),
+            RegExFlagError::UnicodeAndUnicodeSets => {
+                write!(f, "regular expression flags `u` and `v` can't be combined")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegExFlagError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::const_eval::ConstValue;
+
+    #[test]
+    fn member_props() {
+        // a.b
+        let ident = MemberExpr::new(Expr::ident_from("a"), MemberProp::ident_from("b"));
+        assert!(!ident.is_computed());
+        assert_eq!(ident.property.as_ident(), Some(&Ident::from("b")));
+        // a["b"]
+        let string = Expr::Lit(Lit::double_string_from("b"));
+        let lit = MemberExpr::new(Expr::ident_from("a"), MemberProp::computed(string.clone()));
+        assert!(lit.is_computed());
+        assert_eq!(lit.property.as_computed(), Some(&string));
+        assert_eq!(lit.property.as_ident(), None);
+        // a[b]
+        let expr = MemberExpr::new(
+            Expr::ident_from("a"),
+            MemberProp::computed(Expr::ident_from("b")),
+        );
+        assert!(expr.is_computed());
+        assert_ne!(expr, ident);
+        // a.#priv
+        let private = MemberExpr::new(
+            Expr::ident_from("a"),
+            MemberProp::Private(PrivateId::from("priv")),
+        );
+        assert!(!private.is_computed());
+        assert_eq!(private.property.as_private(), Some(&PrivateId::from("priv")));
+    }
+
+    #[test]
+    fn sequence_head_and_tail() {
+        let ident = |name| Expr::ident_from(name);
+        // a, b
+        let two = SequenceExpr::new(ident("a"), vec![ident("b")]);
+        assert_eq!(two.len(), 2);
+        assert_eq!(two.first(), &ident("a"));
+        assert_eq!(two.last(), &ident("b"));
+        // a, b, c
+        let three = SequenceExpr::from_vec(vec![ident("a"), ident("b"), ident("c")]).unwrap();
+        assert_eq!(*three.head, ident("a"));
+        assert_eq!(three.tail, vec![ident("b"), ident("c")]);
+        assert_eq!(three.iter().count(), 3);
+        assert_eq!(three.into_vec(), vec![ident("a"), ident("b"), ident("c")]);
+        assert_eq!(SequenceExpr::from_vec(vec![]), None);
+        assert_eq!(SequenceExpr::try_from(vec![]), Err(EmptySequence));
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "esprima")))]
+    #[test]
+    fn sequence_serializes_as_a_list() {
+        let seq = SequenceExpr::new(Expr::ident_from("a"), vec![Expr::ident_from("b")]);
+        let json = serde_json::to_value(&seq).unwrap();
+        assert!(json.is_array());
+        let back: SequenceExpr = serde_json::from_value(json).unwrap();
+        assert_eq!(back, seq);
+        assert!(serde_json::from_str::<SequenceExpr>("[]").is_err());
+    }
+
+    #[test]
+    fn number_values() {
+        let cases: &[(&str, u32, Option<f64>, bool)] = &[
+            ("0", 10, Some(0.0), true),
+            ("42", 10, Some(42.0), true),
+            ("1_000", 10, Some(1000.0), true),
+            ("1.5", 10, Some(1.5), false),
+            (".5", 10, Some(0.5), false),
+            ("5.", 10, Some(5.0), true),
+            ("1e3", 10, Some(1000.0), true),
+            ("2.5E-3", 10, Some(0.0025), false),
+            ("1e400", 10, Some(f64::INFINITY), false),
+            ("089", 10, Some(89.0), true),
+            ("08.5", 10, Some(8.5), false),
+            ("0777", 8, Some(511.0), true),
+            ("0o17", 8, Some(15.0), true),
+            ("0O17", 8, Some(15.0), true),
+            ("0xff", 16, Some(255.0), true),
+            ("0XFF", 16, Some(255.0), true),
+            ("0b1010", 2, Some(10.0), true),
+            ("0b1_0", 2, Some(2.0), true),
+            ("0x", 16, None, false),
+            ("0b2", 2, None, false),
+        ];
+        for (text, radix, value, integer) in cases {
+            let lit = NumberLit::from(text);
+            assert_eq!(lit.radix(), *radix, "radix of {}", text);
+            assert_eq!(lit.as_f64(), *value, "value of {}", text);
+            assert_eq!(lit.is_integer(), *integer, "is_integer of {}", text);
+        }
+        // larger than a u64
+        let big = NumberLit::from("0xffffffffffffffffffff");
+        assert_eq!(big.as_f64(), Some(2f64.powi(80) - 1.0));
+    }
+
+    #[test]
+    fn constructors() {
+        let a = || Expr::ident_from("a");
+        let b = || Expr::ident_from("b");
+        let unary = UnaryExpr::new(UnaryOp::Not, a());
+        assert!(unary.prefix);
+        assert_eq!(*unary.argument, a());
+        let update = UpdateExpr::new(UpdateOp::Increment, a(), false);
+        assert!(!update.prefix);
+        let bin = BinaryExpr::new(a(), BinaryOp::Plus, b());
+        assert_eq!(*bin.left, a());
+        assert_eq!(*bin.right, b());
+        let assign = AssignExpr::new(AssignLeft::Expr(Box::new(a())), AssignOp::Equal, b());
+        assert_eq!(assign.operator, AssignOp::Equal);
+        let logical = LogicalExpr::new(a(), LogicalOp::NullishCoalescing, b());
+        assert_eq!(*logical.right, b());
+        let member = MemberExpr::new(a(), MemberProp::computed(b()));
+        assert!(member.is_computed() && !member.optional);
+        let cond = ConditionalExpr::new(a(), b(), Expr::This);
+        assert_eq!((*cond.consequent, *cond.alternate), (b(), Expr::This));
+        let call = CallExpr::new(a(), vec![b()]);
+        assert!(!call.optional);
+        assert_eq!(call.arguments, vec![b()]);
+        let import = ImportExpr::new(a());
+        assert_eq!(import.options, None);
+        let new = NewExpr::new(a(), vec![]);
+        assert_eq!(new.arguments, Some(vec![]));
+        let y = YieldExpr::new(None);
+        assert!(y.argument.is_none() && !y.delegate);
+        let y = YieldExpr::delegate(a());
+        assert!(y.argument.is_some() && y.delegate);
+        let tagged = TaggedTemplateExpr::new(
+            a(),
+            TemplateLit {
+                quasis: vec![TemplateElement::from(true, "", "``")],
+                expressions: vec![],
+            },
+        );
+        assert_eq!(*tagged.tag, a());
+        let arrow = ArrowFuncExpr::new(vec![], ArrowFuncBody::Expr(Box::new(a())), true);
+        assert!(arrow.expression && arrow.is_async && !arrow.is_generator());
+        assert_eq!(arrow.id, None);
+        let arrow = ArrowFuncExpr::new(vec![], ArrowFuncBody::FuncBody(FuncBody(vec![])), false);
+        assert!(!arrow.expression);
+    }
+
+    fn method<'a>(key: PropKey<'a>, kind: PropKind, func: Func<'a>) -> Prop<'a> {
+        Prop {
+            key,
+            value: PropValue::Expr(Expr::Func(func)),
+            kind,
+            method: kind == PropKind::Method,
+            computed: false,
+            short_hand: false,
+            is_static: false,
+            decorators: vec![],
+        }
+    }
+
+    #[test]
+    fn prop_flags() {
+        // get x() {}
+        let getter = method(
+            PropKey::Expr(Expr::ident_from("x")),
+            PropKind::Get,
+            Func::builder().build(),
+        );
+        assert!(getter.is_method() && getter.is_accessor() && getter.is_getter());
+        assert!(!getter.is_setter() && !getter.is_async() && !getter.is_generator());
+        // async m() {}
+        let async_method = method(
+            PropKey::Expr(Expr::ident_from("m")),
+            PropKind::Method,
+            Func::builder().async_(true).build(),
+        );
+        assert!(async_method.is_method() && async_method.is_async());
+        assert!(!async_method.is_accessor() && !async_method.is_generator());
+        // *[k]() {}
+        let computed = Prop {
+            computed: true,
+            ..method(
+                PropKey::Expr(Expr::ident_from("k")),
+                PropKind::Method,
+                Func::builder().generator(true).build(),
+            )
+        };
+        assert!(computed.computed && computed.is_generator());
+        // { a: async function() {} } is a property holding an
+        // async function, not an async method
+        let value = Prop {
+            method: false,
+            ..method(
+                PropKey::Expr(Expr::ident_from("a")),
+                PropKind::Init,
+                Func::builder().async_(true).build(),
+            )
+        };
+        assert!(!value.is_method() && !value.is_async());
+        assert_eq!(value.method_func(), None);
+        // { a }
+        let short_hand = Prop {
+            key: PropKey::Expr(Expr::ident_from("a")),
+            value: PropValue::None,
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand: true,
+            is_static: false,
+            decorators: vec![],
+        };
+        assert!(short_hand.short_hand && !short_hand.is_method() && !short_hand.is_accessor());
+    }
+
+    #[test]
+    fn lit_conversions() {
+        assert_eq!(Lit::from("hello"), Lit::double_string_from("hello"));
+        if let Lit::String(StringLit::Double(inner)) = Lit::from("hello") {
+            assert_eq!(inner, "hello");
+        } else {
+            panic!("expected a double quoted string");
+        }
+        assert_eq!(Lit::from(true), Lit::Boolean(true));
+        assert_eq!(Lit::try_from(1.5), Ok(Lit::number_from("1.5")));
+        assert_eq!(Lit::try_from(3.0), Ok(Lit::number_from("3")));
+        assert_eq!(Lit::try_from(-1.5), Err(NotANumberLit(-1.5)));
+        assert!(Lit::try_from(-0.0).is_err());
+        assert!(Lit::try_from(f64::NAN).is_err());
+        assert!(Lit::try_from(f64::INFINITY).is_err());
+        assert!(Lit::try_from(f64::NEG_INFINITY).is_err());
+        let minus = |arg| {
+            Expr::Unary(UnaryExpr {
+                operator: UnaryOp::Minus,
+                prefix: true,
+                argument: Box::new(arg),
+            })
+        };
+        assert_eq!(Expr::from(1.5), Expr::Lit(Lit::number_from("1.5")));
+        assert_eq!(Expr::from(-1.5), minus(Expr::Lit(Lit::number_from("1.5"))));
+        assert_eq!(Expr::from(-0.0), minus(Expr::Lit(Lit::number_from("0"))));
+        assert_eq!(Expr::from(f64::NAN), Expr::ident_from("NaN"));
+        assert_eq!(Expr::from(f64::INFINITY), Expr::ident_from("Infinity"));
+        assert_eq!(Expr::from(f64::NEG_INFINITY), minus(Expr::ident_from("Infinity")));
+    }
+
+    #[test]
+    fn template_cooked_and_raw() {
+        // `a\n`
+        let escaped = TemplateElement::from(true, "a\n", "`a\\n`");
+        assert_eq!(escaped.cooked.as_deref(), Some("a\n"));
+        assert_eq!(escaped.raw, "`a\\n`");
+        // tag`\unicode`
+        let invalid = TemplateElement::invalid(true, "`\\unicode`");
+        assert_eq!(invalid.cooked, None);
+        assert_eq!(invalid.raw, "`\\unicode`");
+    }
+
+    #[test]
+    fn regex_flags() {
+        let all = RegEx::from("a", "dgimsy").parse_flags().unwrap();
+        assert!(all.has_indices());
+        assert!(all.has_global());
+        assert!(all.has_ignore_case());
+        assert!(all.has_multiline());
+        assert!(all.has_dotall());
+        assert!(all.has_sticky());
+        assert!(!all.has_unicode());
+        assert!(!all.has_unicode_sets());
+        assert_eq!(all.to_string(), "dgimsy");
+        let none = RegEx::from("a", "").parse_flags().unwrap();
+        assert!(none.is_empty());
+        let v = RegExFlags::parse("vd").unwrap();
+        assert_eq!(v, RegExFlags::UNICODE_SETS | RegExFlags::HAS_INDICES);
+        assert_eq!(v.to_string(), "dv");
+        assert!(RegExFlags::parse("u").unwrap().has_unicode());
+    }
+
+    #[test]
+    fn regex_flag_errors() {
+        assert_eq!(RegExFlags::parse("gig"), Err(RegExFlagError::Duplicate('g')));
+        assert_eq!(RegExFlags::parse("gx"), Err(RegExFlagError::Unknown('x')));
+        assert_eq!(
+            RegExFlags::parse("uv"),
+            Err(RegExFlagError::UnicodeAndUnicodeSets)
+        );
+    }
+
+    #[test]
+    fn hash_dedup() {
+        use std::collections::HashSet;
+        // a + 1
+        let sum = |name: &'static str| {
+            Expr::Binary(BinaryExpr {
+                operator: BinaryOp::Plus,
+                left: Box::new(Expr::Ident(Ident::new(name.to_string()))),
+                right: Box::new(Expr::Lit(Lit::number_from("1"))),
+            })
+        };
+        let mut set = HashSet::new();
+        assert!(set.insert(sum("a")));
+        assert!(!set.insert(sum("a")));
+        // a borrowed copy hashes the same as an owned one
+        assert!(!set.insert(Expr::Binary(BinaryExpr {
+            operator: BinaryOp::Plus,
+            left: Box::new(Expr::ident_from("a")),
+            right: Box::new(Expr::Lit(Lit::number_from("1"))),
+        })));
+        assert!(set.insert(sum("b")));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn expr_from_ident() {
+        let expr: Expr = Ident::from("x").into();
+        assert_eq!(expr, Expr::ident_from("x"));
+    }
+
+    fn member<'a>(object: Expr<'a>, property: &'a str, optional: bool) -> Expr<'a> {
+        Expr::Member(MemberExpr {
+            object: Box::new(object),
+            property: MemberProp::ident_from(property),
+            optional,
+        })
+    }
+
+    #[test]
+    fn optional_chain_all_optional() {
+        // a?.b?.c
+        let chain = Expr::OptionalChain(Box::new(member(
+            member(Expr::ident_from("a"), "b", true),
+            "c",
+            true,
+        )));
+        if let Expr::OptionalChain(inner) = &chain {
+            if let Expr::Member(c) = &**inner {
+                assert!(c.optional);
+                if let Expr::Member(b) = &*c.object {
+                    assert!(b.optional);
+                    return;
+                }
+            }
+        }
+        panic!("expected a single chain of optional members, found {:?}", chain);
+    }
+
+    #[test]
+    fn optional_chain_boundary() {
+        // a?.b.c
+        let short_circuit = Expr::OptionalChain(Box::new(member(
+            member(Expr::ident_from("a"), "b", true),
+            "c",
+            false,
+        )));
+        // (a?.b).c
+        let parenthesized = member(
+            Expr::OptionalChain(Box::new(member(Expr::ident_from("a"), "b", true))),
+            "c",
+            false,
+        );
+        assert_ne!(short_circuit, parenthesized);
+        if let Expr::Member(c) = &parenthesized {
+            assert!(!c.optional);
+            assert!(matches!(*c.object, Expr::OptionalChain(_)));
+        } else {
+            panic!("expected member, found {:?}", parenthesized);
+        }
+    }
+
+    #[test]
+    fn optional_call() {
+        // a?.()
+        let call = Expr::OptionalChain(Box::new(Expr::Call(CallExpr {
+            callee: Box::new(Expr::ident_from("a")),
+            arguments: vec![],
+            optional: true,
+        })));
+        if let Expr::OptionalChain(inner) = &call {
+            if let Expr::Call(c) = &**inner {
+                assert!(c.optional);
+                assert_eq!(*c.callee, Expr::ident_from("a"));
+                return;
+            }
+        }
+        panic!("expected optional call, found {:?}", call);
+    }
+
+    #[test]
+    fn bigint_radix() {
+        assert_eq!(Lit::bigint_from("123").bigint_radix(), Some(10));
+        assert_eq!(Lit::bigint_from("0x1f").bigint_radix(), Some(16));
+        assert_eq!(Lit::bigint_from("0O17").bigint_radix(), Some(8));
+        assert_eq!(Lit::bigint_from("0b101").bigint_radix(), Some(2));
+        assert_eq!(Lit::number_from("0x1f").bigint_radix(), None);
+    }
+
+    #[test]
+    fn number_separators() {
+        let cases = [
+            ("1_000_000", "1000000"),
+            ("1_000.000_1", "1000.0001"),
+            ("0xFF_FF", "0xFFFF"),
+            ("1_0e1_0", "10e10"),
+            ("1000", "1000"),
+        ];
+        for (raw, stripped) in cases.iter() {
+            let n = NumberLit::from(raw);
+            assert_eq!(n.raw(), *raw);
+            assert_eq!(n.without_separators(), *stripped);
+        }
+        assert_ne!(Lit::number_from("1_000"), Lit::number_from("1000"));
+    }
+
+    fn import<'a>(source: Expr<'a>, options: Option<Expr<'a>>) -> Expr<'a> {
+        Expr::Import(ImportExpr {
+            source: Box::new(source),
+            options: options.map(Box::new),
+        })
+    }
+
+    #[test]
+    fn dynamic_import() {
+        // import('a')
+        let lit = import(Expr::Lit(Lit::single_string_from("a")), None);
+        // import(dynamicVar)
+        let var = import(Expr::ident_from("dynamicVar"), None);
+        // import('a', { assert: { type: 'json' } })
+        let prop = |key: &'static str, value: Expr<'static>| {
+            ObjProp::Prop(Prop {
+                key: PropKey::Expr(Expr::ident_from(key)),
+                value: PropValue::Expr(value),
+                kind: PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: false,
+                is_static: false,
+                decorators: vec![],
+            })
+        };
+        let options = Expr::Obj(vec![prop(
+            "assert",
+            Expr::Obj(vec![prop(
+                "type",
+                Expr::Lit(Lit::single_string_from("json")),
+            )]),
+        )]);
+        let with_options = import(
+            Expr::Lit(Lit::single_string_from("a")),
+            Some(options.clone()),
+        );
+        let Expr::Import(i) = lit else {
+            panic!("expected an import expression, found {:?}", lit)
+        };
+        assert_eq!(*i.source, Expr::Lit(Lit::single_string_from("a")));
+        assert!(i.options.is_none());
+        let Expr::Import(i) = var else {
+            panic!("expected an import expression, found {:?}", var)
+        };
+        assert_eq!(*i.source, Expr::ident_from("dynamicVar"));
+        assert!(i.options.is_none());
+        let Expr::Import(i) = with_options else {
+            panic!("expected an import expression, found {:?}", with_options)
+        };
+        assert_eq!(*i.source, Expr::Lit(Lit::single_string_from("a")));
+        assert_eq!(i.options, Some(Box::new(options)));
+    }
+
+    #[test]
+    fn import_meta_url() {
+        let url = Expr::Member(MemberExpr {
+            object: Box::new(Expr::MetaProp(MetaProp::import_meta())),
+            property: MemberProp::ident_from("url"),
+            optional: false,
+        });
+        if let Expr::Member(m) = url {
+            if let Expr::MetaProp(meta) = *m.object {
+                assert_eq!(meta.meta, Ident::from("import"));
+                assert_eq!(meta.property, Ident::from("meta"));
+                return;
+            }
+        }
+        panic!("expected import.meta.url");
+    }
+
+    #[test]
+    fn tagged_template_source() {
+        // tag`a${b}c`
+        let tagged = TaggedTemplateExpr::new(
+            Expr::ident_from("tag"),
+            TemplateLit {
+                quasis: vec![
+                    TemplateElement::from(false, "a", "`a${"),
+                    TemplateElement::from(true, "c", "}c`"),
+                ],
+                expressions: vec![Expr::ident_from("b")],
+            },
+        );
+        assert_eq!(tagged.tag(), &Expr::ident_from("tag"));
+        assert_eq!(tagged.quasi().reconstruct_source(), "`a${...}c`");
+        // tag`a${`x${y}`}c`
+        let nested = TemplateLit {
+            quasis: vec![
+                TemplateElement::from(false, "x", "`x${"),
+                TemplateElement::from(true, "", "}`"),
+            ],
+            expressions: vec![Expr::ident_from("y")],
+        };
+        let tagged = TaggedTemplateExpr::new(
+            Expr::ident_from("tag"),
+            TemplateLit {
+                quasis: vec![
+                    TemplateElement::from(false, "a", "`a${"),
+                    TemplateElement::from(true, "c", "}c`"),
+                ],
+                expressions: vec![Expr::Lit(Lit::Template(nested))],
+            },
+        );
+        assert_eq!(tagged.quasi().reconstruct_source(), "`a${`x${...}`}c`");
+    }
+
+    #[test]
+    fn assign_targets() {
+        let assign = |left, operator| AssignExpr::new(left, operator, Expr::ident_from("x"));
+        let expr_left = |expr| AssignLeft::Expr(Box::new(expr));
+        let a_b = || {
+            Expr::Member(MemberExpr::new(
+                Expr::ident_from("a"),
+                MemberProp::ident_from("b"),
+            ))
+        };
+        // a = x; a.b = x; a.b += x;
+        let ident = expr_left(Expr::ident_from("a"));
+        assert_eq!(assign(ident, AssignOp::Equal).validate_target(), Ok(()));
+        assert_eq!(assign(expr_left(a_b()), AssignOp::Equal).validate_target(), Ok(()));
+        assert_eq!(assign(expr_left(a_b()), AssignOp::PlusEqual).validate_target(), Ok(()));
+        // [a, a.b = 1, ...c] = x;
+        let array = Pat::Array(vec![
+            Some(ArrayPatPart::Pat(Pat::ident_from("a"))),
+            Some(ArrayPatPart::Expr(Expr::Assign(AssignExpr::new(
+                expr_left(a_b()),
+                AssignOp::Equal,
+                Expr::Lit(Lit::number_from("1")),
+            )))),
+            Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(Pat::ident_from("c"))))),
+        ]);
+        let left = AssignLeft::Pat(array.clone());
+        assert_eq!(assign(left, AssignOp::Equal).validate_target(), Ok(()));
+        // [a, a.b = 1, ...c] += x;
+        assert_eq!(
+            assign(AssignLeft::Pat(array), AssignOp::PlusEqual).validate_target(),
+            Err(AssignError::CompoundDestructure)
+        );
+        // f() = x;
+        let call = Expr::Call(CallExpr::new(Expr::ident_from("f"), vec![]));
+        let err = assign(expr_left(call.clone()), AssignOp::Equal).validate_target();
+        assert_eq!(err, Err(AssignError::InvalidTarget));
+        assert_eq!(err.unwrap_err().to_string(), "invalid assignment target");
+        // [f()] = x;
+        let array = Expr::Array(vec![Some(call)]);
+        assert_eq!(
+            assign(expr_left(array), AssignOp::Equal).validate_target(),
+            Err(AssignError::InvalidTarget)
+        );
+    }
+
+    #[test]
+    fn conditional_chains() {
+        let cond = |test, consequent, alternate| {
+            Expr::Conditional(ConditionalExpr::new(test, consequent, alternate))
+        };
+        let id = Expr::ident_from;
+        // a ? b : c ? d : e
+        let two = ConditionalExpr::new(id("a"), id("b"), cond(id("c"), id("d"), id("e")));
+        assert!(two.is_chained());
+        let (pairs, last) = two.flatten_chain();
+        assert_eq!(pairs, vec![(&id("a"), &id("b")), (&id("c"), &id("d"))]);
+        assert_eq!(last, &id("e"));
+        // a ? b : c ? d : e ? f : g
+        let three = ConditionalExpr::new(
+            id("a"),
+            id("b"),
+            cond(id("c"), id("d"), cond(id("e"), id("f"), id("g"))),
+        );
+        let (pairs, last) = three.flatten_chain();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[2], (&id("e"), &id("f")));
+        assert_eq!(last, &id("g"));
+        // (a ? b : c) ? d : e
+        let left = ConditionalExpr::new(cond(id("a"), id("b"), id("c")), id("d"), id("e"));
+        assert!(!left.is_chained());
+        let (pairs, last) = left.flatten_chain();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].0, &cond(id("a"), id("b"), id("c")));
+        assert_eq!(last, &id("e"));
+    }
+
+    #[test]
+    fn new_arguments() {
+        let foo = || Expr::ident_from("Foo");
+        // new Foo
+        let bare = NewExpr::without_parens(foo());
+        assert!(!bare.has_parens());
+        assert!(bare.args().is_empty());
+        // new Foo()
+        let empty = NewExpr::new(foo(), vec![]);
+        assert!(empty.has_parens());
+        assert!(empty.args().is_empty());
+        assert_ne!(bare, empty);
+        // new Foo.Bar()
+        let member = MemberExpr::new(foo(), MemberProp::ident_from("Bar"));
+        let new = NewExpr::new(Expr::Member(member), vec![Expr::ident_from("a")]);
+        assert!(matches!(*new.callee, Expr::Member(_)));
+        assert_eq!(new.args(), &[Expr::ident_from("a")]);
+    }
+
+    #[test]
+    fn cooked_strings() {
+        let cooked = |raw| StringLit::single_from(raw).cooked().into_owned();
+        assert!(matches!(StringLit::single_from("plain").cooked(), Cow::Borrowed("plain")));
+        assert_eq!(cooked(r"a\nb\tc\\d\'e"), "a\nb\tc\\d'e");
+        assert_eq!(cooked(r"\x41B\u{43}"), "ABC");
+        assert_eq!(cooked(r"\uD83D\uDE00"), "\u{1F600}");
+        assert_eq!(cooked(r"\uD800"), "\u{FFFD}");
+        assert_eq!(cooked(r"\0\101\400\8"), "\0A\u{20}08");
+        assert_eq!(cooked("line\\\ncontinued\\\r\nhere"), "linecontinuedhere");
+    }
+
+    #[test]
+    fn async_arrow() {
+        // async () => {}
+        let arrow = ArrowFuncExpr::new(vec![], ArrowFuncBody::FuncBody(FuncBody(vec![])), true);
+        assert!(arrow.is_async() && !arrow.is_generator() && !arrow.is_expression_body());
+        let literal = ArrowFuncExpr {
+            id: None,
+            params: vec![],
+            body: ArrowFuncBody::FuncBody(FuncBody(vec![])),
+            expression: false,
+            is_async: true,
+        };
+        assert_eq!(arrow, literal);
+    }
+
+    #[test]
+    fn unary_and_update_operators() {
+        let ops = [
+            UnaryOp::Minus,
+            UnaryOp::Plus,
+            UnaryOp::Not,
+            UnaryOp::Tilde,
+            UnaryOp::TypeOf,
+            UnaryOp::Void,
+            UnaryOp::Delete,
+        ];
+        let text: Vec<_> = ops.iter().map(|op| op.as_str()).collect();
+        assert_eq!(text, ["-", "+", "!", "~", "typeof", "void", "delete"]);
+        for op in &ops {
+            let unary = UnaryExpr::new(*op, Expr::ident_from("a"));
+            assert!(unary.prefix);
+            assert!(!unary.is_void_zero());
+        }
+        let void = UnaryExpr::void_zero();
+        assert!(void.is_void_zero());
+        assert_eq!(Expr::Unary(void).const_eval(), Some(ConstValue::Undefined));
+        let pre = UpdateExpr::new(UpdateOp::Increment, Expr::ident_from("x"), true);
+        let post = UpdateExpr::new(UpdateOp::Increment, Expr::ident_from("x"), false);
+        assert!(pre.prefix && !post.prefix);
+        assert_ne!(pre, post);
+        assert_eq!(UpdateOp::Decrement.as_str(), "--");
+    }
+
+    fn obj_prop<'a>(key: PropKey<'a>, computed: bool, short_hand: bool) -> ObjProp<'a> {
+        ObjProp::Prop(Prop {
+            key,
+            value: PropValue::Expr(Expr::Lit(Lit::Null)),
+            kind: PropKind::Init,
+            method: false,
+            computed,
+            short_hand,
+            is_static: false,
+            decorators: vec![],
+        })
+    }
+
+    #[test]
+    fn duplicate_proto() {
+        let ident = || PropKey::Expr(Expr::ident_from("__proto__"));
+        let string = || PropKey::Lit(Lit::double_string_from("__proto__"));
+        // ({ __proto__: null })
+        let single: ObjExpr = vec![obj_prop(ident(), false, false)];
+        assert_eq!(single.validate_proto(), Ok(()));
+        // ({ __proto__: null, "__proto__": null })
+        let duplicate: ObjExpr = vec![
+            obj_prop(ident(), false, false),
+            obj_prop(string(), false, false),
+        ];
+        assert_eq!(duplicate.validate_proto(), Err(ObjError::DuplicateProto));
+        // ({ __proto__: null, ['__proto__']: null, __proto__, __proto__() {} })
+        let method = Prop {
+            key: ident(),
+            value: PropValue::Expr(Expr::Func(Func::new(
+                None,
+                vec![],
+                FuncBody(vec![]),
+                false,
+                false,
+            ))),
+            kind: PropKind::Method,
+            method: true,
+            computed: false,
+            short_hand: false,
+            is_static: false,
+            decorators: vec![],
+        };
+        let exceptions: ObjExpr = vec![
+            obj_prop(ident(), false, false),
+            obj_prop(PropKey::Lit(Lit::single_string_from("__proto__")), true, false),
+            obj_prop(ident(), false, true),
+            ObjProp::Prop(method),
+        ];
+        assert_eq!(ObjExpr::validate_proto(&exceptions), Ok(()));
+    }
+
+    #[test]
+    fn null_and_undefined() {
+        let null = Expr::Lit(Lit::Null);
+        let undefined = Expr::ident_from("undefined");
+        let void = Expr::Unary(UnaryExpr::void_zero());
+        let string = Expr::Lit(Lit::single_string_from("undefined"));
+        assert!(null.is_null_literal());
+        assert!(!null.is_undefined_identifier());
+        assert!(undefined.is_undefined_identifier());
+        assert!(!undefined.is_null_literal());
+        for expr in &[void, string, Expr::ident_from("null")] {
+            assert!(!expr.is_null_literal());
+            assert!(!expr.is_undefined_identifier());
+        }
+    }
 }
@@ -0,0 +1,699 @@
+//! By value transformation of the AST
+//!
+//! Where `VisitorMut` edits a tree in place, `Fold` takes
+//! ownership of each node and returns its replacement, which makes
+//! it straight forward to restructure the tree, for example folding a
+//! call expression into a sequence expression. Every method
+//! defaults to calling the matching `fold_*_children` function, which
+//! folds each of the node's children and rebuilds it.
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// A by value transformation of every node in the AST
+pub trait Fold<'a> {
+    fn fold_program(&mut self, program: Program<'a>) -> Program<'a> {
+        fold_program_children(self, program)
+    }
+    fn fold_program_part(&mut self, part: ProgramPart<'a>) -> ProgramPart<'a> {
+        fold_program_part_children(self, part)
+    }
+    fn fold_dir(&mut self, dir: Dir<'a>) -> Dir<'a> {
+        dir
+    }
+    fn fold_ident(&mut self, ident: Ident<'a>) -> Ident<'a> {
+        ident
+    }
+    fn fold_var_kind(&mut self, kind: VarKind) -> VarKind {
+        kind
+    }
+    fn fold_func(&mut self, func: Func<'a>) -> Func<'a> {
+        fold_func_children(self, func)
+    }
+    fn fold_func_arg(&mut self, arg: FuncArg<'a>) -> FuncArg<'a> {
+        fold_func_arg_children(self, arg)
+    }
+    fn fold_func_body(&mut self, body: FuncBody<'a>) -> FuncBody<'a> {
+        fold_func_body_children(self, body)
+    }
+    fn fold_class(&mut self, class: Class<'a>) -> Class<'a> {
+        fold_class_children(self, class)
+    }
+    fn fold_class_member(&mut self, member: ClassMember<'a>) -> ClassMember<'a> {
+        fold_class_member_children(self, member)
+    }
+    fn fold_decorator(&mut self, decorator: Decorator<'a>) -> Decorator<'a> {
+        fold_decorator_children(self, decorator)
+    }
+    fn fold_decl(&mut self, decl: Decl<'a>) -> Decl<'a> {
+        fold_decl_children(self, decl)
+    }
+    fn fold_var_decl(&mut self, decl: VarDecl<'a>) -> VarDecl<'a> {
+        fold_var_decl_children(self, decl)
+    }
+    fn fold_mod_import(&mut self, import: ModImport<'a>) -> ModImport<'a> {
+        fold_mod_import_children(self, import)
+    }
+    fn fold_mod_export(&mut self, export: ModExport<'a>) -> ModExport<'a> {
+        fold_mod_export_children(self, export)
+    }
+    fn fold_stmt(&mut self, stmt: Stmt<'a>) -> Stmt<'a> {
+        fold_stmt_children(self, stmt)
+    }
+    fn fold_block_stmt(&mut self, block: BlockStmt<'a>) -> BlockStmt<'a> {
+        fold_block_stmt_children(self, block)
+    }
+    fn fold_switch_case(&mut self, case: SwitchCase<'a>) -> SwitchCase<'a> {
+        fold_switch_case_children(self, case)
+    }
+    fn fold_catch_clause(&mut self, clause: CatchClause<'a>) -> CatchClause<'a> {
+        fold_catch_clause_children(self, clause)
+    }
+    fn fold_loop_init(&mut self, init: LoopInit<'a>) -> LoopInit<'a> {
+        fold_loop_init_children(self, init)
+    }
+    fn fold_loop_left(&mut self, left: LoopLeft<'a>) -> LoopLeft<'a> {
+        fold_loop_left_children(self, left)
+    }
+    fn fold_expr(&mut self, expr: Expr<'a>) -> Expr<'a> {
+        fold_expr_children(self, expr)
+    }
+    fn fold_prop(&mut self, prop: Prop<'a>) -> Prop<'a> {
+        fold_prop_children(self, prop)
+    }
+    fn fold_lit(&mut self, lit: Lit<'a>) -> Lit<'a> {
+        fold_lit_children(self, lit)
+    }
+    fn fold_template_lit(&mut self, lit: TemplateLit<'a>) -> TemplateLit<'a> {
+        fold_template_lit_children(self, lit)
+    }
+    fn fold_pat(&mut self, pat: Pat<'a>) -> Pat<'a> {
+        fold_pat_children(self, pat)
+    }
+}
+
+fn fold_boxed_expr<'a, F: Fold<'a> + ?Sized>(f: &mut F, mut expr: Box<Expr<'a>>) -> Box<Expr<'a>> {
+    *expr = f.fold_expr(*expr);
+    expr
+}
+
+fn fold_boxed_stmt<'a, F: Fold<'a> + ?Sized>(f: &mut F, mut stmt: Box<Stmt<'a>>) -> Box<Stmt<'a>> {
+    *stmt = f.fold_stmt(*stmt);
+    stmt
+}
+
+fn fold_exprs<'a, F: Fold<'a> + ?Sized>(f: &mut F, exprs: Vec<Expr<'a>>) -> Vec<Expr<'a>> {
+    exprs.into_iter().map(|e| f.fold_expr(e)).collect()
+}
+
+fn fold_parts<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    parts: Vec<ProgramPart<'a>>,
+) -> Vec<ProgramPart<'a>> {
+    parts.into_iter().map(|p| f.fold_program_part(p)).collect()
+}
+
+fn fold_var_decls<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    decls: Vec<VarDecl<'a>>,
+) -> Vec<VarDecl<'a>> {
+    decls.into_iter().map(|d| f.fold_var_decl(d)).collect()
+}
+
+pub fn fold_program_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    program: Program<'a>,
+) -> Program<'a> {
+    match program {
+        Program::Mod(parts, hashbang) => Program::Mod(fold_parts(f, parts), hashbang),
+        Program::Script(parts, hashbang) => Program::Script(fold_parts(f, parts), hashbang),
+    }
+}
+
+pub fn fold_program_part_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    part: ProgramPart<'a>,
+) -> ProgramPart<'a> {
+    match part {
+        ProgramPart::Dir(dir) => ProgramPart::Dir(f.fold_dir(dir)),
+        ProgramPart::Decl(decl) => ProgramPart::Decl(f.fold_decl(decl)),
+        ProgramPart::Stmt(stmt) => ProgramPart::Stmt(f.fold_stmt(stmt)),
+    }
+}
+
+pub fn fold_func_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, func: Func<'a>) -> Func<'a> {
+    Func {
+        id: func.id.map(|id| f.fold_ident(id)),
+        params: func
+            .params
+            .into_iter()
+            .map(|p| f.fold_func_arg(p))
+            .collect(),
+        body: f.fold_func_body(func.body),
+        generator: func.generator,
+        is_async: func.is_async,
+    }
+}
+
+pub fn fold_func_arg_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    arg: FuncArg<'a>,
+) -> FuncArg<'a> {
+    match arg {
+        FuncArg::Expr(expr) => FuncArg::Expr(f.fold_expr(expr)),
+        FuncArg::Pat(pat) => FuncArg::Pat(f.fold_pat(pat)),
+    }
+}
+
+pub fn fold_func_body_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    body: FuncBody<'a>,
+) -> FuncBody<'a> {
+    FuncBody(fold_parts(f, body.0))
+}
+
+pub fn fold_class_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, class: Class<'a>) -> Class<'a> {
+    Class {
+        id: class.id.map(|id| f.fold_ident(id)),
+        super_class: class.super_class.map(|s| fold_boxed_expr(f, s)),
+        body: ClassBody(
+            class
+                .body
+                .0
+                .into_iter()
+                .map(|m| f.fold_class_member(m))
+                .collect(),
+        ),
+        decorators: class
+            .decorators
+            .into_iter()
+            .map(|d| f.fold_decorator(d))
+            .collect(),
+    }
+}
+
+pub fn fold_class_member_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    member: ClassMember<'a>,
+) -> ClassMember<'a> {
+    match member {
+        ClassMember::Method(method) => ClassMember::Method(MethodDef {
+            decorators: fold_decorators(f, method.decorators),
+            key: fold_prop_key(f, method.key),
+            value: f.fold_func(method.value),
+            ..method
+        }),
+        ClassMember::Field(field) => ClassMember::Field(FieldDef {
+            decorators: fold_decorators(f, field.decorators),
+            key: fold_prop_key(f, field.key),
+            value: field.value.map(|value| f.fold_expr(value)),
+            ..field
+        }),
+        ClassMember::StaticBlock(block) => ClassMember::StaticBlock(f.fold_block_stmt(block)),
+    }
+}
+
+pub fn fold_decorator_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    decorator: Decorator<'a>,
+) -> Decorator<'a> {
+    Decorator {
+        expression: f.fold_expr(decorator.expression),
+    }
+}
+
+pub fn fold_decl_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, decl: Decl<'a>) -> Decl<'a> {
+    match decl {
+        Decl::Var(kind, decls) => Decl::Var(f.fold_var_kind(kind), fold_var_decls(f, decls)),
+        Decl::Func(func) => Decl::Func(f.fold_func(func)),
+        Decl::Class(class) => Decl::Class(f.fold_class(class)),
+        Decl::Import(import) => Decl::Import(Box::new(f.fold_mod_import(*import))),
+        Decl::Export(export) => Decl::Export(Box::new(f.fold_mod_export(*export))),
+    }
+}
+
+pub fn fold_var_decl_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    decl: VarDecl<'a>,
+) -> VarDecl<'a> {
+    VarDecl {
+        id: f.fold_pat(decl.id),
+        init: decl.init.map(|e| f.fold_expr(e)),
+    }
+}
+
+pub fn fold_mod_import_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    import: ModImport<'a>,
+) -> ModImport<'a> {
+    let specifiers = import
+        .specifiers
+        .into_iter()
+        .map(|spec| match spec {
+            ImportSpecifier::Normal(normal) => ImportSpecifier::Normal(NormalImportSpec {
+                local: f.fold_ident(normal.local),
+                imported: normal.imported,
+            }),
+            ImportSpecifier::Default(ident) => ImportSpecifier::Default(f.fold_ident(ident)),
+            ImportSpecifier::Namespace(ident) => ImportSpecifier::Namespace(f.fold_ident(ident)),
+        })
+        .collect();
+    ModImport {
+        specifiers,
+        ..import
+    }
+}
+
+pub fn fold_mod_export_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    export: ModExport<'a>,
+) -> ModExport<'a> {
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+            ModExport::Default(DefaultExportDecl::Decl(f.fold_decl(decl)))
+        }
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+            ModExport::Default(DefaultExportDecl::Expr(f.fold_expr(expr)))
+        }
+        ModExport::Named(NamedExportDecl::Decl(decl)) => {
+            ModExport::Named(NamedExportDecl::Decl(f.fold_decl(decl)))
+        }
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            let specs = specs
+                .into_iter()
+                .map(|spec| ExportSpecifier {
+                    local: f.fold_ident(spec.local),
+                    exported: spec.exported,
+                })
+                .collect();
+            ModExport::Named(NamedExportDecl::Specifier(specs, source))
+        }
+        ModExport::All(alias, source) => ModExport::All(alias, source),
+    }
+}
+
+pub fn fold_stmt_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, stmt: Stmt<'a>) -> Stmt<'a> {
+    match stmt {
+        Stmt::Expr(expr) => Stmt::Expr(f.fold_expr(expr)),
+        Stmt::Block(block) => Stmt::Block(f.fold_block_stmt(block)),
+        Stmt::Empty => Stmt::Empty,
+        Stmt::Debugger => Stmt::Debugger,
+        Stmt::With(with) => Stmt::With(WithStmt {
+            object: f.fold_expr(with.object),
+            body: fold_boxed_stmt(f, with.body),
+        }),
+        Stmt::Return(arg) => Stmt::Return(arg.map(|e| f.fold_expr(e))),
+        Stmt::Labeled(labeled) => Stmt::Labeled(LabeledStmt {
+            label: f.fold_ident(labeled.label),
+            body: fold_boxed_stmt(f, labeled.body),
+        }),
+        Stmt::Break(label) => Stmt::Break(label.map(|l| f.fold_ident(l))),
+        Stmt::Continue(label) => Stmt::Continue(label.map(|l| f.fold_ident(l))),
+        Stmt::If(stmt) => Stmt::If(IfStmt {
+            test: f.fold_expr(stmt.test),
+            consequent: fold_boxed_stmt(f, stmt.consequent),
+            alternate: stmt.alternate.map(|s| fold_boxed_stmt(f, s)),
+        }),
+        Stmt::Switch(stmt) => Stmt::Switch(SwitchStmt {
+            discriminant: f.fold_expr(stmt.discriminant),
+            cases: stmt
+                .cases
+                .into_iter()
+                .map(|c| f.fold_switch_case(c))
+                .collect(),
+        }),
+        Stmt::Throw(expr) => Stmt::Throw(f.fold_expr(expr)),
+        Stmt::Try(stmt) => Stmt::Try(TryStmt {
+            block: f.fold_block_stmt(stmt.block),
+            handler: stmt.handler.map(|h| f.fold_catch_clause(h)),
+            finalizer: stmt.finalizer.map(|b| f.fold_block_stmt(b)),
+        }),
+        Stmt::While(stmt) => Stmt::While(WhileStmt {
+            test: f.fold_expr(stmt.test),
+            body: fold_boxed_stmt(f, stmt.body),
+        }),
+        Stmt::DoWhile(stmt) => Stmt::DoWhile(DoWhileStmt {
+            body: fold_boxed_stmt(f, stmt.body),
+            test: f.fold_expr(stmt.test),
+        }),
+        Stmt::For(stmt) => Stmt::For(ForStmt {
+            init: stmt.init.map(|i| f.fold_loop_init(i)),
+            test: stmt.test.map(|e| f.fold_expr(e)),
+            update: stmt.update.map(|e| f.fold_expr(e)),
+            body: fold_boxed_stmt(f, stmt.body),
+        }),
+        Stmt::ForIn(stmt) => Stmt::ForIn(ForInStmt {
+            left: f.fold_loop_left(stmt.left),
+            right: f.fold_expr(stmt.right),
+            body: fold_boxed_stmt(f, stmt.body),
+        }),
+        Stmt::ForOf(stmt) => Stmt::ForOf(ForOfStmt {
+            left: f.fold_loop_left(stmt.left),
+            right: f.fold_expr(stmt.right),
+            body: fold_boxed_stmt(f, stmt.body),
+            is_await: stmt.is_await,
+        }),
+        Stmt::Var(decls) => Stmt::Var(fold_var_decls(f, decls)),
+    }
+}
+
+pub fn fold_block_stmt_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    block: BlockStmt<'a>,
+) -> BlockStmt<'a> {
+    BlockStmt(fold_parts(f, block.0))
+}
+
+pub fn fold_switch_case_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    case: SwitchCase<'a>,
+) -> SwitchCase<'a> {
+    SwitchCase {
+        test: case.test.map(|e| f.fold_expr(e)),
+        consequent: fold_parts(f, case.consequent),
+    }
+}
+
+pub fn fold_catch_clause_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    clause: CatchClause<'a>,
+) -> CatchClause<'a> {
+    CatchClause {
+        param: clause.param.map(|p| f.fold_pat(p)),
+        body: f.fold_block_stmt(clause.body),
+    }
+}
+
+pub fn fold_loop_init_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    init: LoopInit<'a>,
+) -> LoopInit<'a> {
+    match init {
+        LoopInit::Variable(kind, decls) => {
+            LoopInit::Variable(f.fold_var_kind(kind), fold_var_decls(f, decls))
+        }
+        LoopInit::Expr(expr) => LoopInit::Expr(f.fold_expr(expr)),
+    }
+}
+
+pub fn fold_loop_left_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    left: LoopLeft<'a>,
+) -> LoopLeft<'a> {
+    match left {
+        LoopLeft::Expr(expr) => LoopLeft::Expr(f.fold_expr(expr)),
+        LoopLeft::Variable(kind, decl) => {
+            LoopLeft::Variable(f.fold_var_kind(kind), f.fold_var_decl(decl))
+        }
+        LoopLeft::Pat(pat) => LoopLeft::Pat(f.fold_pat(pat)),
+    }
+}
+
+pub fn fold_expr_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, expr: Expr<'a>) -> Expr<'a> {
+    match expr {
+        Expr::Array(elements) => Expr::Array(
+            elements
+                .into_iter()
+                .map(|e| e.map(|e| f.fold_expr(e)))
+                .collect(),
+        ),
+        Expr::ArrowFunc(arrow) => Expr::ArrowFunc(ArrowFuncExpr {
+            id: arrow.id.map(|id| f.fold_ident(id)),
+            params: arrow
+                .params
+                .into_iter()
+                .map(|p| f.fold_func_arg(p))
+                .collect(),
+            body: match arrow.body {
+                ArrowFuncBody::FuncBody(body) => ArrowFuncBody::FuncBody(f.fold_func_body(body)),
+                ArrowFuncBody::Expr(expr) => ArrowFuncBody::Expr(fold_boxed_expr(f, expr)),
+            },
+            ..arrow
+        }),
+        Expr::ArrowParamPlaceHolder(args, is_async) => Expr::ArrowParamPlaceHolder(
+            args.into_iter().map(|a| f.fold_func_arg(a)).collect(),
+            is_async,
+        ),
+        Expr::Assign(assign) => Expr::Assign(AssignExpr {
+            operator: assign.operator,
+            left: match assign.left {
+                AssignLeft::Pat(pat) => AssignLeft::Pat(f.fold_pat(pat)),
+                AssignLeft::Expr(expr) => AssignLeft::Expr(fold_boxed_expr(f, expr)),
+            },
+            right: fold_boxed_expr(f, assign.right),
+        }),
+        Expr::Await(expr) => Expr::Await(fold_boxed_expr(f, expr)),
+        Expr::Binary(bin) => Expr::Binary(BinaryExpr {
+            operator: bin.operator,
+            left: fold_boxed_expr(f, bin.left),
+            right: fold_boxed_expr(f, bin.right),
+        }),
+        Expr::Class(class) => Expr::Class(f.fold_class(class)),
+        Expr::Call(call) => Expr::Call(CallExpr {
+            callee: fold_boxed_expr(f, call.callee),
+            arguments: fold_exprs(f, call.arguments),
+            optional: call.optional,
+        }),
+        Expr::Conditional(cond) => Expr::Conditional(ConditionalExpr {
+            test: fold_boxed_expr(f, cond.test),
+            consequent: fold_boxed_expr(f, cond.consequent),
+            alternate: fold_boxed_expr(f, cond.alternate),
+        }),
+        Expr::Func(func) => Expr::Func(f.fold_func(func)),
+        Expr::Ident(ident) => Expr::Ident(f.fold_ident(ident)),
+        Expr::Import(import) => Expr::Import(ImportExpr {
+            source: fold_boxed_expr(f, import.source),
+            options: import.options.map(|o| fold_boxed_expr(f, o)),
+        }),
+        Expr::Lit(lit) => Expr::Lit(f.fold_lit(lit)),
+        Expr::Logical(logical) => Expr::Logical(LogicalExpr {
+            operator: logical.operator,
+            left: fold_boxed_expr(f, logical.left),
+            right: fold_boxed_expr(f, logical.right),
+        }),
+        Expr::Member(member) => Expr::Member(MemberExpr {
+            object: fold_boxed_expr(f, member.object),
+            property: match member.property {
+                MemberProp::Ident(ident) => MemberProp::Ident(f.fold_ident(ident)),
+                MemberProp::Private(id) => MemberProp::Private(id),
+                MemberProp::Computed(expr) => MemberProp::Computed(fold_boxed_expr(f, expr)),
+            },
+            ..member
+        }),
+        Expr::MetaProp(meta) => Expr::MetaProp(meta),
+        Expr::OptionalChain(expr) => Expr::OptionalChain(fold_boxed_expr(f, expr)),
+        #[cfg(feature = "preserve-parens")]
+        Expr::Paren(expr) => Expr::Paren(fold_boxed_expr(f, expr)),
+        Expr::PrivateId(id) => Expr::PrivateId(id),
+        Expr::New(new) => Expr::New(NewExpr {
+            callee: fold_boxed_expr(f, new.callee),
+            arguments: new.arguments.map(|args| fold_exprs(f, args)),
+        }),
+        Expr::Obj(props) => Expr::Obj(
+            props
+                .into_iter()
+                .map(|p| match p {
+                    ObjProp::Prop(prop) => ObjProp::Prop(f.fold_prop(prop)),
+                    ObjProp::Spread(expr) => ObjProp::Spread(f.fold_expr(expr)),
+                })
+                .collect(),
+        ),
+        Expr::Sequence(seq) => Expr::Sequence(SequenceExpr {
+            head: fold_boxed_expr(f, seq.head),
+            tail: fold_exprs(f, seq.tail),
+        }),
+        Expr::Spread(expr) => Expr::Spread(fold_boxed_expr(f, expr)),
+        Expr::Super => Expr::Super,
+        Expr::TaggedTemplate(tagged) => Expr::TaggedTemplate(TaggedTemplateExpr {
+            tag: fold_boxed_expr(f, tagged.tag),
+            quasi: f.fold_template_lit(tagged.quasi),
+        }),
+        Expr::This => Expr::This,
+        Expr::Unary(unary) => Expr::Unary(UnaryExpr {
+            argument: fold_boxed_expr(f, unary.argument),
+            ..unary
+        }),
+        Expr::Update(update) => Expr::Update(UpdateExpr {
+            argument: fold_boxed_expr(f, update.argument),
+            ..update
+        }),
+        Expr::Yield(y) => Expr::Yield(YieldExpr {
+            argument: y.argument.map(|a| fold_boxed_expr(f, a)),
+            delegate: y.delegate,
+        }),
+    }
+}
+
+fn fold_prop_key<'a, F: Fold<'a> + ?Sized>(f: &mut F, key: PropKey<'a>) -> PropKey<'a> {
+    match key {
+        PropKey::Lit(lit) => PropKey::Lit(f.fold_lit(lit)),
+        PropKey::Expr(expr) => PropKey::Expr(f.fold_expr(expr)),
+        PropKey::Pat(pat) => PropKey::Pat(f.fold_pat(pat)),
+        PropKey::Private(id) => PropKey::Private(id),
+    }
+}
+
+fn fold_decorators<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    decorators: Vec<Decorator<'a>>,
+) -> Vec<Decorator<'a>> {
+    decorators.into_iter().map(|d| f.fold_decorator(d)).collect()
+}
+
+pub fn fold_prop_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, prop: Prop<'a>) -> Prop<'a> {
+    let key = fold_prop_key(f, prop.key);
+    let value = match prop.value {
+        PropValue::Expr(expr) => PropValue::Expr(f.fold_expr(expr)),
+        PropValue::Pat(pat) => PropValue::Pat(f.fold_pat(pat)),
+        PropValue::None => PropValue::None,
+    };
+    let decorators = fold_decorators(f, prop.decorators);
+    Prop {
+        key,
+        value,
+        decorators,
+        ..prop
+    }
+}
+
+pub fn fold_lit_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, lit: Lit<'a>) -> Lit<'a> {
+    match lit {
+        Lit::Template(template) => Lit::Template(f.fold_template_lit(template)),
+        other => other,
+    }
+}
+
+pub fn fold_template_lit_children<'a, F: Fold<'a> + ?Sized>(
+    f: &mut F,
+    lit: TemplateLit<'a>,
+) -> TemplateLit<'a> {
+    TemplateLit {
+        quasis: lit.quasis,
+        expressions: fold_exprs(f, lit.expressions),
+    }
+}
+
+pub fn fold_pat_children<'a, F: Fold<'a> + ?Sized>(f: &mut F, pat: Pat<'a>) -> Pat<'a> {
+    match pat {
+        Pat::Ident(ident) => Pat::Ident(f.fold_ident(ident)),
+        Pat::Obj(parts) => Pat::Obj(
+            parts
+                .into_iter()
+                .map(|part| match part {
+                    ObjPatPart::Assign(prop) => ObjPatPart::Assign(f.fold_prop(prop)),
+                    ObjPatPart::Rest(pat) => ObjPatPart::Rest(Box::new(f.fold_pat(*pat))),
+                })
+                .collect(),
+        ),
+        Pat::Array(parts) => Pat::Array(
+            parts
+                .into_iter()
+                .map(|part| {
+                    part.map(|part| match part {
+                        ArrayPatPart::Pat(pat) => ArrayPatPart::Pat(f.fold_pat(pat)),
+                        ArrayPatPart::Expr(expr) => ArrayPatPart::Expr(f.fold_expr(expr)),
+                    })
+                })
+                .collect(),
+        ),
+        Pat::RestElement(pat) => Pat::RestElement(Box::new(f.fold_pat(*pat))),
+        Pat::Assign(assign) => Pat::Assign(AssignPat {
+            left: Box::new(f.fold_pat(*assign.left)),
+            right: fold_boxed_expr(f, assign.right),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct LetToVar;
+
+    impl<'a> Fold<'a> for LetToVar {
+        fn fold_var_kind(&mut self, kind: VarKind) -> VarKind {
+            match kind {
+                VarKind::Let => VarKind::Var,
+                other => other,
+            }
+        }
+    }
+
+    /// Folds every call into `(log(callee), callee(...args))`
+    struct LogCalls;
+
+    impl<'a> Fold<'a> for LogCalls {
+        fn fold_expr(&mut self, expr: Expr<'a>) -> Expr<'a> {
+            match fold_expr_children(self, expr) {
+                Expr::Call(call) => Expr::Sequence(SequenceExpr::new(
+                    Expr::Call(CallExpr {
+                        callee: Box::new(Expr::ident_from("log")),
+                        arguments: vec![(*call.callee).clone()],
+                        optional: false,
+                    }),
+                    vec![Expr::Call(call)],
+                )),
+                other => other,
+            }
+        }
+    }
+
+    fn decl<'a>(name: &'a str) -> VarDecl<'a> {
+        VarDecl {
+            id: Pat::ident_from(name),
+            init: None,
+        }
+    }
+
+    // let a; for (let b of c) { const d = 1; let e; }
+    fn decls(outer: VarKind, inner: VarKind) -> Program<'static> {
+        Program::script(vec![
+            ProgramPart::Decl(Decl::Var(outer, vec![decl("a")])),
+            ProgramPart::Stmt(Stmt::ForOf(ForOfStmt {
+                left: LoopLeft::Variable(outer, decl("b")),
+                right: Expr::ident_from("c"),
+                body: Box::new(Stmt::Block(BlockStmt(vec![
+                    ProgramPart::Decl(Decl::Var(
+                        VarKind::Const,
+                        vec![VarDecl {
+                            id: Pat::ident_from("d"),
+                            init: Some(Expr::Lit(Lit::number_from("1"))),
+                        }],
+                    )),
+                    ProgramPart::Decl(Decl::Var(inner, vec![decl("e")])),
+                ]))),
+                is_await: false,
+            })),
+        ])
+    }
+
+    #[test]
+    fn let_to_var() {
+        let program = decls(VarKind::Let, VarKind::Let);
+        let folded = LetToVar.fold_program(program);
+        assert_eq!(folded, decls(VarKind::Var, VarKind::Var));
+    }
+
+    #[test]
+    fn call_to_sequence() {
+        let call = Expr::Call(CallExpr {
+            callee: Box::new(Expr::ident_from("f")),
+            arguments: vec![],
+            optional: false,
+        });
+        let folded = LogCalls.fold_expr(call.clone());
+        assert_eq!(
+            folded,
+            Expr::Sequence(SequenceExpr::new(
+                Expr::Call(CallExpr {
+                    callee: Box::new(Expr::ident_from("log")),
+                    arguments: vec![Expr::ident_from("f")],
+                    optional: false,
+                }),
+                vec![call],
+            ))
+        );
+    }
+}
@@ -4,19 +4,41 @@ extern crate serde_derive;
 
 use std::borrow::Cow;
 
+pub mod analysis;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod const_eval;
 pub mod decl;
+#[cfg(feature = "serialization")]
+pub mod estree;
 pub mod expr;
+pub mod fold;
+#[cfg(all(feature = "msgpack", not(feature = "esprima")))]
+pub mod msgpack;
+pub mod node;
+pub mod owned;
+#[cfg(any(feature = "codegen", feature = "swc-interop"))]
+mod parens;
 pub mod pat;
+pub mod reborrow;
+pub mod rename;
+pub mod sexpr;
 pub mod stmt;
+#[cfg(feature = "swc-interop")]
+pub mod swc_interop;
+pub mod visit;
+pub mod visit_mut;
 #[cfg(feature = "esprima")]
 pub mod serde;
+#[cfg(feature = "spanned")]
+pub mod spanned;
 
 use decl::Decl;
-use expr::{Expr, Lit, Prop};
+use expr::{Expr, Lit, PropKey};
 use pat::Pat;
-use stmt::Stmt;
+use stmt::{BlockStmt, Stmt};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -32,7 +54,102 @@ impl<'a> Ident<'a> {
             name: Cow::Owned(s)
         }
     }
-    pub fn from(s: &'a str) -> Self {
+
+    /// If the name is an `IdentifierName` that isn't a reserved
+    /// word, use `is_reserved(true)` to check strict mode as well.
+    ///
+    /// Unicode escapes (`\u0061`, `\u{61}`) are decoded first.
+    /// The first character must be `$`, `_` or `ID_Start` and the
+    /// rest `$`, ZWNJ, ZWJ or `ID_Continue`. The tables come from
+    /// `unicode-ident` which uses the `XID_` variants of these
+    /// properties, those only differ for a few compatibility characters
+    /// ```
+    /// # use resast::Ident;
+    /// assert!(Ident::from("$thing_1").is_valid());
+    /// assert!(!Ident::from("my thing").is_valid());
+    /// assert!(!Ident::from("class").is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let name = match self.decoded_name() {
+            Some(name) => name,
+            None => return false,
+        };
+        let mut chars = name.chars();
+        let starts = match chars.next() {
+            Some(c) => is_id_start(c),
+            None => false,
+        };
+        starts && chars.all(is_id_continue) && !is_reserved_word(&name, false)
+    }
+
+    /// If the name is a reserved word, `strict` includes the
+    /// words only reserved in strict mode code like `yield`
+    /// and `let`. `await` is only reserved in modules so it
+    /// isn't included
+    pub fn is_reserved(&self, strict: bool) -> bool {
+        self.decoded_name()
+            .map(|name| is_reserved_word(&name, strict))
+            .unwrap_or(false)
+    }
+
+    /// The name with any unicode escapes decoded, `None`
+    /// if an escape is malformed
+    fn decoded_name(&self) -> Option<Cow<'_, str>> {
+        if !self.name.contains('\\') {
+            return Some(Cow::Borrowed(&self.name));
+        }
+        let mut decoded = String::with_capacity(self.name.len());
+        let mut chars = self.name.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                decoded.push(c);
+                continue;
+            }
+            if chars.next() != Some('u') {
+                return None;
+            }
+            let rest = chars.as_str();
+            let (digits, len) = if let Some(braced) = rest.strip_prefix('{') {
+                let end = braced.find('}')?;
+                (&braced[..end], end + 2)
+            } else {
+                (rest.get(..4)?, 4)
+            };
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            let code = u32::from_str_radix(digits, 16).ok()?;
+            decoded.push(std::char::from_u32(code)?);
+            chars = rest[len..].chars();
+        }
+        Some(Cow::Owned(decoded))
+    }
+}
+
+fn is_id_start(c: char) -> bool {
+    c == '$' || c == '_' || unicode_ident::is_xid_start(c)
+}
+
+fn is_id_continue(c: char) -> bool {
+    // ZWNJ and ZWJ are allowed after the first character
+    c == '$' || unicode_ident::is_xid_continue(c) || c == '\u{200C}' || c == '\u{200D}'
+}
+
+fn is_reserved_word(name: &str, strict: bool) -> bool {
+    match name {
+        "break" | "case" | "catch" | "class" | "const" | "continue" | "debugger" | "default"
+        | "delete" | "do" | "else" | "enum" | "export" | "extends" | "false" | "finally"
+        | "for" | "function" | "if" | "import" | "in" | "instanceof" | "new" | "null"
+        | "return" | "super" | "switch" | "this" | "throw" | "true" | "try" | "typeof"
+        | "var" | "void" | "while" | "with" => true,
+        "implements" | "interface" | "let" | "package" | "private" | "protected" | "public"
+        | "static" | "yield" => strict,
+        _ => false,
+    }
+}
+
+impl<'a> From<&'a str> for Ident<'a> {
+    fn from(s: &'a str) -> Self {
         Ident {
             name: Cow::Borrowed(s)
         }
@@ -43,8 +160,8 @@ impl<'a> Ident<'a> {
 ///
 /// It is essentially a collection of `ProgramPart`s
 /// with a flag denoting if the representation is
-/// a ES6 Mod or a Script.
-#[derive(Debug, Clone, PartialEq)]
+/// a ES6 Mod or a Script and an optional hashbang.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -52,23 +169,239 @@ impl<'a> Ident<'a> {
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub enum Program<'a> {
     /// An ES6 Mod
-    Mod(Vec<ProgramPart<'a>>),
+    Mod(Vec<ProgramPart<'a>>, Option<Cow<'a, str>>),
     /// Not an ES6 Mod
-    Script(Vec<ProgramPart<'a>>),
+    Script(Vec<ProgramPart<'a>>, Option<Cow<'a, str>>),
+}
+
+/// An empty script without a hashbang
+impl<'a> Default for Program<'a> {
+    fn default() -> Self {
+        Program::script(Vec::new())
+    }
 }
 
 impl<'a> Program<'a> {
     pub fn module(parts: Vec<ProgramPart<'a>>) -> Self {
-        Program::Mod(parts)
+        Program::Mod(parts, None)
     }
     pub fn script(parts: Vec<ProgramPart<'a>>) -> Self {
-        Program::Script(parts)
+        Program::Script(parts, None)
+    }
+    /// The text following the `#!` on the first line
+    /// of the program, if there was one
+    /// ```js
+    /// #!/usr/bin/env node
+    /// ```
+    pub fn hashbang(&self) -> Option<&str> {
+        match self {
+            Program::Mod(_, ref hashbang) | Program::Script(_, ref hashbang) => {
+                hashbang.as_ref().map(|h| h.as_ref())
+            }
+        }
+    }
+    /// Replace the hashbang of this program, this should not
+    /// include the leading `#!` or any line terminator
+    pub fn set_hashbang(&mut self, text: Option<Cow<'a, str>>) {
+        match self {
+            Program::Mod(_, ref mut hashbang) | Program::Script(_, ref mut hashbang) => {
+                *hashbang = text
+            }
+        }
+    }
+    /// Find the hashbang text in a javascript source string.
+    ///
+    /// A hashbang is only valid as the very first characters
+    /// of the source, so a `#!` anywhere else (say inside of a
+    /// class body) is not matched
+    pub fn hashbang_from(source: &'a str) -> Option<Cow<'a, str>> {
+        if !source.starts_with("#!") {
+            return None;
+        }
+        let rest = &source[2..];
+        let end = rest
+            .find(&['\n', '\r', '\u{2028}', '\u{2029}'][..])
+            .unwrap_or(rest.len());
+        Some(Cow::Borrowed(&rest[..end]))
+    }
+    /// Compare the shape and values of two programs.
+    ///
+    /// The top level AST doesn't carry any position data and
+    /// string values compare by their contents (a borrowed and an
+    /// owned `Cow` are equal), so this is the same as `==`. It exists
+    /// to mirror `spanned::Program::structural_eq` which does
+    /// ignore spans.
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+    /// Every part of this program, including the directive prologue
+    pub fn parts(&self) -> &[ProgramPart<'a>] {
+        match self {
+            Program::Mod(parts, _) | Program::Script(parts, _) => parts,
+        }
+    }
+    /// The directive prologue, the directives that appear before
+    /// any other statement or declaration.
+    ///
+    /// A string expression that comes after the first non-directive
+    /// is just an expression statement and is not included
+    pub fn directives(&self) -> impl Iterator<Item = &Dir<'a>> {
+        directives(self.parts())
+    }
+    /// Everything following the directive prologue
+    pub fn body(&self) -> &[ProgramPart<'a>] {
+        let parts = self.parts();
+        &parts[prologue_len(parts)..]
+    }
+    /// If this program's code is strict mode code, either because
+    /// it is a module or it starts with a `'use strict'` directive
+    pub fn is_strict(&self) -> bool {
+        matches!(self, Program::Mod(_, _)) || self.directives().any(Dir::is_use_strict)
+    }
+    /// If this program uses `await` outside of a function, a
+    /// module that does this is evaluated asynchronously. See
+    /// `analysis::contains_top_level_await`
+    pub fn contains_top_level_await(&self) -> bool {
+        analysis::contains_top_level_await(self)
+    }
+    /// Every module specifier this program imports from or
+    /// re-exports, see `analysis::module_dependencies`
+    pub fn module_dependencies(&self) -> Vec<analysis::ModuleDep<'_>> {
+        analysis::module_dependencies(self)
+    }
+    /// Every string literal in this program, without template
+    /// quasis, see `analysis::string_literals`
+    pub fn string_literals(&self) -> Vec<analysis::StringLiteralRef<'_>> {
+        analysis::string_literals(self, false)
+    }
+    /// Every name this module exports, see `analysis::exported_names`
+    pub fn exported_names(&self) -> Vec<analysis::ExportedName<'_>> {
+        analysis::exported_names(self)
+    }
+    /// Every spread outside of an array or argument list, see
+    /// `analysis::check_spread_positions`
+    pub fn check_spread_positions(&self) -> Vec<analysis::SpreadError<'_>> {
+        analysis::check_spread_positions(self)
+    }
+    /// If this was parsed as a module or a script
+    pub fn source_type(&self) -> SourceType {
+        match self {
+            Program::Mod(_, _) => SourceType::Module,
+            Program::Script(_, _) => SourceType::Script,
+        }
+    }
+    pub fn is_module(&self) -> bool {
+        self.source_type() == SourceType::Module
+    }
+    pub fn is_script(&self) -> bool {
+        self.source_type() == SourceType::Script
+    }
+    /// Treat this program as a module, keeping its parts and
+    /// hashbang.
+    ///
+    /// Any script can be re-labeled this way but module code is
+    /// always strict, `analysis::find_strict_violations` will point
+    /// out anything that was only allowed in sloppy mode
+    pub fn into_module(self) -> Self {
+        match self {
+            Program::Mod(parts, hashbang) | Program::Script(parts, hashbang) => {
+                Program::Mod(parts, hashbang)
+            }
+        }
+    }
+    /// Check that this program would also be a valid script, see
+    /// `ScriptError`
+    pub fn check_script(&self) -> Result<(), ScriptError> {
+        let module_decl = self
+            .parts()
+            .iter()
+            .position(|part| matches!(part, ProgramPart::Decl(Decl::Import(_) | Decl::Export(_))));
+        if let Some(idx) = module_decl {
+            return Err(ScriptError::ImportExport(idx));
+        }
+        let import_meta = self
+            .descendants::<Expr>()
+            .any(|expr| matches!(expr, Expr::MetaProp(meta) if meta.is_import_meta()));
+        if import_meta {
+            return Err(ScriptError::ImportMeta);
+        }
+        if self.contains_top_level_await() {
+            return Err(ScriptError::TopLevelAwait);
+        }
+        Ok(())
+    }
+    /// Treat this program as a script, this fails when it contains
+    /// anything that is only allowed in a module. Use `check_script`
+    /// first to keep the program when it can't be converted
+    pub fn into_script(self) -> Result<Self, ScriptError> {
+        self.check_script()?;
+        match self {
+            Program::Mod(parts, hashbang) | Program::Script(parts, hashbang) => {
+                Ok(Program::Script(parts, hashbang))
+            }
+        }
+    }
+}
+
+/// How a program's source text was parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceType {
+    Script,
+    Module,
+}
+
+/// The reasons a program can't be a script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptError {
+    /// An import or export declaration, the value is its index
+    /// in the program's parts
+    /// ```js
+    /// import a from 'a';
+    /// ```
+    ImportExport(usize),
+    /// ```js
+    /// import.meta.url;
+    /// ```
+    ImportMeta,
+    /// An `await` outside of any function
+    /// ```js
+    /// await ready;
+    /// ```
+    TopLevelAwait,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ScriptError::ImportExport(idx) => {
+                write!(f, "part {} is an import or export declaration", idx)
+            }
+            ScriptError::ImportMeta => write!(f, "import.meta is only allowed in a module"),
+            ScriptError::TopLevelAwait => write!(f, "top level await is only allowed in a module"),
+        }
     }
 }
 
+impl std::error::Error for ScriptError {}
+
+/// The number of `Dir` parts at the start of `parts`
+fn prologue_len(parts: &[ProgramPart]) -> usize {
+    parts
+        .iter()
+        .take_while(|part| matches!(part, ProgramPart::Dir(_)))
+        .count()
+}
+
+fn directives<'r, 'a>(parts: &'r [ProgramPart<'a>]) -> impl Iterator<Item = &'r Dir<'a>> {
+    parts[..prologue_len(parts)].iter().filter_map(|part| match part {
+        ProgramPart::Dir(dir) => Some(dir),
+        _ => None,
+    })
+}
+
 /// A single part of a Javascript program.
 /// This will be either a Directive, Decl or a Stmt
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -91,11 +424,56 @@ impl<'a> ProgramPart<'a> {
     pub fn stmt(inner: Stmt<'a>) -> Self {
         ProgramPart::Stmt(inner)
     }
+    pub fn is_dir(&self) -> bool {
+        matches!(self, ProgramPart::Dir(_))
+    }
+    pub fn is_decl(&self) -> bool {
+        matches!(self, ProgramPart::Decl(_))
+    }
+    pub fn is_stmt(&self) -> bool {
+        matches!(self, ProgramPart::Stmt(_))
+    }
+    pub fn as_dir(&self) -> Option<&Dir<'a>> {
+        match self {
+            ProgramPart::Dir(dir) => Some(dir),
+            _ => None,
+        }
+    }
+    pub fn as_decl(&self) -> Option<&Decl<'a>> {
+        match self {
+            ProgramPart::Decl(decl) => Some(decl),
+            _ => None,
+        }
+    }
+    pub fn as_stmt(&self) -> Option<&Stmt<'a>> {
+        match self {
+            ProgramPart::Stmt(stmt) => Some(stmt),
+            _ => None,
+        }
+    }
+    pub fn as_dir_mut(&mut self) -> Option<&mut Dir<'a>> {
+        match self {
+            ProgramPart::Dir(dir) => Some(dir),
+            _ => None,
+        }
+    }
+    pub fn as_decl_mut(&mut self) -> Option<&mut Decl<'a>> {
+        match self {
+            ProgramPart::Decl(decl) => Some(decl),
+            _ => None,
+        }
+    }
+    pub fn as_stmt_mut(&mut self) -> Option<&mut Stmt<'a>> {
+        match self {
+            ProgramPart::Stmt(stmt) => Some(stmt),
+            _ => None,
+        }
+    }
 }
 
 /// pretty much always `'use strict'`, this can appear at the
 /// top of a file or function
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -106,6 +484,68 @@ pub struct Dir<'a> {
     pub dir: Cow<'a, str>,
 }
 
+impl<'a> Dir<'a> {
+    /// A directive for `text`, this is written with single
+    /// quotes unless `text` contains one.
+    ///
+    /// `text` is the raw source between the quotes so any
+    /// escapes should already be written out
+    /// ```
+    /// # use resast::prelude::*;
+    /// let dir = Dir::from_str("use strict");
+    /// assert!(dir.is_use_strict());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &'a str) -> Self {
+        let expr = if text.contains('\'') {
+            Lit::double_string_from(text)
+        } else {
+            Lit::single_string_from(text)
+        };
+        Dir {
+            expr,
+            dir: Cow::Borrowed(text),
+        }
+    }
+    /// The text between the quotes exactly as it was written,
+    /// escapes are not processed since two directives are only
+    /// the same when their raw text matches
+    pub fn value(&self) -> &str {
+        &self.dir
+    }
+    /// If this is the `'use strict'` directive, the text
+    /// must match exactly, escapes or line continuations
+    /// make it a normal directive
+    pub fn is_use_strict(&self) -> bool {
+        self.dir == "use strict"
+    }
+    /// Which directive this is, with the same exact
+    /// match rules as `is_use_strict`
+    /// ```
+    /// # use resast::prelude::*;
+    /// assert_eq!(Dir::from_str("use asm").classify(), KnownDirective::UseAsm);
+    /// ```
+    pub fn classify(&self) -> KnownDirective<'_> {
+        match &*self.dir {
+            "use strict" => KnownDirective::UseStrict,
+            "use asm" => KnownDirective::UseAsm,
+            other => KnownDirective::Other(Cow::Borrowed(other)),
+        }
+    }
+}
+
+/// The directives that change how the code
+/// after them is run
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KnownDirective<'a> {
+    /// `'use strict'`
+    UseStrict,
+    /// `'use asm'`, the function is asm.js
+    UseAsm,
+    /// Any other directive, with its raw text
+    Other(Cow<'a, str>),
+}
+
 /// A function, this will be part of either a function
 /// declaration (ID is required) or a function expression
 /// (ID is optional)
@@ -116,7 +556,7 @@ pub struct Dir<'a> {
 /// var x = function() {}
 /// let y = function q() {}
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct Func<'a> {
     pub id: Option<Ident<'a>>,
@@ -142,10 +582,114 @@ impl<'a> Func<'a> {
             is_async,
         }
     }
+    /// Start building a function with `FuncBuilder`
+    pub fn builder() -> FuncBuilder<'a> {
+        FuncBuilder::new()
+    }
+    /// If this is an `async function`
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+    /// If this is a `function*`
+    pub fn is_generator(&self) -> bool {
+        self.generator
+    }
+    /// Always `false`, arrow functions are represented
+    /// by `ArrowFuncExpr`
+    pub fn is_arrow(&self) -> bool {
+        false
+    }
+    /// If this function's body starts with a `'use strict'`
+    /// directive, this doesn't account for any enclosing
+    /// strict mode code
+    pub fn is_strict(&self) -> bool {
+        self.body.directives().any(Dir::is_use_strict)
+    }
+    /// Check the parameter list, see `ParamError`
+    pub fn validate_params(&self) -> Result<(), ParamError> {
+        validate_params(&self.params)
+    }
 }
 
-/// A single function argument from a function signature
-#[derive(Debug, Clone, PartialEq)]
+/// A chainable way to build up a `Func`, by default
+/// the function is anonymous, not async, not a generator and
+/// has an empty body
+/// ```
+/// use resast::prelude::*;
+/// let func = FuncBuilder::new()
+///     .name("thing")
+///     .param(FuncArg::Pat(Pat::ident_from("a")))
+///     .async_(true)
+///     .build();
+/// assert!(func.is_async);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FuncBuilder<'a> {
+    id: Option<Ident<'a>>,
+    params: Vec<FuncArg<'a>>,
+    body: Vec<ProgramPart<'a>>,
+    generator: bool,
+    is_async: bool,
+}
+
+impl<'a> FuncBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the function's name
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.id = Some(Ident::from(name));
+        self
+    }
+    /// Set the function's name from an existing `Ident`
+    pub fn id(mut self, id: Ident<'a>) -> Self {
+        self.id = Some(id);
+        self
+    }
+    /// Add a single parameter to the end of the parameter list
+    pub fn param(mut self, param: FuncArg<'a>) -> Self {
+        self.params.push(param);
+        self
+    }
+    /// Add all of `params` to the end of the parameter list
+    pub fn params(mut self, params: impl IntoIterator<Item = FuncArg<'a>>) -> Self {
+        self.params.extend(params);
+        self
+    }
+    pub fn async_(mut self, is_async: bool) -> Self {
+        self.is_async = is_async;
+        self
+    }
+    pub fn generator(mut self, generator: bool) -> Self {
+        self.generator = generator;
+        self
+    }
+    /// Replace the function's body
+    pub fn body(mut self, body: Vec<ProgramPart<'a>>) -> Self {
+        self.body = body;
+        self
+    }
+    pub fn build(self) -> Func<'a> {
+        Func {
+            id: self.id,
+            params: self.params,
+            body: FuncBody(self.body),
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
+}
+
+/// A single function argument from a function signature.
+///
+/// Each kind of parameter is a `Pat`, a default is a
+/// `Pat::Assign` and a rest parameter is a `Pat::RestElement`,
+/// which is only valid as the last parameter. The `Expr`
+/// variant holds the same shapes as their expression forms
+/// ```js
+/// function f(a, { b }, c = 1, ...d) {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -164,16 +708,138 @@ impl<'a> FuncArg<'a> {
     pub fn pat(pat: Pat) -> FuncArg {
         FuncArg::Pat(pat)
     }
+    /// Which kind of parameter this is, `None` for an
+    /// expression that can't be a parameter
+    pub fn kind(&self) -> Option<ParamKind> {
+        let kind = match self {
+            FuncArg::Pat(Pat::Ident(_)) | FuncArg::Expr(Expr::Ident(_)) => ParamKind::Simple,
+            FuncArg::Pat(Pat::Obj(_))
+            | FuncArg::Pat(Pat::Array(_))
+            | FuncArg::Expr(Expr::Obj(_))
+            | FuncArg::Expr(Expr::Array(_)) => ParamKind::Pattern,
+            FuncArg::Pat(Pat::Assign(_)) | FuncArg::Expr(Expr::Assign(_)) => ParamKind::Default,
+            FuncArg::Pat(Pat::RestElement(_)) | FuncArg::Expr(Expr::Spread(_)) => ParamKind::Rest,
+            FuncArg::Expr(_) => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// The kinds of function parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    /// `a`
+    Simple,
+    /// `{ a }` or `[a]`
+    Pattern,
+    /// `a = 1`
+    Default,
+    /// `...a`
+    Rest,
+}
+
+/// The reasons a parameter list is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamError {
+    /// A rest parameter that is followed by another parameter,
+    /// the value is the rest parameter's index
+    /// ```js
+    /// function f(...a, b) {}
+    /// ```
+    RestNotLast(usize),
+    /// A rest parameter with a default value
+    /// ```js
+    /// function f(...a = []) {}
+    /// ```
+    RestWithDefault(usize),
+    /// An expression that can't be a parameter
+    /// ```js
+    /// function f(a()) {}
+    /// ```
+    InvalidParam(usize),
+}
+
+impl std::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParamError::RestNotLast(idx) => {
+                write!(f, "rest parameter {} must be the last parameter", idx)
+            }
+            ParamError::RestWithDefault(idx) => {
+                write!(f, "rest parameter {} can't have a default", idx)
+            }
+            ParamError::InvalidParam(idx) => write!(f, "parameter {} is invalid", idx),
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+pub(crate) fn validate_params(params: &[FuncArg]) -> Result<(), ParamError> {
+    for (idx, param) in params.iter().enumerate() {
+        match param.kind() {
+            None => return Err(ParamError::InvalidParam(idx)),
+            Some(ParamKind::Rest) => {
+                let has_default = match param {
+                    FuncArg::Pat(Pat::RestElement(inner)) => matches!(**inner, Pat::Assign(_)),
+                    FuncArg::Expr(Expr::Spread(inner)) => matches!(**inner, Expr::Assign(_)),
+                    _ => false,
+                };
+                if has_default {
+                    return Err(ParamError::RestWithDefault(idx));
+                }
+                if idx + 1 != params.len() {
+                    return Err(ParamError::RestNotLast(idx));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
 }
 
 /// The block statement that makes up the function's body
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
 )]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct FuncBody<'a>(pub Vec<ProgramPart<'a>>);
+
+impl<'a> FuncBody<'a> {
+    /// The directive prologue of this function body, see
+    /// `Program::directives`
+    pub fn directives(&self) -> impl Iterator<Item = &Dir<'a>> {
+        directives(&self.0)
+    }
+    /// Everything following the directive prologue
+    pub fn body(&self) -> &[ProgramPart<'a>] {
+        &self.0[prologue_len(&self.0)..]
+    }
+    /// Insert `part` as the first statement of this body, after
+    /// any directives so the prologue stays intact
+    pub fn prepend_stmt(&mut self, part: ProgramPart<'a>) {
+        self.0.insert(prologue_len(&self.0), part);
+    }
+    /// Push `part` onto the end of this body
+    pub fn append_stmt(&mut self, part: ProgramPart<'a>) {
+        self.0.push(part);
+    }
+    /// Insert `part` at `index` of `body`, meaning an `index` of 0
+    /// is the position just after the directive prologue
+    ///
+    /// Panics if `index > self.body().len()`
+    pub fn insert_stmt(&mut self, index: usize, part: ProgramPart<'a>) {
+        let start = prologue_len(&self.0);
+        assert!(
+            index <= self.0.len() - start,
+            "insertion index {} is past the end of the body",
+            index
+        );
+        self.0.insert(start + index, part);
+    }
+}
 /// A way to declare object templates
 /// ```js
 /// class Thing {
@@ -200,33 +866,204 @@ pub struct FuncBody<'a>(pub Vec<ProgramPart<'a>>);
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct Class<'a> {
     pub id: Option<Ident<'a>>,
     pub super_class: Option<Box<Expr<'a>>>,
     pub body: ClassBody<'a>,
+    /// Any decorators applied to the class
+    /// ```js
+    /// @sealed
+    /// class Thing {}
+    /// ```
+    pub decorators: Vec<Decorator<'a>>,
+}
+
+/// A decorator applied to a class or class member,
+/// this can be any expression, including a call
+/// ```js
+/// @ns.logged('debug')
+/// class Thing {}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct Decorator<'a> {
+    pub expression: Expr<'a>,
 }
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
 )]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
-pub struct ClassBody<'a>(pub Vec<Prop<'a>>);
+pub struct ClassBody<'a>(pub Vec<ClassMember<'a>>);
+
+/// A single part of a class body
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub enum ClassMember<'a> {
+    /// A constructor, method or accessor
+    /// ```js
+    /// class Thing {
+    ///     constructor() {}
+    ///     static create() {}
+    ///     get size() {}
+    /// }
+    /// ```
+    Method(MethodDef<'a>),
+    /// A field with an optional initializer
+    /// ```js
+    /// class Thing {
+    ///     count = 0;
+    ///     static [key];
+    /// }
+    /// ```
+    Field(FieldDef<'a>),
+    /// A static initialization block
+    /// ```js
+    /// class Thing {
+    ///     static {
+    ///         init();
+    ///     }
+    /// }
+    /// ```
+    StaticBlock(BlockStmt<'a>),
+}
+
+impl<'a> ClassMember<'a> {
+    /// The name of a method or field, static blocks don't have one
+    pub fn key(&self) -> Option<&PropKey<'a>> {
+        match self {
+            ClassMember::Method(method) => Some(&method.key),
+            ClassMember::Field(field) => Some(&field.key),
+            ClassMember::StaticBlock(_) => None,
+        }
+    }
+    /// If this belongs to the class instead of its instances,
+    /// static blocks are always static
+    pub fn is_static(&self) -> bool {
+        match self {
+            ClassMember::Method(method) => method.is_static,
+            ClassMember::Field(field) => field.is_static,
+            ClassMember::StaticBlock(_) => true,
+        }
+    }
+    /// If the key is written inside of `[]`
+    pub fn is_computed(&self) -> bool {
+        match self {
+            ClassMember::Method(method) => method.computed,
+            ClassMember::Field(field) => field.computed,
+            ClassMember::StaticBlock(_) => false,
+        }
+    }
+    pub fn decorators(&self) -> &[Decorator<'a>] {
+        match self {
+            ClassMember::Method(method) => &method.decorators,
+            ClassMember::Field(field) => &field.decorators,
+            ClassMember::StaticBlock(_) => &[],
+        }
+    }
+}
+
+/// A constructor, method or accessor in a class body
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct MethodDef<'a> {
+    pub key: PropKey<'a>,
+    pub value: Func<'a>,
+    pub kind: MethodKind,
+    pub computed: bool,
+    pub is_static: bool,
+    pub decorators: Vec<Decorator<'a>>,
+}
+
+impl<'a> MethodDef<'a> {
+    /// A method that isn't static, computed or decorated
+    pub fn new(key: PropKey<'a>, value: Func<'a>, kind: MethodKind) -> Self {
+        Self {
+            key,
+            value,
+            kind,
+            computed: false,
+            is_static: false,
+            decorators: Vec::new(),
+        }
+    }
+}
+
+/// The kind of method in a class body
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub enum MethodKind {
+    /// `constructor() {}`
+    Constructor,
+    /// `method() {}`
+    Method,
+    /// `get value() {}`
+    Get,
+    /// `set value(v) {}`
+    Set,
+}
+
+/// A field in a class body
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
+pub struct FieldDef<'a> {
+    pub key: PropKey<'a>,
+    /// The initializer, if any
+    pub value: Option<Expr<'a>>,
+    pub computed: bool,
+    pub is_static: bool,
+    pub decorators: Vec<Decorator<'a>>,
+}
+
+impl<'a> FieldDef<'a> {
+    /// A field that isn't static, computed or decorated
+    pub fn new(key: PropKey<'a>, value: Option<Expr<'a>>) -> Self {
+        Self {
+            key,
+            value,
+            computed: false,
+            is_static: false,
+            decorators: Vec::new(),
+        }
+    }
+}
 
 impl<'a> Class<'a> {
-    pub fn new(id: Option<Ident<'a>>, super_class: Option<Expr<'a>>, body: Vec<Prop<'a>>) -> Class<'a> {
+    pub fn new(id: Option<Ident<'a>>, super_class: Option<Expr<'a>>, body: Vec<ClassMember<'a>>) -> Class<'a> {
         Class {
             id,
             super_class: super_class.map(Box::new),
             body: ClassBody(body),
+            decorators: Vec::new(),
         }
     }
 }
 
 /// The kind of variable being defined (`var`/`let`/`const`)
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -239,9 +1076,20 @@ pub enum VarKind {
     Const,
 }
 
+impl VarKind {
+    /// The keyword for this kind
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VarKind::Var => "var",
+            VarKind::Let => "let",
+            VarKind::Const => "const",
+        }
+    }
+}
+
 
 /// The available operators for assignment Exprs
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -261,11 +1109,29 @@ pub enum AssignOp {
     XOrEqual,
     AndEqual,
     PowerOfEqual,
+    /// `&&=`
+    ///
+    /// > note: this short circuits, `a &&= b` is `a && (a = b)`
+    /// > not `a = a && b`, the assignment only happens when `a`
+    /// > is truthy
+    LogicalAndEqual,
+    /// `||=`
+    ///
+    /// > note: this short circuits, `a ||= b` is `a || (a = b)`
+    /// > not `a = a || b`, the assignment only happens when `a`
+    /// > is falsy
+    LogicalOrEqual,
+    /// `??=`
+    ///
+    /// > note: this short circuits, `a ??= b` is `a ?? (a = b)`
+    /// > not `a = a ?? b`, the assignment only happens when `a`
+    /// > is `null` or `undefined`
+    NullishEqual,
 }
 
 
 /// The available logical operators
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -274,11 +1140,18 @@ pub enum AssignOp {
 pub enum LogicalOp {
     Or,
     And,
+    /// `??`
+    ///
+    /// > note: this cannot be mixed with `&&` or `||` without
+    /// > parentheses, `a ?? b || c` is a syntax error while
+    /// > `(a ?? b) || c` is not. Since this is only a data model
+    /// > nothing prevents building the invalid form
+    NullishCoalescing,
 }
 
 
 /// The available operations for `Binary` Exprs
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -311,7 +1184,7 @@ pub enum BinaryOp {
 
 
 /// `++` or `--`
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -324,7 +1197,7 @@ pub enum UpdateOp {
 
 /// The allowed operators for an Expr
 /// to be `Unary`
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -340,9 +1213,196 @@ pub enum UnaryOp {
     Delete,
 }
 
+/// Which side an operator groups with when it is
+/// chained without parentheses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ** b ** c` is `a ** (b ** c)`
+    Right,
+}
+
+// The precedence levels of operators, higher binds tighter.
+// These follow the ECMAScript grammar with `??` just below `||`
+// since the two can't be mixed anyway
+//
+// 2 assignment
+// 4 `??`
+// 5 `||`
+// 6 `&&`
+// 7 `|`
+// 8 `^`
+// 9 `&`
+// 10 equality
+// 11 relational, `in` and `instanceof`
+// 12 shifts
+// 13 `+` and `-`
+// 14 `*`, `/` and `%`
+// 15 `**`
+// 16 unary and prefix updates
+// 17 postfix updates
+
+impl AssignOp {
+    /// The source text of this operator
+    pub fn as_str(self) -> &'static str {
+        use AssignOp::*;
+        match self {
+            Equal => "=",
+            PlusEqual => "+=",
+            MinusEqual => "-=",
+            TimesEqual => "*=",
+            DivEqual => "/=",
+            ModEqual => "%=",
+            LeftShiftEqual => "<<=",
+            RightShiftEqual => ">>=",
+            UnsignedRightShiftEqual => ">>>=",
+            OrEqual => "|=",
+            XOrEqual => "^=",
+            AndEqual => "&=",
+            PowerOfEqual => "**=",
+            LogicalAndEqual => "&&=",
+            LogicalOrEqual => "||=",
+            NullishEqual => "??=",
+        }
+    }
+    /// The precedence level of any assignment, lower
+    /// than every other operator
+    pub const fn precedence(self) -> u8 {
+        2
+    }
+    /// Assignments are always right associative,
+    /// `a = b = c` is `a = (b = c)`
+    pub const fn associativity(self) -> Associativity {
+        Associativity::Right
+    }
+}
+
+impl LogicalOp {
+    /// The source text of this operator
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogicalOp::Or => "||",
+            LogicalOp::And => "&&",
+            LogicalOp::NullishCoalescing => "??",
+        }
+    }
+    /// The precedence level of this operator, `&&`
+    /// binds tighter than `||`
+    pub const fn precedence(self) -> u8 {
+        match self {
+            LogicalOp::NullishCoalescing => 4,
+            LogicalOp::Or => 5,
+            LogicalOp::And => 6,
+        }
+    }
+    pub const fn associativity(self) -> Associativity {
+        Associativity::Left
+    }
+    /// If an expression using this operator can be the direct
+    /// operand of one using `other` without parentheses,
+    /// `??` can only be mixed with itself
+    /// ```
+    /// # use resast::LogicalOp;
+    /// assert!(LogicalOp::And.can_mix_with(LogicalOp::Or));
+    /// assert!(!LogicalOp::NullishCoalescing.can_mix_with(LogicalOp::Or));
+    /// ```
+    pub fn can_mix_with(self, other: LogicalOp) -> bool {
+        (self == LogicalOp::NullishCoalescing) == (other == LogicalOp::NullishCoalescing)
+    }
+}
+
+impl BinaryOp {
+    /// The source text of this operator
+    pub fn as_str(self) -> &'static str {
+        use BinaryOp::*;
+        match self {
+            Equal => "==",
+            NotEqual => "!=",
+            StrictEqual => "===",
+            StrictNotEqual => "!==",
+            LessThan => "<",
+            GreaterThan => ">",
+            LessThanEqual => "<=",
+            GreaterThanEqual => ">=",
+            LeftShift => "<<",
+            RightShift => ">>",
+            UnsignedRightShift => ">>>",
+            Plus => "+",
+            Minus => "-",
+            Times => "*",
+            Over => "/",
+            Mod => "%",
+            Or => "|",
+            XOr => "^",
+            And => "&",
+            In => "in",
+            InstanceOf => "instanceof",
+            PowerOf => "**",
+        }
+    }
+    /// The precedence level of this operator
+    pub const fn precedence(self) -> u8 {
+        use BinaryOp::*;
+        match self {
+            Or => 7,
+            XOr => 8,
+            And => 9,
+            Equal | NotEqual | StrictEqual | StrictNotEqual => 10,
+            LessThan | GreaterThan | LessThanEqual | GreaterThanEqual | In | InstanceOf => 11,
+            LeftShift | RightShift | UnsignedRightShift => 12,
+            Plus | Minus => 13,
+            Times | Over | Mod => 14,
+            PowerOf => 15,
+        }
+    }
+    /// Only `**` is right associative
+    pub const fn associativity(self) -> Associativity {
+        match self {
+            BinaryOp::PowerOf => Associativity::Right,
+            _ => Associativity::Left,
+        }
+    }
+}
+
+impl UpdateOp {
+    /// The source text of this operator
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateOp::Increment => "++",
+            UpdateOp::Decrement => "--",
+        }
+    }
+    /// The precedence level of a postfix update, a prefix
+    /// update has the same level as a `UnaryOp`
+    pub const fn precedence(self) -> u8 {
+        17
+    }
+}
+
+impl UnaryOp {
+    /// The source text of this operator, the keyword
+    /// operators don't include the space after them
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UnaryOp::Minus => "-",
+            UnaryOp::Plus => "+",
+            UnaryOp::Not => "!",
+            UnaryOp::Tilde => "~",
+            UnaryOp::TypeOf => "typeof",
+            UnaryOp::Void => "void",
+            UnaryOp::Delete => "delete",
+        }
+    }
+    /// The precedence level of every unary operator, this is
+    /// also the level of `await` and prefix updates
+    pub const fn precedence(self) -> u8 {
+        16
+    }
+}
 
 /// A flag for determining what kind of property
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -364,22 +1424,35 @@ pub enum PropKind {
 pub mod prelude {
     pub use crate::{
         AssignOp,
+        Associativity,
         BinaryOp,
         Class,
         ClassBody,
+        ClassMember,
+        Decorator,
         Dir,
+        FieldDef,
         Func,
         FuncArg,
         FuncBody,
+        FuncBuilder,
         Ident,
+        KnownDirective,
         LogicalOp,
+        MethodDef,
+        MethodKind,
+        ParamError,
+        ParamKind,
         Program,
         ProgramPart,
         PropKind,
+        ScriptError,
+        SourceType,
         UnaryOp,
         UpdateOp,
         VarKind,
     };
+    pub use crate::const_eval::ConstValue;
     pub use crate::expr::{
         ArrayExpr,
         ArrowFuncBody,
@@ -390,17 +1463,26 @@ pub mod prelude {
         CallExpr,
         ConditionalExpr,
         Expr,
+        ImportExpr,
         Lit,
         LogicalExpr,
         MemberExpr,
+        MemberProp,
         MetaProp,
         NewExpr,
+        NumberLit,
+        ObjError,
         ObjExpr,
+        ObjExprExt,
         ObjProp,
+        PrivateId,
         Prop,
         PropKey,
         PropValue,
         RegEx,
+        RegExFlagError,
+        RegExFlags,
+        SequenceExpr,
         StringLit,
         TaggedTemplateExpr,
         TemplateElement,
@@ -411,14 +1493,20 @@ pub mod prelude {
     };
     pub use crate::decl::{
         Decl,
+        DeclError,
         VarDecl,
         ModDecl,
         ModImport,
+        ImportAttribute,
+        ImportAttributeKey,
+        AttributesKeyword,
         ImportSpecifier,
         ModExport,
         NamedExportDecl,
         DefaultExportDecl,
         ExportSpecifier,
+        ExportSource,
+        ModuleExportName,
         NormalImportSpec
     };
     pub use crate::stmt::{
@@ -446,4 +1534,580 @@ pub mod prelude {
         ObjPatPart,
         Pat,
     };
-}
\ No newline at end of file
+    pub use crate::owned::{Interner, IntoOwned};
+}
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ident_from_str() {
+        let ident: Ident = "not a valid identifier".into();
+        assert_eq!(ident.name, "not a valid identifier");
+        assert!(matches!(ident.name, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn func_builder_defaults() {
+        let func = FuncBuilder::new().build();
+        assert_eq!(func, Func::new(None, vec![], FuncBody(vec![]), false, false));
+    }
+
+    #[test]
+    fn func_builder_async_generator() {
+        // async function* gen(a, b) { yield a; }
+        let body = vec![ProgramPart::Stmt(Stmt::Expr(Expr::Yield(expr::YieldExpr {
+            argument: Some(Box::new(Expr::ident_from("a"))),
+            delegate: false,
+        })))];
+        let func = Func::builder()
+            .name("gen")
+            .param(FuncArg::Pat(Pat::ident_from("a")))
+            .params(vec![FuncArg::Pat(Pat::ident_from("b"))])
+            .async_(true)
+            .generator(true)
+            .body(body.clone())
+            .build();
+        assert_eq!(
+            func,
+            Func {
+                id: Some(Ident::from("gen")),
+                params: vec![
+                    FuncArg::Pat(Pat::ident_from("a")),
+                    FuncArg::Pat(Pat::ident_from("b")),
+                ],
+                body: FuncBody(body),
+                generator: true,
+                is_async: true,
+            }
+        );
+    }
+    use crate::expr::PropKey;
+    use crate::stmt::BlockStmt;
+
+    #[test]
+    fn func_predicates() {
+        // function f() {}
+        let plain = Func::builder().name("f").build();
+        assert!(!plain.is_async() && !plain.is_generator() && !plain.is_arrow());
+        assert!(!plain.is_strict());
+        // async function* f() { 'use strict'; }
+        let strict = Func::builder()
+            .async_(true)
+            .generator(true)
+            .body(vec![dir("use strict")])
+            .build();
+        assert!(strict.is_async() && strict.is_generator() && strict.is_strict());
+        // async x => x
+        let concise = expr::ArrowFuncExpr {
+            id: None,
+            params: vec![FuncArg::Pat(Pat::ident_from("x"))],
+            body: expr::ArrowFuncBody::Expr(Box::new(Expr::ident_from("x"))),
+            expression: true,
+            is_async: true,
+        };
+        assert!(concise.is_arrow() && concise.is_async() && !concise.is_generator());
+        assert!(concise.is_expression_body());
+        assert_eq!(concise.body_expr(), Some(&Expr::ident_from("x")));
+        assert_eq!(concise.body_block(), None);
+        assert!(!concise.is_strict());
+        // x => { 'use strict'; }
+        let block = expr::ArrowFuncExpr {
+            body: expr::ArrowFuncBody::FuncBody(FuncBody(vec![dir("use strict")])),
+            expression: false,
+            is_async: false,
+            ..concise
+        };
+        assert!(!block.is_expression_body() && !block.is_async());
+        assert_eq!(block.body_expr(), None);
+        assert_eq!(block.body_block().map(|b| b.0.len()), Some(1));
+        assert!(block.is_strict());
+    }
+
+    fn call_stmt<'a>(name: &'a str) -> ProgramPart<'a> {
+        ProgramPart::Stmt(Stmt::Expr(Expr::Call(crate::expr::CallExpr {
+            callee: Box::new(Expr::ident_from(name)),
+            arguments: vec![],
+            optional: false,
+        })))
+    }
+
+    #[test]
+    fn hashbang() {
+        let src = "#!/usr/bin/env node\nconsole.log('hi')";
+        let mut program = Program::script(vec![]);
+        assert_eq!(program.hashbang(), None);
+        program.set_hashbang(Program::hashbang_from(src));
+        assert_eq!(program.hashbang(), Some("/usr/bin/env node"));
+        assert_eq!(
+            Program::Mod(vec![], Some(Cow::Borrowed("node"))).hashbang(),
+            Some("node")
+        );
+    }
+
+    fn dir<'a>(text: &'a str) -> ProgramPart<'a> {
+        ProgramPart::Dir(Dir {
+            expr: Lit::single_string_from(text),
+            dir: Cow::Borrowed(text),
+        })
+    }
+
+    #[test]
+    fn part_and_decl_accessors() {
+        // 'use strict';
+        // import a from 'a';
+        // export {a};
+        // var b;
+        // function c() {}
+        // class D {}
+        // e();
+        let import = decl::ModImport {
+            specifiers: vec![decl::ImportSpecifier::Default(Ident::from("a"))],
+            source: Lit::single_string_from("a"),
+            attributes: vec![],
+            attributes_keyword: None,
+        };
+        let export = decl::ModExport::Named(decl::NamedExportDecl::Specifier(
+            vec![decl::ExportSpecifier {
+                local: Ident::from("a"),
+                exported: decl::ModuleExportName::ident_from("a"),
+            }],
+            None,
+        ));
+        let var = Decl::Var(
+            VarKind::Var,
+            vec![decl::VarDecl {
+                id: Pat::ident_from("b"),
+                init: None,
+            }],
+        );
+        let func = Func::builder().name("c").build();
+        let class = Class::new(Some(Ident::from("D")), None, vec![]);
+        let mut program = Program::module(vec![
+            dir("use strict"),
+            ProgramPart::Decl(Decl::Import(Box::new(import.clone()))),
+            ProgramPart::Decl(Decl::Export(Box::new(export.clone()))),
+            ProgramPart::Decl(var),
+            ProgramPart::Decl(Decl::Func(func.clone())),
+            ProgramPart::Decl(Decl::Class(class.clone())),
+            call_stmt("e"),
+        ]);
+        let parts = program.parts();
+        assert!(parts[0].is_dir() && !parts[0].is_decl() && !parts[0].is_stmt());
+        assert_eq!(parts[0].as_dir().map(|d| d.dir.as_ref()), Some("use strict"));
+        assert_eq!(parts[0].as_decl(), None);
+        let decls: Vec<&Decl> = parts.iter().filter_map(ProgramPart::as_decl).collect();
+        assert_eq!(decls.len(), 5);
+        assert_eq!(decls[0].as_import(), Some(&import));
+        assert_eq!(decls[1].as_export(), Some(&export));
+        let (kind, vars) = decls[2].as_var().unwrap();
+        assert_eq!((kind, vars.len()), (VarKind::Var, 1));
+        assert_eq!(decls[3].as_func(), Some(&func));
+        assert_eq!(decls[4].as_class(), Some(&class));
+        assert_eq!(decls[4].as_func(), None);
+        assert!(parts[6].is_stmt());
+        assert_eq!(parts[6].as_stmt(), call_stmt("e").as_stmt());
+        if let Program::Mod(parts, _) = &mut program {
+            *parts[6].as_stmt_mut().unwrap() = Stmt::Empty;
+            parts[0].as_dir_mut().unwrap().dir = Cow::Borrowed("use asm");
+            assert!(parts[1].as_decl_mut().is_some());
+        }
+        assert_eq!(program.parts()[6].as_stmt(), Some(&Stmt::Empty));
+        assert_eq!(program.directives().next().unwrap().dir, "use asm");
+    }
+
+    #[test]
+    fn directive_prologue() {
+        // 'use strict'; 'use asm'; a(); b();
+        let program = Program::script(vec![
+            dir("use strict"),
+            dir("use asm"),
+            call_stmt("a"),
+            call_stmt("b"),
+        ]);
+        let dirs: Vec<&str> = program.directives().map(|d| d.dir.as_ref()).collect();
+        assert_eq!(dirs, vec!["use strict", "use asm"]);
+        assert_eq!(program.body(), &[call_stmt("a"), call_stmt("b")][..]);
+        assert!(program.is_strict());
+        let body = FuncBody(program.parts().to_vec());
+        assert_eq!(body.directives().count(), 2);
+        assert_eq!(body.body().len(), 2);
+    }
+
+    #[test]
+    fn strings_after_code_are_not_directives() {
+        // a(); 'use strict';
+        let late = ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::single_string_from(
+            "use strict",
+        ))));
+        let program = Program::script(vec![call_stmt("a"), late]);
+        assert_eq!(program.directives().count(), 0);
+        assert_eq!(program.body().len(), 2);
+        assert!(!program.is_strict());
+        // even a `Dir` is ignored once the prologue has ended
+        let program = Program::script(vec![call_stmt("a"), dir("use strict")]);
+        assert_eq!(program.directives().count(), 0);
+        assert!(!program.is_strict());
+        assert!(Program::module(vec![call_stmt("a")]).is_strict());
+    }
+
+    #[test]
+    fn hashbang_only_on_first_line() {
+        assert_eq!(Program::hashbang_from("console.log('hi')"), None);
+        assert_eq!(Program::hashbang_from("\n#!/usr/bin/env node"), None);
+        assert_eq!(Program::hashbang_from("class C { #!x }"), None);
+        assert_eq!(Program::hashbang_from("#!node"), Some(Cow::Borrowed("node")));
+    }
+
+    #[test]
+    fn static_blocks_keep_order() {
+        // class C { static { first() } static x = 1; static { second() } }
+        let field = FieldDef {
+            is_static: true,
+            ..FieldDef::new(
+                PropKey::Expr(Expr::ident_from("x")),
+                Some(Expr::Lit(Lit::number_from("1"))),
+            )
+        };
+        let class = Class::new(
+            Some(Ident::from("C")),
+            None,
+            vec![
+                ClassMember::StaticBlock(BlockStmt(vec![call_stmt("first")])),
+                ClassMember::Field(field.clone()),
+                ClassMember::StaticBlock(BlockStmt(vec![call_stmt("second")])),
+            ],
+        );
+        assert_eq!(class.body.0.len(), 3);
+        assert_eq!(
+            class.body.0[0],
+            ClassMember::StaticBlock(BlockStmt(vec![call_stmt("first")]))
+        );
+        assert_eq!(class.body.0[1], ClassMember::Field(field));
+        assert_eq!(
+            class.body.0[2],
+            ClassMember::StaticBlock(BlockStmt(vec![call_stmt("second")]))
+        );
+    }
+
+    /// A decorated method of `kind`, or a field if there is no kind
+    fn decorated<'a>(
+        name: &'a str,
+        kind: Option<MethodKind>,
+        decorator: Expr<'a>,
+    ) -> ClassMember<'a> {
+        let key = PropKey::Expr(Expr::ident_from(name));
+        let decorators = vec![Decorator {
+            expression: decorator,
+        }];
+        match kind {
+            Some(kind) => ClassMember::Method(MethodDef {
+                decorators,
+                ..MethodDef::new(key, Func::new(None, vec![], FuncBody(vec![]), false, false), kind)
+            }),
+            None => ClassMember::Field(FieldDef {
+                decorators,
+                ..FieldDef::new(key, None)
+            }),
+        }
+    }
+
+    #[test]
+    fn decorators() {
+        // @ns.dec(arg)
+        let ns_dec = Expr::Call(crate::expr::CallExpr {
+            callee: Box::new(Expr::Member(crate::expr::MemberExpr {
+                object: Box::new(Expr::ident_from("ns")),
+                property: crate::expr::MemberProp::ident_from("dec"),
+                optional: false,
+            })),
+            arguments: vec![Expr::ident_from("arg")],
+            optional: false,
+        });
+        // @sealed
+        // class C {
+        //     @logged method() {}
+        //     @ns.dec(arg) field;
+        //     @logged get thing() {}
+        // }
+        let mut class = Class::new(
+            Some(Ident::from("C")),
+            None,
+            vec![
+                decorated("method", Some(MethodKind::Method), Expr::ident_from("logged")),
+                decorated("field", None, ns_dec.clone()),
+                decorated("thing", Some(MethodKind::Get), Expr::ident_from("logged")),
+            ],
+        );
+        assert!(class.decorators.is_empty());
+        class.decorators.push(Decorator {
+            expression: Expr::ident_from("sealed"),
+        });
+        assert_eq!(class.decorators[0].expression, Expr::ident_from("sealed"));
+        let member_decorators: Vec<(Option<MethodKind>, &Expr)> = class
+            .body
+            .0
+            .iter()
+            .map(|m| {
+                let kind = match m {
+                    ClassMember::Method(method) => Some(method.kind),
+                    _ => None,
+                };
+                (kind, &m.decorators()[0].expression)
+            })
+            .collect();
+        assert_eq!(
+            member_decorators,
+            vec![
+                (Some(MethodKind::Method), &Expr::ident_from("logged")),
+                (None, &ns_dec),
+                (Some(MethodKind::Get), &Expr::ident_from("logged")),
+            ]
+        );
+    }
+
+    #[test]
+    fn class_member_kinds() {
+        // class C {
+        //     constructor() {}
+        //     static [key] = 1;
+        //     #count;
+        //     static {}
+        // }
+        let body = || FuncBody(vec![]);
+        let ctor = ClassMember::Method(MethodDef::new(
+            PropKey::Expr(Expr::ident_from("constructor")),
+            Func::new(None, vec![], body(), false, false),
+            MethodKind::Constructor,
+        ));
+        let computed = ClassMember::Field(FieldDef {
+            computed: true,
+            is_static: true,
+            ..FieldDef::new(
+                PropKey::Expr(Expr::ident_from("key")),
+                Some(Expr::Lit(Lit::number_from("1"))),
+            )
+        });
+        let private = ClassMember::Field(FieldDef::new(
+            PropKey::Private(expr::PrivateId::from("count")),
+            None,
+        ));
+        let block = ClassMember::StaticBlock(BlockStmt(vec![]));
+        let flags: Vec<(bool, bool)> = [&ctor, &computed, &private, &block]
+            .iter()
+            .map(|m| (m.is_static(), m.is_computed()))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![(false, false), (true, true), (false, false), (true, false)]
+        );
+        assert_eq!(
+            computed.key(),
+            Some(&PropKey::Expr(Expr::ident_from("key")))
+        );
+        assert_eq!(block.key(), None);
+        assert!(block.decorators().is_empty());
+    }
+
+    #[test]
+    fn empty_defaults() {
+        assert_eq!(Program::default(), Program::Script(vec![], None));
+        assert_eq!(FuncBody::default(), FuncBody(vec![]));
+        assert_eq!(ClassBody::default(), ClassBody(vec![]));
+        assert_eq!(stmt::BlockStmt::default(), stmt::BlockStmt(vec![]));
+        assert!(expr::ObjExpr::default().is_empty());
+        assert!(expr::ArrayExpr::default().is_empty());
+        let mut program = Program::default();
+        if let Program::Script(parts, _) = &mut program {
+            parts.push(ProgramPart::Stmt(stmt::Stmt::Empty));
+        }
+        assert_eq!(program.parts().len(), 1);
+    }
+
+    #[test]
+    fn directive_values() {
+        let strict = Dir::from_str("use strict");
+        assert_eq!(strict.value(), "use strict");
+        assert!(strict.is_use_strict());
+        assert_eq!(strict.expr, Lit::single_string_from("use strict"));
+        // 'use\u0020strict' is a directive but not `use strict`
+        let escaped = Dir::from_str("use\\u0020strict");
+        assert_eq!(escaped.value(), "use\\u0020strict");
+        assert!(!escaped.is_use_strict());
+        assert!(!Program::script(vec![ProgramPart::Dir(escaped)]).is_strict());
+        let quoted = Dir::from_str("it's");
+        assert_eq!(quoted.expr, Lit::double_string_from("it's"));
+    }
+
+    #[test]
+    fn operator_precedence() {
+        assert!(BinaryOp::Times.precedence() > BinaryOp::Plus.precedence());
+        assert!(BinaryOp::PowerOf.precedence() > BinaryOp::Times.precedence());
+        assert!(UnaryOp::Minus.precedence() > BinaryOp::PowerOf.precedence());
+        assert!(LogicalOp::And.precedence() > LogicalOp::Or.precedence());
+        assert!(BinaryOp::Or.precedence() > LogicalOp::And.precedence());
+        assert!(AssignOp::PlusEqual.precedence() < LogicalOp::NullishCoalescing.precedence());
+        assert_eq!(BinaryOp::PowerOf.associativity(), Associativity::Right);
+        assert_eq!(BinaryOp::Minus.associativity(), Associativity::Left);
+        assert_eq!(AssignOp::Equal.associativity(), Associativity::Right);
+        let nullish = LogicalOp::NullishCoalescing;
+        assert!(nullish.can_mix_with(nullish));
+        assert!(!nullish.can_mix_with(LogicalOp::And));
+        assert!(!LogicalOp::Or.can_mix_with(nullish));
+        assert!(LogicalOp::Or.can_mix_with(LogicalOp::And));
+    }
+
+    #[test]
+    fn classify_directives() {
+        let cases = vec![
+            ("use strict", KnownDirective::UseStrict),
+            ("use asm", KnownDirective::UseAsm),
+            ("use client", KnownDirective::Other(Cow::Borrowed("use client"))),
+            ("use\\u0020asm", KnownDirective::Other(Cow::Borrowed("use\\u0020asm"))),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(Dir::from_str(text).classify(), expected);
+        }
+    }
+
+    #[test]
+    fn ident_validity() {
+        for name in &["a", "$", "_private", "camelCase1", "café", "\\u0061b", "\\u{62}"] {
+            assert!(Ident::from(*name).is_valid(), "{}", name);
+        }
+        // combining marks (Mn, Mc) and connector punctuation (Pc) can follow
+        // the first character but not start a name
+        for name in &["e\u{301}", "\u{915}\u{94D}\u{937}", "a\u{203F}b", "\u{915}\u{93E}"] {
+            assert!(Ident::from(*name).is_valid(), "{}", name);
+        }
+        for name in &["\u{301}e", "\u{203F}a"] {
+            assert!(!Ident::from(*name).is_valid(), "{}", name);
+        }
+        // a space, a leading digit, an empty name, a bad escape and a
+        // numeric character that isn't ID_Continue
+        for name in &["my name", "1a", "", "a-b", "\\u00", "\\x61", "a\u{b2}"] {
+            assert!(!Ident::from(*name).is_valid(), "{}", name);
+        }
+        let class = Ident::from("class");
+        assert!(!class.is_valid());
+        assert!(class.is_reserved(false) && class.is_reserved(true));
+        // an escaped reserved word is still reserved
+        assert!(Ident::from("cl\\u0061ss").is_reserved(false));
+        let yield_ = Ident::from("yield");
+        assert!(yield_.is_valid());
+        assert!(!yield_.is_reserved(false));
+        assert!(yield_.is_reserved(true));
+        assert!(!Ident::from("await").is_reserved(true));
+    }
+
+    #[test]
+    fn param_kinds() {
+        // function f(a, { b }, c = 1, ...d) {}
+        let params = vec![
+            FuncArg::Pat(Pat::ident_from("a")),
+            FuncArg::Pat(Pat::Obj(vec![])),
+            FuncArg::Pat(Pat::Assign(pat::AssignPat {
+                left: Box::new(Pat::ident_from("c")),
+                right: Box::new(Expr::Lit(Lit::number_from("1"))),
+            })),
+            FuncArg::Pat(Pat::RestElement(Box::new(Pat::ident_from("d")))),
+        ];
+        let kinds: Vec<_> = params.iter().map(FuncArg::kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some(ParamKind::Simple),
+                Some(ParamKind::Pattern),
+                Some(ParamKind::Default),
+                Some(ParamKind::Rest),
+            ]
+        );
+        let func = Func::new(None, params, FuncBody(vec![]), false, false);
+        assert_eq!(func.validate_params(), Ok(()));
+        // (...a, b) => {}
+        let arrow = expr::ArrowFuncExpr::new(
+            vec![
+                FuncArg::Expr(Expr::Spread(Box::new(Expr::ident_from("a")))),
+                FuncArg::Expr(Expr::ident_from("b")),
+            ],
+            expr::ArrowFuncBody::FuncBody(FuncBody(vec![])),
+            false,
+        );
+        assert_eq!(arrow.validate_params(), Err(ParamError::RestNotLast(0)));
+        // function f(this) {}
+        let func = Func::new(None, vec![FuncArg::Expr(Expr::This)], FuncBody(vec![]), false, false);
+        assert_eq!(func.validate_params(), Err(ParamError::InvalidParam(0)));
+    }
+
+    #[test]
+    fn source_types() {
+        // 'use strict'; a;
+        let script = Program::Script(
+            vec![
+                ProgramPart::Dir(Dir {
+                    expr: Lit::single_string_from("use strict"),
+                    dir: Cow::Borrowed("use strict"),
+                }),
+                ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a"))),
+            ],
+            Some(Cow::Borrowed("/usr/bin/env node")),
+        );
+        assert!(script.is_script() && !script.is_module());
+        assert_eq!(script.check_script(), Ok(()));
+        let module = script.clone().into_module();
+        assert_eq!(module.source_type(), SourceType::Module);
+        assert_eq!(module.parts(), script.parts());
+        assert_eq!(module.hashbang(), Some("/usr/bin/env node"));
+        assert_eq!(module.into_script(), Ok(script));
+        // a; import b from 'b';
+        let import = Program::module(vec![
+            ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a"))),
+            ProgramPart::Decl(Decl::Import(Box::new(decl::ModImport {
+                specifiers: vec![decl::ImportSpecifier::Default(Ident::from("b"))],
+                source: Lit::single_string_from("b"),
+                attributes: vec![],
+                attributes_keyword: None,
+            }))),
+        ]);
+        assert_eq!(import.into_script(), Err(ScriptError::ImportExport(1)));
+        // f(import.meta);
+        let meta = Program::module(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Call(
+            expr::CallExpr::new(
+                Expr::ident_from("f"),
+                vec![Expr::MetaProp(expr::MetaProp::import_meta())],
+            ),
+        )))]);
+        assert_eq!(meta.check_script(), Err(ScriptError::ImportMeta));
+        // await a;
+        let tla = Program::module(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Await(Box::new(
+            Expr::ident_from("a"),
+        ))))]);
+        assert_eq!(tla.into_script(), Err(ScriptError::TopLevelAwait));
+    }
+
+    #[test]
+    fn func_body_insertion() {
+        // function f() { 'use strict'; a(); }
+        let mut body = FuncBody(vec![dir("use strict"), call_stmt("a")]);
+        body.prepend_stmt(call_stmt("b"));
+        body.append_stmt(call_stmt("c"));
+        body.insert_stmt(1, call_stmt("d"));
+        assert_eq!(
+            body.0,
+            vec![
+                dir("use strict"),
+                call_stmt("b"),
+                call_stmt("d"),
+                call_stmt("a"),
+                call_stmt("c"),
+            ]
+        );
+        assert_eq!(body.directives().count(), 1);
+        // function f() { a(); 'use strict'; }
+        let mut late = FuncBody(vec![call_stmt("a"), dir("use strict")]);
+        late.prepend_stmt(call_stmt("b"));
+        assert_eq!(late.0[0], call_stmt("b"));
+        late.insert_stmt(3, call_stmt("c"));
+        assert_eq!(late.0[3], call_stmt("c"));
+    }
+}
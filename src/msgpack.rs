@@ -0,0 +1,34 @@
+//! A compact binary encoding of a `Program` using MessagePack
+//!
+//! This uses the same serde derives as the `serialization` feature,
+//! so the encoded tree mirrors the derived JSON shape, just smaller.
+//! Structs are written as maps with their field names which keeps
+//! the output readable by other MessagePack tooling.
+//!
+//! The `esprima` feature replaces those derives with ones that can't
+//! read back what they write, this module isn't available when it is
+//! enabled.
+use crate::Program;
+
+/// The error returned when bytes can't be decoded into a `Program`
+pub use rmp_serde::decode::Error;
+
+/// Encode `program` as MessagePack
+/// ```
+/// # use resast::prelude::*;
+/// use resast::msgpack::{from_msgpack, to_msgpack};
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))]);
+/// let bytes = to_msgpack(&program);
+/// assert_eq!(from_msgpack(&bytes).unwrap(), program);
+/// ```
+pub fn to_msgpack(program: &Program) -> Vec<u8> {
+    // writing to a `Vec` can't fail and every node serializes
+    // to a supported msgpack type
+    rmp_serde::to_vec_named(program).expect("a program is always valid msgpack")
+}
+
+/// Decode a `Program` written by `to_msgpack`, every string in the
+/// result is owned so it doesn't borrow from `bytes`
+pub fn from_msgpack(bytes: &[u8]) -> Result<Program<'static>, Error> {
+    rmp_serde::from_slice(bytes)
+}
@@ -0,0 +1,736 @@
+//! A generic handle to the main node types
+//!
+//! `children` yields the nearest descendants of a node that are
+//! one of the `NodeRef` kinds, the nodes in between (`VarDecl`,
+//! `Prop`, `SwitchCase`, etc) are looked through. This is handy
+//! for quick queries where a full `Visitor` would be overkill.
+//! `descendants` goes all the way down the tree and can pick out
+//! a single node type, e.g. `program.descendants::<CallExpr>()`.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::node::NodeRef;
+//!
+//! let expr = Expr::ident_from("a");
+//! let stmt = Stmt::Expr(expr.clone());
+//! let children: Vec<_> = stmt.children().collect();
+//! assert_eq!(children, vec![NodeRef::Expr(&expr)]);
+//! let idents: Vec<&Ident> = stmt.descendants().collect();
+//! assert_eq!(idents, vec![&Ident::from("a")]);
+//! ```
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// A reference to one of the main node types
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeRef<'r, 'a> {
+    Program(&'r Program<'a>),
+    Decl(&'r Decl<'a>),
+    Stmt(&'r Stmt<'a>),
+    Expr(&'r Expr<'a>),
+    Pat(&'r Pat<'a>),
+    Func(&'r Func<'a>),
+    Class(&'r Class<'a>),
+    Ident(&'r Ident<'a>),
+    Lit(&'r Lit<'a>),
+}
+
+impl<'r, 'a> NodeRef<'r, 'a> {
+    /// The nearest descendants of this node
+    pub fn children(&self) -> impl Iterator<Item = NodeRef<'r, 'a>> {
+        let mut children = Children::default();
+        match *self {
+            NodeRef::Program(program) => children.program(program),
+            NodeRef::Decl(decl) => children.decl(decl),
+            NodeRef::Stmt(stmt) => children.stmt(stmt),
+            NodeRef::Expr(expr) => children.expr(expr),
+            NodeRef::Pat(pat) => children.pat(pat),
+            NodeRef::Func(func) => children.func(func),
+            NodeRef::Class(class) => children.class(class),
+            NodeRef::Ident(_) => {}
+            NodeRef::Lit(lit) => children.lit(lit),
+        }
+        children.0.into_iter()
+    }
+
+    /// Every descendant of this node, depth first and
+    /// in source order
+    pub fn descendants(&self) -> Descendants<'r, 'a> {
+        let mut stack: Vec<_> = self.children().collect();
+        stack.reverse();
+        Descendants { stack }
+    }
+
+    pub fn as_expr(&self) -> Option<&'r Expr<'a>> {
+        match self {
+            NodeRef::Expr(expr) => Some(expr),
+            _ => None,
+        }
+    }
+
+    pub fn as_stmt(&self) -> Option<&'r Stmt<'a>> {
+        match self {
+            NodeRef::Stmt(stmt) => Some(stmt),
+            _ => None,
+        }
+    }
+
+    pub fn as_pat(&self) -> Option<&'r Pat<'a>> {
+        match self {
+            NodeRef::Pat(pat) => Some(pat),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over every descendant of a node, see
+/// [`NodeRef::descendants`]
+pub struct Descendants<'r, 'a> {
+    stack: Vec<NodeRef<'r, 'a>>,
+}
+
+impl<'r, 'a> Iterator for Descendants<'r, 'a> {
+    type Item = NodeRef<'r, 'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let start = self.stack.len();
+        self.stack.extend(node.children());
+        self.stack[start..].reverse();
+        Some(node)
+    }
+}
+
+/// The descendants of a node that are a `T`
+pub type DescendantsOf<'r, 'a, T> =
+    std::iter::FilterMap<Descendants<'r, 'a>, fn(NodeRef<'r, 'a>) -> Option<&'r T>>;
+
+/// A node type that can be picked out of the
+/// descendants of another node
+pub trait FromNode<'r, 'a>: Sized {
+    fn from_node(node: NodeRef<'r, 'a>) -> Option<&'r Self>;
+}
+
+macro_rules! impl_from_node {
+    ($($ty:ident => $pat:pat => $node:ident),* $(,)?) => {
+        $(
+            impl<'r, 'a> FromNode<'r, 'a> for $ty<'a> {
+                fn from_node(node: NodeRef<'r, 'a>) -> Option<&'r Self> {
+                    match node {
+                        $pat => Some($node),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_node!(
+    Decl => NodeRef::Decl(decl) => decl,
+    Stmt => NodeRef::Stmt(stmt) => stmt,
+    Expr => NodeRef::Expr(expr) => expr,
+    Pat => NodeRef::Pat(pat) => pat,
+    Func => NodeRef::Func(func) => func,
+    Class => NodeRef::Class(class) => class,
+    Ident => NodeRef::Ident(ident) => ident,
+    Lit => NodeRef::Lit(lit) => lit,
+    CallExpr => NodeRef::Expr(Expr::Call(call)) => call,
+    NewExpr => NodeRef::Expr(Expr::New(new)) => new,
+    MemberExpr => NodeRef::Expr(Expr::Member(member)) => member,
+);
+
+macro_rules! impl_children {
+    ($($ty:ident),*) => {
+        $(
+            impl<'a> $ty<'a> {
+                /// The nearest descendants of this node, see the
+                /// [`node`](crate::node) module for details
+                pub fn children(&self) -> impl Iterator<Item = NodeRef<'_, 'a>> {
+                    NodeRef::$ty(self).children()
+                }
+
+                /// Every descendant of this node that is a `T`
+                pub fn descendants<'r, T>(&'r self) -> DescendantsOf<'r, 'a, T>
+                where
+                    T: FromNode<'r, 'a> + 'r,
+                {
+                    NodeRef::$ty(self).descendants().filter_map(T::from_node)
+                }
+
+                /// Every call expression inside of this node
+                pub fn descendant_calls(&self) -> impl Iterator<Item = &CallExpr<'a>> {
+                    self.descendants()
+                }
+
+                /// Every identifier inside of this node, including
+                /// property names and labels
+                pub fn descendant_idents(&self) -> impl Iterator<Item = &Ident<'a>> {
+                    self.descendants()
+                }
+
+                /// Every member expression inside of this node
+                pub fn descendant_members(&self) -> impl Iterator<Item = &MemberExpr<'a>> {
+                    self.descendants()
+                }
+
+                /// Every function declaration inside of this node,
+                /// function expressions and methods are skipped
+                pub fn descendant_func_decls(&self) -> impl Iterator<Item = &Func<'a>> {
+                    self.descendants().filter_map(|decl| match decl {
+                        Decl::Func(func) => Some(func),
+                        _ => None,
+                    })
+                }
+            }
+
+            impl<'r, 'a> From<&'r $ty<'a>> for NodeRef<'r, 'a> {
+                fn from(node: &'r $ty<'a>) -> Self {
+                    NodeRef::$ty(node)
+                }
+            }
+        )*
+    };
+}
+
+impl_children!(Program, Decl, Stmt, Expr, Pat, Func, Class, Lit);
+
+impl<'r, 'a> From<&'r Ident<'a>> for NodeRef<'r, 'a> {
+    fn from(node: &'r Ident<'a>) -> Self {
+        NodeRef::Ident(node)
+    }
+}
+
+/// Collects the children of a node, looking through
+/// any of the types that aren't represented in `NodeRef`
+#[derive(Default)]
+struct Children<'r, 'a>(Vec<NodeRef<'r, 'a>>);
+
+impl<'r, 'a> Children<'r, 'a> {
+    fn push(&mut self, node: impl Into<NodeRef<'r, 'a>>) {
+        self.0.push(node.into());
+    }
+
+    fn program(&mut self, program: &'r Program<'a>) {
+        match program {
+            Program::Mod(parts, _) | Program::Script(parts, _) => self.parts(parts),
+        }
+    }
+
+    fn parts(&mut self, parts: &'r [ProgramPart<'a>]) {
+        for part in parts {
+            match part {
+                ProgramPart::Dir(dir) => self.push(&dir.expr),
+                ProgramPart::Decl(decl) => self.push(decl),
+                ProgramPart::Stmt(stmt) => self.push(stmt),
+            }
+        }
+    }
+
+    fn decl(&mut self, decl: &'r Decl<'a>) {
+        match decl {
+            Decl::Var(_, decls) => self.var_decls(decls),
+            Decl::Func(func) => self.push(func),
+            Decl::Class(class) => self.push(class),
+            Decl::Import(import) => {
+                for spec in &import.specifiers {
+                    match spec {
+                        ImportSpecifier::Normal(normal) => {
+                            self.module_export_name(&normal.imported);
+                            self.push(&normal.local);
+                        }
+                        ImportSpecifier::Default(ident) | ImportSpecifier::Namespace(ident) => {
+                            self.push(ident)
+                        }
+                    }
+                }
+                self.push(&import.source);
+                self.import_attributes(&import.attributes);
+            }
+            Decl::Export(export) => match &**export {
+                ModExport::Default(DefaultExportDecl::Decl(decl))
+                | ModExport::Named(NamedExportDecl::Decl(decl)) => self.push(decl),
+                ModExport::Default(DefaultExportDecl::Expr(expr)) => self.push(expr),
+                ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                    for spec in specs {
+                        self.push(&spec.local);
+                        self.module_export_name(&spec.exported);
+                    }
+                    if let Some(source) = source {
+                        self.export_source(source);
+                    }
+                }
+                ModExport::All(alias, source) => {
+                    if let Some(alias) = alias {
+                        self.module_export_name(alias);
+                    }
+                    self.export_source(source);
+                }
+            },
+        }
+    }
+
+    fn var_decls(&mut self, decls: &'r [VarDecl<'a>]) {
+        for decl in decls {
+            self.push(&decl.id);
+            if let Some(init) = &decl.init {
+                self.push(init);
+            }
+        }
+    }
+
+    fn module_export_name(&mut self, name: &'r ModuleExportName<'a>) {
+        match name {
+            ModuleExportName::Ident(ident) => self.push(ident),
+            ModuleExportName::Str(lit) => self.push(lit),
+        }
+    }
+
+    fn import_attributes(&mut self, attrs: &'r [ImportAttribute<'a>]) {
+        for attr in attrs {
+            match &attr.key {
+                ImportAttributeKey::Ident(ident) => self.push(ident),
+                ImportAttributeKey::Lit(lit) => self.push(lit),
+            }
+            self.push(&attr.value);
+        }
+    }
+
+    fn export_source(&mut self, source: &'r ExportSource<'a>) {
+        self.push(&source.source);
+        self.import_attributes(&source.attributes);
+    }
+
+    fn func(&mut self, func: &'r Func<'a>) {
+        if let Some(id) = &func.id {
+            self.push(id);
+        }
+        self.func_args(&func.params);
+        self.parts(&func.body.0);
+    }
+
+    fn func_args(&mut self, args: &'r [FuncArg<'a>]) {
+        for arg in args {
+            match arg {
+                FuncArg::Expr(expr) => self.push(expr),
+                FuncArg::Pat(pat) => self.push(pat),
+            }
+        }
+    }
+
+    fn class(&mut self, class: &'r Class<'a>) {
+        self.decorators(&class.decorators);
+        if let Some(id) = &class.id {
+            self.push(id);
+        }
+        if let Some(super_class) = &class.super_class {
+            self.push(&**super_class);
+        }
+        for member in &class.body.0 {
+            match member {
+                ClassMember::Method(method) => {
+                    self.decorators(&method.decorators);
+                    self.prop_key(&method.key);
+                    self.push(&method.value);
+                }
+                ClassMember::Field(field) => {
+                    self.decorators(&field.decorators);
+                    self.prop_key(&field.key);
+                    if let Some(value) = &field.value {
+                        self.push(value);
+                    }
+                }
+                ClassMember::StaticBlock(block) => self.parts(&block.0),
+            }
+        }
+    }
+
+    fn decorators(&mut self, decorators: &'r [Decorator<'a>]) {
+        for decorator in decorators {
+            self.push(&decorator.expression);
+        }
+    }
+
+    fn prop_key(&mut self, key: &'r PropKey<'a>) {
+        match key {
+            PropKey::Lit(lit) => self.push(lit),
+            PropKey::Expr(expr) => self.push(expr),
+            PropKey::Pat(pat) => self.push(pat),
+            PropKey::Private(_) => {}
+        }
+    }
+
+    fn prop(&mut self, prop: &'r Prop<'a>) {
+        self.decorators(&prop.decorators);
+        self.prop_key(&prop.key);
+        match &prop.value {
+            PropValue::Expr(expr) => self.push(expr),
+            PropValue::Pat(pat) => self.push(pat),
+            PropValue::None => {}
+        }
+    }
+
+    fn stmt(&mut self, stmt: &'r Stmt<'a>) {
+        match stmt {
+            Stmt::Expr(expr) | Stmt::Throw(expr) => self.push(expr),
+            Stmt::Block(block) => self.parts(&block.0),
+            Stmt::Empty | Stmt::Debugger => {}
+            Stmt::With(with) => {
+                self.push(&with.object);
+                self.push(&*with.body);
+            }
+            Stmt::Return(arg) => {
+                if let Some(arg) = arg {
+                    self.push(arg);
+                }
+            }
+            Stmt::Labeled(labeled) => {
+                self.push(&labeled.label);
+                self.push(&*labeled.body);
+            }
+            Stmt::Break(label) | Stmt::Continue(label) => {
+                if let Some(label) = label {
+                    self.push(label);
+                }
+            }
+            Stmt::If(stmt) => {
+                self.push(&stmt.test);
+                self.push(&*stmt.consequent);
+                if let Some(alt) = &stmt.alternate {
+                    self.push(&**alt);
+                }
+            }
+            Stmt::Switch(stmt) => {
+                self.push(&stmt.discriminant);
+                for case in &stmt.cases {
+                    if let Some(test) = &case.test {
+                        self.push(test);
+                    }
+                    self.parts(&case.consequent);
+                }
+            }
+            Stmt::Try(stmt) => {
+                self.parts(&stmt.block.0);
+                if let Some(handler) = &stmt.handler {
+                    if let Some(param) = &handler.param {
+                        self.push(param);
+                    }
+                    self.parts(&handler.body.0);
+                }
+                if let Some(finalizer) = &stmt.finalizer {
+                    self.parts(&finalizer.0);
+                }
+            }
+            Stmt::While(stmt) => {
+                self.push(&stmt.test);
+                self.push(&*stmt.body);
+            }
+            Stmt::DoWhile(stmt) => {
+                self.push(&*stmt.body);
+                self.push(&stmt.test);
+            }
+            Stmt::For(stmt) => {
+                match &stmt.init {
+                    Some(LoopInit::Variable(_, decls)) => self.var_decls(decls),
+                    Some(LoopInit::Expr(expr)) => self.push(expr),
+                    None => {}
+                }
+                if let Some(test) = &stmt.test {
+                    self.push(test);
+                }
+                if let Some(update) = &stmt.update {
+                    self.push(update);
+                }
+                self.push(&*stmt.body);
+            }
+            Stmt::ForIn(stmt) => {
+                self.loop_left(&stmt.left);
+                self.push(&stmt.right);
+                self.push(&*stmt.body);
+            }
+            Stmt::ForOf(stmt) => {
+                self.loop_left(&stmt.left);
+                self.push(&stmt.right);
+                self.push(&*stmt.body);
+            }
+            Stmt::Var(decls) => self.var_decls(decls),
+        }
+    }
+
+    fn loop_left(&mut self, left: &'r LoopLeft<'a>) {
+        match left {
+            LoopLeft::Expr(expr) => self.push(expr),
+            LoopLeft::Variable(_, decl) => self.var_decls(std::slice::from_ref(decl)),
+            LoopLeft::Pat(pat) => self.push(pat),
+        }
+    }
+
+    fn expr(&mut self, expr: &'r Expr<'a>) {
+        match expr {
+            Expr::Array(elements) => {
+                for el in elements.iter().flatten() {
+                    self.push(el);
+                }
+            }
+            Expr::ArrowFunc(arrow) => {
+                if let Some(id) = &arrow.id {
+                    self.push(id);
+                }
+                self.func_args(&arrow.params);
+                match &arrow.body {
+                    ArrowFuncBody::FuncBody(body) => self.parts(&body.0),
+                    ArrowFuncBody::Expr(expr) => self.push(&**expr),
+                }
+            }
+            Expr::ArrowParamPlaceHolder(args, _) => self.func_args(args),
+            Expr::Assign(assign) => {
+                match &assign.left {
+                    AssignLeft::Pat(pat) => self.push(pat),
+                    AssignLeft::Expr(expr) => self.push(&**expr),
+                }
+                self.push(&*assign.right);
+            }
+            Expr::Await(expr) | Expr::OptionalChain(expr) | Expr::Spread(expr) => {
+                self.push(&**expr)
+            }
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(expr) => self.push(&**expr),
+            Expr::Binary(BinaryExpr { left, right, .. })
+            | Expr::Logical(LogicalExpr { left, right, .. }) => {
+                self.push(&**left);
+                self.push(&**right);
+            }
+            Expr::Class(class) => self.push(class),
+            Expr::Call(call) => {
+                self.push(&*call.callee);
+                for arg in &call.arguments {
+                    self.push(arg);
+                }
+            }
+            Expr::Conditional(cond) => {
+                self.push(&*cond.test);
+                self.push(&*cond.consequent);
+                self.push(&*cond.alternate);
+            }
+            Expr::Func(func) => self.push(func),
+            Expr::Ident(ident) => self.push(ident),
+            Expr::Import(import) => {
+                self.push(&*import.source);
+                if let Some(options) = &import.options {
+                    self.push(&**options);
+                }
+            }
+            Expr::Lit(lit) => self.push(lit),
+            Expr::Member(member) => {
+                self.push(&*member.object);
+                match &member.property {
+                    MemberProp::Ident(ident) => self.push(ident),
+                    MemberProp::Private(_) => {}
+                    MemberProp::Computed(expr) => self.push(&**expr),
+                }
+            }
+            Expr::MetaProp(meta) => {
+                self.push(&meta.meta);
+                self.push(&meta.property);
+            }
+            Expr::PrivateId(_) | Expr::Super | Expr::This => {}
+            Expr::New(new) => {
+                self.push(&*new.callee);
+                for arg in new.args() {
+                    self.push(arg);
+                }
+            }
+            Expr::Obj(props) => {
+                for prop in props {
+                    match prop {
+                        ObjProp::Prop(prop) => self.prop(prop),
+                        ObjProp::Spread(expr) => self.push(expr),
+                    }
+                }
+            }
+            Expr::Sequence(exprs) => {
+                for expr in exprs {
+                    self.push(expr);
+                }
+            }
+            Expr::TaggedTemplate(tagged) => {
+                self.push(&*tagged.tag);
+                self.template(&tagged.quasi);
+            }
+            Expr::Unary(unary) => self.push(&*unary.argument),
+            Expr::Update(update) => self.push(&*update.argument),
+            Expr::Yield(y) => {
+                if let Some(arg) = &y.argument {
+                    self.push(&**arg);
+                }
+            }
+        }
+    }
+
+    fn lit(&mut self, lit: &'r Lit<'a>) {
+        if let Lit::Template(template) = lit {
+            self.template(template);
+        }
+    }
+
+    fn template(&mut self, template: &'r TemplateLit<'a>) {
+        for expr in &template.expressions {
+            self.push(expr);
+        }
+    }
+
+    fn pat(&mut self, pat: &'r Pat<'a>) {
+        match pat {
+            Pat::Ident(ident) => self.push(ident),
+            Pat::Obj(parts) => {
+                for part in parts {
+                    match part {
+                        ObjPatPart::Assign(prop) => self.prop(prop),
+                        ObjPatPart::Rest(pat) => self.push(&**pat),
+                    }
+                }
+            }
+            Pat::Array(parts) => {
+                for part in parts.iter().flatten() {
+                    match part {
+                        ArrayPatPart::Pat(pat) => self.push(pat),
+                        ArrayPatPart::Expr(expr) => self.push(expr),
+                    }
+                }
+            }
+            Pat::RestElement(pat) => self.push(&**pat),
+            Pat::Assign(assign) => {
+                self.push(&*assign.left);
+                self.push(&*assign.right);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn call<'a>(callee: Expr<'a>, arguments: Vec<Expr<'a>>) -> Expr<'a> {
+        Expr::Call(CallExpr {
+            callee: Box::new(callee),
+            arguments,
+            optional: false,
+        })
+    }
+
+    #[test]
+    fn descendant_exprs() {
+        // f(a.b, g(1))
+        let member = Expr::Member(MemberExpr {
+            object: Box::new(Expr::ident_from("a")),
+            property: MemberProp::ident_from("b"),
+            optional: false,
+        });
+        let inner = call(Expr::ident_from("g"), vec![Expr::Lit(Lit::number_from("1"))]);
+        let outer = call(Expr::ident_from("f"), vec![member.clone(), inner.clone()]);
+        let mut queue: VecDeque<_> = outer.children().collect();
+        let mut exprs = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            if let Some(expr) = node.as_expr() {
+                exprs.push(expr);
+            }
+            queue.extend(node.children());
+        }
+        assert_eq!(
+            exprs,
+            vec![
+                &Expr::ident_from("f"),
+                &member,
+                &inner,
+                // `b` is a property name not an expression
+                &Expr::ident_from("a"),
+                &Expr::ident_from("g"),
+                &Expr::Lit(Lit::number_from("1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn looks_through_intermediate_nodes() {
+        // for (let i = 0; i < n; i++) {}
+        let stmt = Stmt::For(ForStmt {
+            init: Some(LoopInit::Variable(
+                VarKind::Let,
+                vec![VarDecl {
+                    id: Pat::ident_from("i"),
+                    init: Some(Expr::Lit(Lit::number_from("0"))),
+                }],
+            )),
+            test: Some(Expr::Binary(BinaryExpr {
+                operator: BinaryOp::LessThan,
+                left: Box::new(Expr::ident_from("i")),
+                right: Box::new(Expr::ident_from("n")),
+            })),
+            update: None,
+            body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+        });
+        let kinds: Vec<_> = stmt
+            .children()
+            .map(|node| match node {
+                NodeRef::Pat(_) => "pat",
+                NodeRef::Expr(_) => "expr",
+                NodeRef::Stmt(_) => "stmt",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(kinds, vec!["pat", "expr", "expr", "stmt"]);
+        let ident = Expr::ident_from("a");
+        assert_eq!(
+            ident.children().collect::<Vec<_>>(),
+            vec![NodeRef::Ident(&Ident::from("a"))]
+        );
+    }
+
+    #[test]
+    fn typed_descendants() {
+        // function f() { a.b(g()); }
+        // function h() { const i = function () {}; }
+        // f(h);
+        let body = vec![ProgramPart::Stmt(Stmt::Expr(call(
+            Expr::Member(MemberExpr {
+                object: Box::new(Expr::ident_from("a")),
+                property: MemberProp::ident_from("b"),
+                optional: false,
+            }),
+            vec![call(Expr::ident_from("g"), vec![])],
+        )))];
+        let f = Func::new(Some(Ident::from("f")), vec![], FuncBody(body), false, false);
+        let inner = Func::new(None, vec![], FuncBody(vec![]), false, false);
+        let body = vec![ProgramPart::Decl(Decl::Var(
+            VarKind::Const,
+            vec![VarDecl {
+                id: Pat::ident_from("i"),
+                init: Some(Expr::Func(inner)),
+            }],
+        ))];
+        let h = Func::new(Some(Ident::from("h")), vec![], FuncBody(body), false, false);
+        let program = Program::script(vec![
+            ProgramPart::Decl(Decl::Func(f)),
+            ProgramPart::Decl(Decl::Func(h)),
+            ProgramPart::Stmt(Stmt::Expr(call(
+                Expr::ident_from("f"),
+                vec![Expr::ident_from("h")],
+            ))),
+        ]);
+        assert_eq!(program.descendant_calls().count(), 3);
+        assert_eq!(program.descendant_members().count(), 1);
+        assert_eq!(program.descendants::<Func>().count(), 3);
+        let names: Vec<_> = program
+            .descendant_func_decls()
+            .filter_map(|func| func.id.as_ref())
+            .map(|id| &*id.name)
+            .collect();
+        assert_eq!(names, vec!["f", "h"]);
+        let idents: Vec<_> = program.descendant_idents().map(|id| &*id.name).collect();
+        assert_eq!(idents, vec!["f", "a", "b", "g", "h", "i", "f", "h"]);
+    }
+}
@@ -0,0 +1,898 @@
+//! Detaching an AST from the source text it borrows from
+//!
+//! Every node is generic over the lifetime of the text it was
+//! parsed from, `IntoOwned` converts each `Cow::Borrowed` into a
+//! `Cow::Owned` so the result is `'static` and can outlive
+//! that text. `IntoOwned::intern` instead shares one copy of each
+//! unique string through an `Interner`, the result borrows from
+//! the interner so it can outlive the source text but not the
+//! interner.
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// A conversion applied to every string in a node, producing
+/// text that lives for `'o`
+pub type StrFn<'f, 'o> = dyn FnMut(Cow<'_, str>) -> Cow<'o, str> + 'f;
+
+/// Convert a node into a copy that no longer borrows its text
+/// from the source
+pub trait IntoOwned: Sized {
+    /// This node with its text living for `'o`
+    type Owned<'o>;
+    /// Copy every string into a new allocation
+    fn into_owned(self) -> Self::Owned<'static> {
+        self.convert_strs(&mut owned_str)
+    }
+    /// Share a single copy of each unique string with every other
+    /// node interned with `interner`
+    fn intern<'i>(self, interner: &'i Interner) -> Self::Owned<'i> {
+        self.convert_strs(&mut |s| interner.intern(&s))
+    }
+    /// Replace every string with the result of `f`
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o>;
+}
+
+fn owned_str(s: Cow<str>) -> Cow<'static, str> {
+    Cow::Owned(s.into_owned())
+}
+
+/// A set of strings shared between interned nodes.
+///
+/// Each unique string is copied into the interner the first time
+/// it is seen and every interned node borrows that copy, so the
+/// strings are freed when the interner is dropped
+/// ```compile_fail
+/// # use resast::prelude::*;
+/// let program = {
+///     let interner = Interner::new();
+///     Program::script(vec![]).intern(&interner)
+/// };
+/// ```
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: RefCell<HashSet<Box<str>>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The shared copy of `s`
+    pub fn intern<'i>(&'i self, s: &str) -> Cow<'i, str> {
+        let mut strings = self.strings.borrow_mut();
+        if !strings.contains(s) {
+            strings.insert(Box::from(s));
+        }
+        let interned: *const str = &**strings.get(s).expect("the string was just inserted");
+        // SAFETY: the text lives in its own allocation that doesn't move
+        // when the set grows and nothing removes an entry while `self` is
+        // borrowed, so it stays valid for `'i`
+        Cow::Borrowed(unsafe { &*interned })
+    }
+    /// The number of unique strings
+    pub fn len(&self) -> usize {
+        self.strings.borrow().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.strings.borrow().is_empty()
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned<'o> = Box<T::Owned<'o>>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Box::new((*self).convert_strs(f))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned<'o> = Option<T::Owned<'o>>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        self.map(|node| node.convert_strs(f))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned<'o> = Vec<T::Owned<'o>>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        self.into_iter().map(|node| node.convert_strs(f)).collect()
+    }
+}
+
+impl<'a> IntoOwned for Ident<'a> {
+    type Owned<'o> = Ident<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Ident {
+            name: f(self.name),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Program<'a> {
+    type Owned<'o> = Program<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Program::Mod(parts, hashbang) => {
+                Program::Mod(parts.convert_strs(f), hashbang.map(&mut *f))
+            }
+            Program::Script(parts, hashbang) => {
+                Program::Script(parts.convert_strs(f), hashbang.map(&mut *f))
+            }
+        }
+    }
+}
+
+impl<'a> IntoOwned for ProgramPart<'a> {
+    type Owned<'o> = ProgramPart<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ProgramPart::Dir(dir) => ProgramPart::Dir(dir.convert_strs(f)),
+            ProgramPart::Decl(decl) => ProgramPart::Decl(decl.convert_strs(f)),
+            ProgramPart::Stmt(stmt) => ProgramPart::Stmt(stmt.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Dir<'a> {
+    type Owned<'o> = Dir<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Dir {
+            expr: self.expr.convert_strs(f),
+            dir: f(self.dir),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Func<'a> {
+    type Owned<'o> = Func<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Func {
+            id: self.id.convert_strs(f),
+            params: self.params.convert_strs(f),
+            body: self.body.convert_strs(f),
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
+}
+
+impl<'a> IntoOwned for FuncArg<'a> {
+    type Owned<'o> = FuncArg<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            FuncArg::Expr(expr) => FuncArg::Expr(expr.convert_strs(f)),
+            FuncArg::Pat(pat) => FuncArg::Pat(pat.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for FuncBody<'a> {
+    type Owned<'o> = FuncBody<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        FuncBody(self.0.convert_strs(f))
+    }
+}
+
+impl<'a> IntoOwned for Class<'a> {
+    type Owned<'o> = Class<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Class {
+            id: self.id.convert_strs(f),
+            super_class: self.super_class.convert_strs(f),
+            body: ClassBody(self.body.0.convert_strs(f)),
+            decorators: self.decorators.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ClassMember<'a> {
+    type Owned<'o> = ClassMember<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ClassMember::Method(method) => ClassMember::Method(method.convert_strs(f)),
+            ClassMember::Field(field) => ClassMember::Field(field.convert_strs(f)),
+            ClassMember::StaticBlock(block) => ClassMember::StaticBlock(block.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for MethodDef<'a> {
+    type Owned<'o> = MethodDef<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        MethodDef {
+            key: self.key.convert_strs(f),
+            value: self.value.convert_strs(f),
+            kind: self.kind,
+            computed: self.computed,
+            is_static: self.is_static,
+            decorators: self.decorators.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for FieldDef<'a> {
+    type Owned<'o> = FieldDef<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        FieldDef {
+            key: self.key.convert_strs(f),
+            value: self.value.convert_strs(f),
+            computed: self.computed,
+            is_static: self.is_static,
+            decorators: self.decorators.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Decorator<'a> {
+    type Owned<'o> = Decorator<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Decorator {
+            expression: self.expression.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Decl<'a> {
+    type Owned<'o> = Decl<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Decl::Var(kind, decls) => Decl::Var(kind, decls.convert_strs(f)),
+            Decl::Func(func) => Decl::Func(func.convert_strs(f)),
+            Decl::Class(class) => Decl::Class(class.convert_strs(f)),
+            Decl::Import(import) => Decl::Import(import.convert_strs(f)),
+            Decl::Export(export) => Decl::Export(export.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for VarDecl<'a> {
+    type Owned<'o> = VarDecl<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        VarDecl {
+            id: self.id.convert_strs(f),
+            init: self.init.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ModImport<'a> {
+    type Owned<'o> = ModImport<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        ModImport {
+            specifiers: self.specifiers.convert_strs(f),
+            source: self.source.convert_strs(f),
+            attributes: self.attributes.convert_strs(f),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<'a> IntoOwned for ImportAttribute<'a> {
+    type Owned<'o> = ImportAttribute<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        ImportAttribute {
+            key: match self.key {
+                ImportAttributeKey::Ident(ident) => ImportAttributeKey::Ident(ident.convert_strs(f)),
+                ImportAttributeKey::Lit(lit) => ImportAttributeKey::Lit(lit.convert_strs(f)),
+            },
+            value: self.value.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ImportSpecifier<'a> {
+    type Owned<'o> = ImportSpecifier<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ImportSpecifier::Normal(spec) => ImportSpecifier::Normal(NormalImportSpec {
+                local: spec.local.convert_strs(f),
+                imported: spec.imported.convert_strs(f),
+            }),
+            ImportSpecifier::Default(ident) => ImportSpecifier::Default(ident.convert_strs(f)),
+            ImportSpecifier::Namespace(ident) => ImportSpecifier::Namespace(ident.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ModuleExportName<'a> {
+    type Owned<'o> = ModuleExportName<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ModuleExportName::Ident(ident) => ModuleExportName::Ident(ident.convert_strs(f)),
+            ModuleExportName::Str(lit) => ModuleExportName::Str(lit.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ModExport<'a> {
+    type Owned<'o> = ModExport<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                ModExport::Default(DefaultExportDecl::Decl(decl.convert_strs(f)))
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                ModExport::Default(DefaultExportDecl::Expr(expr.convert_strs(f)))
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                ModExport::Named(NamedExportDecl::Decl(decl.convert_strs(f)))
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                ModExport::Named(NamedExportDecl::Specifier(
+                    specs.convert_strs(f),
+                    source.convert_strs(f),
+                ))
+            }
+            ModExport::All(alias, source) => {
+                ModExport::All(alias.convert_strs(f), source.convert_strs(f))
+            }
+        }
+    }
+}
+
+impl<'a> IntoOwned for ExportSource<'a> {
+    type Owned<'o> = ExportSource<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        ExportSource {
+            source: self.source.convert_strs(f),
+            attributes: self.attributes.convert_strs(f),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<'a> IntoOwned for ExportSpecifier<'a> {
+    type Owned<'o> = ExportSpecifier<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        ExportSpecifier {
+            local: self.local.convert_strs(f),
+            exported: self.exported.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Stmt<'a> {
+    type Owned<'o> = Stmt<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Stmt::Expr(expr) => Stmt::Expr(expr.convert_strs(f)),
+            Stmt::Block(block) => Stmt::Block(block.convert_strs(f)),
+            Stmt::Empty => Stmt::Empty,
+            Stmt::Debugger => Stmt::Debugger,
+            Stmt::With(stmt) => Stmt::With(WithStmt {
+                object: stmt.object.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::Return(arg) => Stmt::Return(arg.convert_strs(f)),
+            Stmt::Labeled(stmt) => Stmt::Labeled(LabeledStmt {
+                label: stmt.label.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::Break(label) => Stmt::Break(label.convert_strs(f)),
+            Stmt::Continue(label) => Stmt::Continue(label.convert_strs(f)),
+            Stmt::If(stmt) => Stmt::If(IfStmt {
+                test: stmt.test.convert_strs(f),
+                consequent: stmt.consequent.convert_strs(f),
+                alternate: stmt.alternate.convert_strs(f),
+            }),
+            Stmt::Switch(stmt) => Stmt::Switch(SwitchStmt {
+                discriminant: stmt.discriminant.convert_strs(f),
+                cases: stmt.cases.convert_strs(f),
+            }),
+            Stmt::Throw(expr) => Stmt::Throw(expr.convert_strs(f)),
+            Stmt::Try(stmt) => Stmt::Try(TryStmt {
+                block: stmt.block.convert_strs(f),
+                handler: stmt.handler.convert_strs(f),
+                finalizer: stmt.finalizer.convert_strs(f),
+            }),
+            Stmt::While(stmt) => Stmt::While(WhileStmt {
+                test: stmt.test.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::DoWhile(stmt) => Stmt::DoWhile(DoWhileStmt {
+                test: stmt.test.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::For(stmt) => Stmt::For(ForStmt {
+                init: stmt.init.convert_strs(f),
+                test: stmt.test.convert_strs(f),
+                update: stmt.update.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::ForIn(stmt) => Stmt::ForIn(ForInStmt {
+                left: stmt.left.convert_strs(f),
+                right: stmt.right.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+            }),
+            Stmt::ForOf(stmt) => Stmt::ForOf(ForOfStmt {
+                left: stmt.left.convert_strs(f),
+                right: stmt.right.convert_strs(f),
+                body: stmt.body.convert_strs(f),
+                is_await: stmt.is_await,
+            }),
+            Stmt::Var(decls) => Stmt::Var(decls.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for BlockStmt<'a> {
+    type Owned<'o> = BlockStmt<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        BlockStmt(self.0.convert_strs(f))
+    }
+}
+
+impl<'a> IntoOwned for SwitchCase<'a> {
+    type Owned<'o> = SwitchCase<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        SwitchCase {
+            test: self.test.convert_strs(f),
+            consequent: self.consequent.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for CatchClause<'a> {
+    type Owned<'o> = CatchClause<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        CatchClause {
+            param: self.param.convert_strs(f),
+            body: self.body.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for LoopInit<'a> {
+    type Owned<'o> = LoopInit<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            LoopInit::Variable(kind, decls) => LoopInit::Variable(kind, decls.convert_strs(f)),
+            LoopInit::Expr(expr) => LoopInit::Expr(expr.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for LoopLeft<'a> {
+    type Owned<'o> = LoopLeft<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            LoopLeft::Expr(expr) => LoopLeft::Expr(expr.convert_strs(f)),
+            LoopLeft::Variable(kind, decl) => LoopLeft::Variable(kind, decl.convert_strs(f)),
+            LoopLeft::Pat(pat) => LoopLeft::Pat(pat.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Expr<'a> {
+    type Owned<'o> = Expr<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Expr::Array(elements) => Expr::Array(elements.convert_strs(f)),
+            Expr::ArrowFunc(arrow) => Expr::ArrowFunc(ArrowFuncExpr {
+                id: arrow.id.convert_strs(f),
+                params: arrow.params.convert_strs(f),
+                body: match arrow.body {
+                    ArrowFuncBody::FuncBody(body) => ArrowFuncBody::FuncBody(body.convert_strs(f)),
+                    ArrowFuncBody::Expr(expr) => ArrowFuncBody::Expr(expr.convert_strs(f)),
+                },
+                expression: arrow.expression,
+                is_async: arrow.is_async,
+            }),
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                Expr::ArrowParamPlaceHolder(args.convert_strs(f), is_async)
+            }
+            Expr::Assign(assign) => Expr::Assign(AssignExpr {
+                operator: assign.operator,
+                left: match assign.left {
+                    AssignLeft::Pat(pat) => AssignLeft::Pat(pat.convert_strs(f)),
+                    AssignLeft::Expr(expr) => AssignLeft::Expr(expr.convert_strs(f)),
+                },
+                right: assign.right.convert_strs(f),
+            }),
+            Expr::Await(expr) => Expr::Await(expr.convert_strs(f)),
+            Expr::Binary(bin) => Expr::Binary(BinaryExpr {
+                operator: bin.operator,
+                left: bin.left.convert_strs(f),
+                right: bin.right.convert_strs(f),
+            }),
+            Expr::Class(class) => Expr::Class(class.convert_strs(f)),
+            Expr::Call(call) => Expr::Call(CallExpr {
+                callee: call.callee.convert_strs(f),
+                arguments: call.arguments.convert_strs(f),
+                optional: call.optional,
+            }),
+            Expr::Conditional(cond) => Expr::Conditional(ConditionalExpr {
+                test: cond.test.convert_strs(f),
+                alternate: cond.alternate.convert_strs(f),
+                consequent: cond.consequent.convert_strs(f),
+            }),
+            Expr::Func(func) => Expr::Func(func.convert_strs(f)),
+            Expr::Ident(ident) => Expr::Ident(ident.convert_strs(f)),
+            Expr::Import(import) => Expr::Import(ImportExpr {
+                source: import.source.convert_strs(f),
+                options: import.options.convert_strs(f),
+            }),
+            Expr::Lit(lit) => Expr::Lit(lit.convert_strs(f)),
+            Expr::Logical(logical) => Expr::Logical(LogicalExpr {
+                operator: logical.operator,
+                left: logical.left.convert_strs(f),
+                right: logical.right.convert_strs(f),
+            }),
+            Expr::Member(member) => Expr::Member(MemberExpr {
+                object: member.object.convert_strs(f),
+                property: member.property.convert_strs(f),
+                optional: member.optional,
+            }),
+            Expr::MetaProp(meta) => Expr::MetaProp(MetaProp {
+                meta: meta.meta.convert_strs(f),
+                property: meta.property.convert_strs(f),
+            }),
+            Expr::OptionalChain(expr) => Expr::OptionalChain(expr.convert_strs(f)),
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(expr) => Expr::Paren(expr.convert_strs(f)),
+            Expr::PrivateId(id) => Expr::PrivateId(id.convert_strs(f)),
+            Expr::New(new) => Expr::New(NewExpr {
+                callee: new.callee.convert_strs(f),
+                arguments: new.arguments.convert_strs(f),
+            }),
+            Expr::Obj(props) => Expr::Obj(props.convert_strs(f)),
+            Expr::Sequence(seq) => Expr::Sequence(SequenceExpr {
+                head: seq.head.convert_strs(f),
+                tail: seq.tail.convert_strs(f),
+            }),
+            Expr::Spread(expr) => Expr::Spread(expr.convert_strs(f)),
+            Expr::Super => Expr::Super,
+            Expr::TaggedTemplate(tagged) => Expr::TaggedTemplate(TaggedTemplateExpr {
+                tag: tagged.tag.convert_strs(f),
+                quasi: tagged.quasi.convert_strs(f),
+            }),
+            Expr::This => Expr::This,
+            Expr::Unary(unary) => Expr::Unary(UnaryExpr {
+                operator: unary.operator,
+                prefix: unary.prefix,
+                argument: unary.argument.convert_strs(f),
+            }),
+            Expr::Update(update) => Expr::Update(UpdateExpr {
+                operator: update.operator,
+                argument: update.argument.convert_strs(f),
+                prefix: update.prefix,
+            }),
+            Expr::Yield(y) => Expr::Yield(YieldExpr {
+                argument: y.argument.convert_strs(f),
+                delegate: y.delegate,
+            }),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ObjProp<'a> {
+    type Owned<'o> = ObjProp<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ObjProp::Prop(prop) => ObjProp::Prop(prop.convert_strs(f)),
+            ObjProp::Spread(expr) => ObjProp::Spread(expr.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Prop<'a> {
+    type Owned<'o> = Prop<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        Prop {
+            key: self.key.convert_strs(f),
+            value: match self.value {
+                PropValue::Expr(expr) => PropValue::Expr(expr.convert_strs(f)),
+                PropValue::Pat(pat) => PropValue::Pat(pat.convert_strs(f)),
+                PropValue::None => PropValue::None,
+            },
+            kind: self.kind,
+            method: self.method,
+            computed: self.computed,
+            short_hand: self.short_hand,
+            is_static: self.is_static,
+            decorators: self.decorators.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for PropKey<'a> {
+    type Owned<'o> = PropKey<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            PropKey::Lit(lit) => PropKey::Lit(lit.convert_strs(f)),
+            PropKey::Expr(expr) => PropKey::Expr(expr.convert_strs(f)),
+            PropKey::Pat(pat) => PropKey::Pat(pat.convert_strs(f)),
+            PropKey::Private(id) => PropKey::Private(id.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for MemberProp<'a> {
+    type Owned<'o> = MemberProp<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            MemberProp::Ident(ident) => MemberProp::Ident(ident.convert_strs(f)),
+            MemberProp::Private(id) => MemberProp::Private(id.convert_strs(f)),
+            MemberProp::Computed(expr) => MemberProp::Computed(expr.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for PrivateId<'a> {
+    type Owned<'o> = PrivateId<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        PrivateId {
+            name: f(self.name),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Lit<'a> {
+    type Owned<'o> = Lit<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Lit::Null => Lit::Null,
+            Lit::String(StringLit::Double(s)) => Lit::String(StringLit::Double(f(s))),
+            Lit::String(StringLit::Single(s)) => Lit::String(StringLit::Single(f(s))),
+            Lit::Number(number) => Lit::Number(NumberLit(f(number.0))),
+            Lit::BigInt(digits) => Lit::BigInt(f(digits)),
+            Lit::Boolean(b) => Lit::Boolean(b),
+            Lit::RegEx(re) => Lit::RegEx(RegEx {
+                pattern: f(re.pattern),
+                flags: f(re.flags),
+            }),
+            Lit::Template(template) => Lit::Template(template.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for TemplateLit<'a> {
+    type Owned<'o> = TemplateLit<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        TemplateLit {
+            quasis: self.quasis.convert_strs(f),
+            expressions: self.expressions.convert_strs(f),
+        }
+    }
+}
+
+impl<'a> IntoOwned for TemplateElement<'a> {
+    type Owned<'o> = TemplateElement<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        TemplateElement {
+            tail: self.tail,
+            cooked: self.cooked.map(&mut *f),
+            raw: f(self.raw),
+        }
+    }
+}
+
+impl<'a> IntoOwned for Pat<'a> {
+    type Owned<'o> = Pat<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            Pat::Ident(ident) => Pat::Ident(ident.convert_strs(f)),
+            Pat::Obj(parts) => Pat::Obj(parts.convert_strs(f)),
+            Pat::Array(parts) => Pat::Array(parts.convert_strs(f)),
+            Pat::RestElement(pat) => Pat::RestElement(pat.convert_strs(f)),
+            Pat::Assign(assign) => Pat::Assign(AssignPat {
+                left: assign.left.convert_strs(f),
+                right: assign.right.convert_strs(f),
+            }),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ArrayPatPart<'a> {
+    type Owned<'o> = ArrayPatPart<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ArrayPatPart::Pat(pat) => ArrayPatPart::Pat(pat.convert_strs(f)),
+            ArrayPatPart::Expr(expr) => ArrayPatPart::Expr(expr.convert_strs(f)),
+        }
+    }
+}
+
+impl<'a> IntoOwned for ObjPatPart<'a> {
+    type Owned<'o> = ObjPatPart<'o>;
+    fn convert_strs<'o>(self, f: &mut StrFn<'_, 'o>) -> Self::Owned<'o> {
+        match self {
+            ObjPatPart::Assign(prop) => ObjPatPart::Assign(prop.convert_strs(f)),
+            ObjPatPart::Rest(pat) => ObjPatPart::Rest(pat.convert_strs(f)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(program: &Program) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        program.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// builds `const <name> = '<value>';` borrowing
+    /// both strings from `src`
+    fn borrowed<'a>(src: &'a str) -> Program<'a> {
+        let (name, value) = src.split_at(src.find('=').unwrap());
+        Program::script(vec![ProgramPart::Decl(Decl::Var(
+            VarKind::Const,
+            vec![VarDecl {
+                id: Pat::ident_from(name),
+                init: Some(Expr::Lit(Lit::single_string_from(&value[1..]))),
+            }],
+        ))])
+    }
+
+    fn detach(src: String) -> Program<'static> {
+        borrowed(&src).into_owned()
+    }
+
+    #[test]
+    fn outlives_source() {
+        let program = detach(String::from("greeting=hello"));
+        let expected = Program::script(vec![ProgramPart::Decl(Decl::Var(
+            VarKind::Const,
+            vec![VarDecl {
+                id: Pat::ident_from("greeting"),
+                init: Some(Expr::Lit(Lit::single_string_from("hello"))),
+            }],
+        ))]);
+        assert_eq!(program, expected);
+        if let Program::Script(parts, _) = &program {
+            if let ProgramPart::Decl(Decl::Var(_, decls)) = &parts[0] {
+                if let Pat::Ident(ident) = &decls[0].id {
+                    assert!(matches!(ident.name, Cow::Owned(_)));
+                    return;
+                }
+            }
+        }
+        panic!("unexpected program shape {:?}", program);
+    }
+
+    #[test]
+    fn interned_idents_share_text() {
+        let interner = Interner::new();
+        let first = borrowed(&String::from("greeting=hello")).intern(&interner);
+        let second = borrowed(&String::from("greeting=hello")).intern(&interner);
+        assert_eq!(first, second);
+        assert_eq!(interner.len(), 2);
+        let name = |program: &Program| -> *const u8 {
+            if let Program::Script(parts, _) = program {
+                if let ProgramPart::Decl(Decl::Var(_, decls)) = &parts[0] {
+                    if let Pat::Ident(ident) = &decls[0].id {
+                        return ident.name.as_ptr();
+                    }
+                }
+            }
+            panic!("unexpected program shape {:?}", program);
+        };
+        assert_eq!(name(&first), name(&second));
+    }
+
+    #[test]
+    fn borrowed_equals_owned() {
+        // #!/usr/bin/env node
+        // 'use strict';
+        // class A { static #x = 1.50; m() { return /a+/gu.test(`t${0x1F}`); } }
+        // let o = { k: async (a, ...b) => void a };
+        let template = TemplateLit {
+            quasis: vec![
+                TemplateElement::from(false, "t", "`t${"),
+                TemplateElement::from(true, "", "}`"),
+            ],
+            expressions: vec![Expr::Lit(Lit::number_from("0x1F"))],
+        };
+        let test = CallExpr::new(
+            Expr::Member(MemberExpr::new(
+                Expr::Lit(Lit::RegEx(RegEx::from("a+", "gu"))),
+                MemberProp::ident_from("test"),
+            )),
+            vec![Expr::Lit(Lit::Template(template))],
+        );
+        let method = Func {
+            id: None,
+            params: vec![],
+            body: FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::Call(test))))]),
+            generator: false,
+            is_async: false,
+        };
+        let class = Class {
+            id: Some(Ident::from("A")),
+            super_class: None,
+            body: ClassBody(vec![
+                ClassMember::Field(FieldDef {
+                    key: PropKey::Private(PrivateId::from("x")),
+                    value: Some(Expr::Lit(Lit::number_from("1.50"))),
+                    is_static: true,
+                    computed: false,
+                    decorators: vec![],
+                }),
+                ClassMember::Method(MethodDef {
+                    key: PropKey::Expr(Expr::ident_from("m")),
+                    value: method,
+                    kind: MethodKind::Method,
+                    is_static: false,
+                    computed: false,
+                    decorators: vec![],
+                }),
+            ]),
+            decorators: vec![],
+        };
+        let arrow = ArrowFuncExpr::new(
+            vec![
+                FuncArg::Pat(Pat::ident_from("a")),
+                FuncArg::Pat(Pat::RestElement(Box::new(Pat::ident_from("b")))),
+            ],
+            ArrowFuncBody::Expr(Box::new(Expr::Unary(UnaryExpr::new(
+                UnaryOp::Void,
+                Expr::ident_from("a"),
+            )))),
+            true,
+        );
+        let obj = Expr::Obj(vec![ObjProp::Prop(Prop {
+            key: PropKey::Expr(Expr::ident_from("k")),
+            value: PropValue::Expr(Expr::ArrowFunc(arrow)),
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand: false,
+            is_static: false,
+            decorators: vec![],
+        })]);
+        let program = Program::Script(
+            vec![
+                ProgramPart::Dir(Dir {
+                    expr: Lit::single_string_from("use strict"),
+                    dir: Cow::Borrowed("use strict"),
+                }),
+                ProgramPart::Decl(Decl::Class(class)),
+                ProgramPart::Decl(Decl::Var(
+                    VarKind::Let,
+                    vec![VarDecl {
+                        id: Pat::ident_from("o"),
+                        init: Some(obj),
+                    }],
+                )),
+            ],
+            Some(Cow::Borrowed("/usr/bin/env node")),
+        );
+        let owned = program.clone().into_owned();
+        assert_eq!(program, owned);
+        assert_eq!(owned, program);
+        assert_eq!(hash(&program), hash(&owned));
+        let interner = Interner::new();
+        let interned = program.clone().intern(&interner);
+        assert_eq!(program, interned);
+        assert_eq!(hash(&program), hash(&interned));
+        assert_eq!(crate::sexpr::to_sexpr(&owned), crate::sexpr::to_sexpr(&program));
+    }
+
+    #[test]
+    fn interned_text_survives_growth() {
+        let interner = Interner::new();
+        let first = interner.intern("first");
+        let address = first.as_ptr();
+        // enough new entries that the set has to reallocate and rehash
+        // several times while `first` is still borrowed from it
+        let rest: Vec<_> = (0..1_000).map(|i| interner.intern(&i.to_string())).collect();
+        assert_eq!(interner.len(), 1_001);
+        assert_eq!(first, "first");
+        assert_eq!(first.as_ptr(), address);
+        assert_eq!(interner.intern("first").as_ptr(), address);
+        for (i, text) in rest.iter().enumerate() {
+            assert_eq!(*text, i.to_string());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! The rules for where parentheses are needed when turning a
+//! tree back into javascript, the tree only holds the structure
+//! so anything writing it out has to put them back
+use crate::expr::*;
+use crate::stmt::*;
+use crate::*;
+
+// Binding power of each kind of expression, a higher
+// number binds more tightly. Operators use their own
+// `precedence` so these only fill in the gaps
+pub(crate) const SEQUENCE: u8 = 1;
+pub(crate) const ASSIGN: u8 = AssignOp::Equal.precedence();
+pub(crate) const CONDITIONAL: u8 = 3;
+pub(crate) const NULLISH: u8 = LogicalOp::NullishCoalescing.precedence();
+pub(crate) const UNARY: u8 = UnaryOp::Minus.precedence();
+pub(crate) const POSTFIX: u8 = UpdateOp::Increment.precedence();
+pub(crate) const CALL: u8 = 18;
+pub(crate) const PRIMARY: u8 = 19;
+
+pub(crate) fn expr_prec(expr: &Expr) -> u8 {
+    match expr {
+        Expr::Sequence(_) => SEQUENCE,
+        Expr::ArrowFunc(_) | Expr::Assign(_) | Expr::Yield(_) => ASSIGN,
+        Expr::Conditional(_) => CONDITIONAL,
+        Expr::Logical(logical) => logical.operator.precedence(),
+        Expr::Binary(bin) => bin.operator.precedence(),
+        Expr::Await(_) | Expr::Unary(_) => UNARY,
+        Expr::Update(update) if update.prefix => UNARY,
+        Expr::Update(_) => POSTFIX,
+        Expr::Call(_)
+        | Expr::Import(_)
+        | Expr::Member(_)
+        | Expr::New(_)
+        | Expr::OptionalChain(_)
+        | Expr::TaggedTemplate(_) => CALL,
+        Expr::Spread(_) => ASSIGN,
+        _ => PRIMARY,
+    }
+}
+
+/// An expression statement can't start with `{`, `function`
+/// or `class`, this checks the left most token of an expression
+pub(crate) fn starts_ambiguously(expr: &Expr) -> bool {
+    match expr {
+        Expr::Obj(_) | Expr::Func(_) | Expr::Class(_) => true,
+        Expr::Binary(BinaryExpr { left, .. }) | Expr::Logical(LogicalExpr { left, .. }) => {
+            starts_ambiguously(left)
+        }
+        Expr::Assign(assign) => match &assign.left {
+            AssignLeft::Expr(expr) => starts_ambiguously(expr),
+            AssignLeft::Pat(pat) => matches!(pat, Pat::Obj(_)),
+        },
+        Expr::Conditional(cond) => starts_ambiguously(&cond.test),
+        Expr::Call(call) => starts_ambiguously(&call.callee),
+        Expr::Member(member) => starts_ambiguously(&member.object),
+        Expr::Sequence(seq) => starts_ambiguously(&seq.head),
+        Expr::Update(update) if !update.prefix => starts_ambiguously(&update.argument),
+        Expr::TaggedTemplate(tagged) => starts_ambiguously(&tagged.tag),
+        Expr::OptionalChain(expr) => starts_ambiguously(expr),
+        _ => false,
+    }
+}
+
+/// `new a.b()` and `new (a().b)()` are different, so a callee
+/// containing a call needs to be wrapped
+pub(crate) fn contains_call(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(_) => true,
+        Expr::Member(member) => contains_call(&member.object),
+        Expr::TaggedTemplate(tagged) => contains_call(&tagged.tag),
+        _ => false,
+    }
+}
+
+/// An `if` without an `else` at the end of a statement would
+/// steal the `else` of an enclosing `if`
+pub(crate) fn ends_with_open_if(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::If(stmt) => match &stmt.alternate {
+            None => true,
+            Some(alt) => ends_with_open_if(alt),
+        },
+        Stmt::While(WhileStmt { body, .. })
+        | Stmt::For(ForStmt { body, .. })
+        | Stmt::ForIn(ForInStmt { body, .. })
+        | Stmt::ForOf(ForOfStmt { body, .. })
+        | Stmt::With(WithStmt { body, .. })
+        | Stmt::Labeled(LabeledStmt { body, .. }) => ends_with_open_if(body),
+        _ => false,
+    }
+}
@@ -1,8 +1,9 @@
-use crate::expr::{Expr, Prop};
-use crate::{Ident};
+use crate::expr::{AssignExpr, AssignLeft, Expr, ObjProp, Prop, PropKey, PropValue};
+use crate::{AssignOp, Ident, PropKind};
+use std::convert::TryFrom;
 /// All of the different ways you can declare an identifier
 /// and/or value
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -12,6 +13,8 @@ use crate::{Ident};
 pub enum Pat<'a> {
     Ident(Ident<'a>),
     Obj(ObjPat<'a>),
+    /// `[a, , b]`, a `None` entry is a hole (elision)
+    /// that skips an element of the value being destructured
     Array(Vec<Option<ArrayPatPart<'a>>>),
     RestElement(Box<Pat<'a>>),
     Assign(AssignPat<'a>),
@@ -23,9 +26,66 @@ impl<'a> Pat<'a> {
             Ident::from(s)
         )
     }
+    /// Every identifier this pattern binds, in source order
+    /// ```js
+    /// const {a, b: [c, ...d], [e]: f = 1} = o; // a, c, d, f
+    /// ```
+    /// Keys and default values are skipped, only the
+    /// targets are included
+    pub fn bound_names(&self) -> Vec<&Ident<'a>> {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names<'b>(&'b self, names: &mut Vec<&'b Ident<'a>>) {
+        match self {
+            Pat::Ident(ident) => names.push(ident),
+            Pat::Obj(parts) => {
+                for part in parts {
+                    match part {
+                        ObjPatPart::Assign(prop) => prop_names(prop, names),
+                        ObjPatPart::Rest(pat) => pat.collect_names(names),
+                    }
+                }
+            }
+            Pat::Array(parts) => {
+                for part in parts.iter().flatten() {
+                    match part {
+                        ArrayPatPart::Pat(pat) => pat.collect_names(names),
+                        ArrayPatPart::Expr(expr) => expr_name(expr, names),
+                    }
+                }
+            }
+            Pat::RestElement(pat) => pat.collect_names(names),
+            Pat::Assign(assign) => assign.left.collect_names(names),
+        }
+    }
+}
+
+/// The names bound by one property of an object pattern, a
+/// shorthand property binds its key otherwise the key is skipped
+fn prop_names<'b, 'a>(prop: &'b Prop<'a>, names: &mut Vec<&'b Ident<'a>>) {
+    match &prop.value {
+        PropValue::Pat(pat) => pat.collect_names(names),
+        PropValue::Expr(expr) => expr_name(expr, names),
+        PropValue::None => match &prop.key {
+            PropKey::Pat(pat) => pat.collect_names(names),
+            PropKey::Expr(expr) if !prop.computed => expr_name(expr, names),
+            _ => {}
+        },
+    }
+}
+
+/// An expression in a pattern only binds a name when it is
+/// an identifier, `[a.b] = c` assigns to a member instead
+fn expr_name<'b, 'a>(expr: &'b Expr<'a>, names: &mut Vec<&'b Ident<'a>>) {
+    if let Expr::Ident(ident) = expr {
+        names.push(ident);
+    }
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum ArrayPatPart<'a> {
@@ -36,16 +96,265 @@ pub enum ArrayPatPart<'a> {
 /// similar to an `ObjectExpr`
 pub type ObjPat<'a> = Vec<ObjPatPart<'a>>;
 /// A single part of an ObjectPat
-#[derive(PartialEq, Debug, Clone)]
-#[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+#[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), serde(untagged))]
 pub enum ObjPatPart<'a> {
+    /// A single property, the `Prop` flags describe how it
+    /// was written
+    /// ```js
+    /// const {a} = o; // short_hand, key and no value
+    /// const {a = 1} = o; // short_hand, value is an `AssignPat`
+    /// const {a: b} = o; // value is the binding
+    /// const {[k]: v = 1} = o; // computed, value is an `AssignPat`
+    /// ```
     Assign(Prop<'a>),
+    /// `{...rest} = o`, the value is the pattern the
+    /// remaining properties are bound to
     Rest(Box<Pat<'a>>),
 }
 
+impl<'a> ObjPatPart<'a> {
+    /// If this is written as `{a}` or `{a = 1}`
+    pub fn is_shorthand(&self) -> bool {
+        matches!(self, ObjPatPart::Assign(prop) if prop.short_hand)
+    }
+    /// If the key is written as `[expr]`
+    pub fn is_computed(&self) -> bool {
+        matches!(self, ObjPatPart::Assign(prop) if prop.computed)
+    }
+    /// The pattern the value is bound to without any default,
+    /// `None` for a shorthand whose key is an expression
+    pub fn binding(&self) -> Option<&Pat<'a>> {
+        match self {
+            ObjPatPart::Assign(prop) => match &prop.value {
+                PropValue::Pat(Pat::Assign(assign)) => Some(&assign.left),
+                PropValue::Pat(pat) => Some(pat),
+                PropValue::None => match &prop.key {
+                    PropKey::Pat(pat) => Some(pat),
+                    _ => None,
+                },
+                PropValue::Expr(_) => None,
+            },
+            ObjPatPart::Rest(pat) => Some(pat),
+        }
+    }
+    /// The value used when the property is `undefined`,
+    /// `1` in `{a = 1}` or `{a: b = 1}`
+    pub fn default_value(&self) -> Option<&Expr<'a>> {
+        match self {
+            ObjPatPart::Assign(Prop {
+                value: PropValue::Pat(Pat::Assign(assign)),
+                ..
+            }) => Some(&assign.right),
+            _ => None,
+        }
+    }
+}
+
+/// The reasons an `Expr` can't be converted into a `Pat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The expression can't be assigned to, e.g. `1`, `f()`
+    /// or an object literal method
+    InvalidTarget,
+    /// A spread that isn't the last element or property
+    /// ```js
+    /// [...a, b] = c;
+    /// ```
+    RestNotLast,
+}
+
+impl std::fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConvertError::InvalidTarget => write!(f, "invalid destructuring target"),
+            ConvertError::RestNotLast => write!(f, "rest element must be last"),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl<'a> Pat<'a> {
+    /// Reinterpret an expression as a destructuring target, this is
+    /// what a parser needs when it finds an `=` after something like
+    /// `{a, ...b}` or `[a = 1]`
+    ///
+    /// Spreads become rest elements, assignments become defaults
+    /// and shorthand keys become `PropKey::Pat`. A member expression
+    /// is a valid target inside of a pattern but has no `Pat` form so
+    /// it is kept as an `ArrayPatPart::Expr` or `PropValue::Expr`
+    /// ```
+    /// # use resast::prelude::*;
+    /// use resast::pat::ConvertError;
+    /// let pat = Pat::try_from_expr(Expr::ident_from("a"));
+    /// assert_eq!(pat, Ok(Pat::ident_from("a")));
+    /// let pat = Pat::try_from_expr(Expr::Lit(Lit::number_from("1")));
+    /// assert_eq!(pat, Err(ConvertError::InvalidTarget));
+    /// ```
+    pub fn try_from_expr(expr: Expr<'a>) -> Result<Self, ConvertError> {
+        match expr {
+            Expr::Ident(ident) => Ok(Pat::Ident(ident)),
+            Expr::Obj(props) => {
+                let len = props.len();
+                let mut parts = Vec::with_capacity(len);
+                for (idx, prop) in props.into_iter().enumerate() {
+                    match prop {
+                        ObjProp::Prop(prop) => parts.push(ObjPatPart::Assign(prop_to_pat(prop)?)),
+                        ObjProp::Spread(_) if idx + 1 != len => {
+                            return Err(ConvertError::RestNotLast)
+                        }
+                        ObjProp::Spread(expr) => match Pat::try_from_expr(expr)? {
+                            Pat::Assign(_) => return Err(ConvertError::InvalidTarget),
+                            pat => parts.push(ObjPatPart::Rest(Box::new(pat))),
+                        },
+                    }
+                }
+                Ok(Pat::Obj(parts))
+            }
+            Expr::Array(elements) => {
+                let len = elements.len();
+                let mut parts = Vec::with_capacity(len);
+                for (idx, el) in elements.into_iter().enumerate() {
+                    let part = match el {
+                        Some(Expr::Spread(_)) if idx + 1 != len => {
+                            return Err(ConvertError::RestNotLast)
+                        }
+                        Some(Expr::Spread(inner)) => match nested_target(*inner)? {
+                            ArrayPatPart::Pat(Pat::Assign(_)) => {
+                                return Err(ConvertError::InvalidTarget)
+                            }
+                            ArrayPatPart::Pat(pat) => {
+                                Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(pat))))
+                            }
+                            ArrayPatPart::Expr(expr) => {
+                                Some(ArrayPatPart::Expr(Expr::Spread(Box::new(expr))))
+                            }
+                        },
+                        Some(el) => Some(nested_target(el)?),
+                        None => None,
+                    };
+                    parts.push(part);
+                }
+                Ok(Pat::Array(parts))
+            }
+            Expr::Assign(assign) if assign.operator == AssignOp::Equal => {
+                let left = match assign.left {
+                    AssignLeft::Pat(pat) => pat,
+                    AssignLeft::Expr(expr) => Pat::try_from_expr(*expr)?,
+                };
+                Ok(Pat::Assign(AssignPat {
+                    left: Box::new(left),
+                    right: assign.right,
+                }))
+            }
+            _ => Err(ConvertError::InvalidTarget),
+        }
+    }
+}
+
+impl<'a> TryFrom<Expr<'a>> for Pat<'a> {
+    type Error = ConvertError;
+    fn try_from(expr: Expr<'a>) -> Result<Self, Self::Error> {
+        Pat::try_from_expr(expr)
+    }
+}
+
+/// A member expression, with or without a default, that
+/// can only be represented as an expression
+fn is_member_target(expr: &Expr) -> bool {
+    match expr {
+        Expr::Member(member) => !member.optional,
+        Expr::Assign(assign) => match &assign.left {
+            AssignLeft::Expr(left) => {
+                assign.operator == AssignOp::Equal && matches!(**left, Expr::Member(_))
+            }
+            AssignLeft::Pat(_) => false,
+        },
+        _ => false,
+    }
+}
+
+fn nested_target(expr: Expr) -> Result<ArrayPatPart, ConvertError> {
+    if is_member_target(&expr) {
+        return Ok(ArrayPatPart::Expr(expr));
+    }
+    Pat::try_from_expr(expr).map(ArrayPatPart::Pat)
+}
+
+fn prop_to_pat(prop: Prop) -> Result<Prop, ConvertError> {
+    if prop.kind != PropKind::Init || prop.method {
+        return Err(ConvertError::InvalidTarget);
+    }
+    let value = match prop.value {
+        PropValue::Expr(expr) => match nested_target(expr)? {
+            ArrayPatPart::Pat(pat) => PropValue::Pat(pat),
+            ArrayPatPart::Expr(expr) => PropValue::Expr(expr),
+        },
+        value => value,
+    };
+    let key = match prop.key {
+        PropKey::Expr(Expr::Ident(ident)) if prop.short_hand => PropKey::Pat(Pat::Ident(ident)),
+        key => key,
+    };
+    Ok(Prop { key, value, ..prop })
+}
+
+impl<'a> From<Pat<'a>> for Expr<'a> {
+    /// The expression form of a pattern, the reverse of
+    /// `Pat::try_from_expr`
+    fn from(pat: Pat<'a>) -> Self {
+        match pat {
+            Pat::Ident(ident) => Expr::Ident(ident),
+            Pat::Obj(parts) => Expr::Obj(
+                parts
+                    .into_iter()
+                    .map(|part| match part {
+                        ObjPatPart::Assign(prop) => ObjProp::Prop(prop_to_expr(prop)),
+                        ObjPatPart::Rest(pat) => ObjProp::Spread(Expr::from(*pat)),
+                    })
+                    .collect(),
+            ),
+            Pat::Array(parts) => Expr::Array(
+                parts
+                    .into_iter()
+                    .map(|part| {
+                        part.map(|part| match part {
+                            ArrayPatPart::Pat(pat) => Expr::from(pat),
+                            ArrayPatPart::Expr(expr) => expr,
+                        })
+                    })
+                    .collect(),
+            ),
+            Pat::RestElement(pat) => Expr::Spread(Box::new(Expr::from(*pat))),
+            Pat::Assign(assign) => Expr::Assign(AssignExpr {
+                operator: AssignOp::Equal,
+                left: AssignLeft::Expr(Box::new(Expr::from(*assign.left))),
+                right: assign.right,
+            }),
+        }
+    }
+}
+
+fn prop_to_expr(prop: Prop) -> Prop {
+    let value = match prop.value {
+        PropValue::Pat(pat) => PropValue::Expr(Expr::from(pat)),
+        value => value,
+    };
+    let key = match prop.key {
+        PropKey::Pat(pat) => PropKey::Expr(Expr::from(pat)),
+        key => key,
+    };
+    Prop { key, value, ..prop }
+}
+
 /// An assignment as a pattern
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -55,3 +364,234 @@ pub struct AssignPat<'a> {
     pub left: Box<Pat<'a>>,
     pub right: Box<Expr<'a>>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::Lit;
+    use crate::PropKind;
+
+    fn prop<'a>(key: PropKey<'a>, value: PropValue<'a>, computed: bool) -> ObjPatPart<'a> {
+        ObjPatPart::Assign(Prop {
+            short_hand: matches!(value, PropValue::None),
+            key,
+            value,
+            kind: PropKind::Init,
+            method: false,
+            computed,
+            is_static: false,
+            decorators: vec![],
+        })
+    }
+
+    fn names<'a>(pat: &'a Pat<'a>) -> Vec<&'a str> {
+        pat.bound_names().into_iter().map(|i| i.name.as_ref()).collect()
+    }
+
+    #[test]
+    fn nested_bound_names() {
+        // {a, b: [c, ...d], [e]: {f = 1, ...g}, h = 2, i: [, j.k]}
+        let one = || Box::new(Expr::Lit(Lit::number_from("1")));
+        let pat = Pat::Obj(vec![
+            prop(PropKey::Pat(Pat::ident_from("a")), PropValue::None, false),
+            prop(
+                PropKey::Expr(Expr::ident_from("b")),
+                PropValue::Pat(Pat::Array(vec![
+                    Some(ArrayPatPart::Pat(Pat::ident_from("c"))),
+                    Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(Pat::ident_from("d"))))),
+                ])),
+                false,
+            ),
+            prop(
+                PropKey::Expr(Expr::ident_from("e")),
+                PropValue::Pat(Pat::Obj(vec![
+                    prop(
+                        PropKey::Pat(Pat::ident_from("f")),
+                        PropValue::Pat(Pat::Assign(AssignPat {
+                            left: Box::new(Pat::ident_from("f")),
+                            right: one(),
+                        })),
+                        false,
+                    ),
+                    ObjPatPart::Rest(Box::new(Pat::ident_from("g"))),
+                ])),
+                true,
+            ),
+            prop(
+                PropKey::Expr(Expr::ident_from("h")),
+                PropValue::Pat(Pat::Assign(AssignPat {
+                    left: Box::new(Pat::ident_from("h")),
+                    right: one(),
+                })),
+                false,
+            ),
+            prop(
+                PropKey::Expr(Expr::ident_from("i")),
+                PropValue::Pat(Pat::Array(vec![
+                    None,
+                    Some(ArrayPatPart::Expr(Expr::Member(crate::expr::MemberExpr {
+                        object: Box::new(Expr::ident_from("j")),
+                        property: crate::expr::MemberProp::ident_from("k"),
+                        optional: false,
+                    }))),
+                ])),
+                false,
+            ),
+        ]);
+        assert_eq!(names(&pat), vec!["a", "c", "d", "f", "g", "h"]);
+    }
+
+    #[test]
+    fn computed_shorthand_keys_are_skipped() {
+        // a parser won't produce a computed shorthand but the key
+        // is an expression, not a binding, either way
+        let key = PropKey::Expr(Expr::ident_from("k"));
+        let pat = Pat::Obj(vec![prop(key, PropValue::None, true)]);
+        assert!(pat.bound_names().is_empty());
+        assert_eq!(names(&Pat::ident_from("x")), vec!["x"]);
+    }
+
+    fn default<'a>(name: &'a str, value: &'a str) -> PropValue<'a> {
+        PropValue::Pat(Pat::Assign(AssignPat {
+            left: Box::new(Pat::ident_from(name)),
+            right: Box::new(Expr::Lit(Lit::number_from(value))),
+        }))
+    }
+
+    #[test]
+    fn obj_pat_part_forms() {
+        // const {a, b: c, [d]: e, f = 1, g: h = 2, ...i} = o
+        let mut shorthand_default =
+            prop(PropKey::Pat(Pat::ident_from("f")), default("f", "1"), false);
+        if let ObjPatPart::Assign(prop) = &mut shorthand_default {
+            prop.short_hand = true;
+        }
+        let parts = [
+            prop(PropKey::Pat(Pat::ident_from("a")), PropValue::None, false),
+            prop(
+                PropKey::Expr(Expr::ident_from("b")),
+                PropValue::Pat(Pat::ident_from("c")),
+                false,
+            ),
+            prop(
+                PropKey::Expr(Expr::ident_from("d")),
+                PropValue::Pat(Pat::ident_from("e")),
+                true,
+            ),
+            shorthand_default,
+            prop(PropKey::Expr(Expr::ident_from("g")), default("h", "2"), false),
+            ObjPatPart::Rest(Box::new(Pat::ident_from("i"))),
+        ];
+        let shorthand: Vec<bool> = parts.iter().map(ObjPatPart::is_shorthand).collect();
+        assert_eq!(shorthand, vec![true, false, false, true, false, false]);
+        let computed: Vec<bool> = parts.iter().map(ObjPatPart::is_computed).collect();
+        assert_eq!(computed, vec![false, false, true, false, false, false]);
+        let bindings: Vec<Option<&Pat>> = parts.iter().map(ObjPatPart::binding).collect();
+        let ident = Pat::ident_from;
+        assert_eq!(
+            bindings,
+            vec![
+                Some(&ident("a")),
+                Some(&ident("c")),
+                Some(&ident("e")),
+                Some(&ident("f")),
+                Some(&ident("h")),
+                Some(&ident("i")),
+            ]
+        );
+        let defaults: Vec<Option<&Expr>> = parts.iter().map(ObjPatPart::default_value).collect();
+        let num = |n| Expr::Lit(Lit::number_from(n));
+        assert_eq!(
+            defaults,
+            vec![None, None, None, Some(&num("1")), Some(&num("2")), None]
+        );
+    }
+
+    fn obj_prop<'a>(key: &'a str, value: PropValue<'a>) -> ObjProp<'a> {
+        ObjProp::Prop(Prop {
+            short_hand: matches!(value, PropValue::None),
+            key: PropKey::Expr(Expr::ident_from(key)),
+            value,
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            is_static: false,
+            decorators: vec![],
+        })
+    }
+
+    #[test]
+    fn object_expr_to_pat() {
+        // ({a, ...b} = o)
+        let expr = Expr::Obj(vec![
+            obj_prop("a", PropValue::None),
+            ObjProp::Spread(Expr::ident_from("b")),
+        ]);
+        let pat = Pat::try_from_expr(expr.clone()).unwrap();
+        assert_eq!(
+            pat,
+            Pat::Obj(vec![
+                prop(PropKey::Pat(Pat::ident_from("a")), PropValue::None, false),
+                ObjPatPart::Rest(Box::new(Pat::ident_from("b"))),
+            ])
+        );
+        assert_eq!(names(&pat), vec!["a", "b"]);
+        assert_eq!(Expr::from_pat(pat), expr);
+        // ({...b, a} = o)
+        let expr = Expr::Obj(vec![
+            ObjProp::Spread(Expr::ident_from("b")),
+            obj_prop("a", PropValue::None),
+        ]);
+        assert_eq!(Pat::try_from_expr(expr), Err(ConvertError::RestNotLast));
+        // ({a: f()} = o)
+        let call = Expr::Call(crate::expr::CallExpr::new(Expr::ident_from("f"), vec![]));
+        let expr = Expr::Obj(vec![obj_prop("a", PropValue::Expr(call))]);
+        assert_eq!(Pat::try_from_expr(expr), Err(ConvertError::InvalidTarget));
+    }
+
+    #[test]
+    fn array_expr_to_pat() {
+        // [a, b = 1, , o.c, ...d] = e
+        let one = || Box::new(Expr::Lit(Lit::number_from("1")));
+        let member = Expr::Member(crate::expr::MemberExpr::new(
+            Expr::ident_from("o"),
+            crate::expr::MemberProp::ident_from("c"),
+        ));
+        let expr = Expr::Array(vec![
+            Some(Expr::ident_from("a")),
+            Some(Expr::Assign(AssignExpr {
+                operator: AssignOp::Equal,
+                left: AssignLeft::Expr(Box::new(Expr::ident_from("b"))),
+                right: one(),
+            })),
+            None,
+            Some(member.clone()),
+            Some(Expr::Spread(Box::new(Expr::ident_from("d")))),
+        ]);
+        let pat = Pat::try_from(expr.clone()).unwrap();
+        assert_eq!(
+            pat,
+            Pat::Array(vec![
+                Some(ArrayPatPart::Pat(Pat::ident_from("a"))),
+                Some(ArrayPatPart::Pat(Pat::Assign(AssignPat {
+                    left: Box::new(Pat::ident_from("b")),
+                    right: one(),
+                }))),
+                None,
+                Some(ArrayPatPart::Expr(member)),
+                Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(Pat::ident_from("d"))))),
+            ])
+        );
+        assert_eq!(Expr::from(pat), expr);
+        // [...a, b] = c
+        let expr = Expr::Array(vec![
+            Some(Expr::Spread(Box::new(Expr::ident_from("a")))),
+            Some(Expr::ident_from("b")),
+        ]);
+        assert_eq!(Pat::try_from_expr(expr), Err(ConvertError::RestNotLast));
+        // [1] = c
+        let expr = Expr::Array(vec![Some(Expr::Lit(Lit::number_from("1")))]);
+        let err = Pat::try_from_expr(expr).unwrap_err();
+        assert_eq!(err.to_string(), "invalid destructuring target");
+    }
+}
@@ -0,0 +1,708 @@
+//! Borrowing an AST for a shorter lifetime
+//!
+//! `Reborrow` builds a copy of a node where every string is a
+//! `Cow::Borrowed` pointing into the original, the text itself is
+//! never copied even when the original owns it. This is handy when
+//! a `Program<'static>` needs to be passed somewhere that expects
+//! the same lifetime as some shorter lived text.
+use std::borrow::Cow;
+
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// Convert a node into a copy that borrows all
+/// of its text from `self`
+/// ```
+/// use resast::prelude::*;
+/// use resast::reborrow::Reborrow;
+/// let owned = Expr::Ident(Ident::new("a".to_string()));
+/// let borrowed = owned.reborrow();
+/// assert_eq!(borrowed, owned);
+/// ```
+pub trait Reborrow<'b> {
+    type Output: 'b;
+    fn reborrow(&'b self) -> Self::Output;
+}
+
+impl<'a> Program<'a> {
+    /// A copy of this program that borrows all of its text
+    /// from `self`, see the [`reborrow`](crate::reborrow) module
+    pub fn reborrow<'b>(&'b self) -> Program<'b>
+    where
+        'a: 'b,
+    {
+        Reborrow::reborrow(self)
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Cow<'a, str> {
+    type Output = Cow<'b, str>;
+    fn reborrow(&'b self) -> Self::Output {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'b, T: Reborrow<'b>> Reborrow<'b> for Box<T> {
+    type Output = Box<T::Output>;
+    fn reborrow(&'b self) -> Self::Output {
+        Box::new((**self).reborrow())
+    }
+}
+
+impl<'b, T: Reborrow<'b>> Reborrow<'b> for Option<T> {
+    type Output = Option<T::Output>;
+    fn reborrow(&'b self) -> Self::Output {
+        self.as_ref().map(|node| node.reborrow())
+    }
+}
+
+impl<'b, T: Reborrow<'b>> Reborrow<'b> for Vec<T> {
+    type Output = Vec<T::Output>;
+    fn reborrow(&'b self) -> Self::Output {
+        self.iter().map(|node| node.reborrow()).collect()
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Ident<'a> {
+    type Output = Ident<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Ident {
+            name: self.name.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Program<'a> {
+    type Output = Program<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Program::Mod(parts, hashbang) => Program::Mod(parts.reborrow(), hashbang.reborrow()),
+            Program::Script(parts, hashbang) => {
+                Program::Script(parts.reborrow(), hashbang.reborrow())
+            }
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ProgramPart<'a> {
+    type Output = ProgramPart<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ProgramPart::Dir(dir) => ProgramPart::Dir(dir.reborrow()),
+            ProgramPart::Decl(decl) => ProgramPart::Decl(decl.reborrow()),
+            ProgramPart::Stmt(stmt) => ProgramPart::Stmt(stmt.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Dir<'a> {
+    type Output = Dir<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Dir {
+            expr: self.expr.reborrow(),
+            dir: self.dir.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Func<'a> {
+    type Output = Func<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Func {
+            id: self.id.reborrow(),
+            params: self.params.reborrow(),
+            body: self.body.reborrow(),
+            generator: self.generator,
+            is_async: self.is_async,
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for FuncArg<'a> {
+    type Output = FuncArg<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            FuncArg::Expr(expr) => FuncArg::Expr(expr.reborrow()),
+            FuncArg::Pat(pat) => FuncArg::Pat(pat.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for FuncBody<'a> {
+    type Output = FuncBody<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        FuncBody(self.0.reborrow())
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Class<'a> {
+    type Output = Class<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Class {
+            id: self.id.reborrow(),
+            super_class: self.super_class.reborrow(),
+            body: ClassBody(self.body.0.reborrow()),
+            decorators: self.decorators.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ClassMember<'a> {
+    type Output = ClassMember<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ClassMember::Method(method) => ClassMember::Method(method.reborrow()),
+            ClassMember::Field(field) => ClassMember::Field(field.reborrow()),
+            ClassMember::StaticBlock(block) => ClassMember::StaticBlock(block.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for MethodDef<'a> {
+    type Output = MethodDef<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        MethodDef {
+            key: self.key.reborrow(),
+            value: self.value.reborrow(),
+            kind: self.kind,
+            computed: self.computed,
+            is_static: self.is_static,
+            decorators: self.decorators.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for FieldDef<'a> {
+    type Output = FieldDef<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        FieldDef {
+            key: self.key.reborrow(),
+            value: self.value.reborrow(),
+            computed: self.computed,
+            is_static: self.is_static,
+            decorators: self.decorators.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Decorator<'a> {
+    type Output = Decorator<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Decorator {
+            expression: self.expression.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Decl<'a> {
+    type Output = Decl<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Decl::Var(kind, decls) => Decl::Var(*kind, decls.reborrow()),
+            Decl::Func(func) => Decl::Func(func.reborrow()),
+            Decl::Class(class) => Decl::Class(class.reborrow()),
+            Decl::Import(import) => Decl::Import(import.reborrow()),
+            Decl::Export(export) => Decl::Export(export.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for VarDecl<'a> {
+    type Output = VarDecl<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        VarDecl {
+            id: self.id.reborrow(),
+            init: self.init.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ModImport<'a> {
+    type Output = ModImport<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        ModImport {
+            specifiers: self.specifiers.reborrow(),
+            source: self.source.reborrow(),
+            attributes: self.attributes.reborrow(),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ImportAttribute<'a> {
+    type Output = ImportAttribute<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        ImportAttribute {
+            key: match &self.key {
+                ImportAttributeKey::Ident(ident) => ImportAttributeKey::Ident(ident.reborrow()),
+                ImportAttributeKey::Lit(lit) => ImportAttributeKey::Lit(lit.reborrow()),
+            },
+            value: self.value.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ImportSpecifier<'a> {
+    type Output = ImportSpecifier<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ImportSpecifier::Normal(spec) => ImportSpecifier::Normal(NormalImportSpec {
+                local: spec.local.reborrow(),
+                imported: spec.imported.reborrow(),
+            }),
+            ImportSpecifier::Default(ident) => ImportSpecifier::Default(ident.reborrow()),
+            ImportSpecifier::Namespace(ident) => ImportSpecifier::Namespace(ident.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ModuleExportName<'a> {
+    type Output = ModuleExportName<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ModuleExportName::Ident(ident) => ModuleExportName::Ident(ident.reborrow()),
+            ModuleExportName::Str(lit) => ModuleExportName::Str(lit.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ModExport<'a> {
+    type Output = ModExport<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                ModExport::Default(DefaultExportDecl::Decl(decl.reborrow()))
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                ModExport::Default(DefaultExportDecl::Expr(expr.reborrow()))
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                ModExport::Named(NamedExportDecl::Decl(decl.reborrow()))
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                ModExport::Named(NamedExportDecl::Specifier(
+                    specs.reborrow(),
+                    source.reborrow(),
+                ))
+            }
+            ModExport::All(alias, source) => {
+                ModExport::All(alias.reborrow(), source.reborrow())
+            }
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ExportSource<'a> {
+    type Output = ExportSource<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        ExportSource {
+            source: self.source.reborrow(),
+            attributes: self.attributes.reborrow(),
+            attributes_keyword: self.attributes_keyword,
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ExportSpecifier<'a> {
+    type Output = ExportSpecifier<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        ExportSpecifier {
+            local: self.local.reborrow(),
+            exported: self.exported.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Stmt<'a> {
+    type Output = Stmt<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Stmt::Expr(expr) => Stmt::Expr(expr.reborrow()),
+            Stmt::Block(block) => Stmt::Block(block.reborrow()),
+            Stmt::Empty => Stmt::Empty,
+            Stmt::Debugger => Stmt::Debugger,
+            Stmt::With(stmt) => Stmt::With(WithStmt {
+                object: stmt.object.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::Return(arg) => Stmt::Return(arg.reborrow()),
+            Stmt::Labeled(stmt) => Stmt::Labeled(LabeledStmt {
+                label: stmt.label.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::Break(label) => Stmt::Break(label.reborrow()),
+            Stmt::Continue(label) => Stmt::Continue(label.reborrow()),
+            Stmt::If(stmt) => Stmt::If(IfStmt {
+                test: stmt.test.reborrow(),
+                consequent: stmt.consequent.reborrow(),
+                alternate: stmt.alternate.reborrow(),
+            }),
+            Stmt::Switch(stmt) => Stmt::Switch(SwitchStmt {
+                discriminant: stmt.discriminant.reborrow(),
+                cases: stmt.cases.reborrow(),
+            }),
+            Stmt::Throw(expr) => Stmt::Throw(expr.reborrow()),
+            Stmt::Try(stmt) => Stmt::Try(TryStmt {
+                block: stmt.block.reborrow(),
+                handler: stmt.handler.reborrow(),
+                finalizer: stmt.finalizer.reborrow(),
+            }),
+            Stmt::While(stmt) => Stmt::While(WhileStmt {
+                test: stmt.test.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::DoWhile(stmt) => Stmt::DoWhile(DoWhileStmt {
+                test: stmt.test.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::For(stmt) => Stmt::For(ForStmt {
+                init: stmt.init.reborrow(),
+                test: stmt.test.reborrow(),
+                update: stmt.update.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::ForIn(stmt) => Stmt::ForIn(ForInStmt {
+                left: stmt.left.reborrow(),
+                right: stmt.right.reborrow(),
+                body: stmt.body.reborrow(),
+            }),
+            Stmt::ForOf(stmt) => Stmt::ForOf(ForOfStmt {
+                left: stmt.left.reborrow(),
+                right: stmt.right.reborrow(),
+                body: stmt.body.reborrow(),
+                is_await: stmt.is_await,
+            }),
+            Stmt::Var(decls) => Stmt::Var(decls.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for BlockStmt<'a> {
+    type Output = BlockStmt<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        BlockStmt(self.0.reborrow())
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for SwitchCase<'a> {
+    type Output = SwitchCase<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        SwitchCase {
+            test: self.test.reborrow(),
+            consequent: self.consequent.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for CatchClause<'a> {
+    type Output = CatchClause<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        CatchClause {
+            param: self.param.reborrow(),
+            body: self.body.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for LoopInit<'a> {
+    type Output = LoopInit<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            LoopInit::Variable(kind, decls) => LoopInit::Variable(*kind, decls.reborrow()),
+            LoopInit::Expr(expr) => LoopInit::Expr(expr.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for LoopLeft<'a> {
+    type Output = LoopLeft<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            LoopLeft::Expr(expr) => LoopLeft::Expr(expr.reborrow()),
+            LoopLeft::Variable(kind, decl) => LoopLeft::Variable(*kind, decl.reborrow()),
+            LoopLeft::Pat(pat) => LoopLeft::Pat(pat.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Expr<'a> {
+    type Output = Expr<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Expr::Array(elements) => Expr::Array(elements.reborrow()),
+            Expr::ArrowFunc(arrow) => Expr::ArrowFunc(ArrowFuncExpr {
+                id: arrow.id.reborrow(),
+                params: arrow.params.reborrow(),
+                body: match &arrow.body {
+                    ArrowFuncBody::FuncBody(body) => ArrowFuncBody::FuncBody(body.reborrow()),
+                    ArrowFuncBody::Expr(expr) => ArrowFuncBody::Expr(expr.reborrow()),
+                },
+                expression: arrow.expression,
+                is_async: arrow.is_async,
+            }),
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                Expr::ArrowParamPlaceHolder(args.reborrow(), *is_async)
+            }
+            Expr::Assign(assign) => Expr::Assign(AssignExpr {
+                operator: assign.operator,
+                left: match &assign.left {
+                    AssignLeft::Pat(pat) => AssignLeft::Pat(pat.reborrow()),
+                    AssignLeft::Expr(expr) => AssignLeft::Expr(expr.reborrow()),
+                },
+                right: assign.right.reborrow(),
+            }),
+            Expr::Await(expr) => Expr::Await(expr.reborrow()),
+            Expr::Binary(bin) => Expr::Binary(BinaryExpr {
+                operator: bin.operator,
+                left: bin.left.reborrow(),
+                right: bin.right.reborrow(),
+            }),
+            Expr::Class(class) => Expr::Class(class.reborrow()),
+            Expr::Call(call) => Expr::Call(CallExpr {
+                callee: call.callee.reborrow(),
+                arguments: call.arguments.reborrow(),
+                optional: call.optional,
+            }),
+            Expr::Conditional(cond) => Expr::Conditional(ConditionalExpr {
+                test: cond.test.reborrow(),
+                alternate: cond.alternate.reborrow(),
+                consequent: cond.consequent.reborrow(),
+            }),
+            Expr::Func(func) => Expr::Func(func.reborrow()),
+            Expr::Ident(ident) => Expr::Ident(ident.reborrow()),
+            Expr::Import(import) => Expr::Import(ImportExpr {
+                source: import.source.reborrow(),
+                options: import.options.reborrow(),
+            }),
+            Expr::Lit(lit) => Expr::Lit(lit.reborrow()),
+            Expr::Logical(logical) => Expr::Logical(LogicalExpr {
+                operator: logical.operator,
+                left: logical.left.reborrow(),
+                right: logical.right.reborrow(),
+            }),
+            Expr::Member(member) => Expr::Member(MemberExpr {
+                object: member.object.reborrow(),
+                property: member.property.reborrow(),
+                optional: member.optional,
+            }),
+            Expr::MetaProp(meta) => Expr::MetaProp(MetaProp {
+                meta: meta.meta.reborrow(),
+                property: meta.property.reborrow(),
+            }),
+            Expr::OptionalChain(expr) => Expr::OptionalChain(expr.reborrow()),
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(expr) => Expr::Paren(expr.reborrow()),
+            Expr::PrivateId(id) => Expr::PrivateId(id.reborrow()),
+            Expr::New(new) => Expr::New(NewExpr {
+                callee: new.callee.reborrow(),
+                arguments: new.arguments.reborrow(),
+            }),
+            Expr::Obj(props) => Expr::Obj(props.reborrow()),
+            Expr::Sequence(seq) => Expr::Sequence(SequenceExpr {
+                head: seq.head.reborrow(),
+                tail: seq.tail.reborrow(),
+            }),
+            Expr::Spread(expr) => Expr::Spread(expr.reborrow()),
+            Expr::Super => Expr::Super,
+            Expr::TaggedTemplate(tagged) => Expr::TaggedTemplate(TaggedTemplateExpr {
+                tag: tagged.tag.reborrow(),
+                quasi: tagged.quasi.reborrow(),
+            }),
+            Expr::This => Expr::This,
+            Expr::Unary(unary) => Expr::Unary(UnaryExpr {
+                operator: unary.operator,
+                prefix: unary.prefix,
+                argument: unary.argument.reborrow(),
+            }),
+            Expr::Update(update) => Expr::Update(UpdateExpr {
+                operator: update.operator,
+                argument: update.argument.reborrow(),
+                prefix: update.prefix,
+            }),
+            Expr::Yield(y) => Expr::Yield(YieldExpr {
+                argument: y.argument.reborrow(),
+                delegate: y.delegate,
+            }),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ObjProp<'a> {
+    type Output = ObjProp<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ObjProp::Prop(prop) => ObjProp::Prop(prop.reborrow()),
+            ObjProp::Spread(expr) => ObjProp::Spread(expr.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Prop<'a> {
+    type Output = Prop<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        Prop {
+            key: self.key.reborrow(),
+            value: match &self.value {
+                PropValue::Expr(expr) => PropValue::Expr(expr.reborrow()),
+                PropValue::Pat(pat) => PropValue::Pat(pat.reborrow()),
+                PropValue::None => PropValue::None,
+            },
+            kind: self.kind,
+            method: self.method,
+            computed: self.computed,
+            short_hand: self.short_hand,
+            is_static: self.is_static,
+            decorators: self.decorators.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for PropKey<'a> {
+    type Output = PropKey<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            PropKey::Lit(lit) => PropKey::Lit(lit.reborrow()),
+            PropKey::Expr(expr) => PropKey::Expr(expr.reborrow()),
+            PropKey::Pat(pat) => PropKey::Pat(pat.reborrow()),
+            PropKey::Private(id) => PropKey::Private(id.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for MemberProp<'a> {
+    type Output = MemberProp<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            MemberProp::Ident(ident) => MemberProp::Ident(ident.reborrow()),
+            MemberProp::Private(id) => MemberProp::Private(id.reborrow()),
+            MemberProp::Computed(expr) => MemberProp::Computed(expr.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for PrivateId<'a> {
+    type Output = PrivateId<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        PrivateId {
+            name: self.name.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Lit<'a> {
+    type Output = Lit<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Lit::Null => Lit::Null,
+            Lit::String(StringLit::Double(s)) => Lit::String(StringLit::Double(s.reborrow())),
+            Lit::String(StringLit::Single(s)) => Lit::String(StringLit::Single(s.reborrow())),
+            Lit::Number(number) => Lit::Number(NumberLit(number.0.reborrow())),
+            Lit::BigInt(digits) => Lit::BigInt(digits.reborrow()),
+            Lit::Boolean(b) => Lit::Boolean(*b),
+            Lit::RegEx(re) => Lit::RegEx(RegEx {
+                pattern: re.pattern.reborrow(),
+                flags: re.flags.reborrow(),
+            }),
+            Lit::Template(template) => Lit::Template(template.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for TemplateLit<'a> {
+    type Output = TemplateLit<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        TemplateLit {
+            quasis: self.quasis.reborrow(),
+            expressions: self.expressions.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for TemplateElement<'a> {
+    type Output = TemplateElement<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        TemplateElement {
+            tail: self.tail,
+            cooked: self.cooked.reborrow(),
+            raw: self.raw.reborrow(),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for Pat<'a> {
+    type Output = Pat<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            Pat::Ident(ident) => Pat::Ident(ident.reborrow()),
+            Pat::Obj(parts) => Pat::Obj(parts.reborrow()),
+            Pat::Array(parts) => Pat::Array(parts.reborrow()),
+            Pat::RestElement(pat) => Pat::RestElement(pat.reborrow()),
+            Pat::Assign(assign) => Pat::Assign(AssignPat {
+                left: assign.left.reborrow(),
+                right: assign.right.reborrow(),
+            }),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ArrayPatPart<'a> {
+    type Output = ArrayPatPart<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ArrayPatPart::Pat(pat) => ArrayPatPart::Pat(pat.reborrow()),
+            ArrayPatPart::Expr(expr) => ArrayPatPart::Expr(expr.reborrow()),
+        }
+    }
+}
+
+impl<'a: 'b, 'b> Reborrow<'b> for ObjPatPart<'a> {
+    type Output = ObjPatPart<'b>;
+    fn reborrow(&'b self) -> Self::Output {
+        match self {
+            ObjPatPart::Assign(prop) => ObjPatPart::Assign(prop.reborrow()),
+            ObjPatPart::Rest(pat) => ObjPatPart::Rest(pat.reborrow()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::owned::IntoOwned;
+
+    #[test]
+    fn reborrow_owned_program() {
+        // function f(a) { return a.b; }
+        let func = Func::new(
+            Some(Ident::from("f")),
+            vec![FuncArg::Pat(Pat::ident_from("a"))],
+            FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::Member(
+                MemberExpr::new(Expr::ident_from("a"), MemberProp::ident_from("b")),
+            ))))]),
+            false,
+            false,
+        );
+        let owned = Program::script(vec![ProgramPart::Decl(Decl::Func(func))]).into_owned();
+        {
+            let view = owned.reborrow();
+            assert_eq!(view, owned);
+            let property = match view.parts()[0] {
+                ProgramPart::Decl(Decl::Func(ref func)) => match func.body.0[0] {
+                    ProgramPart::Stmt(Stmt::Return(Some(Expr::Member(ref member)))) => {
+                        match &member.property {
+                            MemberProp::Ident(ident) => ident,
+                            other => panic!("unexpected property {:?}", other),
+                        }
+                    }
+                    ref other => panic!("unexpected body {:?}", other),
+                },
+                ref other => panic!("unexpected part {:?}", other),
+            };
+            assert_eq!(property.name, "b");
+            assert!(matches!(property.name, Cow::Borrowed(_)));
+        }
+    }
+}
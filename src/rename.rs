@@ -0,0 +1,474 @@
+//! Renaming a binding and every reference to it
+//!
+//! Scopes follow the same rules as `analysis::free_identifiers`, so
+//! a name that is declared again in a nested scope is left alone
+//! inside of that scope.
+use std::borrow::Cow;
+
+use crate::analysis::{body_names, decl_names, lexical_names, loop_left_names, param_names};
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::visit_mut::*;
+use crate::*;
+
+/// A scope in a program, scopes are numbered in the order they
+/// are entered while walking the tree depth first.
+///
+/// The program itself is `ScopeRef::PROGRAM`, after that every
+/// function, arrow function, class, block, `switch` body, `catch`
+/// clause and `for`, `for in` or `for of` loop opens a new scope.
+/// A function's body shares the function's scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeRef(pub usize);
+
+impl ScopeRef {
+    pub const PROGRAM: ScopeRef = ScopeRef(0);
+}
+
+/// Rename the binding `from` that belongs to `scope`, along with
+/// every reference to it, returning the number of identifiers
+/// changed.
+///
+/// An identifier belongs to the innermost scope around it that
+/// declares its name. Identifiers that no scope declares belong
+/// to `ScopeRef::PROGRAM`, renaming a global there renames every
+/// unshadowed use of it. Shorthand properties like `{ x }` are
+/// expanded to `{ x: y }` so the property name doesn't change, and
+/// export specifiers keep their exported name the same way
+/// ```
+/// # use resast::prelude::*;
+/// use resast::rename::{rename_binding, ScopeRef};
+/// // let x = 1; x;
+/// let mut program = Program::script(vec![
+///     ProgramPart::Decl(Decl::Var(
+///         VarKind::Let,
+///         vec![VarDecl {
+///             id: Pat::ident_from("x"),
+///             init: Some(Expr::Lit(Lit::number_from("1"))),
+///         }],
+///     )),
+///     ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("x"))),
+/// ]);
+/// assert_eq!(rename_binding(&mut program, "x", "y", ScopeRef::PROGRAM), 2);
+/// assert_eq!(program.parts()[1], ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("y"))));
+/// ```
+pub fn rename_binding(program: &mut Program, from: &str, to: &str, scope: ScopeRef) -> usize {
+    let mut renamer = Renamer {
+        from,
+        to,
+        target: scope.0,
+        next: 0,
+        scopes: Vec::new(),
+        renamed: 0,
+    };
+    renamer.visit_program(program);
+    renamer.renamed
+}
+
+struct Renamer<'r> {
+    from: &'r str,
+    to: &'r str,
+    target: usize,
+    next: usize,
+    /// The number of each scope we are in and if it declares `from`
+    scopes: Vec<(usize, bool)>,
+    renamed: usize,
+}
+
+impl<'r> Renamer<'r> {
+    /// Run `f` in a new scope, `declares` is if that scope
+    /// has its own binding for `from`
+    fn in_scope(&mut self, declares: bool, f: impl FnOnce(&mut Self)) {
+        self.scopes.push((self.next, declares));
+        self.next += 1;
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn is_target(&self, name: &str) -> bool {
+        if name != self.from {
+            return false;
+        }
+        match self.scopes.iter().rev().find(|(_, declares)| *declares) {
+            Some((scope, _)) => *scope == self.target,
+            None => self.target == ScopeRef::PROGRAM.0,
+        }
+    }
+
+    fn rename(&mut self, ident: &mut Ident) {
+        if self.is_target(&ident.name) {
+            ident.name = Cow::Owned(self.to.to_string());
+            self.renamed += 1;
+        }
+    }
+
+    fn func(&mut self, func: &mut Func, is_expr: bool) {
+        let mut names = vec!["arguments"];
+        // a function expression's name is only visible inside of
+        // it, a declaration's belongs to the enclosing scope
+        if is_expr {
+            names.extend(func.id.iter().map(|id| &*id.name));
+        }
+        param_names(&func.params, &mut names);
+        body_names(&func.body.0, &mut names);
+        let declares = names.contains(&self.from);
+        if let Some(id) = &mut func.id {
+            if !is_expr {
+                self.rename(id);
+            }
+        }
+        self.in_scope(declares, |v| {
+            if let (Some(id), true) = (&mut func.id, is_expr) {
+                v.rename(id);
+            }
+            for param in &mut func.params {
+                v.visit_func_arg(param);
+            }
+            v.visit_func_body(&mut func.body);
+        })
+    }
+
+    fn class(&mut self, class: &mut Class, is_expr: bool) {
+        for decorator in &mut class.decorators {
+            self.visit_decorator(decorator);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_expr(super_class);
+        }
+        let declares = match &mut class.id {
+            Some(id) if is_expr => id.name == self.from,
+            Some(id) => {
+                self.rename(id);
+                false
+            }
+            None => false,
+        };
+        self.in_scope(declares, |v| {
+            if let (Some(id), true) = (&mut class.id, is_expr) {
+                v.rename(id);
+            }
+            for member in &mut class.body.0 {
+                v.visit_class_member(member);
+            }
+        })
+    }
+}
+
+/// The names declared directly in a program's scope
+fn program_names<'a>(parts: &'a [ProgramPart<'a>]) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    body_names(parts, &mut names);
+    for part in parts {
+        match part {
+            ProgramPart::Decl(Decl::Import(import)) => {
+                names.extend(import.specifiers.iter().map(|spec| match spec {
+                    ImportSpecifier::Normal(spec) => &*spec.local.name,
+                    ImportSpecifier::Default(local) | ImportSpecifier::Namespace(local) => {
+                        &*local.name
+                    }
+                }))
+            }
+            ProgramPart::Decl(Decl::Export(export)) => match &**export {
+                ModExport::Named(NamedExportDecl::Decl(d))
+                | ModExport::Default(DefaultExportDecl::Decl(d)) => match d {
+                    Decl::Var(_, decls) => decl_names(decls, &mut names),
+                    Decl::Func(Func { id: Some(id), .. })
+                    | Decl::Class(Class { id: Some(id), .. }) => names.push(&id.name),
+                    _ => {}
+                },
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    names
+}
+
+/// The identifier a shorthand property's value binds or refers to
+fn shorthand_value<'v, 'a>(value: &'v PropValue<'a>) -> Option<&'v Ident<'a>> {
+    match value {
+        PropValue::Expr(Expr::Ident(id)) | PropValue::Pat(Pat::Ident(id)) => Some(id),
+        PropValue::Pat(Pat::Assign(assign)) => match &*assign.left {
+            Pat::Ident(id) => Some(id),
+            _ => None,
+        },
+        PropValue::Expr(Expr::Assign(assign)) => match &assign.left {
+            AssignLeft::Pat(Pat::Ident(id)) => Some(id),
+            AssignLeft::Expr(expr) => match &**expr {
+                Expr::Ident(id) => Some(id),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl<'a, 'r> VisitorMut<'a> for Renamer<'r> {
+    fn visit_program(&mut self, program: &mut Program<'a>) {
+        let declares = program_names(program.parts()).contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_program(v, program))
+    }
+    fn visit_ident(&mut self, ident: &mut Ident<'a>) {
+        self.rename(ident)
+    }
+    fn visit_decl(&mut self, decl: &mut Decl<'a>) {
+        match decl {
+            Decl::Func(func) => self.func(func, false),
+            Decl::Class(class) => self.class(class, false),
+            _ => walk_mut_decl(self, decl),
+        }
+    }
+    fn visit_func(&mut self, func: &mut Func<'a>) {
+        self.func(func, true)
+    }
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        self.class(class, true)
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &mut ArrowFuncExpr<'a>) {
+        let mut names = Vec::new();
+        param_names(&expr.params, &mut names);
+        if let ArrowFuncBody::FuncBody(body) = &expr.body {
+            body_names(&body.0, &mut names);
+        }
+        let declares = names.contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_arrow_func_expr(v, expr))
+    }
+    fn visit_block_stmt(&mut self, block: &mut BlockStmt<'a>) {
+        let mut names = Vec::new();
+        lexical_names(&block.0, &mut names);
+        let declares = names.contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_block_stmt(v, block))
+    }
+    fn visit_switch_stmt(&mut self, stmt: &mut SwitchStmt<'a>) {
+        self.visit_expr(&mut stmt.discriminant);
+        let mut names = Vec::new();
+        for case in &stmt.cases {
+            lexical_names(&case.consequent, &mut names);
+        }
+        let declares = names.contains(&self.from);
+        self.in_scope(declares, |v| {
+            for case in &mut stmt.cases {
+                v.visit_switch_case(case);
+            }
+        })
+    }
+    fn visit_catch_clause(&mut self, clause: &mut CatchClause<'a>) {
+        let declares = clause
+            .param
+            .iter()
+            .flat_map(Pat::bound_names)
+            .any(|id| id.name == self.from);
+        self.in_scope(declares, |v| walk_mut_catch_clause(v, clause))
+    }
+    fn visit_for_stmt(&mut self, stmt: &mut ForStmt<'a>) {
+        let mut names = Vec::new();
+        if let Some(LoopInit::Variable(kind, decls)) = &stmt.init {
+            if *kind != VarKind::Var {
+                decl_names(decls, &mut names);
+            }
+        }
+        let declares = names.contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_for_stmt(v, stmt))
+    }
+    fn visit_for_in_stmt(&mut self, stmt: &mut ForInStmt<'a>) {
+        let declares = loop_left_names(&stmt.left).contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_for_in_stmt(v, stmt))
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &mut ForOfStmt<'a>) {
+        let declares = loop_left_names(&stmt.left).contains(&self.from);
+        self.in_scope(declares, |v| walk_mut_for_of_stmt(v, stmt))
+    }
+    // labels, property names and module export names aren't bindings
+    fn visit_stmt(&mut self, stmt: &mut Stmt<'a>) {
+        match stmt {
+            Stmt::Break(_) | Stmt::Continue(_) => {}
+            _ => walk_mut_stmt(self, stmt),
+        }
+    }
+    fn visit_labeled_stmt(&mut self, stmt: &mut LabeledStmt<'a>) {
+        self.visit_stmt(&mut stmt.body)
+    }
+    fn visit_member_prop(&mut self, prop: &mut MemberProp<'a>) {
+        if let MemberProp::Computed(expr) = prop {
+            self.visit_expr(expr);
+        }
+    }
+    fn visit_meta_prop(&mut self, _meta: &mut MetaProp<'a>) {}
+    fn visit_module_export_name(&mut self, _name: &mut ModuleExportName<'a>) {}
+    fn visit_import_attribute(&mut self, _attr: &mut ImportAttribute<'a>) {}
+    fn visit_mod_export(&mut self, export: &mut ModExport<'a>) {
+        match export {
+            // the names in a re-export are from the other module
+            ModExport::Named(NamedExportDecl::Specifier(_, Some(_))) => {}
+            _ => walk_mut_mod_export(self, export),
+        }
+    }
+    fn visit_prop(&mut self, prop: &mut Prop<'a>) {
+        for decorator in &mut prop.decorators {
+            self.visit_decorator(decorator);
+        }
+        if prop.computed {
+            self.visit_prop_key(&mut prop.key);
+        } else if prop.short_hand && prop.value == PropValue::None {
+            // `{ x }` becomes `{ x: y }`
+            let value = match &prop.key {
+                PropKey::Expr(Expr::Ident(id)) if self.is_target(&id.name) => {
+                    PropValue::Expr(Expr::Ident(Ident::new(self.to.to_string())))
+                }
+                PropKey::Pat(Pat::Ident(id)) if self.is_target(&id.name) => {
+                    PropValue::Pat(Pat::Ident(Ident::new(self.to.to_string())))
+                }
+                _ => return,
+            };
+            prop.value = value;
+            prop.short_hand = false;
+            self.renamed += 1;
+            return;
+        }
+        self.visit_prop_value(&mut prop.value);
+        // `{ x = 1 }` becomes `{ x: y = 1 }`
+        if prop.short_hand && !prop.computed {
+            if let (PropKey::Expr(Expr::Ident(key)), Some(value))
+            | (PropKey::Pat(Pat::Ident(key)), Some(value)) =
+                (&prop.key, shorthand_value(&prop.value))
+            {
+                prop.short_hand = key.name == value.name;
+            }
+        }
+    }
+    fn visit_method_def(&mut self, method: &mut MethodDef<'a>) {
+        for decorator in &mut method.decorators {
+            self.visit_decorator(decorator);
+        }
+        if method.computed {
+            self.visit_prop_key(&mut method.key);
+        }
+        self.visit_func(&mut method.value);
+    }
+    fn visit_field_def(&mut self, field: &mut FieldDef<'a>) {
+        for decorator in &mut field.decorators {
+            self.visit_decorator(decorator);
+        }
+        if field.computed {
+            self.visit_prop_key(&mut field.key);
+        }
+        if let Some(value) = &mut field.value {
+            self.visit_expr(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn let_decl<'a>(name: &'a str, value: &'a str) -> ProgramPart<'a> {
+        ProgramPart::Decl(Decl::Var(
+            VarKind::Let,
+            vec![VarDecl {
+                id: Pat::ident_from(name),
+                init: Some(Expr::ident_from(value)),
+            }],
+        ))
+    }
+
+    fn expr_stmt(name: &str) -> ProgramPart<'_> {
+        ProgramPart::Stmt(Stmt::Expr(Expr::ident_from(name)))
+    }
+
+    fn func<'a>(name: &'a str, params: &[&'a str], body: Vec<ProgramPart<'a>>) -> ProgramPart<'a> {
+        ProgramPart::Decl(Decl::Func(Func::new(
+            Some(Ident::from(name)),
+            params.iter().map(|p| FuncArg::Pat(Pat::ident_from(p))).collect(),
+            FuncBody(body),
+            false,
+            false,
+        )))
+    }
+
+    /// let x = one;
+    /// function f(x) { x; }
+    /// function g() { let y = x; { let x = two; x; } return { x }; }
+    /// x;
+    ///
+    /// with each `x` replaced by the next name in `names`
+    fn program<'a>(names: [&'a str; 7]) -> Program<'a> {
+        let short_hand = names[5] == "x";
+        let prop = Prop {
+            key: PropKey::Expr(Expr::ident_from("x")),
+            value: if short_hand {
+                PropValue::None
+            } else {
+                PropValue::Expr(Expr::ident_from(names[5]))
+            },
+            kind: PropKind::Init,
+            method: false,
+            computed: false,
+            short_hand,
+            is_static: false,
+            decorators: vec![],
+        };
+        let block = Stmt::Block(BlockStmt(vec![
+            let_decl(names[3], "two"),
+            expr_stmt(names[4]),
+        ]));
+        Program::script(vec![
+            let_decl(names[0], "one"),
+            func("f", &[names[1]], vec![expr_stmt(names[1])]),
+            func(
+                "g",
+                &[],
+                vec![
+                    let_decl("y", names[2]),
+                    ProgramPart::Stmt(block),
+                    ProgramPart::Stmt(Stmt::Return(Some(Expr::Obj(vec![ObjProp::Prop(prop)])))),
+                ],
+            ),
+            expr_stmt(names[6]),
+        ])
+    }
+
+    #[test]
+    fn shadowed_in_inner_function() {
+        let mut renamed = program(["x"; 7]);
+        assert_eq!(rename_binding(&mut renamed, "x", "z", ScopeRef::PROGRAM), 4);
+        assert_eq!(renamed, program(["z", "x", "z", "x", "x", "z", "z"]));
+        // scope 1 is `f`, its parameter shadows the top level `x`
+        let mut renamed = program(["x"; 7]);
+        assert_eq!(rename_binding(&mut renamed, "x", "a", ScopeRef(1)), 2);
+        assert_eq!(renamed, program(["x", "a", "x", "x", "x", "x", "x"]));
+        // scope 3 is the block in `g`
+        let mut renamed = program(["x"; 7]);
+        assert_eq!(rename_binding(&mut renamed, "x", "b", ScopeRef(3)), 2);
+        assert_eq!(renamed, program(["x", "x", "x", "b", "b", "x", "x"]));
+        // `g` doesn't declare `x` so nothing belongs to it
+        let mut renamed = program(["x"; 7]);
+        assert_eq!(rename_binding(&mut renamed, "x", "c", ScopeRef(2)), 0);
+        assert_eq!(renamed, program(["x"; 7]));
+    }
+
+    #[test]
+    fn names_that_are_not_bindings() {
+        // x: for (;;) { o.x; break x; }
+        let member = MemberExpr::new(Expr::ident_from("o"), MemberProp::ident_from("x"));
+        let body = Stmt::Block(BlockStmt(vec![
+            ProgramPart::Stmt(Stmt::Expr(Expr::Member(member))),
+            ProgramPart::Stmt(Stmt::Break(Some(Ident::from("x")))),
+        ]));
+        let mut program = Program::script(vec![ProgramPart::Stmt(Stmt::Labeled(LabeledStmt {
+            label: Ident::from("x"),
+            body: Box::new(Stmt::For(ForStmt {
+                init: None,
+                test: None,
+                update: None,
+                body: Box::new(body),
+            })),
+        }))]);
+        let before = program.clone();
+        assert_eq!(rename_binding(&mut program, "x", "y", ScopeRef::PROGRAM), 0);
+        assert_eq!(program, before);
+    }
+}
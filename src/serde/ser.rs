@@ -1,3 +1,4 @@
+use crate::estree::unescape::unescaper;
 use crate::prelude::*;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
@@ -9,15 +10,18 @@ impl<'a> Serialize for Program<'a> {
         let mut state = serializer.serialize_struct("Node", 3)?;
         state.serialize_field("type", "Program")?;
         match self {
-            Program::Script(ref body) => {
+            Program::Script(ref body, _) => {
                 state.serialize_field("sourceType", "script")?;
                 state.serialize_field("body", &body)?;
             }
-            Program::Mod(ref body) => {
+            Program::Mod(ref body, _) => {
                 state.serialize_field("sourceType", "module")?;
                 state.serialize_field("body", body)?;
             }
         }
+        if let Some(hashbang) = self.hashbang() {
+            state.serialize_field("hashbang", hashbang)?;
+        }
         state.end()
     }
 }
@@ -82,6 +86,9 @@ impl<'a> Serialize for Decl<'a> {
                 state.serialize_field("body", &c.body)?;
                 state.serialize_field("id", &c.id)?;
                 state.serialize_field("superClass", &c.super_class)?;
+                if !c.decorators.is_empty() {
+                    state.serialize_field("decorators", &c.decorators)?;
+                }
                 state.end()
             }
             Decl::Var(ref kind, ref vs) => {
@@ -96,6 +103,9 @@ impl<'a> Serialize for Decl<'a> {
                 state.serialize_field("type", "ImportDeclaration")?;
                 state.serialize_field("specifiers", &imp.specifiers)?;
                 state.serialize_field("source", &imp.source)?;
+                if let Some(keyword) = imp.clause_keyword() {
+                    state.serialize_field(attributes_key(keyword), &imp.attributes)?;
+                }
                 state.end()
             }
             Decl::Export(ref exp) => exp.serialize(serializer),
@@ -103,16 +113,29 @@ impl<'a> Serialize for Decl<'a> {
     }
 }
 
+/// `with` is the standard `attributes`, the legacy `assert` keeps
+/// the `assertions` name acorn and babel used for it
+fn attributes_key(keyword: AttributesKeyword) -> &'static str {
+    match keyword {
+        AttributesKeyword::With => "attributes",
+        AttributesKeyword::Assert => "assertions",
+    }
+}
+
 impl<'a> Serialize for ModExport<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         match self {
-            ModExport::All(ref source) => {
+            ModExport::All(ref alias, ref source) => {
                 let mut state = serializer.serialize_struct("Node", 7)?;
                 state.serialize_field("type", "ExportAllDeclaration")?;
-                state.serialize_field("source", source)?;
+                state.serialize_field("exported", alias)?;
+                state.serialize_field("source", &source.source)?;
+                if let Some(keyword) = source.clause_keyword() {
+                    state.serialize_field(attributes_key(keyword), &source.attributes)?;
+                }
                 state.end()
             }
             ModExport::Default(ref def) => {
@@ -136,7 +159,15 @@ impl<'a> Serialize for ModExport<'a> {
                         let decl: Option<()> = None;
                         state.serialize_field("declaration", &decl)?;
                         state.serialize_field("specifiers", specs)?;
-                        state.serialize_field("source", source)?;
+                        state.serialize_field("source", &source.as_ref().map(|s| &s.source))?;
+                        if let Some(ref source) = source {
+                            if let Some(keyword) = source.clause_keyword() {
+                                state.serialize_field(
+                                    attributes_key(keyword),
+                                    &source.attributes,
+                                )?;
+                            }
+                        }
                     }
                 }
                 state.end()
@@ -185,6 +216,32 @@ impl<'a> Serialize for ImportSpecifier<'a> {
         }
     }
 }
+impl<'a> Serialize for ModuleExportName<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ModuleExportName::Ident(ref i) => i.serialize(serializer),
+            ModuleExportName::Str(ref s) => s.serialize(serializer),
+        }
+    }
+}
+impl<'a> Serialize for ImportAttribute<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Node", 3)?;
+        state.serialize_field("type", "ImportAttribute")?;
+        match self.key {
+            ImportAttributeKey::Ident(ref i) => state.serialize_field("key", i)?,
+            ImportAttributeKey::Lit(ref l) => state.serialize_field("key", l)?,
+        }
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
 impl<'a> Serialize for ExportSpecifier<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -216,8 +273,37 @@ impl<'a> Serialize for ClassBody<'a> {
     {
         let mut state = serializer.serialize_struct("Node", 7)?;
         state.serialize_field("type", "ClassBody")?;
-        let body: Vec<MethodDef> = self.0.iter().map(MethodDef).collect();
-        state.serialize_field("body", &body)?;
+        state.serialize_field("body", &self.0)?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for ClassMember<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ClassMember::Method(ref m) => m.serialize(serializer),
+            ClassMember::Field(ref f) => f.serialize(serializer),
+            ClassMember::StaticBlock(ref b) => {
+                let mut state = serializer.serialize_struct("Node", 2)?;
+                state.serialize_field("type", "StaticBlock")?;
+                state.serialize_field("body", &b.0)?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<'a> Serialize for Decorator<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Node", 2)?;
+        state.serialize_field("type", "Decorator")?;
+        state.serialize_field("expression", &self.expression)?;
         state.end()
     }
 }
@@ -248,6 +334,18 @@ impl<'a> Serialize for Ident<'a> {
     }
 }
 
+impl<'a> Serialize for PrivateId<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Node", 2)?;
+        state.serialize_field("type", "PrivateIdentifier")?;
+        state.serialize_field("name", &self.name)?;
+        state.end()
+    }
+}
+
 impl<'a> Serialize for VarDecl<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -341,11 +439,12 @@ impl<'a> Serialize for Stmt<'a> {
                 state.end()
             }
             Stmt::ForOf(ref f) => {
-                let mut state = serializer.serialize_struct("Node", 4)?;
+                let mut state = serializer.serialize_struct("Node", 5)?;
                 state.serialize_field("type", "ForOfStatement")?;
                 state.serialize_field("left", &f.left)?;
                 state.serialize_field("right", &f.right)?;
                 state.serialize_field("body", &f.body)?;
+                state.serialize_field("await", &f.is_await)?;
                 state.end()
             }
             Stmt::If(ref f) => {
@@ -414,6 +513,15 @@ impl<'a> Serialize for Lit<'a> {
     {
         match self {
             Lit::Number(ref n) => serialize_number(serializer, n),
+            Lit::BigInt(ref n) => {
+                let mut state = serializer.serialize_struct("Node", 4)?;
+                state.serialize_field("type", "Literal")?;
+                let value: Option<()> = None;
+                state.serialize_field("value", &value)?;
+                state.serialize_field("raw", &format!("{}n", n))?;
+                state.serialize_field("bigint", n)?;
+                state.end()
+            }
             Lit::String(ref sl) => {
                 let mut state = serializer.serialize_struct("Node", 3)?;
                 state.serialize_field("type", "Literal")?;
@@ -509,13 +617,14 @@ fn format_regex_value(r: &RegEx) -> String {
     ret
 }
 
-fn serialize_number<S>(s: S, n: &str) -> Result<S::Ok, S::Error>
+fn serialize_number<S>(s: S, number: &NumberLit) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
     let mut state = s.serialize_struct("Node", 3)?;
     state.serialize_field("type", "Literal")?;
-    state.serialize_field("raw", &n)?;
+    state.serialize_field("raw", number.raw())?;
+    let n: &str = &number.without_separators();
     if n.starts_with("0") {
         if n.len() == 1 {
             serialize_int(&mut state, 10, n)?;
@@ -586,7 +695,7 @@ impl<'a> Serialize for Expr<'a> {
                 state.serialize_field("type", "ArrowFunctionExpression")?;
                 state.serialize_field("id", &a.id)?;
                 state.serialize_field("expression", &a.expression)?;
-                state.serialize_field("generator", &a.generator)?;
+                state.serialize_field("generator", &false)?;
                 state.serialize_field("params", &a.params)?;
                 state.serialize_field("async", &a.is_async)?;
                 match a.body {
@@ -629,6 +738,7 @@ impl<'a> Serialize for Expr<'a> {
                 state.serialize_field("type", "CallExpression")?;
                 state.serialize_field("callee", &c.callee)?;
                 state.serialize_field("arguments", &c.arguments)?;
+                state.serialize_field("optional", &c.optional)?;
                 state.end()
             }
             Expr::Class(ref c) => {
@@ -637,6 +747,9 @@ impl<'a> Serialize for Expr<'a> {
                 state.serialize_field("id", &c.id)?;
                 state.serialize_field("superClass", &c.super_class)?;
                 state.serialize_field("body", &c.body)?;
+                if !c.decorators.is_empty() {
+                    state.serialize_field("decorators", &c.decorators)?;
+                }
                 state.end()
             }
             Expr::Conditional(ref c) => {
@@ -647,18 +760,17 @@ impl<'a> Serialize for Expr<'a> {
                 state.serialize_field("alternate", &c.alternate)?;
                 state.end()
             }
-            Expr::Func(ref f) => {
-                let mut state = serializer.serialize_struct("Node", 6)?;
-                state.serialize_field("type", "FunctionExpression")?;
-                state.serialize_field("id", &f.id)?;
-                state.serialize_field("params", &f.params)?;
-                state.serialize_field("body", &f.body)?;
-                state.serialize_field("generator", &f.generator)?;
-                state.serialize_field("async", &f.is_async)?;
-                state.serialize_field("expression", &false)?;
+            Expr::Func(ref f) => FuncExpr(f).serialize(serializer),
+            Expr::Ident(ref i) => i.serialize(serializer),
+            Expr::Import(ref i) => {
+                let mut state = serializer.serialize_struct("Node", 3)?;
+                state.serialize_field("type", "ImportExpression")?;
+                state.serialize_field("source", &i.source)?;
+                if let Some(ref options) = i.options {
+                    state.serialize_field("options", options)?;
+                }
                 state.end()
             }
-            Expr::Ident(ref i) => i.serialize(serializer),
             Expr::Lit(ref l) => l.serialize(serializer),
             Expr::Logical(ref l) => {
                 let mut state = serializer.serialize_struct("Node", 4)?;
@@ -673,9 +785,24 @@ impl<'a> Serialize for Expr<'a> {
                 state.serialize_field("type", "MemberExpression")?;
                 state.serialize_field("object", &m.object)?;
                 state.serialize_field("property", &m.property)?;
-                state.serialize_field("computed", &m.computed)?;
+                state.serialize_field("computed", &m.is_computed())?;
+                state.serialize_field("optional", &m.optional)?;
+                state.end()
+            }
+            Expr::OptionalChain(ref c) => {
+                let mut state = serializer.serialize_struct("Node", 2)?;
+                state.serialize_field("type", "ChainExpression")?;
+                state.serialize_field("expression", c)?;
+                state.end()
+            }
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(ref p) => {
+                let mut state = serializer.serialize_struct("Node", 2)?;
+                state.serialize_field("type", "ParenthesizedExpression")?;
+                state.serialize_field("expression", p)?;
                 state.end()
             }
+            Expr::PrivateId(ref p) => p.serialize(serializer),
             Expr::MetaProp(ref m) => {
                 let mut state = serializer.serialize_struct("Node", 3)?;
                 state.serialize_field("type", "MetaProperty")?;
@@ -687,7 +814,7 @@ impl<'a> Serialize for Expr<'a> {
                 let mut state = serializer.serialize_struct("Node", 3)?;
                 state.serialize_field("type", "NewExpression")?;
                 state.serialize_field("callee", &n.callee)?;
-                state.serialize_field("arguments", &n.arguments)?;
+                state.serialize_field("arguments", n.args())?;
                 state.end()
             }
             Expr::Obj(ref o) => {
@@ -699,7 +826,7 @@ impl<'a> Serialize for Expr<'a> {
             Expr::Sequence(ref s) => {
                 let mut state = serializer.serialize_struct("Node", 3)?;
                 state.serialize_field("type", "SequenceExpression")?;
-                state.serialize_field("expressions", s)?;
+                state.serialize_field("expressions", &s.iter().collect::<Vec<_>>())?;
                 state.end()
             }
             Expr::Spread(ref s) => {
@@ -827,25 +954,123 @@ impl<'a> Serialize for Prop<'a> {
     }
 }
 
-struct MethodDef<'a>(pub &'a Prop<'a>);
+struct FuncExpr<'a>(pub &'a Func<'a>);
+
+impl<'a> Serialize for FuncExpr<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Node", 6)?;
+        state.serialize_field("type", "FunctionExpression")?;
+        state.serialize_field("id", &self.0.id)?;
+        state.serialize_field("params", &self.0.params)?;
+        state.serialize_field("body", &self.0.body)?;
+        state.serialize_field("generator", &self.0.generator)?;
+        state.serialize_field("async", &self.0.is_async)?;
+        state.serialize_field("expression", &false)?;
+        state.end()
+    }
+}
 
 impl<'a> Serialize for MethodDef<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Node", 3)?;
+        let mut state = serializer.serialize_struct("Node", 7)?;
         state.serialize_field("type", "MethodDefinition")?;
-        state.serialize_field("static", &self.0.is_static)?;
-        state.serialize_field("static", &self.0.is_static)?;
-        state.serialize_field("value", &self.0.value)?;
-        state.serialize_field("computed", &self.0.computed)?;
-        state.serialize_field("kind", &self.0.kind)?;
-        state.serialize_field("key", &self.0.key)?;
+        state.serialize_field("static", &self.is_static)?;
+        state.serialize_field("value", &FuncExpr(&self.value))?;
+        state.serialize_field("computed", &self.computed)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("key", &self.key)?;
+        if !self.decorators.is_empty() {
+            state.serialize_field("decorators", &self.decorators)?;
+        }
         state.end()
     }
 }
 
+impl Serialize for MethodKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            MethodKind::Constructor => serializer.serialize_str("constructor"),
+            MethodKind::Method => serializer.serialize_str("method"),
+            MethodKind::Get => serializer.serialize_str("get"),
+            MethodKind::Set => serializer.serialize_str("set"),
+        }
+    }
+}
+
+impl<'a> Serialize for FieldDef<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Node", 6)?;
+        state.serialize_field("type", "PropertyDefinition")?;
+        state.serialize_field("static", &self.is_static)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("computed", &self.computed)?;
+        state.serialize_field("key", &self.key)?;
+        if !self.decorators.is_empty() {
+            state.serialize_field("decorators", &self.decorators)?;
+        }
+        state.end()
+    }
+}
+
+impl<'a> Serialize for ObjProp<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ObjProp::Prop(ref p) => p.serialize(serializer),
+            ObjProp::Spread(ref s) => {
+                let mut state = serializer.serialize_struct("Node", 2)?;
+                state.serialize_field("type", "SpreadElement")?;
+                state.serialize_field("argument", s)?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl<'a> Serialize for MemberProp<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            MemberProp::Ident(ref i) => i.serialize(serializer),
+            MemberProp::Private(ref p) => p.serialize(serializer),
+            MemberProp::Computed(ref e) => e.serialize(serializer),
+        }
+    }
+}
+
+impl<'a> Serialize for ObjPatPart<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ObjPatPart::Assign(ref p) => p.serialize(serializer),
+            ObjPatPart::Rest(ref r) => {
+                let mut state = serializer.serialize_struct("Node", 2)?;
+                state.serialize_field("type", "RestElement")?;
+                state.serialize_field("argument", r)?;
+                state.end()
+            }
+        }
+    }
+}
+
 impl<'a> Serialize for TemplateLit<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -868,14 +1093,13 @@ impl<'a> Serialize for TemplateElement<'a> {
         state.serialize_field("type", "TemplateElement")?;
         state.serialize_field("tail", &self.tail)?;
         let mut value = ::std::collections::HashMap::new();
-        let cooked = if let Some(s) = unescaper(&self.cooked) {
-            s
-        } else {
-            self.cooked.to_string()
-        };
-        value.insert("cooked", cooked.as_str());
+        let cooked = self
+            .cooked
+            .as_ref()
+            .map(|c| unescaper(c).unwrap_or_else(|| c.to_string()));
+        value.insert("cooked", cooked.as_deref());
         let end_len = if self.raw.ends_with("${") { 2 } else { 1 };
-        value.insert("raw", &self.raw[1..self.raw.len() - end_len]);
+        value.insert("raw", Some(&self.raw[1..self.raw.len() - end_len]));
         state.serialize_field("value", &value)?;
         state.end()
     }
@@ -897,7 +1121,7 @@ impl<'a> Serialize for CatchClause<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Node", 2)?;
+        let mut state = serializer.serialize_struct("Node", 3)?;
         state.serialize_field("type", "CatchClause")?;
         state.serialize_field("param", &self.param)?;
         state.serialize_field("body", &self.body)?;
@@ -936,6 +1160,9 @@ impl Serialize for AssignOp {
             AssignOp::XOrEqual => "^=",
             AssignOp::AndEqual => "&=",
             AssignOp::PowerOfEqual => "**=",
+            AssignOp::LogicalAndEqual => "&&=",
+            AssignOp::LogicalOrEqual => "||=",
+            AssignOp::NullishEqual => "??=",
         };
         serializer.serialize_str(s)
     }
@@ -980,6 +1207,7 @@ impl Serialize for LogicalOp {
         let s = match self {
             LogicalOp::And => "&&",
             LogicalOp::Or => "||",
+            LogicalOp::NullishCoalescing => "??",
         };
         serializer.serialize_str(s)
     }
@@ -1061,185 +1289,519 @@ impl<'a> Serialize for AssignPat<'a> {
     }
 }
 
-use std::collections::VecDeque;
-fn unescaper(s: &str) -> Option<String> {
-    let mut queue: VecDeque<_> = String::from(s).chars().collect();
-    let mut s = String::new();
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn array_holes() {
+        // [, a, , b, ,]
+        let expr = Expr::Array(vec![
+            None,
+            Some(Expr::ident_from("a")),
+            None,
+            Some(Expr::ident_from("b")),
+            None,
+        ]);
+        let json = serde_json::to_value(&expr).expect("failed to serialize array");
+        let expectation = serde_json::json!({
+            "type": "ArrayExpression",
+            "elements": [
+                null,
+                { "type": "Identifier", "name": "a" },
+                null,
+                { "type": "Identifier", "name": "b" },
+                null
+            ]
+        });
+        assert_eq!(json, expectation);
+        // [, b, ,] = arr
+        let pat = Pat::Array(vec![
+            None,
+            Some(ArrayPatPart::Pat(Pat::ident_from("b"))),
+            None,
+        ]);
+        let json = serde_json::to_value(&pat).expect("failed to serialize pattern");
+        let expectation = serde_json::json!({
+            "type": "ArrayPattern",
+            "elements": [null, { "type": "Identifier", "name": "b" }, null]
+        });
+        assert_eq!(json, expectation);
+    }
 
-    while let Some(c) = queue.pop_front() {
-        if c != '\\' {
-            s.push(c);
-            continue;
+    #[test]
+    fn template_elements() {
+        // `a\n${b}c${d}`
+        let template = TemplateLit {
+            quasis: vec![
+                TemplateElement::from(false, "a\n", "`a\\n${"),
+                TemplateElement::from(false, "c", "}c${"),
+                TemplateElement::from(true, "", "}`"),
+            ],
+            expressions: vec![Expr::ident_from("b"), Expr::ident_from("d")],
+        };
+        let json = serde_json::to_value(&template).expect("failed to serialize template");
+        let element = |cooked: &str, raw: &str, tail: bool| {
+            serde_json::json!({
+                "type": "TemplateElement",
+                "tail": tail,
+                "value": { "cooked": cooked, "raw": raw }
+            })
+        };
+        let expectation = serde_json::json!({
+            "type": "TemplateLiteral",
+            "expressions": [
+                { "type": "Identifier", "name": "b" },
+                { "type": "Identifier", "name": "d" }
+            ],
+            "quasis": [
+                element("a\n", "a\\n", false),
+                element("c", "c", false),
+                element("", "", true)
+            ]
+        });
+        assert_eq!(json, expectation);
+        // tag`\unicode`
+        let invalid = TemplateElement::invalid(true, "`\\unicode`");
+        let json = serde_json::to_value(&invalid).expect("failed to serialize element");
+        let expectation = serde_json::json!({
+            "type": "TemplateElement",
+            "tail": true,
+            "value": { "cooked": null, "raw": "\\unicode" }
+        });
+        assert_eq!(json, expectation);
+    }
+
+    #[test]
+    fn for_await() {
+        for is_await in &[true, false] {
+            // for await (const x of stream) {}
+            let stmt = Stmt::ForOf(ForOfStmt {
+                left: LoopLeft::Variable(
+                    VarKind::Const,
+                    VarDecl {
+                        id: Pat::ident_from("x"),
+                        init: None,
+                    },
+                ),
+                right: Expr::ident_from("stream"),
+                body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+                is_await: *is_await,
+            });
+            let json = serde_json::to_value(&stmt).expect("failed to serialize for of");
+            assert_eq!(json["type"], "ForOfStatement");
+            assert_eq!(json["await"], *is_await);
         }
+    }
 
-        match queue.pop_front() {
-            Some('b') => s.push('\u{0008}'),
-            Some('f') => s.push('\u{000C}'),
-            Some('n') => s.push('\n'),
-            Some('r') => s.push('\r'),
-            Some('t') => s.push('\t'),
-            Some('v') => s.push('\u{000b}'),
-            Some('\'') => s.push('\''),
-            Some('\"') => s.push('\"'),
-            Some('\\') => s.push('\\'),
-            Some('u') => {
-                if let Some(x) = unescape_unicode(&mut queue) {
-                    s.push(x);
-                } else {
-                    return None;
-                }
-            }
-            Some('x') => {
-                if let Some(x) = unescape_byte(&mut queue) {
-                    s.push(x)
-                } else {
-                    return None;
-                }
-            }
-            Some('\0') => s.push('\0'),
-            Some(c) => {
-                if c.is_digit(8) {
-                    if let Some(x) = unescape_octal(c, &mut queue) {
-                        s.push(x);
-                    } else {
-                        return None;
-                    }
-                } else {
-                    s.push(c)
-                }
-            }
-            _ => return None,
+    #[test]
+    fn yield_delegation() {
+        let yields = vec![
+            // yield v
+            YieldExpr {
+                argument: Some(Box::new(Expr::ident_from("v"))),
+                delegate: false,
+            },
+            // yield
+            YieldExpr {
+                argument: None,
+                delegate: false,
+            },
+            // yield* g()
+            YieldExpr {
+                argument: Some(Box::new(Expr::Call(CallExpr {
+                    callee: Box::new(Expr::ident_from("g")),
+                    arguments: vec![],
+                    optional: false,
+                }))),
+                delegate: true,
+            },
+        ];
+        let json: Vec<_> = yields
+            .into_iter()
+            .map(|y| serde_json::to_value(&Expr::Yield(y)).expect("failed to serialize yield"))
+            .collect();
+        assert_eq!(json[0]["delegate"], false);
+        assert_eq!(json[0]["argument"]["name"], "v");
+        assert_eq!(json[1]["delegate"], false);
+        assert!(json[1]["argument"].is_null());
+        assert_eq!(json[2]["type"], "YieldExpression");
+        assert_eq!(json[2]["delegate"], true);
+        assert_eq!(json[2]["argument"]["type"], "CallExpression");
+    }
+
+    #[test]
+    fn object_spread_and_rest() {
+        // ({...a, b: c})
+        let expr = Expr::Obj(vec![
+            ObjProp::Spread(Expr::ident_from("a")),
+            ObjProp::Prop(Prop {
+                key: PropKey::Expr(Expr::ident_from("b")),
+                value: PropValue::Expr(Expr::ident_from("c")),
+                kind: PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: false,
+                is_static: false,
+                decorators: vec![],
+            }),
+        ]);
+        let json = serde_json::to_value(&expr).expect("failed to serialize object");
+        assert_eq!(json["type"], "ObjectExpression");
+        assert_eq!(
+            json["properties"][0],
+            serde_json::json!({
+                "type": "SpreadElement",
+                "argument": { "type": "Identifier", "name": "a" }
+            })
+        );
+        assert_eq!(json["properties"][1]["type"], "Property");
+        // ({...rest} = o)
+        let pat = Pat::Obj(vec![ObjPatPart::Rest(Box::new(Pat::ident_from("rest")))]);
+        let json = serde_json::to_value(&pat).expect("failed to serialize pattern");
+        let expectation = serde_json::json!({
+            "type": "ObjectPattern",
+            "properties": [{
+                "type": "RestElement",
+                "argument": { "type": "Identifier", "name": "rest" }
+            }]
+        });
+        assert_eq!(json, expectation);
+    }
+
+    #[test]
+    fn catch_param() {
+        let catch = |param| CatchClause {
+            param,
+            body: BlockStmt(vec![]),
         };
+        // catch (e) {}
+        let json = serde_json::to_value(&catch(Some(Pat::ident_from("e"))))
+            .expect("failed to serialize catch");
+        assert_eq!(
+            json["param"],
+            serde_json::json!({ "type": "Identifier", "name": "e" })
+        );
+        // catch {}
+        let json = serde_json::to_value(&catch(None)).expect("failed to serialize catch");
+        let expectation = serde_json::json!({
+            "type": "CatchClause",
+            "param": null,
+            "body": { "type": "BlockStatement", "body": [] }
+        });
+        assert_eq!(json, expectation);
     }
 
-    Some(s)
-}
+    #[test]
+    fn optional_chain() {
+        // a?.b.c
+        let expr = Expr::OptionalChain(Box::new(Expr::Member(MemberExpr {
+            object: Box::new(Expr::Member(MemberExpr {
+                object: Box::new(Expr::ident_from("a")),
+                property: MemberProp::ident_from("b"),
+                optional: true,
+            })),
+            property: MemberProp::ident_from("c"),
+            optional: false,
+        })));
+        let json = serde_json::to_value(&expr).expect("failed to serialize chain");
+        let expectation = serde_json::json!({
+            "type": "ChainExpression",
+            "expression": {
+                "type": "MemberExpression",
+                "object": {
+                    "type": "MemberExpression",
+                    "object": { "type": "Identifier", "name": "a" },
+                    "property": { "type": "Identifier", "name": "b" },
+                    "computed": false,
+                    "optional": true
+                },
+                "property": { "type": "Identifier", "name": "c" },
+                "computed": false,
+                "optional": false
+            }
+        });
+        assert_eq!(json, expectation);
+    }
 
-fn unescape_unicode(queue: &mut VecDeque<char>) -> Option<char> {
-    let ret = hex_char_code(queue)?;
-    ::std::char::from_u32(ret)
-}
+    #[test]
+    fn nullish_coalescing() {
+        let expr = Expr::Logical(LogicalExpr {
+            operator: LogicalOp::NullishCoalescing,
+            left: Box::new(Expr::ident_from("a")),
+            right: Box::new(Expr::ident_from("b")),
+        });
+        let json = serde_json::to_value(&expr).expect("failed to serialize logical");
+        let expectation = serde_json::json!({
+            "type": "LogicalExpression",
+            "left": { "type": "Identifier", "name": "a" },
+            "operator": "??",
+            "right": { "type": "Identifier", "name": "b" }
+        });
+        assert_eq!(json, expectation);
+    }
 
-fn hex_char_code(queue: &mut VecDeque<char>) -> Option<u32> {
-    if let Some(c) = queue.pop_front() {
-        if c == '{' {
-            let mut x = 0;
-            while let Some(c) = queue.pop_front() {
-                if c == '}' {
-                    break;
-                }
-                x = x * 16 + c.to_digit(16)?;
-            }
-            Some(x)
-        } else {
-            let mut x = c.to_digit(16)?;
-            for _ in 0..3 {
-                if let Some(u) = queue.pop_front() {
-                    x = x * 16 + u.to_digit(16)?;
-                }
-            }
-            if x >= 0xD800 && x <= 0xDBFF {
-                debug_assert!(queue.pop_front() == Some('\\'));
-                debug_assert!(queue.pop_front() == Some('u'));
-                let high = (x - 0xD800) * 0x400;
-                let low = hex_char_code(queue)? - 0xDC00;
-                x = 0x10000 + high + low;
-            }
-            Some(x)
+    #[test]
+    fn assign_ops() {
+        let ops = [
+            (AssignOp::Equal, "="),
+            (AssignOp::OrEqual, "|="),
+            (AssignOp::AndEqual, "&="),
+            (AssignOp::PowerOfEqual, "**="),
+            (AssignOp::LogicalAndEqual, "&&="),
+            (AssignOp::LogicalOrEqual, "||="),
+            (AssignOp::NullishEqual, "??="),
+        ];
+        for (op, expectation) in ops.iter() {
+            let expr = Expr::Assign(AssignExpr {
+                operator: *op,
+                left: AssignLeft::Expr(Box::new(Expr::ident_from("a"))),
+                right: Box::new(Expr::ident_from("b")),
+            });
+            let json = serde_json::to_value(&expr).expect("failed to serialize assign");
+            assert_eq!(json["operator"], *expectation);
         }
-    } else {
-        None
     }
-}
 
-fn unescape_byte(queue: &mut VecDeque<char>) -> Option<char> {
-    let mut s = String::new();
+    #[test]
+    fn private_member() {
+        let expr = Expr::Member(MemberExpr {
+            object: Box::new(Expr::This),
+            property: MemberProp::Private(PrivateId::from("x")),
+            optional: false,
+        });
+        let json = serde_json::to_value(&expr).expect("failed to serialize member");
+        let expectation = serde_json::json!({
+            "type": "MemberExpression",
+            "object": { "type": "ThisExpression" },
+            "property": { "type": "PrivateIdentifier", "name": "x" },
+            "computed": false,
+            "optional": false
+        });
+        assert_eq!(json, expectation);
+    }
 
-    for _ in 0..2 {
-        if let Some(c) = queue.pop_front() {
-            s.push(c)
-        } else {
-            return None;
-        }
+    #[test]
+    fn private_class_members() {
+        // class C { #x = 1; #inc() { return this.#x } }
+        let this_x = Expr::Member(MemberExpr {
+            object: Box::new(Expr::This),
+            property: MemberProp::Private(PrivateId::from("x")),
+            optional: false,
+        });
+        let class = Class::new(
+            Some(Ident::from("C")),
+            None,
+            vec![
+                ClassMember::Field(FieldDef::new(
+                    PropKey::Private(PrivateId::from("x")),
+                    Some(Expr::Lit(Lit::number_from("1"))),
+                )),
+                ClassMember::Method(MethodDef::new(
+                    PropKey::Private(PrivateId::from("inc")),
+                    Func::new(
+                        None,
+                        vec![],
+                        FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(this_x)))]),
+                        false,
+                        false,
+                    ),
+                    MethodKind::Method,
+                )),
+            ],
+        );
+        let json = serde_json::to_value(&Decl::Class(class)).expect("failed to serialize class");
+        let members = &json["body"]["body"];
+        let private_x = serde_json::json!({ "type": "PrivateIdentifier", "name": "x" });
+        assert_eq!(members[0]["type"], "PropertyDefinition");
+        assert_eq!(members[0]["key"], private_x);
+        assert_eq!(members[1]["type"], "MethodDefinition");
+        assert_eq!(
+            members[1]["key"],
+            serde_json::json!({ "type": "PrivateIdentifier", "name": "inc" })
+        );
+        let returned = &members[1]["value"]["body"]["body"][0]["argument"];
+        assert_eq!(returned["type"], "MemberExpression");
+        assert_eq!(returned["property"], private_x);
     }
-    match u32::from_str_radix(&s, 16) {
-        Ok(u) => ::std::char::from_u32(u),
-        Err(e) => {
-            panic!("{}", e);
-        }
+
+    #[test]
+    fn private_brand_check() {
+        // #x in obj
+        let check = Expr::Binary(BinaryExpr {
+            operator: BinaryOp::In,
+            left: Box::new(Expr::PrivateId(PrivateId::from("x"))),
+            right: Box::new(Expr::ident_from("obj")),
+        });
+        let json = serde_json::to_value(&check).expect("failed to serialize brand check");
+        let expectation = serde_json::json!({
+            "type": "BinaryExpression",
+            "operator": "in",
+            "left": { "type": "PrivateIdentifier", "name": "x" },
+            "right": { "type": "Identifier", "name": "obj" }
+        });
+        assert_eq!(json, expectation);
     }
-}
 
-fn unescape_octal(c: char, queue: &mut VecDeque<char>) -> Option<char> {
-    let (ret, ct) = if let Some(next) = queue.get(0) {
-        if !next.is_digit(8) {
-            let d = c.to_digit(8)?;
-            return std::char::from_u32(d);
-        } else if c >= '0' && c < '4' {
-            let s = if let Some(third) = queue.get(1) {
-                if !third.is_digit(8) {
-                    format!("{}{}", c, next)
-                } else {
-                    format!("{}{}{}", c, next, third)
-                }
-            } else {
-                format!("{}{}", c, next)
-            };
-            let ct = s.len().saturating_sub(1);
-            match u32::from_str_radix(&s, 8) {
-                Ok(r) => (::std::char::from_u32(r), ct),
-                Err(e) => panic!("{}", e),
-            }
-        } else {
-            match u32::from_str_radix(&format!("{}{}", c, next), 8) {
-                Ok(r) => (::std::char::from_u32(r), 1),
-                Err(e) => panic!("{}", e),
-            }
-        }
-    } else {
-        (Some(c), 0)
-    };
-    for _ in 0..ct {
-        let _ = queue.pop_front();
+    #[test]
+    fn dynamic_import() {
+        let expr = Expr::Import(ImportExpr {
+            source: Box::new(Expr::Lit(Lit::single_string_from("a"))),
+            options: None,
+        });
+        let json = serde_json::to_value(&expr).expect("failed to serialize import");
+        let expectation = serde_json::json!({
+            "type": "ImportExpression",
+            "source": { "type": "Literal", "value": "a", "raw": "'a'" }
+        });
+        assert_eq!(json, expectation);
+    }
+
+    #[test]
+    fn import_meta() {
+        let expr = Expr::Member(MemberExpr {
+            object: Box::new(Expr::MetaProp(MetaProp::import_meta())),
+            property: MemberProp::ident_from("url"),
+            optional: false,
+        });
+        let json = serde_json::to_value(&expr).expect("failed to serialize member");
+        let expectation = serde_json::json!({
+            "type": "MemberExpression",
+            "object": {
+                "type": "MetaProperty",
+                "meta": { "type": "Identifier", "name": "import" },
+                "property": { "type": "Identifier", "name": "meta" }
+            },
+            "property": { "type": "Identifier", "name": "url" },
+            "computed": false,
+            "optional": false
+        });
+        assert_eq!(json, expectation);
+    }
+
+    fn json_import(keyword: Option<AttributesKeyword>) -> Decl<'static> {
+        Decl::Import(Box::new(ModImport {
+            specifiers: vec![ImportSpecifier::Default(Ident::from("data"))],
+            source: Lit::single_string_from("./data.json"),
+            attributes: vec![ImportAttribute {
+                key: ImportAttributeKey::Ident(Ident::from("type")),
+                value: Lit::single_string_from("json"),
+            }],
+            attributes_keyword: keyword,
+        }))
     }
-    ret
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
     #[test]
-    fn four_hundred() {
-        let js = r#""\1\00\400\000\""#;
-        let expectation = "\"\u{1}\u{0} 0\u{0}\"";
-        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    fn import_attributes() {
+        let expectation = serde_json::json!({
+            "type": "ImportDeclaration",
+            "specifiers": [{
+                "type": "ImportDefaultSpecifier",
+                "local": { "type": "Identifier", "name": "data" }
+            }],
+            "source": { "type": "Literal", "value": "./data.json", "raw": "'./data.json'" },
+            "attributes": [{
+                "type": "ImportAttribute",
+                "key": { "type": "Identifier", "name": "type" },
+                "value": { "type": "Literal", "value": "json", "raw": "'json'" }
+            }]
+        });
+        let with = serde_json::to_value(&json_import(Some(AttributesKeyword::With)))
+            .expect("failed to serialize import");
+        assert_eq!(with, expectation);
+        let unset = serde_json::to_value(&json_import(None)).expect("failed to serialize import");
+        assert_eq!(unset, expectation);
+        let assert = serde_json::to_value(&json_import(Some(AttributesKeyword::Assert)))
+            .expect("failed to serialize import");
+        assert_ne!(assert, with);
+        assert_eq!(assert["assertions"], expectation["attributes"]);
+        assert_eq!(assert.get("attributes"), None);
     }
+
     #[test]
-    fn escape_lots() {
-        let js = "\"\\'\\\"\\\\\\b\\f\\n\\r\\t\\v\\0\"";
-        let expectation = "\"'\"\\\u{8}\u{c}\n\r\t\u{b}\u{0}\"";
-        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    fn re_export_attributes() {
+        let export = ModExport::All(None, ExportSource {
+            source: Lit::single_string_from("./data.json"),
+            attributes: vec![ImportAttribute {
+                key: ImportAttributeKey::Lit(Lit::single_string_from("type")),
+                value: Lit::single_string_from("json"),
+            }],
+            attributes_keyword: Some(AttributesKeyword::With),
+        });
+        let json = serde_json::to_value(&export).expect("failed to serialize export");
+        let expectation = serde_json::json!({
+            "type": "ExportAllDeclaration",
+            "exported": null,
+            "source": { "type": "Literal", "value": "./data.json", "raw": "'./data.json'" },
+            "attributes": [{
+                "type": "ImportAttribute",
+                "key": { "type": "Literal", "value": "type", "raw": "'type'" },
+                "value": { "type": "Literal", "value": "json", "raw": "'json'" }
+            }]
+        });
+        assert_eq!(json, expectation);
     }
 
     #[test]
-    fn escaped_new_line() {
-        let js = r#""\\\n""#;
-        let expectation = "\"\\\n\"";
-        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    fn decorators() {
+        let mut class = Class::new(Some(Ident::from("C")), None, vec![]);
+        class.decorators.push(Decorator {
+            expression: Expr::ident_from("sealed"),
+        });
+        let json = serde_json::to_value(&Decl::Class(class)).expect("failed to serialize class");
+        let expectation = serde_json::json!({
+            "type": "ClassDeclaration",
+            "id": { "type": "Identifier", "name": "C" },
+            "superClass": null,
+            "body": { "type": "ClassBody", "body": [] },
+            "decorators": [{
+                "type": "Decorator",
+                "expression": { "type": "Identifier", "name": "sealed" }
+            }]
+        });
+        assert_eq!(json, expectation);
     }
 
     #[test]
-    fn unicode_ident() {
-        let js = "φ";
-        let expectation = "φ";
-        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    fn export_all_alias() {
+        let export = ModExport::All(
+            Some(ModuleExportName::ident_from("ns")),
+            ExportSource::new(Lit::single_string_from("m")),
+        );
+        let json = serde_json::to_value(&export).expect("failed to serialize export");
+        let expectation = serde_json::json!({
+            "type": "ExportAllDeclaration",
+            "exported": { "type": "Identifier", "name": "ns" },
+            "source": { "type": "Literal", "value": "m", "raw": "'m'" }
+        });
+        assert_eq!(json, expectation);
     }
 
     #[test]
-    fn unicode_string() {
-        let js = r#""\uD834\uDF06\u2603\u03C6 \u{0000001F4a9}\u{1D306}\u{2603}\u{3c6} 𝌆☃φ""#;
-        let expectation = "\"𝌆☃φ 💩𝌆☃φ 𝌆☃φ\"";
-        assert_eq!(unescaper(js).unwrap(), expectation.to_string());
+    fn string_export_name() {
+        let spec = ExportSpecifier {
+            local: Ident::from("x"),
+            exported: ModuleExportName::str_from("a-b"),
+        };
+        let json = serde_json::to_value(&spec).expect("failed to serialize specifier");
+        let expectation = serde_json::json!({
+            "type": "ExportSpecifier",
+            "local": { "type": "Identifier", "name": "x" },
+            "exported": { "type": "Literal", "value": "a-b", "raw": "\"a-b\"" }
+        });
+        assert_eq!(json, expectation);
     }
 
+    #[test]
+    fn bigint() {
+        for n in &["123", "0x1f", "0o17", "0b101"] {
+            let json = serde_json::to_value(&Lit::bigint_from(n)).expect("failed to serialize bigint");
+            let expectation = serde_json::json!({
+                "type": "Literal",
+                "value": null,
+                "raw": format!("{}n", n),
+                "bigint": n,
+            });
+            assert_eq!(json, expectation);
+        }
+    }
 }
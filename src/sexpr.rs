@@ -0,0 +1,635 @@
+//! A compact s-expression dump of a tree
+//!
+//! Each node is written as `(Kind ...)` with its fields in a fixed
+//! order, lists are wrapped in `[]` and a missing optional value is
+//! `_`. Boolean flags are only written when they are set, so adding
+//! a new flag won't change the output for existing trees
+//! ```txt
+//! (Script (ExprStmt (BinaryExpr + (Lit 1) (Lit 2))))
+//! ```
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// Write `program` as a single line s-expression
+/// ```
+/// # use resast::prelude::*;
+/// use resast::sexpr::to_sexpr;
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Binary(
+///     BinaryExpr::new(
+///         Expr::Lit(Lit::number_from("1")),
+///         BinaryOp::Plus,
+///         Expr::Lit(Lit::number_from("2")),
+///     ),
+/// )))]);
+/// assert_eq!(
+///     to_sexpr(&program),
+///     "(Script (ExprStmt (BinaryExpr + (Lit 1) (Lit 2))))"
+/// );
+/// ```
+pub fn to_sexpr(program: &Program) -> String {
+    let mut w = Writer::default();
+    w.program(program);
+    w.out
+}
+
+#[derive(Default)]
+struct Writer {
+    out: String,
+}
+
+impl Writer {
+    fn atom(&mut self, text: &str) {
+        if !self.out.is_empty() && !self.out.ends_with('(') && !self.out.ends_with('[') {
+            self.out.push(' ');
+        }
+        self.out.push_str(text);
+    }
+
+    fn node(&mut self, kind: &str, f: impl FnOnce(&mut Self)) {
+        self.atom("(");
+        self.out.push_str(kind);
+        f(self);
+        self.out.push(')');
+    }
+
+    fn list<T>(&mut self, items: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        self.atom("[");
+        for item in items {
+            f(self, item);
+        }
+        self.out.push(']');
+    }
+
+    fn opt<T>(&mut self, item: Option<&T>, f: impl FnOnce(&mut Self, &T)) {
+        match item {
+            Some(item) => f(self, item),
+            None => self.atom("_"),
+        }
+    }
+
+    fn flag(&mut self, set: bool, name: &str) {
+        if set {
+            self.atom(name);
+        }
+    }
+
+    fn program(&mut self, program: &Program) {
+        let (kind, parts, hashbang) = match program {
+            Program::Mod(parts, hashbang) => ("Module", parts, hashbang),
+            Program::Script(parts, hashbang) => ("Script", parts, hashbang),
+        };
+        self.node(kind, |w| {
+            if let Some(hashbang) = hashbang {
+                w.node("Hashbang", |w| w.atom(hashbang));
+            }
+            for part in parts {
+                w.part(part);
+            }
+        })
+    }
+
+    fn part(&mut self, part: &ProgramPart) {
+        match part {
+            ProgramPart::Dir(dir) => self.node("Dir", |w| w.lit(&dir.expr)),
+            ProgramPart::Decl(decl) => self.decl(decl),
+            ProgramPart::Stmt(stmt) => self.stmt(stmt),
+        }
+    }
+
+    fn parts(&mut self, parts: &[ProgramPart]) {
+        self.list(parts, |w, part| w.part(part))
+    }
+
+    fn ident(&mut self, ident: &Ident) {
+        self.node("Ident", |w| w.atom(&ident.name))
+    }
+
+    fn private_id(&mut self, id: &PrivateId) {
+        self.node("PrivateId", |w| w.atom(&id.name))
+    }
+
+    fn decorators(&mut self, decorators: &[Decorator]) {
+        if !decorators.is_empty() {
+            self.node("Decorators", |w| {
+                w.list(decorators, |w, d| w.expr(&d.expression))
+            })
+        }
+    }
+
+    fn func(&mut self, func: &Func) {
+        self.node("Func", |w| {
+            w.flag(func.is_async, "async");
+            w.flag(func.generator, "generator");
+            w.opt(func.id.as_ref(), Self::ident);
+            w.list(&func.params, Self::func_arg);
+            w.func_body(&func.body);
+        })
+    }
+
+    fn func_arg(&mut self, arg: &FuncArg) {
+        match arg {
+            FuncArg::Expr(expr) => self.expr(expr),
+            FuncArg::Pat(pat) => self.pat(pat),
+        }
+    }
+
+    fn func_body(&mut self, body: &FuncBody) {
+        self.node("FuncBody", |w| w.parts(&body.0))
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.node("Class", |w| {
+            w.opt(class.id.as_ref(), Self::ident);
+            w.opt(class.super_class.as_deref(), Self::expr);
+            w.list(&class.body.0, Self::class_member);
+            w.decorators(&class.decorators);
+        })
+    }
+
+    fn class_member(&mut self, member: &ClassMember) {
+        match member {
+            ClassMember::Method(method) => self.node("MethodDef", |w| {
+                w.atom(match method.kind {
+                    MethodKind::Constructor => "constructor",
+                    MethodKind::Method => "method",
+                    MethodKind::Get => "get",
+                    MethodKind::Set => "set",
+                });
+                w.flag(method.is_static, "static");
+                w.flag(method.computed, "computed");
+                w.prop_key(&method.key);
+                w.func(&method.value);
+                w.decorators(&method.decorators);
+            }),
+            ClassMember::Field(field) => self.node("FieldDef", |w| {
+                w.flag(field.is_static, "static");
+                w.flag(field.computed, "computed");
+                w.prop_key(&field.key);
+                w.opt(field.value.as_ref(), Self::expr);
+                w.decorators(&field.decorators);
+            }),
+            ClassMember::StaticBlock(block) => self.node("StaticBlock", |w| w.parts(&block.0)),
+        }
+    }
+
+    fn decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Var(kind, decls) => self.var_decls(*kind, decls),
+            Decl::Func(func) => self.func(func),
+            Decl::Class(class) => self.class(class),
+            Decl::Import(import) => self.node("ImportDecl", |w| {
+                w.list(&import.specifiers, Self::import_specifier);
+                w.lit(&import.source);
+                w.attributes(&import.attributes, import.clause_keyword());
+            }),
+            Decl::Export(export) => self.export(export),
+        }
+    }
+
+    fn var_decls(&mut self, kind: VarKind, decls: &[VarDecl]) {
+        self.node("VarDecl", |w| {
+            w.atom(kind.as_str());
+            w.list(decls, Self::var_decl);
+        })
+    }
+
+    fn var_decl(&mut self, decl: &VarDecl) {
+        self.node("VarDeclarator", |w| {
+            w.pat(&decl.id);
+            w.opt(decl.init.as_ref(), Self::expr);
+        })
+    }
+
+    fn import_specifier(&mut self, spec: &ImportSpecifier) {
+        match spec {
+            ImportSpecifier::Normal(spec) => self.node("ImportSpec", |w| {
+                w.module_export_name(&spec.imported);
+                w.ident(&spec.local);
+            }),
+            ImportSpecifier::Default(ident) => self.node("DefaultImport", |w| w.ident(ident)),
+            ImportSpecifier::Namespace(ident) => {
+                self.node("NamespaceImport", |w| w.ident(ident))
+            }
+        }
+    }
+
+    fn attributes(&mut self, attributes: &[ImportAttribute], keyword: Option<AttributesKeyword>) {
+        let keyword = match keyword {
+            Some(AttributesKeyword::With) => "With",
+            Some(AttributesKeyword::Assert) => "Assert",
+            None => return,
+        };
+        self.node(keyword, |w| {
+            w.list(attributes, |w, attr| {
+                w.node("ImportAttribute", |w| {
+                    match &attr.key {
+                        ImportAttributeKey::Ident(ident) => w.ident(ident),
+                        ImportAttributeKey::Lit(lit) => w.lit(lit),
+                    }
+                    w.lit(&attr.value);
+                })
+            })
+        })
+    }
+
+    fn module_export_name(&mut self, name: &ModuleExportName) {
+        match name {
+            ModuleExportName::Ident(ident) => self.ident(ident),
+            ModuleExportName::Str(lit) => self.lit(lit),
+        }
+    }
+
+    fn export_source(&mut self, source: &ExportSource) {
+        self.node("ExportSource", |w| {
+            w.lit(&source.source);
+            w.attributes(&source.attributes, source.clause_keyword());
+        })
+    }
+
+    fn export(&mut self, export: &ModExport) {
+        match export {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                self.node("ExportDefault", |w| w.decl(decl))
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                self.node("ExportDefault", |w| w.expr(expr))
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                self.node("ExportNamed", |w| w.decl(decl))
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                self.node("ExportNamed", |w| {
+                    w.list(specs, |w, spec| {
+                        w.node("ExportSpec", |w| {
+                            w.ident(&spec.local);
+                            w.module_export_name(&spec.exported);
+                        })
+                    });
+                    w.opt(source.as_ref(), Self::export_source);
+                })
+            }
+            ModExport::All(alias, source) => self.node("ExportAll", |w| {
+                w.opt(alias.as_ref(), Self::module_export_name);
+                w.export_source(source);
+            }),
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::Expr(expr) => self.node("ExprStmt", |w| w.expr(expr)),
+            Stmt::Block(block) => self.block(block),
+            Stmt::Empty => self.atom("EmptyStmt"),
+            Stmt::Debugger => self.atom("DebuggerStmt"),
+            Stmt::With(with) => self.node("WithStmt", |w| {
+                w.expr(&with.object);
+                w.stmt(&with.body);
+            }),
+            Stmt::Return(arg) => self.node("ReturnStmt", |w| w.opt(arg.as_ref(), Self::expr)),
+            Stmt::Labeled(labeled) => self.node("LabeledStmt", |w| {
+                w.ident(&labeled.label);
+                w.stmt(&labeled.body);
+            }),
+            Stmt::Break(label) => self.node("BreakStmt", |w| w.opt(label.as_ref(), Self::ident)),
+            Stmt::Continue(label) => {
+                self.node("ContinueStmt", |w| w.opt(label.as_ref(), Self::ident))
+            }
+            Stmt::If(stmt) => self.node("IfStmt", |w| {
+                w.expr(&stmt.test);
+                w.stmt(&stmt.consequent);
+                w.opt(stmt.alternate.as_deref(), Self::stmt);
+            }),
+            Stmt::Switch(stmt) => self.node("SwitchStmt", |w| {
+                w.expr(&stmt.discriminant);
+                w.list(&stmt.cases, |w, case| {
+                    w.node("SwitchCase", |w| {
+                        w.opt(case.test.as_ref(), Self::expr);
+                        w.parts(&case.consequent);
+                    })
+                });
+            }),
+            Stmt::Throw(expr) => self.node("ThrowStmt", |w| w.expr(expr)),
+            Stmt::Try(stmt) => self.node("TryStmt", |w| {
+                w.block(&stmt.block);
+                w.opt(stmt.handler.as_ref(), |w, handler| {
+                    w.node("CatchClause", |w| {
+                        w.opt(handler.param.as_ref(), Self::pat);
+                        w.block(&handler.body);
+                    })
+                });
+                w.opt(stmt.finalizer.as_ref(), Self::block);
+            }),
+            Stmt::While(stmt) => self.node("WhileStmt", |w| {
+                w.expr(&stmt.test);
+                w.stmt(&stmt.body);
+            }),
+            Stmt::DoWhile(stmt) => self.node("DoWhileStmt", |w| {
+                w.stmt(&stmt.body);
+                w.expr(&stmt.test);
+            }),
+            Stmt::For(stmt) => self.node("ForStmt", |w| {
+                w.opt(stmt.init.as_ref(), |w, init| match init {
+                    LoopInit::Variable(kind, decls) => w.var_decls(*kind, decls),
+                    LoopInit::Expr(expr) => w.expr(expr),
+                });
+                w.opt(stmt.test.as_ref(), Self::expr);
+                w.opt(stmt.update.as_ref(), Self::expr);
+                w.stmt(&stmt.body);
+            }),
+            Stmt::ForIn(stmt) => self.node("ForInStmt", |w| {
+                w.loop_left(&stmt.left);
+                w.expr(&stmt.right);
+                w.stmt(&stmt.body);
+            }),
+            Stmt::ForOf(stmt) => self.node("ForOfStmt", |w| {
+                w.flag(stmt.is_await, "await");
+                w.loop_left(&stmt.left);
+                w.expr(&stmt.right);
+                w.stmt(&stmt.body);
+            }),
+            Stmt::Var(decls) => self.node("VarStmt", |w| w.list(decls, Self::var_decl)),
+        }
+    }
+
+    fn block(&mut self, block: &BlockStmt) {
+        self.node("BlockStmt", |w| w.parts(&block.0))
+    }
+
+    fn loop_left(&mut self, left: &LoopLeft) {
+        match left {
+            LoopLeft::Expr(expr) => self.expr(expr),
+            LoopLeft::Variable(kind, decl) => self.var_decls(*kind, std::slice::from_ref(decl)),
+            LoopLeft::Pat(pat) => self.pat(pat),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Array(elements) => self.node("ArrayExpr", |w| {
+                w.list(elements, |w, el| w.opt(el.as_ref(), Self::expr))
+            }),
+            Expr::ArrowFunc(arrow) => self.node("ArrowFuncExpr", |w| {
+                w.flag(arrow.is_async, "async");
+                w.list(&arrow.params, Self::func_arg);
+                match &arrow.body {
+                    ArrowFuncBody::FuncBody(body) => w.func_body(body),
+                    ArrowFuncBody::Expr(expr) => w.expr(expr),
+                }
+            }),
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                self.node("ArrowParamPlaceHolder", |w| {
+                    w.flag(*is_async, "async");
+                    w.list(args, Self::func_arg);
+                })
+            }
+            Expr::Assign(assign) => self.node("AssignExpr", |w| {
+                w.atom(assign.operator.as_str());
+                match &assign.left {
+                    AssignLeft::Pat(pat) => w.pat(pat),
+                    AssignLeft::Expr(expr) => w.expr(expr),
+                }
+                w.expr(&assign.right);
+            }),
+            Expr::Await(arg) => self.node("AwaitExpr", |w| w.expr(arg)),
+            Expr::Binary(bin) => self.node("BinaryExpr", |w| {
+                w.atom(bin.operator.as_str());
+                w.expr(&bin.left);
+                w.expr(&bin.right);
+            }),
+            Expr::Class(class) => self.class(class),
+            Expr::Call(call) => self.node("CallExpr", |w| {
+                w.flag(call.optional, "optional");
+                w.expr(&call.callee);
+                w.list(&call.arguments, Self::expr);
+            }),
+            Expr::Conditional(cond) => self.node("ConditionalExpr", |w| {
+                w.expr(&cond.test);
+                w.expr(&cond.consequent);
+                w.expr(&cond.alternate);
+            }),
+            Expr::Func(func) => self.func(func),
+            Expr::Ident(ident) => self.ident(ident),
+            Expr::Import(import) => self.node("ImportExpr", |w| {
+                w.expr(&import.source);
+                w.opt(import.options.as_deref(), Self::expr);
+            }),
+            Expr::Lit(lit) => self.lit(lit),
+            Expr::Logical(logical) => self.node("LogicalExpr", |w| {
+                w.atom(logical.operator.as_str());
+                w.expr(&logical.left);
+                w.expr(&logical.right);
+            }),
+            Expr::Member(member) => self.node("MemberExpr", |w| {
+                w.flag(member.optional, "optional");
+                w.expr(&member.object);
+                match &member.property {
+                    MemberProp::Ident(ident) => w.ident(ident),
+                    MemberProp::Private(id) => w.private_id(id),
+                    MemberProp::Computed(expr) => w.node("Computed", |w| w.expr(expr)),
+                }
+            }),
+            Expr::MetaProp(meta) => self.node("MetaProp", |w| {
+                w.atom(&meta.meta.name);
+                w.atom(&meta.property.name);
+            }),
+            Expr::PrivateId(id) => self.private_id(id),
+            Expr::New(new) => self.node("NewExpr", |w| {
+                w.expr(&new.callee);
+                w.opt(new.arguments.as_ref(), |w, args| w.list(args, Self::expr));
+            }),
+            Expr::Obj(props) => self.node("ObjExpr", |w| {
+                w.list(props, |w, prop| match prop {
+                    ObjProp::Prop(prop) => w.prop(prop),
+                    ObjProp::Spread(expr) => w.node("Spread", |w| w.expr(expr)),
+                })
+            }),
+            Expr::Sequence(seq) => self.node("SequenceExpr", |w| {
+                for expr in seq {
+                    w.expr(expr);
+                }
+            }),
+            Expr::Spread(expr) => self.node("Spread", |w| w.expr(expr)),
+            Expr::Super => self.atom("Super"),
+            Expr::This => self.atom("This"),
+            Expr::TaggedTemplate(tagged) => self.node("TaggedTemplateExpr", |w| {
+                w.expr(&tagged.tag);
+                w.template(&tagged.quasi);
+            }),
+            Expr::Unary(unary) => self.node("UnaryExpr", |w| {
+                w.atom(unary.operator.as_str());
+                w.expr(&unary.argument);
+            }),
+            Expr::Update(update) => self.node("UpdateExpr", |w| {
+                w.atom(update.operator.as_str());
+                w.flag(update.prefix, "prefix");
+                w.expr(&update.argument);
+            }),
+            Expr::Yield(y) => self.node("YieldExpr", |w| {
+                w.flag(y.delegate, "delegate");
+                w.opt(y.argument.as_deref(), Self::expr);
+            }),
+            Expr::OptionalChain(expr) => self.node("OptionalChain", |w| w.expr(expr)),
+            #[cfg(feature = "preserve-parens")]
+            Expr::Paren(expr) => self.node("Paren", |w| w.expr(expr)),
+        }
+    }
+
+    fn prop(&mut self, prop: &Prop) {
+        self.node("Prop", |w| {
+            w.atom(match prop.kind {
+                PropKind::Init => "init",
+                PropKind::Get => "get",
+                PropKind::Set => "set",
+                PropKind::Ctor => "ctor",
+                PropKind::Method => "method",
+            });
+            w.flag(prop.method, "method");
+            w.flag(prop.computed, "computed");
+            w.flag(prop.short_hand, "shorthand");
+            w.flag(prop.is_static, "static");
+            w.prop_key(&prop.key);
+            match &prop.value {
+                PropValue::Expr(expr) => w.expr(expr),
+                PropValue::Pat(pat) => w.pat(pat),
+                PropValue::None => w.atom("_"),
+            }
+            w.decorators(&prop.decorators);
+        })
+    }
+
+    fn prop_key(&mut self, key: &PropKey) {
+        match key {
+            PropKey::Lit(lit) => self.lit(lit),
+            PropKey::Expr(expr) => self.expr(expr),
+            PropKey::Pat(pat) => self.pat(pat),
+            PropKey::Private(id) => self.private_id(id),
+        }
+    }
+
+    fn lit(&mut self, lit: &Lit) {
+        match lit {
+            Lit::Null => self.node("Lit", |w| w.atom("null")),
+            Lit::String(StringLit::Double(s)) => {
+                self.node("Lit", |w| w.atom(&format!("\"{}\"", s)))
+            }
+            Lit::String(StringLit::Single(s)) => self.node("Lit", |w| w.atom(&format!("'{}'", s))),
+            Lit::Number(n) => self.node("Lit", |w| w.atom(n.raw())),
+            Lit::BigInt(digits) => self.node("Lit", |w| w.atom(&format!("{}n", digits))),
+            Lit::Boolean(b) => self.node("Lit", |w| w.atom(if *b { "true" } else { "false" })),
+            Lit::RegEx(re) => {
+                self.node("Lit", |w| w.atom(&format!("/{}/{}", re.pattern, re.flags)))
+            }
+            Lit::Template(template) => self.template(template),
+        }
+    }
+
+    fn template(&mut self, template: &TemplateLit) {
+        self.node("TemplateLit", |w| {
+            w.list(&template.quasis, |w, quasi| w.atom(&quasi.raw));
+            w.list(&template.expressions, Self::expr);
+        })
+    }
+
+    fn pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(ident) => self.ident(ident),
+            Pat::Obj(parts) => self.node("ObjPat", |w| {
+                w.list(parts, |w, part| match part {
+                    ObjPatPart::Assign(prop) => w.prop(prop),
+                    ObjPatPart::Rest(pat) => w.node("Rest", |w| w.pat(pat)),
+                })
+            }),
+            Pat::Array(parts) => self.node("ArrayPat", |w| {
+                w.list(parts, |w, part| match part {
+                    Some(ArrayPatPart::Pat(pat)) => w.pat(pat),
+                    Some(ArrayPatPart::Expr(expr)) => w.expr(expr),
+                    None => w.atom("_"),
+                })
+            }),
+            Pat::RestElement(pat) => self.node("Rest", |w| w.pat(pat)),
+            Pat::Assign(assign) => self.node("AssignPat", |w| {
+                w.pat(&assign.left);
+                w.expr(&assign.right);
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn small_program_snapshot() {
+        // 'use strict';
+        // let [a, , ...b] = xs;
+        // function f(x = 1) { return x ? a?.b : -x; }
+        // for (const k of ks) {}
+        let one = Expr::Lit(Lit::number_from("1"));
+        let program = Program::script(vec![
+            ProgramPart::Dir(Dir::from_str("use strict")),
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Let,
+                vec![VarDecl {
+                    id: Pat::Array(vec![
+                        Some(ArrayPatPart::Pat(Pat::ident_from("a"))),
+                        None,
+                        Some(ArrayPatPart::Pat(Pat::RestElement(Box::new(Pat::ident_from(
+                            "b",
+                        ))))),
+                    ]),
+                    init: Some(Expr::ident_from("xs")),
+                }],
+            )),
+            ProgramPart::Decl(Decl::Func(Func::new(
+                Some(Ident::from("f")),
+                vec![FuncArg::Pat(Pat::Assign(AssignPat {
+                    left: Box::new(Pat::ident_from("x")),
+                    right: Box::new(one),
+                }))],
+                FuncBody(vec![ProgramPart::Stmt(Stmt::Return(Some(Expr::Conditional(
+                    ConditionalExpr {
+                        test: Box::new(Expr::ident_from("x")),
+                        consequent: Box::new(Expr::Member(MemberExpr {
+                            optional: true,
+                            ..MemberExpr::new(Expr::ident_from("a"), MemberProp::ident_from("b"))
+                        })),
+                        alternate: Box::new(Expr::Unary(UnaryExpr::new(
+                            UnaryOp::Minus,
+                            Expr::ident_from("x"),
+                        ))),
+                    },
+                ))))]),
+                false,
+                false,
+            ))),
+            ProgramPart::Stmt(Stmt::ForOf(ForOfStmt {
+                left: LoopLeft::Variable(
+                    VarKind::Const,
+                    VarDecl {
+                        id: Pat::ident_from("k"),
+                        init: None,
+                    },
+                ),
+                right: Expr::ident_from("ks"),
+                body: Box::new(Stmt::Block(BlockStmt(vec![]))),
+                is_await: false,
+            })),
+        ]);
+        let expected = concat!(
+            "(Script",
+            " (Dir (Lit 'use strict'))",
+            " (VarDecl let [(VarDeclarator",
+            " (ArrayPat [(Ident a) _ (Rest (Ident b))]) (Ident xs))])",
+            " (Func (Ident f) [(AssignPat (Ident x) (Lit 1))] (FuncBody [(ReturnStmt",
+            " (ConditionalExpr (Ident x) (MemberExpr optional (Ident a) (Ident b))",
+            " (UnaryExpr - (Ident x))))]))",
+            " (ForOfStmt (VarDecl const [(VarDeclarator (Ident k) _)]) (Ident ks)",
+            " (BlockStmt [])))",
+        );
+        assert_eq!(to_sexpr(&program), expected);
+    }
+}
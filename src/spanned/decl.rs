@@ -0,0 +1,287 @@
+use crate::decl as plain;
+use crate::decl::AttributesKeyword;
+use crate::spanned::expr::{Expr, Lit};
+use crate::spanned::pat::Pat;
+use crate::spanned::{strip, strip_all, Class, Func, Ident, Node, VarKind};
+
+/// The declaration of a variable, function, class, import or export
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Decl<'a> {
+    /// A variable declaration
+    Var(VarKind, Vec<Node<VarDecl<'a>>>),
+    /// A function declaration
+    Func(Func<'a>),
+    /// A class declaration
+    Class(Class<'a>),
+    /// An import declaration
+    Import(Box<ModImport<'a>>),
+    /// An export declaration
+    Export(Box<ModExport<'a>>),
+}
+
+impl<'a> From<Decl<'a>> for plain::Decl<'a> {
+    fn from(other: Decl<'a>) -> Self {
+        match other {
+            Decl::Var(kind, decls) => plain::Decl::Var(kind, strip_all(decls)),
+            Decl::Func(func) => plain::Decl::Func(func.into()),
+            Decl::Class(class) => plain::Decl::Class(class.into()),
+            Decl::Import(import) => plain::Decl::Import(Box::new((*import).into())),
+            Decl::Export(export) => plain::Decl::Export(Box::new((*export).into())),
+        }
+    }
+}
+
+/// The identifier and optional value of a variable declaration
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct VarDecl<'a> {
+    pub id: Node<Pat<'a>>,
+    pub init: Option<Node<Expr<'a>>>,
+}
+
+impl<'a> From<VarDecl<'a>> for plain::VarDecl<'a> {
+    fn from(other: VarDecl<'a>) -> Self {
+        plain::VarDecl {
+            id: strip(other.id),
+            init: other.init.map(strip),
+        }
+    }
+}
+
+/// A declaration that imports exported
+/// members of another module
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ModImport<'a> {
+    pub specifiers: Vec<Node<ImportSpecifier<'a>>>,
+    pub source: Node<Lit<'a>>,
+    pub attributes: Vec<Node<ImportAttribute<'a>>>,
+    pub attributes_keyword: Option<AttributesKeyword>,
+}
+
+impl<'a> From<ModImport<'a>> for plain::ModImport<'a> {
+    fn from(other: ModImport<'a>) -> Self {
+        plain::ModImport {
+            specifiers: strip_all(other.specifiers),
+            source: strip(other.source),
+            attributes: strip_all(other.attributes),
+            attributes_keyword: other.attributes_keyword,
+        }
+    }
+}
+
+/// A single `key: "value"` entry in an import attributes clause
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ImportAttribute<'a> {
+    pub key: Node<ImportAttributeKey<'a>>,
+    pub value: Node<Lit<'a>>,
+}
+
+impl<'a> From<ImportAttribute<'a>> for plain::ImportAttribute<'a> {
+    fn from(other: ImportAttribute<'a>) -> Self {
+        plain::ImportAttribute {
+            key: strip(other.key),
+            value: strip(other.value),
+        }
+    }
+}
+
+/// The key of an import attribute
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ImportAttributeKey<'a> {
+    Ident(Ident<'a>),
+    Lit(Lit<'a>),
+}
+
+impl<'a> From<ImportAttributeKey<'a>> for plain::ImportAttributeKey<'a> {
+    fn from(other: ImportAttributeKey<'a>) -> Self {
+        match other {
+            ImportAttributeKey::Ident(ident) => plain::ImportAttributeKey::Ident(ident),
+            ImportAttributeKey::Lit(lit) => plain::ImportAttributeKey::Lit(lit.into()),
+        }
+    }
+}
+
+/// The name of the thing being imported
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ImportSpecifier<'a> {
+    /// A specifier in curly braces, this might
+    /// have a local alias
+    Normal(NormalImportSpec<'a>),
+    /// A specifier that has been exported with the
+    /// default keyword
+    Default(Node<Ident<'a>>),
+    /// Import all exported members from a module
+    /// in a namespace
+    Namespace(Node<Ident<'a>>),
+}
+
+impl<'a> From<ImportSpecifier<'a>> for plain::ImportSpecifier<'a> {
+    fn from(other: ImportSpecifier<'a>) -> Self {
+        match other {
+            ImportSpecifier::Normal(spec) => plain::ImportSpecifier::Normal(plain::NormalImportSpec {
+                local: strip(spec.local),
+                imported: strip(spec.imported),
+            }),
+            ImportSpecifier::Default(ident) => plain::ImportSpecifier::Default(strip(ident)),
+            ImportSpecifier::Namespace(ident) => plain::ImportSpecifier::Namespace(strip(ident)),
+        }
+    }
+}
+
+/// A specifier in curly braces
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct NormalImportSpec<'a> {
+    pub local: Node<Ident<'a>>,
+    pub imported: Node<ModuleExportName<'a>>,
+}
+
+/// The name of a module export
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ModuleExportName<'a> {
+    Ident(Ident<'a>),
+    Str(Lit<'a>),
+}
+
+impl<'a> From<ModuleExportName<'a>> for plain::ModuleExportName<'a> {
+    fn from(other: ModuleExportName<'a>) -> Self {
+        match other {
+            ModuleExportName::Ident(ident) => plain::ModuleExportName::Ident(ident),
+            ModuleExportName::Str(lit) => plain::ModuleExportName::Str(lit.into()),
+        }
+    }
+}
+
+/// Something exported from this module
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ModExport<'a> {
+    /// `export default` followed by a declaration or expression
+    Default(DefaultExportDecl<'a>),
+    /// `export` followed by a declaration or specifiers
+    Named(NamedExportDecl<'a>),
+    /// `export * from 'module'`, optionally with an alias
+    All(Option<Node<ModuleExportName<'a>>>, ExportSource<'a>),
+}
+
+impl<'a> From<ModExport<'a>> for plain::ModExport<'a> {
+    fn from(other: ModExport<'a>) -> Self {
+        match other {
+            ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+                plain::ModExport::Default(plain::DefaultExportDecl::Decl(strip(decl)))
+            }
+            ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+                plain::ModExport::Default(plain::DefaultExportDecl::Expr(strip(expr)))
+            }
+            ModExport::Named(NamedExportDecl::Decl(decl)) => {
+                plain::ModExport::Named(plain::NamedExportDecl::Decl(strip(decl)))
+            }
+            ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+                plain::ModExport::Named(plain::NamedExportDecl::Specifier(
+                    strip_all(specs),
+                    source.map(Into::into),
+                ))
+            }
+            ModExport::All(alias, source) => {
+                plain::ModExport::All(alias.map(strip), source.into())
+            }
+        }
+    }
+}
+
+/// The module a re-export pulls from
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ExportSource<'a> {
+    pub source: Node<Lit<'a>>,
+    pub attributes: Vec<Node<ImportAttribute<'a>>>,
+    pub attributes_keyword: Option<AttributesKeyword>,
+}
+
+impl<'a> From<ExportSource<'a>> for plain::ExportSource<'a> {
+    fn from(other: ExportSource<'a>) -> Self {
+        plain::ExportSource {
+            source: strip(other.source),
+            attributes: strip_all(other.attributes),
+            attributes_keyword: other.attributes_keyword,
+        }
+    }
+}
+
+/// An export that has a name
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum NamedExportDecl<'a> {
+    Decl(Node<Decl<'a>>),
+    Specifier(Vec<Node<ExportSpecifier<'a>>>, Option<ExportSource<'a>>),
+}
+
+/// A default export
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum DefaultExportDecl<'a> {
+    Decl(Node<Decl<'a>>),
+    Expr(Node<Expr<'a>>),
+}
+
+/// The name of the thing being exported
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ExportSpecifier<'a> {
+    pub local: Node<Ident<'a>>,
+    pub exported: Node<ModuleExportName<'a>>,
+}
+
+impl<'a> From<ExportSpecifier<'a>> for plain::ExportSpecifier<'a> {
+    fn from(other: ExportSpecifier<'a>) -> Self {
+        plain::ExportSpecifier {
+            local: strip(other.local),
+            exported: strip(other.exported),
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+use crate::expr as plain;
+use crate::spanned::pat::Pat;
+use crate::spanned::{
+    strip, strip_all, strip_box, AssignOp, BinaryOp, Class, Decorator, Func, FuncArg, FuncBody,
+    GetSpan, Ident, LogicalOp, Node, PropKind, Span, UnaryOp, UpdateOp,
+};
+
+pub use crate::expr::{NumberLit, PrivateId, RegEx, StringLit, TemplateElement};
+
+/// A slightly more granular program part that a statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Expr<'a> {
+    /// `[0,,]`
+    Array(ArrayExpr<'a>),
+    /// An arrow function
+    ArrowFunc(ArrowFuncExpr<'a>),
+    /// Used for resolving possible sequence expressions
+    /// that are arrow parameters
+    ArrowParamPlaceHolder(Vec<Node<FuncArg<'a>>>, bool),
+    /// Assignment or update assignment
+    Assign(AssignExpr<'a>),
+    /// The `await` keyword followed by another `Expr`
+    Await(Box<Node<Expr<'a>>>),
+    /// An operation that has two arguments
+    Binary(BinaryExpr<'a>),
+    /// A class expression
+    Class(Class<'a>),
+    /// Calling a function or method
+    Call(CallExpr<'a>),
+    /// A ternery expression
+    Conditional(ConditionalExpr<'a>),
+    /// see `Function`
+    Func(Func<'a>),
+    /// An identifier
+    Ident(Ident<'a>),
+    /// `import(source)`
+    Import(ImportExpr<'a>),
+    /// A literal value
+    Lit(Lit<'a>),
+    /// A specialized `BinaryExpr` for logical evaluation
+    Logical(LogicalExpr<'a>),
+    /// Accessing the member of a value
+    Member(MemberExpr<'a>),
+    /// currently just `new.target` and `import.meta`
+    MetaProp(MetaProp<'a>),
+    /// The outer most expression of an optional chain
+    OptionalChain(Box<Node<Expr<'a>>>),
+    /// A private name like `#x`
+    PrivateId(PrivateId<'a>),
+    /// `new Uint8Array(32)`
+    New(NewExpr<'a>),
+    /// `{}`
+    Obj(ObjExpr<'a>),
+    /// Any sequence of expressions separated with a comma
+    Sequence(SequenceExpr<'a>),
+    /// `...` followed by an `Expr`
+    Spread(Box<Node<Expr<'a>>>),
+    /// `super`
+    Super,
+    /// A template literal preceded by a tag function identifier
+    TaggedTemplate(TaggedTemplateExpr<'a>),
+    /// `this`
+    This,
+    /// An operation that has one argument
+    Unary(UnaryExpr<'a>),
+    /// Increment or decrement
+    Update(UpdateExpr<'a>),
+    /// yield a value from inside of a generator function
+    Yield(YieldExpr<'a>),
+}
+
+impl<'a> Expr<'a> {
+    /// Compare the shape and values of two expressions
+    /// while ignoring the spans of every node
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        crate::spanned::eq_ignoring_spans(self, other)
+    }
+}
+
+impl<'a> From<Expr<'a>> for plain::Expr<'a> {
+    fn from(other: Expr<'a>) -> Self {
+        match other {
+            Expr::Array(elements) => {
+                plain::Expr::Array(elements.into_iter().map(|e| e.map(strip)).collect())
+            }
+            Expr::ArrowFunc(arrow) => plain::Expr::ArrowFunc(plain::ArrowFuncExpr {
+                id: arrow.id.map(strip),
+                params: strip_all(arrow.params),
+                body: strip(arrow.body),
+                expression: arrow.expression,
+                is_async: arrow.is_async,
+            }),
+            Expr::ArrowParamPlaceHolder(args, is_async) => {
+                plain::Expr::ArrowParamPlaceHolder(strip_all(args), is_async)
+            }
+            Expr::Assign(assign) => plain::Expr::Assign(plain::AssignExpr {
+                operator: assign.operator,
+                left: strip(assign.left),
+                right: strip_box(assign.right),
+            }),
+            Expr::Await(expr) => plain::Expr::Await(strip_box(expr)),
+            Expr::Binary(bin) => plain::Expr::Binary(plain::BinaryExpr {
+                operator: bin.operator,
+                left: strip_box(bin.left),
+                right: strip_box(bin.right),
+            }),
+            Expr::Class(class) => plain::Expr::Class(class.into()),
+            Expr::Call(call) => plain::Expr::Call(plain::CallExpr {
+                callee: strip_box(call.callee),
+                arguments: strip_all(call.arguments),
+                optional: call.optional,
+            }),
+            Expr::Conditional(cond) => plain::Expr::Conditional(plain::ConditionalExpr {
+                test: strip_box(cond.test),
+                alternate: strip_box(cond.alternate),
+                consequent: strip_box(cond.consequent),
+            }),
+            Expr::Func(func) => plain::Expr::Func(func.into()),
+            Expr::Ident(ident) => plain::Expr::Ident(ident),
+            Expr::Import(import) => plain::Expr::Import(plain::ImportExpr {
+                source: strip_box(import.source),
+                options: import.options.map(strip_box),
+            }),
+            Expr::Lit(lit) => plain::Expr::Lit(lit.into()),
+            Expr::Logical(logical) => plain::Expr::Logical(plain::LogicalExpr {
+                operator: logical.operator,
+                left: strip_box(logical.left),
+                right: strip_box(logical.right),
+            }),
+            Expr::Member(member) => plain::Expr::Member(plain::MemberExpr {
+                object: strip_box(member.object),
+                property: member.property.into(),
+                optional: member.optional,
+            }),
+            Expr::MetaProp(meta) => plain::Expr::MetaProp(plain::MetaProp {
+                meta: strip(meta.meta),
+                property: strip(meta.property),
+            }),
+            Expr::OptionalChain(expr) => plain::Expr::OptionalChain(strip_box(expr)),
+            Expr::PrivateId(id) => plain::Expr::PrivateId(id),
+            Expr::New(new) => plain::Expr::New(plain::NewExpr {
+                callee: strip_box(new.callee),
+                arguments: Some(strip_all(new.arguments)),
+            }),
+            Expr::Obj(props) => plain::Expr::Obj(strip_all(props)),
+            Expr::Sequence(seq) => plain::Expr::Sequence(seq.into()),
+            Expr::Spread(expr) => plain::Expr::Spread(strip_box(expr)),
+            Expr::Super => plain::Expr::Super,
+            Expr::TaggedTemplate(tagged) => plain::Expr::TaggedTemplate(plain::TaggedTemplateExpr {
+                tag: strip_box(tagged.tag),
+                quasi: strip(tagged.quasi),
+            }),
+            Expr::This => plain::Expr::This,
+            Expr::Unary(unary) => plain::Expr::Unary(plain::UnaryExpr {
+                operator: unary.operator,
+                prefix: unary.prefix,
+                argument: strip_box(unary.argument),
+            }),
+            Expr::Update(update) => plain::Expr::Update(plain::UpdateExpr {
+                operator: update.operator,
+                argument: strip_box(update.argument),
+                prefix: update.prefix,
+            }),
+            Expr::Yield(y) => plain::Expr::Yield(plain::YieldExpr {
+                argument: y.argument.map(strip_box),
+                delegate: y.delegate,
+            }),
+        }
+    }
+}
+
+/// `[a, b, c]`
+pub type ArrayExpr<'a> = Vec<Option<Node<Expr<'a>>>>;
+/// `{a: 'b', c, ...d}`
+pub type ObjExpr<'a> = Vec<Node<ObjProp<'a>>>;
+/// `a, b, c`, always has at least one expression
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize),
+    serde(try_from = "Vec<Node<Expr<'a>>>", into = "Vec<Node<Expr<'a>>>")
+)]
+pub struct SequenceExpr<'a> {
+    pub head: Box<Node<Expr<'a>>>,
+    pub tail: Vec<Node<Expr<'a>>>,
+}
+
+impl<'a> SequenceExpr<'a> {
+    /// `None` if `exprs` is empty
+    pub fn from_vec(mut exprs: Vec<Node<Expr<'a>>>) -> Option<Self> {
+        if exprs.is_empty() {
+            return None;
+        }
+        let tail = exprs.split_off(1);
+        let head = Box::new(exprs.pop()?);
+        Some(Self { head, tail })
+    }
+}
+
+impl<'a> From<SequenceExpr<'a>> for Vec<Node<Expr<'a>>> {
+    fn from(seq: SequenceExpr<'a>) -> Self {
+        let mut exprs = vec![*seq.head];
+        exprs.extend(seq.tail);
+        exprs
+    }
+}
+
+impl<'a> TryFrom<Vec<Node<Expr<'a>>>> for SequenceExpr<'a> {
+    type Error = plain::EmptySequence;
+    fn try_from(exprs: Vec<Node<Expr<'a>>>) -> Result<Self, Self::Error> {
+        Self::from_vec(exprs).ok_or(plain::EmptySequence)
+    }
+}
+
+impl<'a> From<SequenceExpr<'a>> for plain::SequenceExpr<'a> {
+    fn from(other: SequenceExpr<'a>) -> Self {
+        plain::SequenceExpr {
+            head: strip_box(other.head),
+            tail: strip_all(other.tail),
+        }
+    }
+}
+
+/// A single part of an object literal
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ObjProp<'a> {
+    Prop(Prop<'a>),
+    Spread(Node<Expr<'a>>),
+}
+
+impl<'a> From<ObjProp<'a>> for plain::ObjProp<'a> {
+    fn from(other: ObjProp<'a>) -> Self {
+        match other {
+            ObjProp::Prop(prop) => plain::ObjProp::Prop(prop.into()),
+            ObjProp::Spread(expr) => plain::ObjProp::Spread(strip(expr)),
+        }
+    }
+}
+
+/// A single part of an object literal or class
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Prop<'a> {
+    pub key: Node<PropKey<'a>>,
+    pub value: PropValue<'a>,
+    pub kind: PropKind,
+    pub method: bool,
+    pub computed: bool,
+    pub short_hand: bool,
+    pub is_static: bool,
+    pub decorators: Vec<Node<Decorator<'a>>>,
+}
+
+impl<'a> From<Prop<'a>> for plain::Prop<'a> {
+    fn from(other: Prop<'a>) -> Self {
+        plain::Prop {
+            key: strip(other.key),
+            value: other.value.into(),
+            kind: other.kind,
+            method: other.method,
+            computed: other.computed,
+            short_hand: other.short_hand,
+            is_static: other.is_static,
+            decorators: strip_all(other.decorators),
+        }
+    }
+}
+
+/// An object literal or class property identifier
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum PropKey<'a> {
+    Lit(Lit<'a>),
+    Expr(Expr<'a>),
+    Pat(Pat<'a>),
+    Private(PrivateId<'a>),
+}
+
+impl<'a> From<PropKey<'a>> for plain::PropKey<'a> {
+    fn from(other: PropKey<'a>) -> Self {
+        match other {
+            PropKey::Lit(lit) => plain::PropKey::Lit(lit.into()),
+            PropKey::Expr(expr) => plain::PropKey::Expr(expr.into()),
+            PropKey::Pat(pat) => plain::PropKey::Pat(pat.into()),
+            PropKey::Private(id) => plain::PropKey::Private(id),
+        }
+    }
+}
+
+/// The value of an object literal or class property
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum PropValue<'a> {
+    Expr(Node<Expr<'a>>),
+    Pat(Node<Pat<'a>>),
+    None,
+}
+
+impl<'a> From<PropValue<'a>> for plain::PropValue<'a> {
+    fn from(other: PropValue<'a>) -> Self {
+        match other {
+            PropValue::Expr(expr) => plain::PropValue::Expr(strip(expr)),
+            PropValue::Pat(pat) => plain::PropValue::Pat(strip(pat)),
+            PropValue::None => plain::PropValue::None,
+        }
+    }
+}
+
+/// An operation that takes one argument
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct UnaryExpr<'a> {
+    pub operator: UnaryOp,
+    pub prefix: bool,
+    pub argument: Box<Node<Expr<'a>>>,
+}
+
+/// Increment or decrementing a value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct UpdateExpr<'a> {
+    pub operator: UpdateOp,
+    pub argument: Box<Node<Expr<'a>>>,
+    pub prefix: bool,
+}
+
+/// An operation that requires 2 arguments
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct BinaryExpr<'a> {
+    pub operator: BinaryOp,
+    pub left: Box<Node<Expr<'a>>>,
+    pub right: Box<Node<Expr<'a>>>,
+}
+
+/// An assignment or update + assignment operation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct AssignExpr<'a> {
+    pub operator: AssignOp,
+    pub left: Node<AssignLeft<'a>>,
+    pub right: Box<Node<Expr<'a>>>,
+}
+
+/// The value being assigned to
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum AssignLeft<'a> {
+    Pat(Pat<'a>),
+    Expr(Box<Expr<'a>>),
+}
+
+impl<'a> From<AssignLeft<'a>> for plain::AssignLeft<'a> {
+    fn from(other: AssignLeft<'a>) -> Self {
+        match other {
+            AssignLeft::Pat(pat) => plain::AssignLeft::Pat(pat.into()),
+            AssignLeft::Expr(expr) => plain::AssignLeft::Expr(Box::new((*expr).into())),
+        }
+    }
+}
+
+/// A specialized `BinaryExpr` for logical evaluation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct LogicalExpr<'a> {
+    pub operator: LogicalOp,
+    pub left: Box<Node<Expr<'a>>>,
+    pub right: Box<Node<Expr<'a>>>,
+}
+
+/// Accessing the member of a value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct MemberExpr<'a> {
+    pub object: Box<Node<Expr<'a>>>,
+    pub property: MemberProp<'a>,
+    pub optional: bool,
+}
+
+/// The property of a `MemberExpr`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum MemberProp<'a> {
+    /// `a.b`
+    Ident(Node<Ident<'a>>),
+    /// `a.#b`
+    Private(Node<PrivateId<'a>>),
+    /// `a['b']` or `a[b]`, the span doesn't include the brackets
+    Computed(Box<Node<Expr<'a>>>),
+}
+
+impl<'a> GetSpan for MemberProp<'a> {
+    fn span(&self) -> Span {
+        match self {
+            MemberProp::Ident(ident) => ident.span,
+            MemberProp::Private(id) => id.span,
+            MemberProp::Computed(expr) => expr.span,
+        }
+    }
+}
+
+impl<'a> From<MemberProp<'a>> for plain::MemberProp<'a> {
+    fn from(other: MemberProp<'a>) -> Self {
+        match other {
+            MemberProp::Ident(ident) => plain::MemberProp::Ident(strip(ident)),
+            MemberProp::Private(id) => plain::MemberProp::Private(strip(id)),
+            MemberProp::Computed(expr) => plain::MemberProp::Computed(strip_box(expr)),
+        }
+    }
+}
+
+/// A ternery expression
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ConditionalExpr<'a> {
+    pub test: Box<Node<Expr<'a>>>,
+    pub alternate: Box<Node<Expr<'a>>>,
+    pub consequent: Box<Node<Expr<'a>>>,
+}
+
+/// Calling a function or method
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct CallExpr<'a> {
+    pub callee: Box<Node<Expr<'a>>>,
+    pub arguments: Vec<Node<Expr<'a>>>,
+    pub optional: bool,
+}
+
+/// A dynamic import
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ImportExpr<'a> {
+    pub source: Box<Node<Expr<'a>>>,
+    pub options: Option<Box<Node<Expr<'a>>>>,
+}
+
+/// Calling a constructor
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct NewExpr<'a> {
+    pub callee: Box<Node<Expr<'a>>>,
+    pub arguments: Vec<Node<Expr<'a>>>,
+}
+
+/// An arrow function
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ArrowFuncExpr<'a> {
+    pub id: Option<Node<Ident<'a>>>,
+    pub params: Vec<Node<FuncArg<'a>>>,
+    pub body: Node<ArrowFuncBody<'a>>,
+    pub expression: bool,
+    pub is_async: bool,
+}
+
+/// The body portion of an arrow function can be either an expression or a block of statements
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ArrowFuncBody<'a> {
+    FuncBody(FuncBody<'a>),
+    Expr(Box<Expr<'a>>),
+}
+
+impl<'a> From<ArrowFuncBody<'a>> for plain::ArrowFuncBody<'a> {
+    fn from(other: ArrowFuncBody<'a>) -> Self {
+        match other {
+            ArrowFuncBody::FuncBody(body) => plain::ArrowFuncBody::FuncBody(body.into()),
+            ArrowFuncBody::Expr(expr) => plain::ArrowFuncBody::Expr(Box::new((*expr).into())),
+        }
+    }
+}
+
+/// yield a value from inside of a generator function
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct YieldExpr<'a> {
+    pub argument: Option<Box<Node<Expr<'a>>>>,
+    pub delegate: bool,
+}
+
+/// A Template literal preceded by a function identifier
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct TaggedTemplateExpr<'a> {
+    pub tag: Box<Node<Expr<'a>>>,
+    pub quasi: Node<TemplateLit<'a>>,
+}
+
+/// A template string literal
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct TemplateLit<'a> {
+    pub quasis: Vec<Node<TemplateElement<'a>>>,
+    pub expressions: Vec<Node<Expr<'a>>>,
+}
+
+impl<'a> From<TemplateLit<'a>> for plain::TemplateLit<'a> {
+    fn from(other: TemplateLit<'a>) -> Self {
+        plain::TemplateLit {
+            quasis: strip_all(other.quasis),
+            expressions: strip_all(other.expressions),
+        }
+    }
+}
+
+/// pretty much just `new.target` or `import.meta`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct MetaProp<'a> {
+    pub meta: Node<Ident<'a>>,
+    pub property: Node<Ident<'a>>,
+}
+
+/// A literal value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Lit<'a> {
+    /// `null`
+    Null,
+    /// `"string"` or `'string'`
+    String(StringLit<'a>),
+    /// `0`, `0.0`, `.0`, `-0`, `0x0` and friends
+    Number(NumberLit<'a>),
+    /// `10n`
+    BigInt(Cow<'a, str>),
+    /// `true` or `false`
+    Boolean(bool),
+    /// `/.+/g`
+    RegEx(RegEx<'a>),
+    /// `${p}`
+    Template(TemplateLit<'a>),
+}
+
+impl<'a> From<Lit<'a>> for plain::Lit<'a> {
+    fn from(other: Lit<'a>) -> Self {
+        match other {
+            Lit::Null => plain::Lit::Null,
+            Lit::String(s) => plain::Lit::String(s),
+            Lit::Number(n) => plain::Lit::Number(n),
+            Lit::BigInt(n) => plain::Lit::BigInt(n),
+            Lit::Boolean(b) => plain::Lit::Boolean(b),
+            Lit::RegEx(re) => plain::Lit::RegEx(re),
+            Lit::Template(template) => plain::Lit::Template(template.into()),
+        }
+    }
+}
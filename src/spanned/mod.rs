@@ -0,0 +1,845 @@
+//! An AST where every node knows where it came from
+//!
+//! The types in this module mirror the ones at the top level of the
+//! crate, the difference is that every child node is wrapped in
+//! a `Node` which pairs the value with the `Span` of source text
+//! it was parsed from. Leaf values that can't contain other nodes
+//! (identifiers, operators and most literals) are shared with the
+//! top level AST.
+//!
+//! Every type here can be converted into its top level counterpart
+//! with `From`/`Into`, which drops the position information.
+//!
+//! With the `serialization` feature, and without `esprima`, these
+//! types implement `Serialize` and `Deserialize`. A `Node` is written
+//! as its inner value with extra `span`, `range` and `loc` fields,
+//! the last two match the ones ESTree tools like eslint expect.
+// every child carrying a span puts some variants over the size limit
+#![allow(clippy::large_enum_variant)]
+use std::borrow::Cow;
+use std::cell::Cell;
+
+pub mod decl;
+pub mod expr;
+pub mod pat;
+pub mod stmt;
+
+use decl::Decl;
+use expr::{Expr, Lit, PropKey};
+use pat::Pat;
+use stmt::BlockStmt;
+
+pub use crate::{
+    AssignOp, BinaryOp, Ident, LogicalOp, PropKind, UnaryOp, UpdateOp, VarKind,
+};
+
+/// A single point in the source text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Position {
+    /// The line number, starting at 1
+    pub line: usize,
+    /// The number of characters from the start of the line, starting at 0
+    pub column: usize,
+    /// The byte offset from the start of the source
+    pub offset: usize,
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize, offset: usize) -> Self {
+        Self {
+            line,
+            column,
+            offset,
+        }
+    }
+    /// The line and column of the byte `offset` into `source`, an
+    /// offset past the end is clamped to the end of `source`
+    /// ```
+    /// use resast::spanned::Position;
+    /// let source = "let a;\nlet b;";
+    /// assert_eq!(Position::from_offset(source, 11), Position::new(2, 4, 11));
+    /// ```
+    pub fn from_offset(source: &str, offset: usize) -> Self {
+        let mut end = offset.min(source.len());
+        // back up to a char boundary so the slice can't panic
+        while !source.is_char_boundary(end) {
+            end -= 1;
+        }
+        let before = &source[..end];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count(),
+            offset,
+        }
+    }
+}
+
+/// A line and column without the byte offset, this is
+/// the shape of the `start` and `end` of an ESTree `loc`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct LineCol {
+    /// The line number, starting at 1
+    pub line: usize,
+    /// The number of characters from the start of the line, starting at 0
+    pub column: usize,
+}
+
+impl From<Position> for LineCol {
+    fn from(other: Position) -> Self {
+        Self {
+            line: other.line,
+            column: other.column,
+        }
+    }
+}
+
+/// The ESTree `loc` of a node
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct SourceLocation {
+    pub start: LineCol,
+    pub end: LineCol,
+    /// The name of the file the node came from, a `Span` doesn't
+    /// know this so it is always `None` when converting from one
+    pub source: Option<String>,
+}
+
+impl From<Span> for SourceLocation {
+    fn from(other: Span) -> Self {
+        Self {
+            start: other.start.into(),
+            end: other.end.into(),
+            source: None,
+        }
+    }
+}
+
+/// The range of source text a node was parsed from, `end`
+/// is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+    /// The span of the bytes `start..end` of `source`
+    pub fn from_offsets(source: &str, start: usize, end: usize) -> Self {
+        Self {
+            start: Position::from_offset(source, start),
+            end: Position::from_offset(source, end),
+        }
+    }
+    /// The ESTree `range`, the start and end byte offsets
+    pub fn range(&self) -> [usize; 2] {
+        [self.start.offset, self.end.offset]
+    }
+    /// The ESTree `loc`, the start and end lines and columns
+    pub fn loc(&self) -> SourceLocation {
+        (*self).into()
+    }
+    /// If `other` falls completely inside of this span
+    pub fn contains(&self, other: &Span) -> bool {
+        self.start.offset <= other.start.offset && other.end.offset <= self.end.offset
+    }
+    /// The number of bytes this span covers, a span that ends
+    /// before it starts is empty
+    pub fn len(&self) -> usize {
+        self.end.offset.saturating_sub(self.start.offset)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A value paired with the span of source it was parsed from
+///
+/// With the `serde` feature the fields of `inner` are flattened
+/// into the node, next to `span`, `range` and `loc` fields. The
+/// last two are only written, reading a node only needs the `span`
+#[derive(Debug, Clone)]
+#[cfg_attr(all(feature = "serde", not(feature = "esprima")), derive(Deserialize))]
+pub struct Node<T> {
+    #[cfg_attr(all(feature = "serde", not(feature = "esprima")), serde(flatten))]
+    pub inner: T,
+    pub span: Span,
+}
+
+thread_local! {
+    /// Set while `eq_ignoring_spans` is comparing two trees
+    static IGNORE_SPANS: Cell<bool> = const { Cell::new(false) };
+}
+
+impl<T: PartialEq> PartialEq for Node<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (IGNORE_SPANS.with(Cell::get) || self.span == other.span) && self.inner == other.inner
+    }
+}
+
+/// Compare `left` and `right` with their derived `PartialEq`, walking
+/// both trees together, but with `Node` skipping its span
+pub(crate) fn eq_ignoring_spans<T: PartialEq>(left: &T, right: &T) -> bool {
+    /// Puts the previous value back even if a comparison panics
+    struct Reset(bool);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            IGNORE_SPANS.with(|ignore| ignore.set(self.0));
+        }
+    }
+    let _reset = Reset(IGNORE_SPANS.with(|ignore| ignore.replace(true)));
+    left == right
+}
+
+impl<T> Node<T> {
+    pub fn new(inner: T, span: Span) -> Self {
+        Self { inner, span }
+    }
+    pub fn span(&self) -> Span {
+        self.span
+    }
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+    /// Apply `f` to the inner value, keeping the same span
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Node<U> {
+        Node {
+            inner: f(self.inner),
+            span: self.span,
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", not(feature = "esprima")))]
+impl<T: serde::Serialize> serde::Serialize for Node<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Located<'n, T> {
+            #[serde(flatten)]
+            inner: &'n T,
+            span: Span,
+            range: [usize; 2],
+            loc: SourceLocation,
+        }
+        Located {
+            inner: &self.inner,
+            span: self.span,
+            range: self.span.range(),
+            loc: self.span.loc(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<T> std::ops::Deref for Node<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T> std::ops::DerefMut for Node<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+/// Anything that knows the span of source it came from
+pub trait GetSpan {
+    fn span(&self) -> Span;
+    /// The text of `source` this was parsed from, `source`
+    /// needs to be the full text that was parsed
+    fn source<'s>(&self, source: &'s str) -> &'s str {
+        slice_source(source, self.span())
+    }
+    /// The ESTree `range` of this node
+    fn range(&self) -> [usize; 2] {
+        self.span().range()
+    }
+    /// The ESTree `loc` of this node
+    fn loc(&self) -> SourceLocation {
+        self.span().loc()
+    }
+}
+
+impl GetSpan for Span {
+    fn span(&self) -> Span {
+        *self
+    }
+}
+
+impl<T> GetSpan for Node<T> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl<T: GetSpan> GetSpan for Box<T> {
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+}
+
+/// The text of `source` covered by `span`, if the span is out of
+/// bounds or doesn't fall on a `char` boundary this is an
+/// empty string
+pub fn slice_source(source: &str, span: Span) -> &str {
+    source.get(span.start.offset..span.end.offset).unwrap_or("")
+}
+
+/// The newtype bodies would serialize as a plain list, which
+/// can't be flattened into a `Node`, so they are written as
+/// `{ "body": [...] }` instead
+#[cfg(all(feature = "serde", not(feature = "esprima")))]
+macro_rules! body_serde {
+    ($ty:ty, $item:ty) => {
+        impl<'a> serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+                let mut state = serializer.serialize_struct("Body", 1)?;
+                state.serialize_field("body", &self.0)?;
+                state.end()
+            }
+        }
+
+        impl<'de, 'a> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                struct Body<T> {
+                    body: Vec<T>,
+                }
+                Body::<$item>::deserialize(deserializer).map(|b| Self(b.body))
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "serde", not(feature = "esprima")))]
+body_serde!(FuncBody<'a>, Node<ProgramPart<'a>>);
+#[cfg(all(feature = "serde", not(feature = "esprima")))]
+body_serde!(ClassBody<'a>, Node<ClassMember<'a>>);
+#[cfg(all(feature = "serde", not(feature = "esprima")))]
+body_serde!(stmt::BlockStmt<'a>, Node<ProgramPart<'a>>);
+
+/// Drop the span of a node and convert its value to the
+/// top level AST
+pub(crate) fn strip<T: Into<U>, U>(node: Node<T>) -> U {
+    node.inner.into()
+}
+
+// the box is the node's container in the parent, there's no
+// way to reuse the allocation since the inner type changes
+#[allow(clippy::boxed_local)]
+pub(crate) fn strip_box<T: Into<U>, U>(node: Box<Node<T>>) -> Box<U> {
+    Box::new(node.inner.into())
+}
+
+pub(crate) fn strip_all<T: Into<U>, U>(nodes: Vec<Node<T>>) -> Vec<U> {
+    nodes.into_iter().map(strip).collect()
+}
+
+/// A fully parsed javascript program.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Program<'a> {
+    /// An ES6 Mod
+    Mod(Vec<Node<ProgramPart<'a>>>, Option<Cow<'a, str>>),
+    /// Not an ES6 Mod
+    Script(Vec<Node<ProgramPart<'a>>>, Option<Cow<'a, str>>),
+}
+
+impl<'a> Program<'a> {
+    /// Compare the shape and values of two programs
+    /// while ignoring the spans of every node
+    pub fn structural_eq(&self, other: &Self) -> bool {
+        eq_ignoring_spans(self, other)
+    }
+}
+
+impl<'a> From<Program<'a>> for crate::Program<'a> {
+    fn from(other: Program<'a>) -> Self {
+        match other {
+            Program::Mod(parts, hashbang) => crate::Program::Mod(strip_all(parts), hashbang),
+            Program::Script(parts, hashbang) => crate::Program::Script(strip_all(parts), hashbang),
+        }
+    }
+}
+
+/// A single part of a Javascript program.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ProgramPart<'a> {
+    /// A Directive like `'use strict';`
+    Dir(Dir<'a>),
+    /// A variable, function or module declaration
+    Decl(Decl<'a>),
+    /// Any other kind of statement
+    Stmt(stmt::Stmt<'a>),
+}
+
+impl<'a> From<ProgramPart<'a>> for crate::ProgramPart<'a> {
+    fn from(other: ProgramPart<'a>) -> Self {
+        match other {
+            ProgramPart::Dir(dir) => crate::ProgramPart::Dir(dir.into()),
+            ProgramPart::Decl(decl) => crate::ProgramPart::Decl(decl.into()),
+            ProgramPart::Stmt(stmt) => crate::ProgramPart::Stmt(stmt.into()),
+        }
+    }
+}
+
+/// pretty much always `'use strict'`, this can appear at the
+/// top of a file or function
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Dir<'a> {
+    pub expr: Node<Lit<'a>>,
+    pub dir: Cow<'a, str>,
+}
+
+impl<'a> From<Dir<'a>> for crate::Dir<'a> {
+    fn from(other: Dir<'a>) -> Self {
+        crate::Dir {
+            expr: strip(other.expr),
+            dir: other.dir,
+        }
+    }
+}
+
+/// A function, this will be part of either a function
+/// declaration (ID is required) or a function expression
+/// (ID is optional)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Func<'a> {
+    pub id: Option<Node<Ident<'a>>>,
+    pub params: Vec<Node<FuncArg<'a>>>,
+    pub body: Node<FuncBody<'a>>,
+    pub generator: bool,
+    pub is_async: bool,
+}
+
+impl<'a> From<Func<'a>> for crate::Func<'a> {
+    fn from(other: Func<'a>) -> Self {
+        crate::Func {
+            id: other.id.map(strip),
+            params: strip_all(other.params),
+            body: strip(other.body),
+            generator: other.generator,
+            is_async: other.is_async,
+        }
+    }
+}
+
+/// A single function argument from a function signature
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum FuncArg<'a> {
+    Expr(Expr<'a>),
+    Pat(Pat<'a>),
+}
+
+impl<'a> From<FuncArg<'a>> for crate::FuncArg<'a> {
+    fn from(other: FuncArg<'a>) -> Self {
+        match other {
+            FuncArg::Expr(expr) => crate::FuncArg::Expr(expr.into()),
+            FuncArg::Pat(pat) => crate::FuncArg::Pat(pat.into()),
+        }
+    }
+}
+
+/// The block statement that makes up the function's body
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuncBody<'a>(pub Vec<Node<ProgramPart<'a>>>);
+
+impl<'a> From<FuncBody<'a>> for crate::FuncBody<'a> {
+    fn from(other: FuncBody<'a>) -> Self {
+        crate::FuncBody(strip_all(other.0))
+    }
+}
+
+/// A way to declare object templates
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Class<'a> {
+    pub id: Option<Node<Ident<'a>>>,
+    pub super_class: Option<Box<Node<Expr<'a>>>>,
+    pub body: Node<ClassBody<'a>>,
+    pub decorators: Vec<Node<Decorator<'a>>>,
+}
+
+impl<'a> From<Class<'a>> for crate::Class<'a> {
+    fn from(other: Class<'a>) -> Self {
+        crate::Class {
+            id: other.id.map(strip),
+            super_class: other.super_class.map(strip_box),
+            body: strip(other.body),
+            decorators: strip_all(other.decorators),
+        }
+    }
+}
+
+/// A decorator applied to a class or class member
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct Decorator<'a> {
+    pub expression: Node<Expr<'a>>,
+}
+
+impl<'a> From<Decorator<'a>> for crate::Decorator<'a> {
+    fn from(other: Decorator<'a>) -> Self {
+        crate::Decorator {
+            expression: strip(other.expression),
+        }
+    }
+}
+
+/// The body of a class
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassBody<'a>(pub Vec<Node<ClassMember<'a>>>);
+
+impl<'a> From<ClassBody<'a>> for crate::ClassBody<'a> {
+    fn from(other: ClassBody<'a>) -> Self {
+        crate::ClassBody(strip_all(other.0))
+    }
+}
+
+/// A single entry in a class body
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ClassMember<'a> {
+    /// A method, getter, setter or constructor
+    Method(MethodDef<'a>),
+    /// A field with an optional initializer
+    Field(FieldDef<'a>),
+    /// A `static { }` initialization block
+    StaticBlock(BlockStmt<'a>),
+}
+
+impl<'a> From<ClassMember<'a>> for crate::ClassMember<'a> {
+    fn from(other: ClassMember<'a>) -> Self {
+        match other {
+            ClassMember::Method(method) => crate::ClassMember::Method(method.into()),
+            ClassMember::Field(field) => crate::ClassMember::Field(field.into()),
+            ClassMember::StaticBlock(block) => crate::ClassMember::StaticBlock(block.into()),
+        }
+    }
+}
+
+/// A method in a class body
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct MethodDef<'a> {
+    pub key: Node<PropKey<'a>>,
+    pub value: Node<Func<'a>>,
+    pub kind: crate::MethodKind,
+    pub computed: bool,
+    pub is_static: bool,
+    pub decorators: Vec<Node<Decorator<'a>>>,
+}
+
+impl<'a> From<MethodDef<'a>> for crate::MethodDef<'a> {
+    fn from(other: MethodDef<'a>) -> Self {
+        crate::MethodDef {
+            key: strip(other.key),
+            value: strip(other.value),
+            kind: other.kind,
+            computed: other.computed,
+            is_static: other.is_static,
+            decorators: strip_all(other.decorators),
+        }
+    }
+}
+
+/// A field in a class body
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct FieldDef<'a> {
+    pub key: Node<PropKey<'a>>,
+    pub value: Option<Node<Expr<'a>>>,
+    pub computed: bool,
+    pub is_static: bool,
+    pub decorators: Vec<Node<Decorator<'a>>>,
+}
+
+impl<'a> From<FieldDef<'a>> for crate::FieldDef<'a> {
+    fn from(other: FieldDef<'a>) -> Self {
+        crate::FieldDef {
+            key: strip(other.key),
+            value: other.value.map(strip),
+            computed: other.computed,
+            is_static: other.is_static,
+            decorators: strip_all(other.decorators),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::expr::BinaryExpr;
+    use super::*;
+
+    fn pos(offset: usize) -> Position {
+        Position::new(1, offset, offset)
+    }
+
+    fn node<T>(inner: T, start: usize, end: usize) -> Node<T> {
+        Node::new(inner, Span::new(pos(start), pos(end)))
+    }
+
+    fn ident<'a>(name: &'a str, start: usize) -> Box<Node<Expr<'a>>> {
+        Box::new(node(
+            Expr::Ident(Ident::from(name)),
+            start,
+            start + name.len(),
+        ))
+    }
+
+    #[test]
+    fn slice_member_and_string() {
+        let src = "let x = a.b + 'hi';";
+        // a.b
+        let member = node(
+            Expr::Member(expr::MemberExpr {
+                object: ident("a", 8),
+                property: expr::MemberProp::Ident(node(Ident::from("b"), 10, 11)),
+                optional: false,
+            }),
+            8,
+            11,
+        );
+        assert_eq!(member.source(src), "a.b");
+        if let Expr::Member(inner) = &member.inner {
+            assert_eq!(inner.property.source(src), "b");
+        }
+        // 'hi'
+        let string = node(
+            Expr::Lit(Lit::String(expr::StringLit::single_from("hi"))),
+            14,
+            18,
+        );
+        assert_eq!(string.source(src), "'hi'");
+        assert_eq!(slice_source(src, string.span()), "'hi'");
+        assert_eq!(node((), 14, 100).source(src), "");
+    }
+
+    #[cfg(all(feature = "serde", not(feature = "esprima")))]
+    #[test]
+    fn serde_round_trip() {
+        // a + b
+        let sum = node(
+            Expr::Binary(BinaryExpr {
+                operator: BinaryOp::Plus,
+                left: ident("a", 0),
+                right: ident("b", 4),
+            }),
+            0,
+            5,
+        );
+        let json = serde_json::to_value(&sum).expect("failed to serialize spanned expr");
+        assert_eq!(json["span"]["start"]["offset"], 0);
+        assert_eq!(json["span"]["end"]["offset"], 5);
+        assert_eq!(json["Binary"]["left"]["span"]["end"]["column"], 1);
+        assert_eq!(json["range"], serde_json::json!([0, 5]));
+        assert_eq!(json["loc"]["start"]["line"], 1);
+        assert_eq!(json["loc"]["end"]["column"], 5);
+        assert!(json["loc"]["source"].is_null());
+        let back: Node<Expr> = serde_json::from_value(json).expect("failed to deserialize");
+        assert_eq!(back, sum);
+        // function f() { a + b }
+        let func = Func {
+            id: Some(node(Ident::from("f"), 9, 10)),
+            params: vec![],
+            body: node(
+                FuncBody(vec![node(ProgramPart::Stmt(stmt::Stmt::Expr(sum)), 15, 20)]),
+                13,
+                22,
+            ),
+            generator: false,
+            is_async: false,
+        };
+        let program = Program::Script(
+            vec![node(ProgramPart::Decl(decl::Decl::Func(func)), 0, 22)],
+            None,
+        );
+        let json = serde_json::to_string(&program).expect("failed to serialize spanned program");
+        let back: Program = serde_json::from_str(&json).expect("failed to deserialize");
+        assert_eq!(back, program);
+    }
+
+    #[test]
+    fn nested_spans() {
+        // (a + b) * c
+        let sum = Box::new(node(
+            Expr::Binary(BinaryExpr {
+                operator: BinaryOp::Plus,
+                left: ident("a", 1),
+                right: ident("b", 5),
+            }),
+            1,
+            6,
+        ));
+        let product = node(
+            Expr::Binary(BinaryExpr {
+                operator: BinaryOp::Times,
+                left: sum,
+                right: ident("c", 10),
+            }),
+            0,
+            11,
+        );
+        let outer = product.span();
+        assert_eq!(outer.len(), 11);
+        let backwards = Span {
+            start: outer.end,
+            end: outer.start,
+        };
+        assert_eq!(backwards.len(), 0);
+        assert!(backwards.is_empty());
+        let (left, right) = match &product.inner {
+            Expr::Binary(bin) => (&bin.left, &bin.right),
+            _ => unreachable!(),
+        };
+        assert!(outer.contains(&left.span()));
+        assert!(outer.contains(&right.span()));
+        assert!(left.span().end.offset <= right.span().start.offset);
+        match &left.inner {
+            Expr::Binary(bin) => {
+                assert!(left.span().contains(&bin.left.span()));
+                assert!(left.span().contains(&bin.right.span()));
+                assert!(!bin.left.span().contains(&left.span()));
+            }
+            _ => unreachable!(),
+        }
+        let moved = node(
+            Expr::Binary(BinaryExpr {
+                operator: BinaryOp::Times,
+                left: Box::new(node(
+                    Expr::Binary(BinaryExpr {
+                        operator: BinaryOp::Plus,
+                        left: ident("a", 0),
+                        right: ident("b", 2),
+                    }),
+                    0,
+                    3,
+                )),
+                right: ident("c", 4),
+            }),
+            0,
+            5,
+        );
+        assert_ne!(product, moved);
+        assert!(product.structural_eq(&moved));
+        let plain: crate::expr::Expr = product.inner.into();
+        assert_eq!(
+            plain,
+            crate::expr::Expr::Binary(crate::expr::BinaryExpr {
+                operator: BinaryOp::Times,
+                left: Box::new(crate::expr::Expr::Binary(crate::expr::BinaryExpr {
+                    operator: BinaryOp::Plus,
+                    left: Box::new(crate::expr::Expr::ident_from("a")),
+                    right: Box::new(crate::expr::Expr::ident_from("b")),
+                })),
+                right: Box::new(crate::expr::Expr::ident_from("c")),
+            })
+        );
+    }
+
+    #[test]
+    fn structural_eq_ignores_spans() {
+        // a;
+        let stmt = |start, end| {
+            node(
+                ProgramPart::Stmt(stmt::Stmt::Expr(*ident("a", start))),
+                start,
+                end,
+            )
+        };
+        let first = Program::Script(vec![stmt(0, 2)], None);
+        let second = Program::Script(vec![stmt(10, 12)], None);
+        assert_ne!(first, second);
+        assert!(first.structural_eq(&second));
+        assert_ne!(first, second);
+        let other = Program::Script(vec![stmt(0, 2), stmt(2, 4)], None);
+        assert!(!first.structural_eq(&other));
+        let module = Program::Mod(vec![stmt(0, 2)], None);
+        assert!(!first.structural_eq(&module));
+    }
+
+    #[test]
+    fn estree_locations() {
+        let src = "let a = 1;\nfunction f() {\n    return a;\n}\n";
+        let span = |text: &str| {
+            let start = src.find(text).unwrap();
+            Span::from_offsets(src, start, start + text.len())
+        };
+        let ret = span("return a;");
+        assert_eq!(ret.range(), [30, 39]);
+        assert_eq!(
+            ret.loc(),
+            SourceLocation {
+                start: LineCol { line: 3, column: 4 },
+                end: LineCol { line: 3, column: 13 },
+                source: None,
+            }
+        );
+        let func = Node::new((), span("function f() {\n    return a;\n}"));
+        assert_eq!(func.loc().start, LineCol { line: 2, column: 0 });
+        assert_eq!(func.loc().end, LineCol { line: 4, column: 1 });
+        assert_eq!(func.range(), [11, 41]);
+        // columns count characters, not bytes
+        assert_eq!(Position::from_offset("\u{e9}\u{e9}x", 4), Position::new(1, 2, 4));
+        assert_eq!(Position::from_offset("\u{e9}", 1).column, 0);
+        assert_eq!(Position::from_offset("a\n", 10), Position::new(2, 0, 10));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::spanned::expr::{Expr, Prop};
+use crate::spanned::{strip, strip_box, Ident, Node};
+use crate::pat as plain;
+
+/// All of the different ways you can declare an identifier
+/// and/or value
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Pat<'a> {
+    Ident(Ident<'a>),
+    Obj(ObjPat<'a>),
+    Array(Vec<Option<Node<ArrayPatPart<'a>>>>),
+    RestElement(Box<Node<Pat<'a>>>),
+    Assign(AssignPat<'a>),
+}
+
+impl<'a> From<Pat<'a>> for plain::Pat<'a> {
+    fn from(other: Pat<'a>) -> Self {
+        match other {
+            Pat::Ident(ident) => plain::Pat::Ident(ident),
+            Pat::Obj(parts) => plain::Pat::Obj(parts.into_iter().map(strip).collect()),
+            Pat::Array(parts) => {
+                plain::Pat::Array(parts.into_iter().map(|p| p.map(strip)).collect())
+            }
+            Pat::RestElement(pat) => plain::Pat::RestElement(strip_box(pat)),
+            Pat::Assign(assign) => plain::Pat::Assign(assign.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ArrayPatPart<'a> {
+    Pat(Pat<'a>),
+    Expr(Expr<'a>),
+}
+
+impl<'a> From<ArrayPatPart<'a>> for plain::ArrayPatPart<'a> {
+    fn from(other: ArrayPatPart<'a>) -> Self {
+        match other {
+            ArrayPatPart::Pat(pat) => plain::ArrayPatPart::Pat(pat.into()),
+            ArrayPatPart::Expr(expr) => plain::ArrayPatPart::Expr(expr.into()),
+        }
+    }
+}
+
+/// similar to an `ObjectExpr`
+pub type ObjPat<'a> = Vec<Node<ObjPatPart<'a>>>;
+
+/// A single part of an ObjectPat
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum ObjPatPart<'a> {
+    Assign(Prop<'a>),
+    Rest(Box<Node<Pat<'a>>>),
+}
+
+impl<'a> From<ObjPatPart<'a>> for plain::ObjPatPart<'a> {
+    fn from(other: ObjPatPart<'a>) -> Self {
+        match other {
+            ObjPatPart::Assign(prop) => plain::ObjPatPart::Assign(prop.into()),
+            ObjPatPart::Rest(pat) => plain::ObjPatPart::Rest(strip_box(pat)),
+        }
+    }
+}
+
+/// An assignment as a pattern
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct AssignPat<'a> {
+    pub left: Box<Node<Pat<'a>>>,
+    pub right: Box<Node<Expr<'a>>>,
+}
+
+impl<'a> From<AssignPat<'a>> for plain::AssignPat<'a> {
+    fn from(other: AssignPat<'a>) -> Self {
+        plain::AssignPat {
+            left: strip_box(other.left),
+            right: strip_box(other.right),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::spanned::decl::VarDecl;
+use crate::spanned::expr::Expr;
+use crate::spanned::pat::Pat;
+use crate::spanned::{strip, strip_all, strip_box, Ident, Node, ProgramPart, VarKind};
+use crate::stmt as plain;
+
+/// A slightly more granular part of an es program than ProgramPart
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum Stmt<'a> {
+    /// Any expression
+    Expr(Node<Expr<'a>>),
+    /// A collection of program parts wrapped in curly braces
+    Block(BlockStmt<'a>),
+    /// A single semi-colon
+    Empty,
+    /// The contextual keyword `debugger`
+    Debugger,
+    /// A with statement, this puts one object at the top of
+    /// the identifier search tree.
+    With(WithStmt<'a>),
+    /// A return statement
+    Return(Option<Node<Expr<'a>>>),
+    /// A labeled statement
+    Labeled(LabeledStmt<'a>),
+    /// A break statement
+    Break(Option<Node<Ident<'a>>>),
+    /// A short circuit continuation of a loop
+    Continue(Option<Node<Ident<'a>>>),
+    /// An if statement
+    If(IfStmt<'a>),
+    /// A switch statement
+    Switch(SwitchStmt<'a>),
+    /// A statement that throws an error
+    Throw(Node<Expr<'a>>),
+    /// A try/catch block
+    Try(TryStmt<'a>),
+    /// A while loop
+    While(WhileStmt<'a>),
+    /// A while loop that executes its body first
+    DoWhile(DoWhileStmt<'a>),
+    /// A "c-style" for loop
+    For(ForStmt<'a>),
+    /// A for in statement, this kind of for statement
+    /// will extract each key from an indexable thing
+    ForIn(ForInStmt<'a>),
+    /// A for of statement, this kind of for statement
+    /// will extract the value from a generator or iterator
+    ForOf(ForOfStmt<'a>),
+    /// A var statement
+    Var(Vec<Node<VarDecl<'a>>>),
+}
+
+impl<'a> From<Stmt<'a>> for plain::Stmt<'a> {
+    fn from(other: Stmt<'a>) -> Self {
+        match other {
+            Stmt::Expr(expr) => plain::Stmt::Expr(strip(expr)),
+            Stmt::Block(block) => plain::Stmt::Block(block.into()),
+            Stmt::Empty => plain::Stmt::Empty,
+            Stmt::Debugger => plain::Stmt::Debugger,
+            Stmt::With(stmt) => plain::Stmt::With(plain::WithStmt {
+                object: strip(stmt.object),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::Return(arg) => plain::Stmt::Return(arg.map(strip)),
+            Stmt::Labeled(stmt) => plain::Stmt::Labeled(plain::LabeledStmt {
+                label: strip(stmt.label),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::Break(label) => plain::Stmt::Break(label.map(strip)),
+            Stmt::Continue(label) => plain::Stmt::Continue(label.map(strip)),
+            Stmt::If(stmt) => plain::Stmt::If(plain::IfStmt {
+                test: strip(stmt.test),
+                consequent: strip_box(stmt.consequent),
+                alternate: stmt.alternate.map(strip_box),
+            }),
+            Stmt::Switch(stmt) => plain::Stmt::Switch(plain::SwitchStmt {
+                discriminant: strip(stmt.discriminant),
+                cases: strip_all(stmt.cases),
+            }),
+            Stmt::Throw(expr) => plain::Stmt::Throw(strip(expr)),
+            Stmt::Try(stmt) => plain::Stmt::Try(plain::TryStmt {
+                block: strip(stmt.block),
+                handler: stmt.handler.map(strip),
+                finalizer: stmt.finalizer.map(strip),
+            }),
+            Stmt::While(stmt) => plain::Stmt::While(plain::WhileStmt {
+                test: strip(stmt.test),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::DoWhile(stmt) => plain::Stmt::DoWhile(plain::DoWhileStmt {
+                test: strip(stmt.test),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::For(stmt) => plain::Stmt::For(plain::ForStmt {
+                init: stmt.init.map(strip),
+                test: stmt.test.map(strip),
+                update: stmt.update.map(strip),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::ForIn(stmt) => plain::Stmt::ForIn(plain::ForInStmt {
+                left: strip(stmt.left),
+                right: strip(stmt.right),
+                body: strip_box(stmt.body),
+            }),
+            Stmt::ForOf(stmt) => plain::Stmt::ForOf(plain::ForOfStmt {
+                left: strip(stmt.left),
+                right: strip(stmt.right),
+                body: strip_box(stmt.body),
+                is_await: stmt.is_await,
+            }),
+            Stmt::Var(decls) => plain::Stmt::Var(strip_all(decls)),
+        }
+    }
+}
+
+/// A with statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct WithStmt<'a> {
+    pub object: Node<Expr<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// A break statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct LabeledStmt<'a> {
+    pub label: Node<Ident<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// An if statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct IfStmt<'a> {
+    pub test: Node<Expr<'a>>,
+    pub consequent: Box<Node<Stmt<'a>>>,
+    pub alternate: Option<Box<Node<Stmt<'a>>>>,
+}
+
+/// A switch statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct SwitchStmt<'a> {
+    pub discriminant: Node<Expr<'a>>,
+    pub cases: Vec<Node<SwitchCase<'a>>>,
+}
+
+/// A single case part of a switch statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct SwitchCase<'a> {
+    pub test: Option<Node<Expr<'a>>>,
+    pub consequent: Vec<Node<ProgramPart<'a>>>,
+}
+
+impl<'a> From<SwitchCase<'a>> for plain::SwitchCase<'a> {
+    fn from(other: SwitchCase<'a>) -> Self {
+        plain::SwitchCase {
+            test: other.test.map(strip),
+            consequent: strip_all(other.consequent),
+        }
+    }
+}
+
+/// A collection of program parts wrapped in curly braces
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockStmt<'a>(pub Vec<Node<ProgramPart<'a>>>);
+
+impl<'a> From<BlockStmt<'a>> for plain::BlockStmt<'a> {
+    fn from(other: BlockStmt<'a>) -> Self {
+        plain::BlockStmt(strip_all(other.0))
+    }
+}
+
+/// A try/catch block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct TryStmt<'a> {
+    pub block: Node<BlockStmt<'a>>,
+    pub handler: Option<Node<CatchClause<'a>>>,
+    pub finalizer: Option<Node<BlockStmt<'a>>>,
+}
+
+/// The error handling part of a `TryStmt`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct CatchClause<'a> {
+    pub param: Option<Node<Pat<'a>>>,
+    pub body: Node<BlockStmt<'a>>,
+}
+
+impl<'a> From<CatchClause<'a>> for plain::CatchClause<'a> {
+    fn from(other: CatchClause<'a>) -> Self {
+        plain::CatchClause {
+            param: other.param.map(strip),
+            body: strip(other.body),
+        }
+    }
+}
+
+/// A while loop
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct WhileStmt<'a> {
+    pub test: Node<Expr<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// A while loop that executes its body first
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct DoWhileStmt<'a> {
+    pub test: Node<Expr<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// A "c-style" for loop
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ForStmt<'a> {
+    pub init: Option<Node<LoopInit<'a>>>,
+    pub test: Option<Node<Expr<'a>>>,
+    pub update: Option<Node<Expr<'a>>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// The left most triple of a for loops parenthetical
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum LoopInit<'a> {
+    Variable(VarKind, Vec<Node<VarDecl<'a>>>),
+    Expr(Expr<'a>),
+}
+
+impl<'a> From<LoopInit<'a>> for plain::LoopInit<'a> {
+    fn from(other: LoopInit<'a>) -> Self {
+        match other {
+            LoopInit::Variable(kind, decls) => plain::LoopInit::Variable(kind, strip_all(decls)),
+            LoopInit::Expr(expr) => plain::LoopInit::Expr(expr.into()),
+        }
+    }
+}
+
+/// A for in statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ForInStmt<'a> {
+    pub left: Node<LoopLeft<'a>>,
+    pub right: Node<Expr<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+}
+
+/// A for of statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub struct ForOfStmt<'a> {
+    pub left: Node<LoopLeft<'a>>,
+    pub right: Node<Expr<'a>>,
+    pub body: Box<Node<Stmt<'a>>>,
+    pub is_await: bool,
+}
+
+/// The values on the left hand side of the keyword
+/// in a for in or for of loop
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "esprima")),
+    derive(Deserialize, Serialize)
+)]
+pub enum LoopLeft<'a> {
+    Expr(Expr<'a>),
+    Variable(VarKind, Node<VarDecl<'a>>),
+    Pat(Pat<'a>),
+}
+
+impl<'a> From<LoopLeft<'a>> for plain::LoopLeft<'a> {
+    fn from(other: LoopLeft<'a>) -> Self {
+        match other {
+            LoopLeft::Expr(expr) => plain::LoopLeft::Expr(expr.into()),
+            LoopLeft::Variable(kind, decl) => plain::LoopLeft::Variable(kind, strip(decl)),
+            LoopLeft::Pat(pat) => plain::LoopLeft::Pat(pat.into()),
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use crate::VarKind;
-use crate::decl::VarDecl;
-use crate::expr::Expr;
+use crate::decl::{Decl, VarDecl};
+use crate::expr::{ArrowFuncExpr, Expr};
 use crate::pat::Pat;
-use crate::{Ident, ProgramPart};
+use crate::visit::{walk_stmt, Visitor};
+use crate::{Class, Func, Ident, ProgramPart};
 /// A slightly more granular part of an es program than ProgramPart
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -176,6 +177,89 @@ pub enum Stmt<'a> {
     Var(Vec<VarDecl<'a>>),
 }
 
+impl<'a> Stmt<'a> {
+    /// If this is one of the iteration statements, the only
+    /// statements a `continue` can target
+    pub fn is_loop(&self) -> bool {
+        matches!(
+            self,
+            Stmt::While(_) | Stmt::DoWhile(_) | Stmt::For(_) | Stmt::ForIn(_) | Stmt::ForOf(_)
+        )
+    }
+    /// If this statement always transfers control somewhere
+    /// else, so nothing after it in the same block can run.
+    ///
+    /// `return`, `throw`, `break` and `continue` are abrupt, a
+    /// block is if any of its statements are, an `if` only when it
+    /// has an `else` and both branches are and a `try` when the
+    /// block and `catch` both are or the `finally` is. Loops,
+    /// `switch` and labeled statements are always treated as
+    /// completing normally
+    /// ```js
+    /// if (a) return; else throw b; // abrupt
+    /// if (a) return; // not abrupt
+    /// ```
+    pub fn is_abrupt_completion(&self) -> bool {
+        match self {
+            Stmt::Return(_) | Stmt::Throw(_) | Stmt::Break(_) | Stmt::Continue(_) => true,
+            Stmt::Block(block) => is_abrupt(&block.0),
+            Stmt::If(stmt) => match &stmt.alternate {
+                Some(alt) => stmt.consequent.is_abrupt_completion() && alt.is_abrupt_completion(),
+                None => false,
+            },
+            Stmt::Try(stmt) => {
+                let finalizer = matches!(&stmt.finalizer, Some(f) if is_abrupt(&f.0));
+                let handler = match &stmt.handler {
+                    Some(handler) => is_abrupt(&handler.body.0),
+                    None => true,
+                };
+                finalizer || (is_abrupt(&stmt.block.0) && handler)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn is_abrupt(parts: &[ProgramPart]) -> bool {
+    parts.iter().any(|part| match part {
+        ProgramPart::Stmt(stmt) => stmt.is_abrupt_completion(),
+        _ => false,
+    })
+}
+
+/// The indices of the parts of a block that can never run because
+/// they follow a statement that always completes abruptly, see
+/// `Stmt::is_abrupt_completion`. Function declarations are hoisted
+/// so they are never included
+/// ```
+/// # use resast::prelude::*;
+/// use resast::stmt::unreachable_after;
+/// // return; a; b;
+/// let parts = vec![
+///     ProgramPart::Stmt(Stmt::Return(None)),
+///     ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a"))),
+///     ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("b"))),
+/// ];
+/// assert_eq!(unreachable_after(&parts), vec![1, 2]);
+/// ```
+pub fn unreachable_after(parts: &[ProgramPart]) -> Vec<usize> {
+    let first = parts.iter().position(|part| match part {
+        ProgramPart::Stmt(stmt) => stmt.is_abrupt_completion(),
+        _ => false,
+    });
+    let first = match first {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    parts
+        .iter()
+        .enumerate()
+        .skip(first + 1)
+        .filter(|(_, part)| !matches!(part, ProgramPart::Decl(Decl::Func(_))))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
 /// A with statement, this puts one object at the top of
 /// the identifier search tree.
 /// > note: this cannot be used in a strict context
@@ -189,7 +273,7 @@ pub enum Stmt<'a> {
 /// }
 /// //rand !== 0
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct WithStmt<'a> {
     pub object: Expr<'a>,
@@ -205,7 +289,7 @@ pub struct WithStmt<'a> {
 ///     break;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct LabeledStmt<'a> {
     pub label: Ident<'a>,
@@ -220,7 +304,7 @@ pub struct LabeledStmt<'a> {
 ///     console.log('Never true');
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct IfStmt<'a> {
     pub test: Expr<'a>,
@@ -242,15 +326,53 @@ pub struct IfStmt<'a> {
 ///         return true;
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct SwitchStmt<'a> {
     pub discriminant: Expr<'a>,
     pub cases: Vec<SwitchCase<'a>>,
 }
 
+impl<'a> SwitchStmt<'a> {
+    /// Check that this switch has at most one `default` case
+    pub fn validate(&self) -> Result<(), SwitchError> {
+        let mut defaults = self
+            .cases
+            .iter()
+            .enumerate()
+            .filter(|(_, case)| case.is_default())
+            .map(|(idx, _)| idx);
+        match (defaults.next(), defaults.next()) {
+            (Some(first), Some(second)) => Err(SwitchError::DuplicateDefault { first, second }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The reasons a `SwitchStmt` is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchError {
+    /// More than one `default` case, the indexes are the
+    /// positions in `cases` of the first two
+    DuplicateDefault { first: usize, second: usize },
+}
+
+impl std::fmt::Display for SwitchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SwitchError::DuplicateDefault { first, second } => write!(
+                f,
+                "more than one default clause in switch, cases {} and {}",
+                first, second
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SwitchError {}
+
 /// A single case part of a switch statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -261,8 +383,15 @@ pub struct SwitchCase<'a> {
     pub consequent: Vec<ProgramPart<'a>>,
 }
 
+impl<'a> SwitchCase<'a> {
+    /// If this is the `default` case
+    pub fn is_default(&self) -> bool {
+        self.test.is_none()
+    }
+}
+
 /// A collection of program parts wrapped in curly braces
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -270,6 +399,24 @@ pub struct SwitchCase<'a> {
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct BlockStmt<'a>(pub Vec<ProgramPart<'a>>);
 
+impl<'a> BlockStmt<'a> {
+    /// Insert `part` as the first statement of this block, blocks
+    /// have no directive prologue so this is always index 0
+    pub fn prepend_stmt(&mut self, part: ProgramPart<'a>) {
+        self.0.insert(0, part);
+    }
+    /// Push `part` onto the end of this block
+    pub fn append_stmt(&mut self, part: ProgramPart<'a>) {
+        self.0.push(part);
+    }
+    /// Insert `part` at `index` of this block
+    ///
+    /// Panics if `index > self.0.len()`
+    pub fn insert_stmt(&mut self, index: usize, part: ProgramPart<'a>) {
+        self.0.insert(index, part);
+    }
+}
+
 /// A try/catch block
 /// ```js
 /// try {
@@ -280,7 +427,11 @@ pub struct BlockStmt<'a>(pub Vec<ProgramPart<'a>>);
 ///
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+///
+/// At least one of `handler` or `finalizer` must be `Some`,
+/// a bare `try {}` is a syntax error. Nothing here enforces
+/// that, see `validate`
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct TryStmt<'a> {
     pub block: BlockStmt<'a>,
@@ -288,14 +439,43 @@ pub struct TryStmt<'a> {
     pub finalizer: Option<BlockStmt<'a>>,
 }
 
+impl<'a> TryStmt<'a> {
+    /// Check that this try has a `catch` or a `finally`
+    pub fn validate(&self) -> Result<(), TryError> {
+        if self.handler.is_none() && self.finalizer.is_none() {
+            return Err(TryError::MissingHandler);
+        }
+        Ok(())
+    }
+}
+
+/// The reasons a `TryStmt` is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryError {
+    /// Neither a `catch` nor a `finally` block
+    MissingHandler,
+}
+
+impl std::fmt::Display for TryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TryError::MissingHandler => write!(f, "try without catch or finally"),
+        }
+    }
+}
+
+impl std::error::Error for TryError {}
+
 /// The error handling part of a `TryStmt`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
 )]
 #[cfg_attr(all(feature = "serde", feature = "esprima"), derive(Deserialize))]
 pub struct CatchClause<'a> {
+    /// The binding for the error, this will be `None`
+    /// for `try {} catch {}`
     pub param: Option<Pat<'a>>,
     pub body: BlockStmt<'a>,
 }
@@ -314,26 +494,57 @@ pub struct CatchClause<'a> {
 ///     }
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct WhileStmt<'a> {
     pub test: Expr<'a>,
     pub body: Box<Stmt<'a>>,
 }
 
+impl<'a> WhileStmt<'a> {
+    pub fn test(&self) -> &Expr<'a> {
+        &self.test
+    }
+    pub fn body(&self) -> &Stmt<'a> {
+        &self.body
+    }
+    /// If the test is a constant that is always truthy and nothing
+    /// in the body can leave the loop, see `can_exit_loop`
+    /// ```js
+    /// while (true) {} // true
+    /// while (1) { if (a) break; } // false
+    /// ```
+    pub fn is_obviously_infinite(&self) -> bool {
+        is_truthy_const(&self.test) && !can_exit_loop(&self.body)
+    }
+}
+
 /// A while loop that executes its body first
 /// ```js
 /// do {
 ///     console.log('at least once')
 /// } while (Math.floor(Math.random() * 100) < 75)
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct DoWhileStmt<'a> {
     pub test: Expr<'a>,
     pub body: Box<Stmt<'a>>,
 }
 
+impl<'a> DoWhileStmt<'a> {
+    pub fn test(&self) -> &Expr<'a> {
+        &self.test
+    }
+    pub fn body(&self) -> &Stmt<'a> {
+        &self.body
+    }
+    /// The same as `WhileStmt::is_obviously_infinite`
+    pub fn is_obviously_infinite(&self) -> bool {
+        is_truthy_const(&self.test) && !can_exit_loop(&self.body)
+    }
+}
+
 /// A "c-style" for loop
 /// ```js
 /// for (var i = 0; i < 100; i++) console.log(i);
@@ -341,7 +552,7 @@ pub struct DoWhileStmt<'a> {
 ///     console.log('forever!');
 /// }
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct ForStmt<'a> {
     pub init: Option<LoopInit<'a>>,
@@ -350,11 +561,85 @@ pub struct ForStmt<'a> {
     pub body: Box<Stmt<'a>>,
 }
 
+impl<'a> ForStmt<'a> {
+    /// The same as `WhileStmt::is_obviously_infinite`, a missing
+    /// test counts as `true`
+    /// ```js
+    /// for (;;) {}
+    /// ```
+    pub fn is_obviously_infinite(&self) -> bool {
+        self.test.as_ref().map(is_truthy_const).unwrap_or(true) && !can_exit_loop(&self.body)
+    }
+}
+
+fn is_truthy_const(test: &Expr) -> bool {
+    matches!(test.const_eval(), Some(value) if value.is_truthy())
+}
+
+/// If `body` contains a `return` or `throw`, an unlabeled `break`
+/// that isn't inside a nested loop or `switch` or a labeled `break`
+/// or `continue` whose label isn't declared inside of `body`.
+///
+/// A `continue` to an outer label might target the loop itself,
+/// `outer: while (true) { continue outer; }`, since that can't be
+/// told apart from here it is counted as an exit.
+///
+/// Nested functions and classes are skipped, `yield` and `await`
+/// pause the loop but don't leave it so they aren't counted
+fn can_exit_loop<'a>(body: &'a Stmt<'a>) -> bool {
+    let mut exits = LoopExits::default();
+    exits.visit_stmt(body);
+    exits.found
+}
+
+#[derive(Default)]
+struct LoopExits<'a> {
+    /// Nested statements an unlabeled `break` would target
+    breakable: usize,
+    labels: Vec<&'a str>,
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for LoopExits<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt<'a>) {
+        if self.found {
+            return;
+        }
+        match stmt {
+            Stmt::Return(_) | Stmt::Throw(_) => self.found = true,
+            Stmt::Break(None) => self.found = self.breakable == 0,
+            Stmt::Break(Some(label)) | Stmt::Continue(Some(label)) => {
+                self.found = !self.labels.contains(&&*label.name)
+            }
+            Stmt::Labeled(labeled) => {
+                self.labels.push(&labeled.label.name);
+                walk_stmt(self, stmt);
+                self.labels.pop();
+            }
+            Stmt::Switch(_) => {
+                self.breakable += 1;
+                walk_stmt(self, stmt);
+                self.breakable -= 1;
+            }
+            _ if stmt.is_loop() => {
+                self.breakable += 1;
+                walk_stmt(self, stmt);
+                self.breakable -= 1;
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+    fn visit_func(&mut self, _func: &'a Func<'a>) {}
+    fn visit_arrow_func_expr(&mut self, _expr: &'a ArrowFuncExpr<'a>) {}
+    fn visit_class(&mut self, _class: &'a Class<'a>) {}
+}
+
 /// The left most triple of a for loops parenthetical
 /// ```js
 ///  //  vvvvvvvvv
 /// for (var i = 0;i < 100; i++)
-#[derive(Debug, Clone, PartialEq)]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -365,6 +650,14 @@ pub enum LoopInit<'a> {
     Expr(Expr<'a>),
 }
 
+impl<'a> LoopInit<'a> {
+    /// If this declares new variables, `for (let i = 0;;)`
+    /// instead of `for (i = 0;;)`
+    pub fn is_decl(&self) -> bool {
+        matches!(self, LoopInit::Variable(_, _))
+    }
+}
+
 /// A for in statement, this kind of for statement
 /// will extract each key from an indexable thing
 /// ```js
@@ -377,7 +670,7 @@ pub enum LoopInit<'a> {
 /// }
 /// //prints a, b
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct ForInStmt<'a> {
     pub left: LoopLeft<'a>,
@@ -393,18 +686,19 @@ pub struct ForInStmt<'a> {
 /// }
 /// //prints 2, 3, 4, 5, 6
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
 #[cfg_attr(all(feature = "serialization"), derive(Deserialize, Serialize))]
 pub struct ForOfStmt<'a> {
     pub left: LoopLeft<'a>,
     pub right: Expr<'a>,
     pub body: Box<Stmt<'a>>,
+    /// `for await (const x of stream) {}`
     pub is_await: bool,
 }
 
 /// The values on the left hand side of the keyword
 /// in a for in or for of loop
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(
     all(feature = "serde", not(feature = "esprima")),
     derive(Deserialize, Serialize)
@@ -415,3 +709,294 @@ pub enum LoopLeft<'a> {
     Variable(VarKind, VarDecl<'a>),
     Pat(Pat<'a>),
 }
+
+impl<'a> LoopLeft<'a> {
+    /// If this declares a new variable, `for (const k in o)`
+    /// instead of `for (k in o)` or `for ({ k } of o)`
+    pub fn is_decl(&self) -> bool {
+        matches!(self, LoopLeft::Variable(_, _))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::expr::{ArrowFuncBody, Lit};
+    use crate::FuncBody;
+
+    fn switch<'a>(tests: Vec<Option<Expr<'a>>>) -> SwitchStmt<'a> {
+        SwitchStmt {
+            discriminant: Expr::ident_from("x"),
+            cases: tests
+                .into_iter()
+                .map(|test| SwitchCase {
+                    test,
+                    consequent: vec![ProgramPart::Stmt(Stmt::Break(None))],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn switch_defaults() {
+        // switch (x) { case a: break; default: break; }
+        let valid = switch(vec![Some(Expr::ident_from("a")), None]);
+        assert_eq!(valid.validate(), Ok(()));
+        // switch (x) { case a: break; case b: break; }
+        let none = switch(vec![Some(Expr::ident_from("a")), Some(Expr::ident_from("b"))]);
+        assert_eq!(none.validate(), Ok(()));
+        // switch (x) { default: break; case a: break; default: break; }
+        let two = switch(vec![None, Some(Expr::ident_from("a")), None]);
+        assert_eq!(
+            two.validate(),
+            Err(SwitchError::DuplicateDefault {
+                first: 0,
+                second: 2
+            })
+        );
+    }
+
+    fn for_loop<'a>(init: Option<LoopInit<'a>>) -> ForStmt<'a> {
+        ForStmt {
+            init,
+            test: None,
+            update: None,
+            body: Box::new(Stmt::Empty),
+        }
+    }
+
+    #[test]
+    fn for_inits() {
+        let zero = || Expr::Lit(crate::expr::Lit::number_from("0"));
+        // for (let i = 0;;);
+        let decl = for_loop(Some(LoopInit::Variable(
+            VarKind::Let,
+            vec![VarDecl {
+                id: Pat::ident_from("i"),
+                init: Some(zero()),
+            }],
+        )));
+        // for (i = 0;;);
+        let assign = for_loop(Some(LoopInit::Expr(Expr::Assign(crate::expr::AssignExpr {
+            operator: crate::AssignOp::Equal,
+            left: crate::expr::AssignLeft::Expr(Box::new(Expr::ident_from("i"))),
+            right: Box::new(zero()),
+        }))));
+        // for (;;);
+        let empty = for_loop(None);
+        assert!(decl.init.as_ref().map(LoopInit::is_decl).unwrap());
+        match &decl.init {
+            Some(LoopInit::Variable(VarKind::Let, decls)) => {
+                assert_eq!(decls[0].id, Pat::ident_from("i"))
+            }
+            _ => panic!("expected a let declaration"),
+        }
+        assert!(!assign.init.as_ref().map(LoopInit::is_decl).unwrap());
+        assert!(empty.init.is_none());
+    }
+
+    #[test]
+    fn for_in_lefts() {
+        // for (const k in o); for (k in o); for ({ k } of o);
+        let decl = LoopLeft::Variable(
+            VarKind::Const,
+            VarDecl {
+                id: Pat::ident_from("k"),
+                init: None,
+            },
+        );
+        let target = LoopLeft::Expr(Expr::ident_from("k"));
+        let pat = LoopLeft::Pat(Pat::Obj(vec![crate::pat::ObjPatPart::Assign(
+            crate::expr::Prop {
+                key: crate::expr::PropKey::Pat(Pat::ident_from("k")),
+                value: crate::expr::PropValue::None,
+                kind: crate::PropKind::Init,
+                method: false,
+                computed: false,
+                short_hand: true,
+                is_static: false,
+                decorators: vec![],
+            },
+        )]));
+        let lefts: Vec<bool> = [decl, target, pat].iter().map(LoopLeft::is_decl).collect();
+        assert_eq!(lefts, vec![true, false, false]);
+    }
+
+    #[test]
+    fn try_handlers() {
+        let try_stmt = |handler, finalizer| TryStmt {
+            block: BlockStmt(vec![]),
+            handler,
+            finalizer,
+        };
+        let catch = || {
+            Some(CatchClause {
+                param: Some(Pat::ident_from("e")),
+                body: BlockStmt(vec![]),
+            })
+        };
+        let finally = || Some(BlockStmt(vec![]));
+        // try {} catch (e) {}
+        assert_eq!(try_stmt(catch(), None).validate(), Ok(()));
+        // try {} finally {}
+        assert_eq!(try_stmt(None, finally()).validate(), Ok(()));
+        // try {} catch (e) {} finally {}
+        assert_eq!(try_stmt(catch(), finally()).validate(), Ok(()));
+        // try {}
+        let bare = try_stmt(None, None).validate();
+        assert_eq!(bare, Err(TryError::MissingHandler));
+        assert_eq!(bare.unwrap_err().to_string(), "try without catch or finally");
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let stmt = |stmt| ProgramPart::Stmt(stmt);
+        let expr = |name| ProgramPart::Stmt(Stmt::Expr(Expr::ident_from(name)));
+        let if_a = |consequent, alternate: Option<Stmt<'static>>| {
+            Stmt::If(IfStmt {
+                test: Expr::ident_from("a"),
+                consequent: Box::new(consequent),
+                alternate: alternate.map(Box::new),
+            })
+        };
+        // a; return; b; function f() {} c;
+        let func = crate::Func::new(
+            Some(Ident::from("f")),
+            vec![],
+            crate::FuncBody(vec![]),
+            false,
+            false,
+        );
+        let parts = vec![
+            expr("a"),
+            stmt(Stmt::Return(None)),
+            expr("b"),
+            ProgramPart::Decl(Decl::Func(func)),
+            expr("c"),
+        ];
+        assert_eq!(unreachable_after(&parts), vec![2, 4]);
+        // if (a) return; else { throw b; } c;
+        let both = if_a(
+            Stmt::Return(None),
+            Some(Stmt::Block(BlockStmt(vec![stmt(Stmt::Throw(Expr::ident_from("b")))]))),
+        );
+        assert!(both.is_abrupt_completion());
+        assert_eq!(unreachable_after(&[stmt(both), expr("c")]), vec![1]);
+        // if (a) return; c;
+        let one = if_a(Stmt::Return(None), None);
+        assert!(!one.is_abrupt_completion());
+        assert_eq!(unreachable_after(&[stmt(one), expr("c")]), Vec::<usize>::new());
+        // if (a) return; else b;
+        let one = if_a(Stmt::Return(None), Some(Stmt::Expr(Expr::ident_from("b"))));
+        assert!(!one.is_abrupt_completion());
+        // try { return; } catch { b; }
+        let try_stmt = Stmt::Try(TryStmt {
+            block: BlockStmt(vec![stmt(Stmt::Return(None))]),
+            handler: Some(CatchClause {
+                param: None,
+                body: BlockStmt(vec![expr("b")]),
+            }),
+            finalizer: None,
+        });
+        assert!(!try_stmt.is_abrupt_completion());
+    }
+
+    #[test]
+    fn obviously_infinite_loops() {
+        let truthy = || Expr::Lit(Lit::Boolean(true));
+        let block = |stmts: Vec<Stmt<'static>>| {
+            Box::new(Stmt::Block(BlockStmt(
+                stmts.into_iter().map(ProgramPart::Stmt).collect(),
+            )))
+        };
+        let while_loop = |body| WhileStmt {
+            test: truthy(),
+            body,
+        };
+        let label = |name, body| {
+            Stmt::Labeled(LabeledStmt {
+                label: Ident::from(name),
+                body: Box::new(body),
+            })
+        };
+        // while (true) {}
+        assert!(while_loop(block(vec![])).is_obviously_infinite());
+        // while (true) { break; }
+        assert!(!while_loop(block(vec![Stmt::Break(None)])).is_obviously_infinite());
+        // while (x) {}
+        let unknown = WhileStmt {
+            test: Expr::ident_from("x"),
+            body: block(vec![]),
+        };
+        assert!(!unknown.is_obviously_infinite());
+        // for (;;) {}
+        let forever = ForStmt {
+            init: None,
+            test: None,
+            update: None,
+            body: block(vec![]),
+        };
+        assert!(forever.is_obviously_infinite());
+        // do {} while (1)
+        let do_while = DoWhileStmt {
+            test: Expr::Lit(Lit::number_from("1")),
+            body: block(vec![]),
+        };
+        assert!(do_while.is_obviously_infinite());
+        // while (true) { switch (x) { case 1: break; } }
+        let switch = Stmt::Switch(switch(vec![Some(Expr::Lit(Lit::number_from("1")))]));
+        assert!(while_loop(block(vec![switch])).is_obviously_infinite());
+        // while (true) { inner: { break inner; } }
+        let inner = label("inner", Stmt::Block(BlockStmt(vec![ProgramPart::Stmt(Stmt::Break(
+            Some(Ident::from("inner")),
+        ))])));
+        assert!(while_loop(block(vec![inner])).is_obviously_infinite());
+        // while (true) { while (true) { break outer; } }
+        let nested = Stmt::While(while_loop(block(vec![Stmt::Break(Some(Ident::from(
+            "outer",
+        )))])));
+        assert!(!while_loop(block(vec![nested])).is_obviously_infinite());
+        // the inner loop of `outer: for (;;) { while (true) { continue outer; } }`
+        let to_outer = Stmt::Continue(Some(Ident::from("outer")));
+        assert!(!while_loop(block(vec![to_outer])).is_obviously_infinite());
+        // while (true) { inner: while (x) { continue inner; } }
+        let to_inner = label(
+            "inner",
+            Stmt::While(WhileStmt {
+                test: Expr::ident_from("x"),
+                body: block(vec![Stmt::Continue(Some(Ident::from("inner")))]),
+            }),
+        );
+        assert!(while_loop(block(vec![to_inner])).is_obviously_infinite());
+        // while (true) { continue; }
+        let unlabeled = Stmt::Continue(None);
+        assert!(while_loop(block(vec![unlabeled])).is_obviously_infinite());
+        // while (true) { () => { return; }; }
+        let arrow = Stmt::Expr(Expr::ArrowFunc(ArrowFuncExpr::new(
+            vec![],
+            ArrowFuncBody::FuncBody(FuncBody(vec![ProgramPart::Stmt(Stmt::Return(None))])),
+            false,
+        )));
+        assert!(while_loop(block(vec![arrow])).is_obviously_infinite());
+        // while (true) { throw e; }
+        let throw = Stmt::Throw(Expr::ident_from("e"));
+        assert!(!while_loop(block(vec![throw])).is_obviously_infinite());
+    }
+
+    #[test]
+    fn block_insertion() {
+        let call = |name| ProgramPart::Stmt(Stmt::Expr(Expr::ident_from(name)));
+        // { 'use strict'; a; }
+        let use_strict = ProgramPart::Stmt(Stmt::Expr(Expr::Lit(Lit::single_string_from(
+            "use strict",
+        ))));
+        let mut block = BlockStmt(vec![use_strict.clone(), call("a")]);
+        block.prepend_stmt(call("b"));
+        block.append_stmt(call("c"));
+        block.insert_stmt(2, call("d"));
+        assert_eq!(
+            block.0,
+            vec![call("b"), use_strict, call("d"), call("a"), call("c")]
+        );
+    }
+}
@@ -0,0 +1,1287 @@
+//! Converting a `Program` into the tree used by swc
+//!
+//! The result can be handed to any swc tool that works on
+//! javascript, like its emitter or minifier. swc keeps parentheses
+//! as nodes and its emitter doesn't add any, so they are inserted
+//! wherever the structure of the tree needs them.
+//!
+//! Every span is `DUMMY_SP` and every identifier has an empty
+//! `SyntaxContext`, swc's resolver needs to run before anything
+//! that depends on scopes.
+use crate::decl::*;
+use crate::expr::*;
+use crate::parens::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+use swc_ecma_ast as swc;
+
+type Res<T> = Result<T, SwcError>;
+
+/// The reasons a tree can't be converted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwcError {
+    /// A node swc has no equivalent for
+    Unsupported(&'static str),
+    /// A node somewhere it can't appear, like `super` on its own
+    /// or a spread outside of an array or argument list
+    Invalid(&'static str),
+    /// A function or class declaration without a name
+    Decl(DeclError),
+}
+
+impl std::fmt::Display for SwcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SwcError::Unsupported(node) => write!(f, "{} can't be converted to swc", node),
+            SwcError::Invalid(node) => write!(f, "invalid {}", node),
+            SwcError::Decl(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SwcError {}
+
+/// Convert `program` into an swc `Program`, a `Program::Mod`
+/// becomes a `Module` and a `Program::Script` a `Script`
+/// ```
+/// # use resast::prelude::*;
+/// use resast::swc_interop::to_swc;
+/// use swc_ecma_ast as swc;
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))]);
+/// match to_swc(&program).unwrap() {
+///     swc::Program::Script(script) => assert_eq!(script.body.len(), 1),
+///     swc::Program::Module(_) => unreachable!(),
+/// }
+/// ```
+pub fn to_swc(program: &Program) -> Result<swc::Program, SwcError> {
+    let shebang = program.hashbang().map(Into::into);
+    Ok(match program {
+        Program::Mod(parts, _) => swc::Program::Module(swc::Module {
+            body: parts.iter().map(module_item).collect::<Res<_>>()?,
+            shebang,
+            ..Default::default()
+        }),
+        Program::Script(parts, _) => swc::Program::Script(swc::Script {
+            body: stmts(parts)?,
+            shebang,
+            ..Default::default()
+        }),
+    })
+}
+
+fn module_item(part: &ProgramPart) -> Res<swc::ModuleItem> {
+    Ok(match part {
+        ProgramPart::Decl(Decl::Import(import)) => {
+            swc::ModuleItem::ModuleDecl(swc::ModuleDecl::Import(import_decl(import)?))
+        }
+        ProgramPart::Decl(Decl::Export(export)) => {
+            swc::ModuleItem::ModuleDecl(export_decl(export)?)
+        }
+        _ => swc::ModuleItem::Stmt(program_part(part)?),
+    })
+}
+
+fn stmts(parts: &[ProgramPart]) -> Res<Vec<swc::Stmt>> {
+    parts.iter().map(program_part).collect()
+}
+
+fn block(parts: &[ProgramPart]) -> Res<swc::BlockStmt> {
+    Ok(swc::BlockStmt {
+        stmts: stmts(parts)?,
+        ..Default::default()
+    })
+}
+
+fn program_part(part: &ProgramPart) -> Res<swc::Stmt> {
+    match part {
+        // swc keeps directives as plain expression statements
+        ProgramPart::Dir(dir) => Ok(swc::Stmt::Expr(swc::ExprStmt {
+            expr: Box::new(swc::Expr::Lit(swc::Lit::Str(str_lit(&dir.expr)?))),
+            span: Default::default(),
+        })),
+        ProgramPart::Decl(decl) => self::decl(decl).map(swc::Stmt::Decl),
+        ProgramPart::Stmt(stmt) => self::stmt(stmt),
+    }
+}
+
+fn decl(decl: &Decl) -> Res<swc::Decl> {
+    decl.validate().map_err(SwcError::Decl)?;
+    Ok(match decl {
+        Decl::Var(kind, decls) => swc::Decl::Var(var_decl(*kind, decls)?),
+        Decl::Func(func) => swc::Decl::Fn(swc::FnDecl {
+            ident: func.id.as_ref().map(ident).unwrap_or_default(),
+            declare: false,
+            function: function(func)?,
+        }),
+        Decl::Class(class) => swc::Decl::Class(swc::ClassDecl {
+            ident: class.id.as_ref().map(ident).unwrap_or_default(),
+            declare: false,
+            class: self::class(class)?,
+        }),
+        Decl::Import(_) | Decl::Export(_) => {
+            return Err(SwcError::Invalid("import or export outside of a module's top level"))
+        }
+    })
+}
+
+fn var_decl(kind: VarKind, decls: &[VarDecl]) -> Res<Box<swc::VarDecl>> {
+    let kind = match kind {
+        VarKind::Var => swc::VarDeclKind::Var,
+        VarKind::Let => swc::VarDeclKind::Let,
+        VarKind::Const => swc::VarDeclKind::Const,
+    };
+    let decls = decls
+        .iter()
+        .map(|decl| {
+            Ok(swc::VarDeclarator {
+                name: pat(&decl.id)?,
+                init: opt_expr(&decl.init, ASSIGN)?,
+                span: Default::default(),
+                definite: false,
+            })
+        })
+        .collect::<Res<_>>()?;
+    Ok(Box::new(swc::VarDecl {
+        kind,
+        decls,
+        ..Default::default()
+    }))
+}
+
+fn import_decl(import: &ModImport) -> Res<swc::ImportDecl> {
+    Ok(swc::ImportDecl {
+        specifiers: import
+            .specifiers
+            .iter()
+            .map(import_specifier)
+            .collect::<Res<_>>()?,
+        src: Box::new(str_lit(&import.source)?),
+        with: attributes(&import.attributes, import.clause_keyword())?,
+        phase: swc::ImportPhase::Evaluation,
+        span: Default::default(),
+        type_only: false,
+    })
+}
+
+fn import_specifier(spec: &ImportSpecifier) -> Res<swc::ImportSpecifier> {
+    Ok(match spec {
+        ImportSpecifier::Default(local) => {
+            swc::ImportSpecifier::Default(swc::ImportDefaultSpecifier {
+                local: ident(local),
+                span: Default::default(),
+            })
+        }
+        ImportSpecifier::Namespace(local) => {
+            swc::ImportSpecifier::Namespace(swc::ImportStarAsSpecifier {
+                local: ident(local),
+                span: Default::default(),
+            })
+        }
+        ImportSpecifier::Normal(spec) => swc::ImportSpecifier::Named(swc::ImportNamedSpecifier {
+            local: ident(&spec.local),
+            imported: renamed(&spec.imported, &spec.local)?,
+            span: Default::default(),
+            is_type_only: false,
+        }),
+    })
+}
+
+/// `name` unless it is the same as `local`, swc leaves
+/// out the `as` for an import or export that isn't renamed
+fn renamed(name: &ModuleExportName, local: &Ident) -> Res<Option<swc::ModuleExportName>> {
+    match name {
+        ModuleExportName::Ident(name) if name == local => Ok(None),
+        _ => module_export_name(name).map(Some),
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> Res<swc::ModuleExportName> {
+    Ok(match name {
+        ModuleExportName::Ident(name) => swc::ModuleExportName::Ident(ident(name)),
+        ModuleExportName::Str(lit) => swc::ModuleExportName::Str(str_lit(lit)?),
+    })
+}
+
+fn attributes(
+    attributes: &[ImportAttribute],
+    keyword: Option<AttributesKeyword>,
+) -> Res<Option<Box<swc::ObjectLit>>> {
+    match keyword {
+        None => return Ok(None),
+        Some(AttributesKeyword::Assert) => return Err(SwcError::Unsupported("import assertions")),
+        Some(AttributesKeyword::With) => {}
+    }
+    let props = attributes
+        .iter()
+        .map(|attr| {
+            let key = match &attr.key {
+                ImportAttributeKey::Ident(key) => swc::PropName::Ident(ident_name(key)),
+                ImportAttributeKey::Lit(key) => swc::PropName::Str(str_lit(key)?),
+            };
+            let value = Box::new(swc::Expr::Lit(swc::Lit::Str(str_lit(&attr.value)?)));
+            Ok(swc::PropOrSpread::Prop(Box::new(swc::Prop::KeyValue(
+                swc::KeyValueProp { key, value },
+            ))))
+        })
+        .collect::<Res<_>>()?;
+    Ok(Some(Box::new(swc::ObjectLit {
+        props,
+        ..Default::default()
+    })))
+}
+
+type Source = (Box<swc::Str>, Option<Box<swc::ObjectLit>>);
+
+fn export_source(source: &ExportSource) -> Res<Source> {
+    Ok((
+        Box::new(str_lit(&source.source)?),
+        attributes(&source.attributes, source.clause_keyword())?,
+    ))
+}
+
+fn export_decl(export: &ModExport) -> Res<swc::ModuleDecl> {
+    Ok(match export {
+        ModExport::Named(NamedExportDecl::Decl(decl)) => {
+            swc::ModuleDecl::ExportDecl(swc::ExportDecl {
+                decl: self::decl(decl)?,
+                span: Default::default(),
+            })
+        }
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            let specifiers = specs
+                .iter()
+                .map(|spec| {
+                    Ok(swc::ExportSpecifier::Named(swc::ExportNamedSpecifier {
+                        orig: swc::ModuleExportName::Ident(ident(&spec.local)),
+                        exported: renamed(&spec.exported, &spec.local)?,
+                        span: Default::default(),
+                        is_type_only: false,
+                    }))
+                })
+                .collect::<Res<_>>()?;
+            let (src, with) = match source {
+                Some(source) => {
+                    let (src, with) = export_source(source)?;
+                    (Some(src), with)
+                }
+                None => (None, None),
+            };
+            swc::ModuleDecl::ExportNamed(swc::NamedExport {
+                specifiers,
+                src,
+                with,
+                span: Default::default(),
+                type_only: false,
+            })
+        }
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => {
+            let decl = match decl {
+                Decl::Func(func) => swc::DefaultDecl::Fn(fn_expr(func)?),
+                Decl::Class(class) => swc::DefaultDecl::Class(class_expr(class)?),
+                _ => return Err(SwcError::Invalid("default export declaration")),
+            };
+            swc::ModuleDecl::ExportDefaultDecl(swc::ExportDefaultDecl {
+                decl,
+                span: Default::default(),
+            })
+        }
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => {
+            swc::ModuleDecl::ExportDefaultExpr(swc::ExportDefaultExpr {
+                expr: unambiguous(expr, ASSIGN)?,
+                span: Default::default(),
+            })
+        }
+        ModExport::All(None, source) => {
+            let (src, with) = export_source(source)?;
+            swc::ModuleDecl::ExportAll(swc::ExportAll {
+                src,
+                with,
+                span: Default::default(),
+                type_only: false,
+            })
+        }
+        ModExport::All(Some(name), source) => {
+            let (src, with) = export_source(source)?;
+            let name = module_export_name(name)?;
+            swc::ModuleDecl::ExportNamed(swc::NamedExport {
+                specifiers: vec![swc::ExportSpecifier::Namespace(
+                    swc::ExportNamespaceSpecifier {
+                        name,
+                        span: Default::default(),
+                    },
+                )],
+                src: Some(src),
+                with,
+                span: Default::default(),
+                type_only: false,
+            })
+        }
+    })
+}
+
+fn function(func: &Func) -> Res<Box<swc::Function>> {
+    Ok(Box::new(swc::Function {
+        params: params(&func.params)?,
+        body: Some(swc::FunctionBody {
+            stmts: stmts(&func.body.0)?,
+            ..Default::default()
+        }),
+        is_generator: func.generator,
+        is_async: func.is_async,
+        ..Default::default()
+    }))
+}
+
+fn fn_expr(func: &Func) -> Res<swc::FnExpr> {
+    Ok(swc::FnExpr {
+        ident: func.id.as_ref().map(ident),
+        function: function(func)?,
+    })
+}
+
+fn params(params: &[FuncArg]) -> Res<Vec<swc::Param>> {
+    params
+        .iter()
+        .map(|param| {
+            Ok(swc::Param {
+                pat: func_arg(param)?,
+                decorators: Vec::new(),
+                span: Default::default(),
+            })
+        })
+        .collect()
+}
+
+fn func_arg(arg: &FuncArg) -> Res<swc::Pat> {
+    match arg {
+        FuncArg::Pat(pat) => self::pat(pat),
+        FuncArg::Expr(expr) => expr_pat(expr),
+    }
+}
+
+fn class(class: &Class) -> Res<Box<swc::Class>> {
+    Ok(Box::new(swc::Class {
+        decorators: decorators(&class.decorators)?,
+        body: class
+            .body
+            .0
+            .iter()
+            .map(class_member)
+            .collect::<Res<_>>()?,
+        super_class: class.super_class.as_deref().map(callee).transpose()?,
+        ..Default::default()
+    }))
+}
+
+fn class_expr(class: &Class) -> Res<swc::ClassExpr> {
+    Ok(swc::ClassExpr {
+        ident: class.id.as_ref().map(ident),
+        class: self::class(class)?,
+    })
+}
+
+fn decorators(decorators: &[Decorator]) -> Res<Vec<swc::Decorator>> {
+    decorators
+        .iter()
+        .map(|decorator| {
+            Ok(swc::Decorator {
+                expr: expr(&decorator.expression, CALL)?,
+                span: Default::default(),
+            })
+        })
+        .collect()
+}
+
+fn class_member(member: &ClassMember) -> Res<swc::ClassMember> {
+    Ok(match member {
+        ClassMember::Method(method) => {
+            let mut function = function(&method.value)?;
+            function.decorators = decorators(&method.decorators)?;
+            let kind = match method.kind {
+                MethodKind::Method => swc::MethodKind::Method,
+                MethodKind::Get => swc::MethodKind::Getter,
+                MethodKind::Set => swc::MethodKind::Setter,
+                MethodKind::Constructor => {
+                    return Ok(swc::ClassMember::Constructor(swc::Constructor {
+                        key: prop_name(&method.key, method.computed)?,
+                        params: function
+                            .params
+                            .into_iter()
+                            .map(swc::ParamOrTsParamProp::Param)
+                            .collect(),
+                        body: function.body,
+                        ..Default::default()
+                    }))
+                }
+            };
+            match &method.key {
+                PropKey::Private(id) => swc::ClassMember::PrivateMethod(swc::PrivateMethod {
+                    key: private_name(id),
+                    function,
+                    kind,
+                    is_static: method.is_static,
+                    ..Default::default()
+                }),
+                key => swc::ClassMember::Method(swc::ClassMethod {
+                    key: prop_name(key, method.computed)?,
+                    function,
+                    kind,
+                    is_static: method.is_static,
+                    ..Default::default()
+                }),
+            }
+        }
+        ClassMember::Field(field) => {
+            let value = opt_expr(&field.value, ASSIGN)?;
+            let decorators = decorators(&field.decorators)?;
+            match &field.key {
+                PropKey::Private(id) => swc::ClassMember::PrivateProp(swc::PrivateProp {
+                    key: private_name(id),
+                    value,
+                    is_static: field.is_static,
+                    decorators,
+                    ..Default::default()
+                }),
+                key => swc::ClassMember::ClassProp(swc::ClassProp {
+                    key: prop_name(key, field.computed)?,
+                    value,
+                    is_static: field.is_static,
+                    decorators,
+                    ..Default::default()
+                }),
+            }
+        }
+        ClassMember::StaticBlock(body) => swc::ClassMember::StaticBlock(swc::StaticBlock {
+            body: block(&body.0)?,
+            span: Default::default(),
+        }),
+    })
+}
+
+fn stmt(stmt: &Stmt) -> Res<swc::Stmt> {
+    Ok(match stmt {
+        Stmt::Expr(expr) => swc::Stmt::Expr(swc::ExprStmt {
+            expr: unambiguous(expr, SEQUENCE)?,
+            span: Default::default(),
+        }),
+        Stmt::Block(body) => swc::Stmt::Block(block(&body.0)?),
+        Stmt::Empty => swc::Stmt::Empty(swc::EmptyStmt {
+            span: Default::default(),
+        }),
+        Stmt::Debugger => swc::Stmt::Debugger(swc::DebuggerStmt {
+            span: Default::default(),
+        }),
+        Stmt::With(stmt) => swc::Stmt::With(swc::WithStmt {
+            obj: expr(&stmt.object, SEQUENCE)?,
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::Return(arg) => swc::Stmt::Return(swc::ReturnStmt {
+            arg: opt_expr(arg, SEQUENCE)?,
+            span: Default::default(),
+        }),
+        Stmt::Labeled(stmt) => swc::Stmt::Labeled(swc::LabeledStmt {
+            label: ident(&stmt.label),
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::Break(label) => swc::Stmt::Break(swc::BreakStmt {
+            label: label.as_ref().map(ident),
+            span: Default::default(),
+        }),
+        Stmt::Continue(label) => swc::Stmt::Continue(swc::ContinueStmt {
+            label: label.as_ref().map(ident),
+            span: Default::default(),
+        }),
+        Stmt::If(stmt) => {
+            // an `if` without an `else` would steal this one's
+            let cons = if stmt.alternate.is_some() && ends_with_open_if(&stmt.consequent) {
+                swc::Stmt::Block(swc::BlockStmt {
+                    stmts: vec![self::stmt(&stmt.consequent)?],
+                    ..Default::default()
+                })
+            } else {
+                self::stmt(&stmt.consequent)?
+            };
+            swc::Stmt::If(swc::IfStmt {
+                test: expr(&stmt.test, SEQUENCE)?,
+                cons: Box::new(cons),
+                alt: stmt.alternate.as_deref().map(boxed).transpose()?,
+                span: Default::default(),
+            })
+        }
+        Stmt::Switch(stmt) => swc::Stmt::Switch(swc::SwitchStmt {
+            discriminant: expr(&stmt.discriminant, SEQUENCE)?,
+            cases: stmt
+                .cases
+                .iter()
+                .map(|case| {
+                    Ok(swc::SwitchCase {
+                        test: opt_expr(&case.test, SEQUENCE)?,
+                        cons: stmts(&case.consequent)?,
+                        span: Default::default(),
+                    })
+                })
+                .collect::<Res<_>>()?,
+            ..Default::default()
+        }),
+        Stmt::Throw(arg) => swc::Stmt::Throw(swc::ThrowStmt {
+            arg: expr(arg, SEQUENCE)?,
+            span: Default::default(),
+        }),
+        Stmt::Try(stmt) => {
+            let handler = match &stmt.handler {
+                Some(handler) => Some(swc::CatchClause {
+                    param: handler.param.as_ref().map(pat).transpose()?,
+                    body: block(&handler.body.0)?,
+                    span: Default::default(),
+                }),
+                None => None,
+            };
+            swc::Stmt::Try(Box::new(swc::TryStmt {
+                block: block(&stmt.block.0)?,
+                handler,
+                finalizer: stmt
+                    .finalizer
+                    .as_ref()
+                    .map(|body| block(&body.0))
+                    .transpose()?,
+                span: Default::default(),
+            }))
+        }
+        Stmt::While(stmt) => swc::Stmt::While(swc::WhileStmt {
+            test: expr(&stmt.test, SEQUENCE)?,
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::DoWhile(stmt) => swc::Stmt::DoWhile(swc::DoWhileStmt {
+            test: expr(&stmt.test, SEQUENCE)?,
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::For(stmt) => {
+            let init = match &stmt.init {
+                Some(LoopInit::Variable(kind, decls)) => {
+                    Some(swc::VarDeclOrExpr::VarDecl(var_decl(*kind, decls)?))
+                }
+                Some(LoopInit::Expr(init)) => Some(swc::VarDeclOrExpr::Expr(expr(init, SEQUENCE)?)),
+                None => None,
+            };
+            swc::Stmt::For(swc::ForStmt {
+                init,
+                test: opt_expr(&stmt.test, SEQUENCE)?,
+                update: opt_expr(&stmt.update, SEQUENCE)?,
+                body: boxed(&stmt.body)?,
+                span: Default::default(),
+            })
+        }
+        Stmt::ForIn(stmt) => swc::Stmt::ForIn(swc::ForInStmt {
+            left: for_head(&stmt.left)?,
+            right: expr(&stmt.right, SEQUENCE)?,
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::ForOf(stmt) => swc::Stmt::ForOf(swc::ForOfStmt {
+            is_await: stmt.is_await,
+            left: for_head(&stmt.left)?,
+            right: expr(&stmt.right, ASSIGN)?,
+            body: boxed(&stmt.body)?,
+            span: Default::default(),
+        }),
+        Stmt::Var(decls) => swc::Stmt::Decl(swc::Decl::Var(var_decl(VarKind::Var, decls)?)),
+    })
+}
+
+fn boxed(stmt: &Stmt) -> Res<Box<swc::Stmt>> {
+    self::stmt(stmt).map(Box::new)
+}
+
+fn for_head(left: &LoopLeft) -> Res<swc::ForHead> {
+    Ok(match left {
+        LoopLeft::Expr(expr) => swc::ForHead::Pat(Box::new(expr_pat(expr)?)),
+        LoopLeft::Pat(pat) => swc::ForHead::Pat(Box::new(self::pat(pat)?)),
+        LoopLeft::Variable(kind, decl) => {
+            swc::ForHead::VarDecl(var_decl(*kind, std::slice::from_ref(decl))?)
+        }
+    })
+}
+
+fn pat(pat: &Pat) -> Res<swc::Pat> {
+    Ok(match pat {
+        Pat::Ident(id) => swc::Pat::Ident(ident(id).into()),
+        Pat::Obj(parts) => swc::Pat::Object(swc::ObjectPat {
+            props: parts.iter().map(obj_pat_part).collect::<Res<_>>()?,
+            span: Default::default(),
+            optional: false,
+            type_ann: None,
+        }),
+        Pat::Array(parts) => swc::Pat::Array(swc::ArrayPat {
+            elems: parts
+                .iter()
+                .map(|part| part.as_ref().map(array_pat_part).transpose())
+                .collect::<Res<_>>()?,
+            span: Default::default(),
+            optional: false,
+            type_ann: None,
+        }),
+        Pat::RestElement(arg) => rest(self::pat(arg)?),
+        Pat::Assign(assign) => swc::Pat::Assign(swc::AssignPat {
+            left: Box::new(self::pat(&assign.left)?),
+            right: expr(&assign.right, ASSIGN)?,
+            span: Default::default(),
+        }),
+    })
+}
+
+fn rest(arg: swc::Pat) -> swc::Pat {
+    swc::Pat::Rest(rest_pat(arg))
+}
+
+fn rest_pat(arg: swc::Pat) -> swc::RestPat {
+    swc::RestPat {
+        arg: Box::new(arg),
+        span: Default::default(),
+        dot3_token: Default::default(),
+        type_ann: None,
+    }
+}
+
+fn array_pat_part(part: &ArrayPatPart) -> Res<swc::Pat> {
+    match part {
+        ArrayPatPart::Pat(pat) => self::pat(pat),
+        ArrayPatPart::Expr(expr) => expr_pat(expr),
+    }
+}
+
+fn obj_pat_part(part: &ObjPatPart) -> Res<swc::ObjectPatProp> {
+    let prop = match part {
+        ObjPatPart::Rest(arg) => {
+            return Ok(swc::ObjectPatProp::Rest(rest_pat(pat(arg)?)))
+        }
+        ObjPatPart::Assign(prop) => prop,
+    };
+    if prop.short_hand {
+        let key = key_ident(&prop.key).ok_or(SwcError::Invalid("shorthand property key"))?;
+        let value = match &prop.value {
+            PropValue::Pat(Pat::Assign(assign)) => Some(expr(&assign.right, ASSIGN)?),
+            _ => None,
+        };
+        return Ok(swc::ObjectPatProp::Assign(swc::AssignPatProp {
+            key: ident(key).into(),
+            value,
+            span: Default::default(),
+        }));
+    }
+    let value = match &prop.value {
+        PropValue::Pat(value) => pat(value)?,
+        PropValue::Expr(value) => expr_pat(value)?,
+        PropValue::None => return Err(SwcError::Invalid("pattern property without a value")),
+    };
+    Ok(swc::ObjectPatProp::KeyValue(swc::KeyValuePatProp {
+        key: prop_name(&prop.key, prop.computed)?,
+        value: Box::new(value),
+    }))
+}
+
+/// An expression used as a destructuring or assignment target
+fn expr_pat(expr: &Expr) -> Res<swc::Pat> {
+    match expr {
+        Expr::Member(_) => Ok(swc::Pat::Expr(self::expr(expr, CALL)?)),
+        Expr::Spread(arg) => Ok(rest(expr_pat(arg)?)),
+        _ => match Pat::try_from_expr(expr.clone()) {
+            Ok(pat) => self::pat(&pat),
+            Err(_) => Err(SwcError::Invalid("assignment target")),
+        },
+    }
+}
+
+fn assign_target(left: &AssignLeft) -> Res<swc::AssignTarget> {
+    let pat = match left {
+        AssignLeft::Pat(pat) => self::pat(pat)?,
+        AssignLeft::Expr(expr) => expr_pat(expr)?,
+    };
+    Ok(match pat {
+        swc::Pat::Ident(id) => swc::AssignTarget::Simple(swc::SimpleAssignTarget::Ident(id)),
+        swc::Pat::Array(pat) => swc::AssignTarget::Pat(swc::AssignTargetPat::Array(pat)),
+        swc::Pat::Object(pat) => swc::AssignTarget::Pat(swc::AssignTargetPat::Object(pat)),
+        swc::Pat::Expr(expr) => match *expr {
+            swc::Expr::Member(member) => {
+                swc::AssignTarget::Simple(swc::SimpleAssignTarget::Member(member))
+            }
+            swc::Expr::SuperProp(member) => {
+                swc::AssignTarget::Simple(swc::SimpleAssignTarget::SuperProp(member))
+            }
+            _ => return Err(SwcError::Invalid("assignment target")),
+        },
+        _ => return Err(SwcError::Invalid("assignment target")),
+    })
+}
+
+fn key_ident<'k, 'a>(key: &'k PropKey<'a>) -> Option<&'k Ident<'a>> {
+    match key {
+        PropKey::Expr(Expr::Ident(id)) | PropKey::Pat(Pat::Ident(id)) => Some(id),
+        _ => None,
+    }
+}
+
+fn prop_name(key: &PropKey, computed: bool) -> Res<swc::PropName> {
+    if computed {
+        let expr = match key {
+            PropKey::Expr(expr) => self::expr(expr, ASSIGN)?,
+            PropKey::Lit(lit) => Box::new(self::lit(lit)?),
+            _ => return Err(SwcError::Invalid("computed property key")),
+        };
+        return Ok(swc::PropName::Computed(swc::ComputedPropName {
+            expr,
+            span: Default::default(),
+        }));
+    }
+    if let Some(id) = key_ident(key) {
+        return Ok(swc::PropName::Ident(ident_name(id)));
+    }
+    Ok(match key {
+        PropKey::Lit(Lit::String(lit)) => swc::PropName::Str(string(lit)),
+        PropKey::Lit(Lit::Number(lit)) => swc::PropName::Num(number(lit)?),
+        PropKey::Lit(Lit::BigInt(lit)) => swc::PropName::BigInt(big_int(lit)?),
+        PropKey::Lit(Lit::Null) => swc::PropName::Ident("null".into()),
+        PropKey::Lit(Lit::Boolean(true)) => swc::PropName::Ident("true".into()),
+        PropKey::Lit(Lit::Boolean(false)) => swc::PropName::Ident("false".into()),
+        _ => return Err(SwcError::Invalid("property key")),
+    })
+}
+
+fn ident(id: &Ident) -> swc::Ident {
+    swc::Ident::from(&*id.name)
+}
+
+fn ident_name(id: &Ident) -> swc::IdentName {
+    swc::IdentName::from(&*id.name)
+}
+
+fn private_name(id: &PrivateId) -> swc::PrivateName {
+    swc::PrivateName {
+        name: (&*id.name).into(),
+        span: Default::default(),
+    }
+}
+
+fn paren(expr: swc::Expr) -> swc::Expr {
+    swc::Expr::Paren(swc::ParenExpr {
+        expr: Box::new(expr),
+        span: Default::default(),
+    })
+}
+
+/// Convert an expression, wrapping it in parentheses when it
+/// binds more loosely than `min`
+fn expr(expr: &Expr, min: u8) -> Res<Box<swc::Expr>> {
+    let converted = expr_inner(expr)?;
+    Ok(Box::new(if expr_prec(expr) < min {
+        paren(converted)
+    } else {
+        converted
+    }))
+}
+
+fn opt_expr(expr: &Option<Expr>, min: u8) -> Res<Option<Box<swc::Expr>>> {
+    expr.as_ref().map(|expr| self::expr(expr, min)).transpose()
+}
+
+/// The body of an expression statement, concise arrow or default
+/// export, which can't start with `{`, `function` or `class`
+fn unambiguous(expr: &Expr, min: u8) -> Res<Box<swc::Expr>> {
+    if starts_ambiguously(expr) {
+        Ok(Box::new(paren(expr_inner(expr)?)))
+    } else {
+        self::expr(expr, min)
+    }
+}
+
+/// The object of a member, the callee of a call or the tag of a
+/// tagged template, see `Codegen::callee`
+fn callee(expr: &Expr) -> Res<Box<swc::Expr>> {
+    match expr {
+        Expr::OptionalChain(_) | Expr::Lit(Lit::Number(_)) => {
+            Ok(Box::new(paren(expr_inner(expr)?)))
+        }
+        Expr::New(new) if !new.has_parens() => Ok(Box::new(paren(expr_inner(expr)?))),
+        _ => self::expr(expr, CALL),
+    }
+}
+
+fn args(args: &[Expr]) -> Res<Vec<swc::ExprOrSpread>> {
+    args.iter().map(expr_or_spread).collect()
+}
+
+fn expr_or_spread(expr: &Expr) -> Res<swc::ExprOrSpread> {
+    Ok(match expr {
+        Expr::Spread(arg) => swc::ExprOrSpread {
+            spread: Some(Default::default()),
+            expr: self::expr(arg, ASSIGN)?,
+        },
+        _ => swc::ExprOrSpread {
+            spread: None,
+            expr: self::expr(expr, ASSIGN)?,
+        },
+    })
+}
+
+/// If a member or call uses `?.` somewhere in its own chain
+fn has_optional(expr: &Expr) -> bool {
+    match expr {
+        Expr::Member(member) => member.optional || has_optional(&member.object),
+        Expr::Call(call) => call.optional || has_optional(&call.callee),
+        _ => false,
+    }
+}
+
+/// swc wraps every link of an optional chain after the first `?.`
+/// in an `OptChainExpr`, marking the ones that use `?.`
+fn chain(expr: &Expr) -> Res<swc::Expr> {
+    if !has_optional(expr) {
+        return expr_inner(expr);
+    }
+    let link = |expr: &Expr| match expr {
+        Expr::Member(_) | Expr::Call(_) => chain(expr).map(Box::new),
+        _ => callee(expr),
+    };
+    let (optional, base) = match expr {
+        Expr::Member(member) => (
+            member.optional,
+            swc::OptChainBase::Member(member_expr(member, link(&member.object)?)?),
+        ),
+        Expr::Call(call) => (
+            call.optional,
+            swc::OptChainBase::Call(swc::OptCall {
+                callee: link(&call.callee)?,
+                args: args(&call.arguments)?,
+                ..Default::default()
+            }),
+        ),
+        _ => return expr_inner(expr),
+    };
+    Ok(swc::Expr::OptChain(swc::OptChainExpr {
+        optional,
+        base: Box::new(base),
+        span: Default::default(),
+    }))
+}
+
+fn member_expr(member: &MemberExpr, obj: Box<swc::Expr>) -> Res<swc::MemberExpr> {
+    let prop = match &member.property {
+        MemberProp::Ident(id) => swc::MemberProp::Ident(ident_name(id)),
+        MemberProp::Private(id) => swc::MemberProp::PrivateName(private_name(id)),
+        MemberProp::Computed(prop) => swc::MemberProp::Computed(swc::ComputedPropName {
+            expr: expr(prop, SEQUENCE)?,
+            span: Default::default(),
+        }),
+    };
+    Ok(swc::MemberExpr {
+        obj,
+        prop,
+        span: Default::default(),
+    })
+}
+
+fn expr_inner(expr: &Expr) -> Res<swc::Expr> {
+    Ok(match expr {
+        Expr::Array(elements) => swc::Expr::Array(swc::ArrayLit {
+            elems: elements
+                .iter()
+                .map(|el| el.as_ref().map(expr_or_spread).transpose())
+                .collect::<Res<_>>()?,
+            span: Default::default(),
+        }),
+        Expr::ArrowFunc(arrow) => {
+            let body = match &arrow.body {
+                ArrowFuncBody::FuncBody(body) => {
+                    swc::ArrowFunctionBody::FunctionBody(swc::FunctionBody {
+                        stmts: stmts(&body.0)?,
+                        ..Default::default()
+                    })
+                }
+                ArrowFuncBody::Expr(body) => {
+                    swc::ArrowFunctionBody::Expr(unambiguous(body, ASSIGN)?)
+                }
+            };
+            swc::Expr::Arrow(swc::ArrowExpr {
+                params: arrow.params.iter().map(func_arg).collect::<Res<_>>()?,
+                body: Box::new(body),
+                is_async: arrow.is_async,
+                ..Default::default()
+            })
+        }
+        Expr::ArrowParamPlaceHolder(_, _) => {
+            return Err(SwcError::Unsupported("arrow parameter placeholders"))
+        }
+        Expr::Assign(assign) => swc::Expr::Assign(swc::AssignExpr {
+            op: assign_op(assign.operator),
+            left: assign_target(&assign.left)?,
+            right: self::expr(&assign.right, ASSIGN)?,
+            span: Default::default(),
+        }),
+        Expr::Await(arg) => swc::Expr::Await(swc::AwaitExpr {
+            arg: self::expr(arg, UNARY)?,
+            span: Default::default(),
+        }),
+        Expr::Binary(bin) => {
+            let prec = bin.operator.precedence();
+            let (left, right) = if bin.operator.associativity() == Associativity::Right {
+                (POSTFIX, prec)
+            } else {
+                (prec, prec + 1)
+            };
+            swc::Expr::Bin(swc::BinExpr {
+                op: binary_op(bin.operator),
+                left: self::expr(&bin.left, left)?,
+                right: self::expr(&bin.right, right)?,
+                span: Default::default(),
+            })
+        }
+        Expr::Class(class) => swc::Expr::Class(class_expr(class)?),
+        Expr::Call(_) | Expr::Member(_) if has_optional(expr) => chain(expr)?,
+        Expr::Call(call) => swc::Expr::Call(swc::CallExpr {
+            callee: match &*call.callee {
+                Expr::Super => swc::Callee::Super(swc::Super {
+                    span: Default::default(),
+                }),
+                callee => swc::Callee::Expr(self::callee(callee)?),
+            },
+            args: args(&call.arguments)?,
+            ..Default::default()
+        }),
+        Expr::Conditional(cond) => swc::Expr::Cond(swc::CondExpr {
+            test: self::expr(&cond.test, NULLISH)?,
+            cons: self::expr(&cond.consequent, ASSIGN)?,
+            alt: self::expr(&cond.alternate, ASSIGN)?,
+            span: Default::default(),
+        }),
+        Expr::Func(func) => swc::Expr::Fn(fn_expr(func)?),
+        Expr::Ident(id) => swc::Expr::Ident(ident(id)),
+        Expr::Import(import) => {
+            let mut args = vec![expr_or_spread(&import.source)?];
+            if let Some(options) = &import.options {
+                args.push(expr_or_spread(options)?);
+            }
+            swc::Expr::Call(swc::CallExpr {
+                callee: swc::Callee::Import(swc::Import {
+                    phase: swc::ImportPhase::Evaluation,
+                    span: Default::default(),
+                }),
+                args,
+                ..Default::default()
+            })
+        }
+        Expr::Lit(lit) => self::lit(lit)?,
+        Expr::Logical(logical) => {
+            let prec = logical.operator.precedence();
+            // `??` can't be mixed with `&&` or `||` without parentheses
+            let operand = |e: &Expr, min| match e {
+                Expr::Logical(child) if !child.operator.can_mix_with(logical.operator) => {
+                    Ok(Box::new(paren(expr_inner(e)?)))
+                }
+                _ => self::expr(e, min),
+            };
+            let op = match logical.operator {
+                LogicalOp::Or => swc::BinaryOp::LogicalOr,
+                LogicalOp::And => swc::BinaryOp::LogicalAnd,
+                LogicalOp::NullishCoalescing => swc::BinaryOp::NullishCoalescing,
+            };
+            swc::Expr::Bin(swc::BinExpr {
+                op,
+                left: operand(&logical.left, prec)?,
+                right: operand(&logical.right, prec + 1)?,
+                span: Default::default(),
+            })
+        }
+        Expr::Member(member) => match &*member.object {
+            Expr::Super => {
+                let prop = match &member.property {
+                    MemberProp::Ident(id) => swc::SuperProp::Ident(ident_name(id)),
+                    MemberProp::Computed(prop) => swc::SuperProp::Computed(swc::ComputedPropName {
+                        expr: self::expr(prop, SEQUENCE)?,
+                        span: Default::default(),
+                    }),
+                    MemberProp::Private(_) => return Err(SwcError::Invalid("super.#private")),
+                };
+                swc::Expr::SuperProp(swc::SuperPropExpr {
+                    obj: swc::Super {
+                        span: Default::default(),
+                    },
+                    prop,
+                    span: Default::default(),
+                })
+            }
+            object => swc::Expr::Member(member_expr(member, callee(object)?)?),
+        },
+        Expr::MetaProp(meta) => {
+            let kind = match (&*meta.meta.name, &*meta.property.name) {
+                ("new", "target") => swc::MetaPropKind::NewTarget,
+                ("import", "meta") => swc::MetaPropKind::ImportMeta,
+                _ => return Err(SwcError::Invalid("meta property")),
+            };
+            swc::Expr::MetaProp(swc::MetaPropExpr {
+                kind,
+                span: Default::default(),
+            })
+        }
+        Expr::OptionalChain(inner) => chain(inner)?,
+        #[cfg(feature = "preserve-parens")]
+        Expr::Paren(inner) => paren(*self::expr(inner, SEQUENCE)?),
+        Expr::PrivateId(id) => swc::Expr::PrivateName(private_name(id)),
+        Expr::New(new) => {
+            let callee = if contains_call(&new.callee) {
+                Box::new(paren(expr_inner(&new.callee)?))
+            } else {
+                callee(&new.callee)?
+            };
+            swc::Expr::New(swc::NewExpr {
+                callee,
+                args: new.arguments.as_deref().map(args).transpose()?,
+                ..Default::default()
+            })
+        }
+        Expr::Obj(props) => swc::Expr::Object(swc::ObjectLit {
+            props: props.iter().map(obj_prop).collect::<Res<_>>()?,
+            span: Default::default(),
+        }),
+        Expr::Sequence(seq) => swc::Expr::Seq(swc::SeqExpr {
+            exprs: std::iter::once(&*seq.head)
+                .chain(&seq.tail)
+                .map(|expr| self::expr(expr, ASSIGN))
+                .collect::<Res<_>>()?,
+            span: Default::default(),
+        }),
+        Expr::Spread(_) => return Err(SwcError::Invalid("spread outside of an array or arguments")),
+        Expr::Super => return Err(SwcError::Invalid("super outside of a call or member")),
+        Expr::TaggedTemplate(tagged) => swc::Expr::TaggedTpl(swc::TaggedTpl {
+            tag: callee(&tagged.tag)?,
+            tpl: Box::new(template(&tagged.quasi)?),
+            ..Default::default()
+        }),
+        Expr::This => swc::Expr::This(swc::ThisExpr {
+            span: Default::default(),
+        }),
+        Expr::Unary(unary) => swc::Expr::Unary(swc::UnaryExpr {
+            op: unary_op(unary.operator),
+            arg: self::expr(&unary.argument, UNARY)?,
+            span: Default::default(),
+        }),
+        Expr::Update(update) => swc::Expr::Update(swc::UpdateExpr {
+            op: match update.operator {
+                UpdateOp::Increment => swc::UpdateOp::PlusPlus,
+                UpdateOp::Decrement => swc::UpdateOp::MinusMinus,
+            },
+            prefix: update.prefix,
+            arg: self::expr(&update.argument, if update.prefix { UNARY } else { CALL })?,
+            span: Default::default(),
+        }),
+        Expr::Yield(y) => swc::Expr::Yield(swc::YieldExpr {
+            arg: y.argument.as_deref().map(|arg| self::expr(arg, ASSIGN)).transpose()?,
+            delegate: y.delegate,
+            span: Default::default(),
+        }),
+    })
+}
+
+fn obj_prop(prop: &ObjProp) -> Res<swc::PropOrSpread> {
+    let prop = match prop {
+        ObjProp::Spread(expr) => {
+            return Ok(swc::PropOrSpread::Spread(swc::SpreadElement {
+                expr: self::expr(expr, ASSIGN)?,
+                dot3_token: Default::default(),
+            }))
+        }
+        ObjProp::Prop(prop) => prop,
+    };
+    let prop = if let Some(func) = prop.method_func() {
+        let key = prop_name(&prop.key, prop.computed)?;
+        let function = function(func)?;
+        match prop.kind {
+            PropKind::Get => swc::Prop::Getter(swc::GetterProp {
+                key,
+                function,
+                span: Default::default(),
+            }),
+            PropKind::Set => swc::Prop::Setter(swc::SetterProp {
+                key,
+                function,
+                span: Default::default(),
+            }),
+            _ => swc::Prop::Method(swc::MethodProp { key, function }),
+        }
+    } else if prop.short_hand {
+        let key = key_ident(&prop.key).ok_or(SwcError::Invalid("shorthand property key"))?;
+        swc::Prop::Shorthand(ident(key))
+    } else {
+        match &prop.value {
+            PropValue::Expr(value) => swc::Prop::KeyValue(swc::KeyValueProp {
+                key: prop_name(&prop.key, prop.computed)?,
+                value: expr(value, ASSIGN)?,
+            }),
+            _ => return Err(SwcError::Invalid("object property without a value")),
+        }
+    };
+    Ok(swc::PropOrSpread::Prop(Box::new(prop)))
+}
+
+fn lit(lit: &Lit) -> Res<swc::Expr> {
+    let lit = match lit {
+        Lit::Null => swc::Lit::Null(swc::Null {
+            span: Default::default(),
+        }),
+        Lit::Boolean(value) => swc::Lit::Bool(swc::Bool {
+            value: *value,
+            span: Default::default(),
+        }),
+        Lit::String(lit) => swc::Lit::Str(string(lit)),
+        Lit::Number(lit) => swc::Lit::Num(number(lit)?),
+        Lit::BigInt(lit) => swc::Lit::BigInt(big_int(lit)?),
+        Lit::RegEx(re) => swc::Lit::Regex(swc::Regex {
+            exp: (&*re.pattern).into(),
+            flags: (&*re.flags).into(),
+            span: Default::default(),
+        }),
+        Lit::Template(template) => return Ok(swc::Expr::Tpl(self::template(template)?)),
+    };
+    Ok(swc::Expr::Lit(lit))
+}
+
+fn str_lit(lit: &Lit) -> Res<swc::Str> {
+    match lit {
+        Lit::String(lit) => Ok(string(lit)),
+        _ => Err(SwcError::Invalid("string literal")),
+    }
+}
+
+fn string(lit: &StringLit) -> swc::Str {
+    let (quote, value) = match lit {
+        StringLit::Double(value) => ('"', value),
+        StringLit::Single(value) => ('\'', value),
+    };
+    swc::Str {
+        value: (&*lit.cooked()).into(),
+        raw: Some(format!("{}{}{}", quote, value, quote).into()),
+        span: Default::default(),
+    }
+}
+
+fn number(lit: &NumberLit) -> Res<swc::Number> {
+    Ok(swc::Number {
+        value: lit.as_f64().ok_or(SwcError::Invalid("number literal"))?,
+        raw: Some(lit.raw().into()),
+        span: Default::default(),
+    })
+}
+
+fn big_int(digits: &str) -> Res<swc::BigInt> {
+    let clean = digits.replace('_', "");
+    let (radix, value) = match clean.get(..2) {
+        Some("0x") | Some("0X") => (16, &clean[2..]),
+        Some("0o") | Some("0O") => (8, &clean[2..]),
+        Some("0b") | Some("0B") => (2, &clean[2..]),
+        _ => (10, &clean[..]),
+    };
+    let value = swc::BigIntValue::parse_bytes(value.as_bytes(), radix)
+        .ok_or(SwcError::Invalid("BigInt literal"))?;
+    Ok(swc::BigInt {
+        value: Box::new(value),
+        raw: Some(format!("{}n", digits).into()),
+        span: Default::default(),
+    })
+}
+
+fn template(template: &TemplateLit) -> Res<swc::Tpl> {
+    let quasis = template
+        .quasis
+        .iter()
+        .map(|quasi| {
+            // swc's raw text doesn't include the delimiters
+            let raw = &*quasi.raw;
+            let raw = raw
+                .strip_prefix('`')
+                .or_else(|| raw.strip_prefix('}'))
+                .unwrap_or(raw);
+            let raw = raw
+                .strip_suffix('`')
+                .or_else(|| raw.strip_suffix("${"))
+                .unwrap_or(raw);
+            swc::TplElement {
+                tail: quasi.tail,
+                cooked: quasi.cooked.as_deref().map(Into::into),
+                raw: raw.into(),
+                span: Default::default(),
+            }
+        })
+        .collect();
+    Ok(swc::Tpl {
+        exprs: template
+            .expressions
+            .iter()
+            .map(|expr| self::expr(expr, SEQUENCE))
+            .collect::<Res<_>>()?,
+        quasis,
+        span: Default::default(),
+    })
+}
+
+fn assign_op(op: AssignOp) -> swc::AssignOp {
+    match op {
+        AssignOp::Equal => swc::AssignOp::Assign,
+        AssignOp::PlusEqual => swc::AssignOp::AddAssign,
+        AssignOp::MinusEqual => swc::AssignOp::SubAssign,
+        AssignOp::TimesEqual => swc::AssignOp::MulAssign,
+        AssignOp::DivEqual => swc::AssignOp::DivAssign,
+        AssignOp::ModEqual => swc::AssignOp::ModAssign,
+        AssignOp::LeftShiftEqual => swc::AssignOp::LShiftAssign,
+        AssignOp::RightShiftEqual => swc::AssignOp::RShiftAssign,
+        AssignOp::UnsignedRightShiftEqual => swc::AssignOp::ZeroFillRShiftAssign,
+        AssignOp::OrEqual => swc::AssignOp::BitOrAssign,
+        AssignOp::XOrEqual => swc::AssignOp::BitXorAssign,
+        AssignOp::AndEqual => swc::AssignOp::BitAndAssign,
+        AssignOp::PowerOfEqual => swc::AssignOp::ExpAssign,
+        AssignOp::LogicalAndEqual => swc::AssignOp::AndAssign,
+        AssignOp::LogicalOrEqual => swc::AssignOp::OrAssign,
+        AssignOp::NullishEqual => swc::AssignOp::NullishAssign,
+    }
+}
+
+fn binary_op(op: BinaryOp) -> swc::BinaryOp {
+    match op {
+        BinaryOp::Equal => swc::BinaryOp::EqEq,
+        BinaryOp::NotEqual => swc::BinaryOp::NotEq,
+        BinaryOp::StrictEqual => swc::BinaryOp::EqEqEq,
+        BinaryOp::StrictNotEqual => swc::BinaryOp::NotEqEq,
+        BinaryOp::LessThan => swc::BinaryOp::Lt,
+        BinaryOp::GreaterThan => swc::BinaryOp::Gt,
+        BinaryOp::LessThanEqual => swc::BinaryOp::LtEq,
+        BinaryOp::GreaterThanEqual => swc::BinaryOp::GtEq,
+        BinaryOp::LeftShift => swc::BinaryOp::LShift,
+        BinaryOp::RightShift => swc::BinaryOp::RShift,
+        BinaryOp::UnsignedRightShift => swc::BinaryOp::ZeroFillRShift,
+        BinaryOp::Plus => swc::BinaryOp::Add,
+        BinaryOp::Minus => swc::BinaryOp::Sub,
+        BinaryOp::Times => swc::BinaryOp::Mul,
+        BinaryOp::Over => swc::BinaryOp::Div,
+        BinaryOp::Mod => swc::BinaryOp::Mod,
+        BinaryOp::Or => swc::BinaryOp::BitOr,
+        BinaryOp::XOr => swc::BinaryOp::BitXor,
+        BinaryOp::And => swc::BinaryOp::BitAnd,
+        BinaryOp::In => swc::BinaryOp::In,
+        BinaryOp::InstanceOf => swc::BinaryOp::InstanceOf,
+        BinaryOp::PowerOf => swc::BinaryOp::Exp,
+    }
+}
+
+fn unary_op(op: UnaryOp) -> swc::UnaryOp {
+    match op {
+        UnaryOp::Minus => swc::UnaryOp::Minus,
+        UnaryOp::Plus => swc::UnaryOp::Plus,
+        UnaryOp::Not => swc::UnaryOp::Bang,
+        UnaryOp::Tilde => swc::UnaryOp::Tilde,
+        UnaryOp::TypeOf => swc::UnaryOp::TypeOf,
+        UnaryOp::Void => swc::UnaryOp::Void,
+        UnaryOp::Delete => swc::UnaryOp::Delete,
+    }
+}
@@ -0,0 +1,966 @@
+//! Read-only traversal of the AST
+//!
+//! Implement `Visitor` and override only the methods for the nodes
+//! you care about, every method defaults to calling the matching
+//! `walk_*` function which visits that node's children. If you
+//! override a method and still want to visit the children, call the
+//! `walk_*` function from your implementation.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::visit::{walk_call_expr, Visitor};
+//!
+//! #[derive(Default)]
+//! struct CallCounter(usize);
+//!
+//! impl<'a> Visitor<'a> for CallCounter {
+//!     fn visit_call_expr(&mut self, call: &'a CallExpr<'a>) {
+//!         self.0 += 1;
+//!         walk_call_expr(self, call);
+//!     }
+//! }
+//! ```
+use crate::decl::*;
+use crate::expr::*;
+use crate::node::NodeRef;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// A read-only visitor over every node in the AST
+pub trait Visitor<'a> {
+    fn visit_program(&mut self, program: &'a Program<'a>) {
+        walk_program(self, program)
+    }
+    fn visit_program_part(&mut self, part: &'a ProgramPart<'a>) {
+        walk_program_part(self, part)
+    }
+    fn visit_dir(&mut self, dir: &'a Dir<'a>) {
+        walk_dir(self, dir)
+    }
+    fn visit_ident(&mut self, _ident: &'a Ident<'a>) {}
+    fn visit_private_id(&mut self, _id: &'a PrivateId<'a>) {}
+    fn visit_func(&mut self, func: &'a Func<'a>) {
+        walk_func(self, func)
+    }
+    fn visit_func_arg(&mut self, arg: &'a FuncArg<'a>) {
+        walk_func_arg(self, arg)
+    }
+    fn visit_func_body(&mut self, body: &'a FuncBody<'a>) {
+        walk_func_body(self, body)
+    }
+    fn visit_class(&mut self, class: &'a Class<'a>) {
+        walk_class(self, class)
+    }
+    fn visit_class_member(&mut self, member: &'a ClassMember<'a>) {
+        walk_class_member(self, member)
+    }
+    fn visit_method_def(&mut self, method: &'a MethodDef<'a>) {
+        walk_method_def(self, method)
+    }
+    fn visit_field_def(&mut self, field: &'a FieldDef<'a>) {
+        walk_field_def(self, field)
+    }
+    fn visit_decorator(&mut self, decorator: &'a Decorator<'a>) {
+        walk_decorator(self, decorator)
+    }
+    fn visit_decl(&mut self, decl: &'a Decl<'a>) {
+        walk_decl(self, decl)
+    }
+    fn visit_var_decl(&mut self, decl: &'a VarDecl<'a>) {
+        walk_var_decl(self, decl)
+    }
+    fn visit_mod_import(&mut self, import: &'a ModImport<'a>) {
+        walk_mod_import(self, import)
+    }
+    fn visit_import_specifier(&mut self, spec: &'a ImportSpecifier<'a>) {
+        walk_import_specifier(self, spec)
+    }
+    fn visit_import_attribute(&mut self, attr: &'a ImportAttribute<'a>) {
+        walk_import_attribute(self, attr)
+    }
+    fn visit_module_export_name(&mut self, name: &'a ModuleExportName<'a>) {
+        walk_module_export_name(self, name)
+    }
+    fn visit_mod_export(&mut self, export: &'a ModExport<'a>) {
+        walk_mod_export(self, export)
+    }
+    fn visit_export_source(&mut self, source: &'a ExportSource<'a>) {
+        walk_export_source(self, source)
+    }
+    fn visit_export_specifier(&mut self, spec: &'a ExportSpecifier<'a>) {
+        walk_export_specifier(self, spec)
+    }
+    fn visit_stmt(&mut self, stmt: &'a Stmt<'a>) {
+        walk_stmt(self, stmt)
+    }
+    fn visit_block_stmt(&mut self, block: &'a BlockStmt<'a>) {
+        walk_block_stmt(self, block)
+    }
+    fn visit_with_stmt(&mut self, stmt: &'a WithStmt<'a>) {
+        walk_with_stmt(self, stmt)
+    }
+    fn visit_labeled_stmt(&mut self, stmt: &'a LabeledStmt<'a>) {
+        walk_labeled_stmt(self, stmt)
+    }
+    fn visit_if_stmt(&mut self, stmt: &'a IfStmt<'a>) {
+        walk_if_stmt(self, stmt)
+    }
+    fn visit_switch_stmt(&mut self, stmt: &'a SwitchStmt<'a>) {
+        walk_switch_stmt(self, stmt)
+    }
+    fn visit_switch_case(&mut self, case: &'a SwitchCase<'a>) {
+        walk_switch_case(self, case)
+    }
+    fn visit_try_stmt(&mut self, stmt: &'a TryStmt<'a>) {
+        walk_try_stmt(self, stmt)
+    }
+    fn visit_catch_clause(&mut self, clause: &'a CatchClause<'a>) {
+        walk_catch_clause(self, clause)
+    }
+    fn visit_while_stmt(&mut self, stmt: &'a WhileStmt<'a>) {
+        walk_while_stmt(self, stmt)
+    }
+    fn visit_do_while_stmt(&mut self, stmt: &'a DoWhileStmt<'a>) {
+        walk_do_while_stmt(self, stmt)
+    }
+    fn visit_for_stmt(&mut self, stmt: &'a ForStmt<'a>) {
+        walk_for_stmt(self, stmt)
+    }
+    fn visit_loop_init(&mut self, init: &'a LoopInit<'a>) {
+        walk_loop_init(self, init)
+    }
+    fn visit_for_in_stmt(&mut self, stmt: &'a ForInStmt<'a>) {
+        walk_for_in_stmt(self, stmt)
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &'a ForOfStmt<'a>) {
+        walk_for_of_stmt(self, stmt)
+    }
+    fn visit_loop_left(&mut self, left: &'a LoopLeft<'a>) {
+        walk_loop_left(self, left)
+    }
+    fn visit_expr(&mut self, expr: &'a Expr<'a>) {
+        walk_expr(self, expr)
+    }
+    fn visit_obj_prop(&mut self, prop: &'a ObjProp<'a>) {
+        walk_obj_prop(self, prop)
+    }
+    fn visit_prop(&mut self, prop: &'a Prop<'a>) {
+        walk_prop(self, prop)
+    }
+    fn visit_prop_key(&mut self, key: &'a PropKey<'a>) {
+        walk_prop_key(self, key)
+    }
+    fn visit_prop_value(&mut self, value: &'a PropValue<'a>) {
+        walk_prop_value(self, value)
+    }
+    fn visit_unary_expr(&mut self, expr: &'a UnaryExpr<'a>) {
+        walk_unary_expr(self, expr)
+    }
+    fn visit_update_expr(&mut self, expr: &'a UpdateExpr<'a>) {
+        walk_update_expr(self, expr)
+    }
+    fn visit_binary_expr(&mut self, expr: &'a BinaryExpr<'a>) {
+        walk_binary_expr(self, expr)
+    }
+    fn visit_assign_expr(&mut self, expr: &'a AssignExpr<'a>) {
+        walk_assign_expr(self, expr)
+    }
+    fn visit_assign_left(&mut self, left: &'a AssignLeft<'a>) {
+        walk_assign_left(self, left)
+    }
+    fn visit_logical_expr(&mut self, expr: &'a LogicalExpr<'a>) {
+        walk_logical_expr(self, expr)
+    }
+    fn visit_member_expr(&mut self, expr: &'a MemberExpr<'a>) {
+        walk_member_expr(self, expr)
+    }
+    fn visit_member_prop(&mut self, prop: &'a MemberProp<'a>) {
+        walk_member_prop(self, prop)
+    }
+    fn visit_conditional_expr(&mut self, expr: &'a ConditionalExpr<'a>) {
+        walk_conditional_expr(self, expr)
+    }
+    fn visit_call_expr(&mut self, expr: &'a CallExpr<'a>) {
+        walk_call_expr(self, expr)
+    }
+    fn visit_import_expr(&mut self, expr: &'a ImportExpr<'a>) {
+        walk_import_expr(self, expr)
+    }
+    fn visit_new_expr(&mut self, expr: &'a NewExpr<'a>) {
+        walk_new_expr(self, expr)
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &'a ArrowFuncExpr<'a>) {
+        walk_arrow_func_expr(self, expr)
+    }
+    fn visit_yield_expr(&mut self, expr: &'a YieldExpr<'a>) {
+        walk_yield_expr(self, expr)
+    }
+    fn visit_tagged_template_expr(&mut self, expr: &'a TaggedTemplateExpr<'a>) {
+        walk_tagged_template_expr(self, expr)
+    }
+    fn visit_template_lit(&mut self, lit: &'a TemplateLit<'a>) {
+        walk_template_lit(self, lit)
+    }
+    fn visit_template_element(&mut self, _element: &'a TemplateElement<'a>) {}
+    fn visit_meta_prop(&mut self, meta: &'a MetaProp<'a>) {
+        walk_meta_prop(self, meta)
+    }
+    fn visit_lit(&mut self, lit: &'a Lit<'a>) {
+        walk_lit(self, lit)
+    }
+    fn visit_pat(&mut self, pat: &'a Pat<'a>) {
+        walk_pat(self, pat)
+    }
+    fn visit_array_pat_part(&mut self, part: &'a ArrayPatPart<'a>) {
+        walk_array_pat_part(self, part)
+    }
+    fn visit_obj_pat_part(&mut self, part: &'a ObjPatPart<'a>) {
+        walk_obj_pat_part(self, part)
+    }
+    fn visit_assign_pat(&mut self, pat: &'a AssignPat<'a>) {
+        walk_assign_pat(self, pat)
+    }
+}
+
+pub fn walk_program<'a, V: Visitor<'a> + ?Sized>(v: &mut V, program: &'a Program<'a>) {
+    match program {
+        Program::Mod(parts, _) | Program::Script(parts, _) => {
+            for part in parts {
+                v.visit_program_part(part);
+            }
+        }
+    }
+}
+
+pub fn walk_program_part<'a, V: Visitor<'a> + ?Sized>(v: &mut V, part: &'a ProgramPart<'a>) {
+    match part {
+        ProgramPart::Dir(dir) => v.visit_dir(dir),
+        ProgramPart::Decl(decl) => v.visit_decl(decl),
+        ProgramPart::Stmt(stmt) => v.visit_stmt(stmt),
+    }
+}
+
+pub fn walk_dir<'a, V: Visitor<'a> + ?Sized>(v: &mut V, dir: &'a Dir<'a>) {
+    v.visit_lit(&dir.expr);
+}
+
+pub fn walk_func<'a, V: Visitor<'a> + ?Sized>(v: &mut V, func: &'a Func<'a>) {
+    if let Some(id) = &func.id {
+        v.visit_ident(id);
+    }
+    for arg in &func.params {
+        v.visit_func_arg(arg);
+    }
+    v.visit_func_body(&func.body);
+}
+
+pub fn walk_func_arg<'a, V: Visitor<'a> + ?Sized>(v: &mut V, arg: &'a FuncArg<'a>) {
+    match arg {
+        FuncArg::Expr(expr) => v.visit_expr(expr),
+        FuncArg::Pat(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_func_body<'a, V: Visitor<'a> + ?Sized>(v: &mut V, body: &'a FuncBody<'a>) {
+    for part in &body.0 {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_class<'a, V: Visitor<'a> + ?Sized>(v: &mut V, class: &'a Class<'a>) {
+    for decorator in &class.decorators {
+        v.visit_decorator(decorator);
+    }
+    if let Some(id) = &class.id {
+        v.visit_ident(id);
+    }
+    if let Some(super_class) = &class.super_class {
+        v.visit_expr(super_class);
+    }
+    for member in &class.body.0 {
+        v.visit_class_member(member);
+    }
+}
+
+pub fn walk_class_member<'a, V: Visitor<'a> + ?Sized>(v: &mut V, member: &'a ClassMember<'a>) {
+    match member {
+        ClassMember::Method(method) => v.visit_method_def(method),
+        ClassMember::Field(field) => v.visit_field_def(field),
+        ClassMember::StaticBlock(block) => v.visit_block_stmt(block),
+    }
+}
+
+pub fn walk_method_def<'a, V: Visitor<'a> + ?Sized>(v: &mut V, method: &'a MethodDef<'a>) {
+    for decorator in &method.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&method.key);
+    v.visit_func(&method.value);
+}
+
+pub fn walk_field_def<'a, V: Visitor<'a> + ?Sized>(v: &mut V, field: &'a FieldDef<'a>) {
+    for decorator in &field.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&field.key);
+    if let Some(value) = &field.value {
+        v.visit_expr(value);
+    }
+}
+
+pub fn walk_decorator<'a, V: Visitor<'a> + ?Sized>(v: &mut V, decorator: &'a Decorator<'a>) {
+    v.visit_expr(&decorator.expression);
+}
+
+pub fn walk_decl<'a, V: Visitor<'a> + ?Sized>(v: &mut V, decl: &'a Decl<'a>) {
+    match decl {
+        Decl::Var(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+        Decl::Func(func) => v.visit_func(func),
+        Decl::Class(class) => v.visit_class(class),
+        Decl::Import(import) => v.visit_mod_import(import),
+        Decl::Export(export) => v.visit_mod_export(export),
+    }
+}
+
+pub fn walk_var_decl<'a, V: Visitor<'a> + ?Sized>(v: &mut V, decl: &'a VarDecl<'a>) {
+    v.visit_pat(&decl.id);
+    if let Some(init) = &decl.init {
+        v.visit_expr(init);
+    }
+}
+
+pub fn walk_mod_import<'a, V: Visitor<'a> + ?Sized>(v: &mut V, import: &'a ModImport<'a>) {
+    for spec in &import.specifiers {
+        v.visit_import_specifier(spec);
+    }
+    v.visit_lit(&import.source);
+    for attr in &import.attributes {
+        v.visit_import_attribute(attr);
+    }
+}
+
+pub fn walk_import_specifier<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    spec: &'a ImportSpecifier<'a>,
+) {
+    match spec {
+        ImportSpecifier::Normal(normal) => {
+            v.visit_module_export_name(&normal.imported);
+            v.visit_ident(&normal.local);
+        }
+        ImportSpecifier::Default(ident) | ImportSpecifier::Namespace(ident) => {
+            v.visit_ident(ident)
+        }
+    }
+}
+
+pub fn walk_import_attribute<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    attr: &'a ImportAttribute<'a>,
+) {
+    match &attr.key {
+        ImportAttributeKey::Ident(ident) => v.visit_ident(ident),
+        ImportAttributeKey::Lit(lit) => v.visit_lit(lit),
+    }
+    v.visit_lit(&attr.value);
+}
+
+pub fn walk_module_export_name<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    name: &'a ModuleExportName<'a>,
+) {
+    match name {
+        ModuleExportName::Ident(ident) => v.visit_ident(ident),
+        ModuleExportName::Str(lit) => v.visit_lit(lit),
+    }
+}
+
+pub fn walk_mod_export<'a, V: Visitor<'a> + ?Sized>(v: &mut V, export: &'a ModExport<'a>) {
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => v.visit_expr(expr),
+        ModExport::Named(NamedExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            for spec in specs {
+                v.visit_export_specifier(spec);
+            }
+            if let Some(source) = source {
+                v.visit_export_source(source);
+            }
+        }
+        ModExport::All(alias, source) => {
+            if let Some(alias) = alias {
+                v.visit_module_export_name(alias);
+            }
+            v.visit_export_source(source);
+        }
+    }
+}
+
+pub fn walk_export_source<'a, V: Visitor<'a> + ?Sized>(v: &mut V, source: &'a ExportSource<'a>) {
+    v.visit_lit(&source.source);
+    for attr in &source.attributes {
+        v.visit_import_attribute(attr);
+    }
+}
+
+pub fn walk_export_specifier<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    spec: &'a ExportSpecifier<'a>,
+) {
+    v.visit_ident(&spec.local);
+    v.visit_module_export_name(&spec.exported);
+}
+
+pub fn walk_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a Stmt<'a>) {
+    match stmt {
+        Stmt::Expr(expr) | Stmt::Throw(expr) => v.visit_expr(expr),
+        Stmt::Block(block) => v.visit_block_stmt(block),
+        Stmt::Empty | Stmt::Debugger => {}
+        Stmt::With(with) => v.visit_with_stmt(with),
+        Stmt::Return(arg) => {
+            if let Some(arg) = arg {
+                v.visit_expr(arg);
+            }
+        }
+        Stmt::Labeled(labeled) => v.visit_labeled_stmt(labeled),
+        Stmt::Break(label) | Stmt::Continue(label) => {
+            if let Some(label) = label {
+                v.visit_ident(label);
+            }
+        }
+        Stmt::If(stmt) => v.visit_if_stmt(stmt),
+        Stmt::Switch(stmt) => v.visit_switch_stmt(stmt),
+        Stmt::Try(stmt) => v.visit_try_stmt(stmt),
+        Stmt::While(stmt) => v.visit_while_stmt(stmt),
+        Stmt::DoWhile(stmt) => v.visit_do_while_stmt(stmt),
+        Stmt::For(stmt) => v.visit_for_stmt(stmt),
+        Stmt::ForIn(stmt) => v.visit_for_in_stmt(stmt),
+        Stmt::ForOf(stmt) => v.visit_for_of_stmt(stmt),
+        Stmt::Var(decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+    }
+}
+
+pub fn walk_block_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, block: &'a BlockStmt<'a>) {
+    for part in &block.0 {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_with_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a WithStmt<'a>) {
+    v.visit_expr(&stmt.object);
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_labeled_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a LabeledStmt<'a>) {
+    v.visit_ident(&stmt.label);
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_if_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a IfStmt<'a>) {
+    v.visit_expr(&stmt.test);
+    v.visit_stmt(&stmt.consequent);
+    if let Some(alt) = &stmt.alternate {
+        v.visit_stmt(alt);
+    }
+}
+
+pub fn walk_switch_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a SwitchStmt<'a>) {
+    v.visit_expr(&stmt.discriminant);
+    for case in &stmt.cases {
+        v.visit_switch_case(case);
+    }
+}
+
+pub fn walk_switch_case<'a, V: Visitor<'a> + ?Sized>(v: &mut V, case: &'a SwitchCase<'a>) {
+    if let Some(test) = &case.test {
+        v.visit_expr(test);
+    }
+    for part in &case.consequent {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_try_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a TryStmt<'a>) {
+    v.visit_block_stmt(&stmt.block);
+    if let Some(handler) = &stmt.handler {
+        v.visit_catch_clause(handler);
+    }
+    if let Some(finalizer) = &stmt.finalizer {
+        v.visit_block_stmt(finalizer);
+    }
+}
+
+pub fn walk_catch_clause<'a, V: Visitor<'a> + ?Sized>(v: &mut V, clause: &'a CatchClause<'a>) {
+    if let Some(param) = &clause.param {
+        v.visit_pat(param);
+    }
+    v.visit_block_stmt(&clause.body);
+}
+
+pub fn walk_while_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a WhileStmt<'a>) {
+    v.visit_expr(&stmt.test);
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_do_while_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a DoWhileStmt<'a>) {
+    v.visit_stmt(&stmt.body);
+    v.visit_expr(&stmt.test);
+}
+
+pub fn walk_for_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a ForStmt<'a>) {
+    if let Some(init) = &stmt.init {
+        v.visit_loop_init(init);
+    }
+    if let Some(test) = &stmt.test {
+        v.visit_expr(test);
+    }
+    if let Some(update) = &stmt.update {
+        v.visit_expr(update);
+    }
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_loop_init<'a, V: Visitor<'a> + ?Sized>(v: &mut V, init: &'a LoopInit<'a>) {
+    match init {
+        LoopInit::Variable(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+        LoopInit::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_for_in_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a ForInStmt<'a>) {
+    v.visit_loop_left(&stmt.left);
+    v.visit_expr(&stmt.right);
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_for_of_stmt<'a, V: Visitor<'a> + ?Sized>(v: &mut V, stmt: &'a ForOfStmt<'a>) {
+    v.visit_loop_left(&stmt.left);
+    v.visit_expr(&stmt.right);
+    v.visit_stmt(&stmt.body);
+}
+
+pub fn walk_loop_left<'a, V: Visitor<'a> + ?Sized>(v: &mut V, left: &'a LoopLeft<'a>) {
+    match left {
+        LoopLeft::Expr(expr) => v.visit_expr(expr),
+        LoopLeft::Variable(_, decl) => v.visit_var_decl(decl),
+        LoopLeft::Pat(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a Expr<'a>) {
+    match expr {
+        Expr::Array(elements) => {
+            for el in elements.iter().flatten() {
+                v.visit_expr(el);
+            }
+        }
+        Expr::ArrowFunc(arrow) => v.visit_arrow_func_expr(arrow),
+        Expr::ArrowParamPlaceHolder(args, _) => {
+            for arg in args {
+                v.visit_func_arg(arg);
+            }
+        }
+        Expr::Assign(assign) => v.visit_assign_expr(assign),
+        Expr::Await(expr) | Expr::OptionalChain(expr) | Expr::Spread(expr) => v.visit_expr(expr),
+        #[cfg(feature = "preserve-parens")]
+        Expr::Paren(expr) => v.visit_expr(expr),
+        Expr::Binary(bin) => v.visit_binary_expr(bin),
+        Expr::Class(class) => v.visit_class(class),
+        Expr::Call(call) => v.visit_call_expr(call),
+        Expr::Conditional(cond) => v.visit_conditional_expr(cond),
+        Expr::Func(func) => v.visit_func(func),
+        Expr::Ident(ident) => v.visit_ident(ident),
+        Expr::Import(import) => v.visit_import_expr(import),
+        Expr::Lit(lit) => v.visit_lit(lit),
+        Expr::Logical(logical) => v.visit_logical_expr(logical),
+        Expr::Member(member) => v.visit_member_expr(member),
+        Expr::MetaProp(meta) => v.visit_meta_prop(meta),
+        Expr::PrivateId(id) => v.visit_private_id(id),
+        Expr::New(new) => v.visit_new_expr(new),
+        Expr::Obj(props) => {
+            for prop in props {
+                v.visit_obj_prop(prop);
+            }
+        }
+        Expr::Sequence(exprs) => {
+            for expr in exprs {
+                v.visit_expr(expr);
+            }
+        }
+        Expr::Super | Expr::This => {}
+        Expr::TaggedTemplate(tagged) => v.visit_tagged_template_expr(tagged),
+        Expr::Unary(unary) => v.visit_unary_expr(unary),
+        Expr::Update(update) => v.visit_update_expr(update),
+        Expr::Yield(y) => v.visit_yield_expr(y),
+    }
+}
+
+pub fn walk_obj_prop<'a, V: Visitor<'a> + ?Sized>(v: &mut V, prop: &'a ObjProp<'a>) {
+    match prop {
+        ObjProp::Prop(prop) => v.visit_prop(prop),
+        ObjProp::Spread(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_prop<'a, V: Visitor<'a> + ?Sized>(v: &mut V, prop: &'a Prop<'a>) {
+    for decorator in &prop.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&prop.key);
+    v.visit_prop_value(&prop.value);
+}
+
+pub fn walk_prop_key<'a, V: Visitor<'a> + ?Sized>(v: &mut V, key: &'a PropKey<'a>) {
+    match key {
+        PropKey::Lit(lit) => v.visit_lit(lit),
+        PropKey::Expr(expr) => v.visit_expr(expr),
+        PropKey::Pat(pat) => v.visit_pat(pat),
+        PropKey::Private(id) => v.visit_private_id(id),
+    }
+}
+
+pub fn walk_prop_value<'a, V: Visitor<'a> + ?Sized>(v: &mut V, value: &'a PropValue<'a>) {
+    match value {
+        PropValue::Expr(expr) => v.visit_expr(expr),
+        PropValue::Pat(pat) => v.visit_pat(pat),
+        PropValue::None => {}
+    }
+}
+
+pub fn walk_unary_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a UnaryExpr<'a>) {
+    v.visit_expr(&expr.argument);
+}
+
+pub fn walk_update_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a UpdateExpr<'a>) {
+    v.visit_expr(&expr.argument);
+}
+
+pub fn walk_binary_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a BinaryExpr<'a>) {
+    v.visit_expr(&expr.left);
+    v.visit_expr(&expr.right);
+}
+
+pub fn walk_assign_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a AssignExpr<'a>) {
+    v.visit_assign_left(&expr.left);
+    v.visit_expr(&expr.right);
+}
+
+pub fn walk_assign_left<'a, V: Visitor<'a> + ?Sized>(v: &mut V, left: &'a AssignLeft<'a>) {
+    match left {
+        AssignLeft::Pat(pat) => v.visit_pat(pat),
+        AssignLeft::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_logical_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a LogicalExpr<'a>) {
+    v.visit_expr(&expr.left);
+    v.visit_expr(&expr.right);
+}
+
+pub fn walk_member_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a MemberExpr<'a>) {
+    v.visit_expr(&expr.object);
+    v.visit_member_prop(&expr.property);
+}
+
+pub fn walk_member_prop<'a, V: Visitor<'a> + ?Sized>(v: &mut V, prop: &'a MemberProp<'a>) {
+    match prop {
+        MemberProp::Ident(ident) => v.visit_ident(ident),
+        MemberProp::Private(id) => v.visit_private_id(id),
+        MemberProp::Computed(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_conditional_expr<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    expr: &'a ConditionalExpr<'a>,
+) {
+    v.visit_expr(&expr.test);
+    v.visit_expr(&expr.consequent);
+    v.visit_expr(&expr.alternate);
+}
+
+pub fn walk_call_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a CallExpr<'a>) {
+    v.visit_expr(&expr.callee);
+    for arg in &expr.arguments {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_import_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a ImportExpr<'a>) {
+    v.visit_expr(&expr.source);
+    if let Some(options) = &expr.options {
+        v.visit_expr(options);
+    }
+}
+
+pub fn walk_new_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a NewExpr<'a>) {
+    v.visit_expr(&expr.callee);
+    for arg in expr.args() {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_arrow_func_expr<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    expr: &'a ArrowFuncExpr<'a>,
+) {
+    if let Some(id) = &expr.id {
+        v.visit_ident(id);
+    }
+    for arg in &expr.params {
+        v.visit_func_arg(arg);
+    }
+    match &expr.body {
+        ArrowFuncBody::FuncBody(body) => v.visit_func_body(body),
+        ArrowFuncBody::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_yield_expr<'a, V: Visitor<'a> + ?Sized>(v: &mut V, expr: &'a YieldExpr<'a>) {
+    if let Some(arg) = &expr.argument {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_tagged_template_expr<'a, V: Visitor<'a> + ?Sized>(
+    v: &mut V,
+    expr: &'a TaggedTemplateExpr<'a>,
+) {
+    v.visit_expr(&expr.tag);
+    v.visit_template_lit(&expr.quasi);
+}
+
+pub fn walk_template_lit<'a, V: Visitor<'a> + ?Sized>(v: &mut V, lit: &'a TemplateLit<'a>) {
+    // the quasis and expressions alternate, starting and
+    // ending with a quasi
+    let mut exprs = lit.expressions.iter();
+    for quasi in &lit.quasis {
+        v.visit_template_element(quasi);
+        if let Some(expr) = exprs.next() {
+            v.visit_expr(expr);
+        }
+    }
+    for expr in exprs {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn walk_meta_prop<'a, V: Visitor<'a> + ?Sized>(v: &mut V, meta: &'a MetaProp<'a>) {
+    v.visit_ident(&meta.meta);
+    v.visit_ident(&meta.property);
+}
+
+pub fn walk_lit<'a, V: Visitor<'a> + ?Sized>(v: &mut V, lit: &'a Lit<'a>) {
+    if let Lit::Template(template) = lit {
+        v.visit_template_lit(template);
+    }
+}
+
+pub fn walk_pat<'a, V: Visitor<'a> + ?Sized>(v: &mut V, pat: &'a Pat<'a>) {
+    match pat {
+        Pat::Ident(ident) => v.visit_ident(ident),
+        Pat::Obj(parts) => {
+            for part in parts {
+                v.visit_obj_pat_part(part);
+            }
+        }
+        Pat::Array(parts) => {
+            for part in parts.iter().flatten() {
+                v.visit_array_pat_part(part);
+            }
+        }
+        Pat::RestElement(pat) => v.visit_pat(pat),
+        Pat::Assign(assign) => v.visit_assign_pat(assign),
+    }
+}
+
+pub fn walk_array_pat_part<'a, V: Visitor<'a> + ?Sized>(v: &mut V, part: &'a ArrayPatPart<'a>) {
+    match part {
+        ArrayPatPart::Pat(pat) => v.visit_pat(pat),
+        ArrayPatPart::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_obj_pat_part<'a, V: Visitor<'a> + ?Sized>(v: &mut V, part: &'a ObjPatPart<'a>) {
+    match part {
+        ObjPatPart::Assign(prop) => v.visit_prop(prop),
+        ObjPatPart::Rest(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_assign_pat<'a, V: Visitor<'a> + ?Sized>(v: &mut V, pat: &'a AssignPat<'a>) {
+    v.visit_pat(&pat.left);
+    v.visit_expr(&pat.right);
+}
+
+/// Walk every node reachable from `root` in source order, calling `f`
+/// with each node and its ancestors from the root down to its parent.
+///
+/// This is built on `NodeRef::children` so only the `NodeRef` kinds
+/// show up in the path, a `Visitor` is still needed for the nodes in
+/// between
+/// ```
+/// use resast::prelude::*;
+/// use resast::node::NodeRef;
+/// use resast::visit::walk_with_path;
+///
+/// // a;
+/// let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::ident_from("a")))]);
+/// let mut depths = Vec::new();
+/// walk_with_path(&program, |node, path| {
+///     if let NodeRef::Ident(ident) = node {
+///         depths.push((ident.name.to_string(), path.len()));
+///     }
+/// });
+/// // Program > Stmt > Expr > Ident
+/// assert_eq!(depths, vec![("a".to_string(), 3)]);
+/// ```
+pub fn walk_with_path<'r, 'a, F>(root: impl Into<NodeRef<'r, 'a>>, mut f: F)
+where
+    'a: 'r,
+    F: FnMut(NodeRef<'r, 'a>, &[NodeRef<'r, 'a>]),
+{
+    fn walk<'r, 'a, F>(node: NodeRef<'r, 'a>, path: &mut Vec<NodeRef<'r, 'a>>, f: &mut F)
+    where
+        'a: 'r,
+        F: FnMut(NodeRef<'r, 'a>, &[NodeRef<'r, 'a>]),
+    {
+        f(node, path);
+        path.push(node);
+        for child in node.children() {
+            walk(child, path, f);
+        }
+        path.pop();
+    }
+    walk(root.into(), &mut Vec::new(), &mut f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct IdentCounter<'a> {
+        names: Vec<&'a str>,
+    }
+
+    impl<'a> Visitor<'a> for IdentCounter<'a> {
+        fn visit_ident(&mut self, ident: &'a Ident<'a>) {
+            self.names.push(&ident.name);
+        }
+    }
+
+    #[derive(Default)]
+    struct CallCollector<'a> {
+        callees: Vec<&'a Expr<'a>>,
+    }
+
+    impl<'a> Visitor<'a> for CallCollector<'a> {
+        fn visit_call_expr(&mut self, call: &'a CallExpr<'a>) {
+            self.callees.push(&call.callee);
+            walk_call_expr(self, call);
+        }
+    }
+
+    fn call<'a>(callee: Expr<'a>, arguments: Vec<Expr<'a>>) -> Expr<'a> {
+        Expr::Call(CallExpr {
+            callee: Box::new(callee),
+            arguments,
+            optional: false,
+        })
+    }
+
+    // function f(a) {
+    //     let b = a;
+    //     return g(b, h(a));
+    // }
+    fn sample() -> Program<'static> {
+        Program::script(vec![ProgramPart::Decl(Decl::Func(Func::new(
+            Some(Ident::from("f")),
+            vec![FuncArg::Pat(Pat::ident_from("a"))],
+            FuncBody(vec![
+                ProgramPart::Decl(Decl::Var(
+                    VarKind::Let,
+                    vec![VarDecl {
+                        id: Pat::ident_from("b"),
+                        init: Some(Expr::ident_from("a")),
+                    }],
+                )),
+                ProgramPart::Stmt(Stmt::Return(Some(call(
+                    Expr::ident_from("g"),
+                    vec![
+                        Expr::ident_from("b"),
+                        call(Expr::ident_from("h"), vec![Expr::ident_from("a")]),
+                    ],
+                )))),
+            ]),
+            false,
+            false,
+        )))])
+    }
+
+    #[test]
+    fn count_idents() {
+        let program = sample();
+        let mut counter = IdentCounter::default();
+        counter.visit_program(&program);
+        assert_eq!(counter.names, vec!["f", "a", "b", "a", "g", "b", "h", "a"]);
+    }
+
+    #[test]
+    fn collect_calls() {
+        let program = sample();
+        let mut collector = CallCollector::default();
+        collector.visit_program(&program);
+        assert_eq!(
+            collector.callees,
+            vec![&Expr::ident_from("g"), &Expr::ident_from("h")]
+        );
+    }
+
+    #[test]
+    fn deepest_ident_path() {
+        let program = sample();
+        let mut deepest: Option<(&Ident, Vec<NodeRef>)> = None;
+        walk_with_path(&program, |node, path| {
+            if let NodeRef::Ident(ident) = node {
+                if deepest.as_ref().map(|(_, p)| path.len() >= p.len()).unwrap_or(true) {
+                    deepest = Some((ident, path.to_vec()));
+                }
+            }
+        });
+        // `h` is just as deep but comes first
+        let (ident, path) = deepest.unwrap();
+        assert_eq!(ident.name, "a");
+        let kinds: Vec<_> = path
+            .iter()
+            .map(|node| match node {
+                NodeRef::Program(_) => "Program",
+                NodeRef::Decl(_) => "Decl",
+                NodeRef::Func(_) => "Func",
+                NodeRef::Stmt(Stmt::Return(_)) => "Return",
+                NodeRef::Expr(Expr::Call(call)) => match &*call.callee {
+                    Expr::Ident(callee) => &callee.name,
+                    _ => "Call",
+                },
+                NodeRef::Expr(Expr::Ident(_)) => "Ident",
+                _ => "Other",
+            })
+            .collect();
+        assert_eq!(kinds, vec!["Program", "Decl", "Func", "Return", "g", "h", "Ident"]);
+    }
+}
@@ -0,0 +1,889 @@
+//! In place modification of the AST
+//!
+//! This mirrors the `visit` module but every method is handed a
+//! mutable reference to its node, this means any node can be
+//! replaced wholesale with a new one (e.g. `*expr = Expr::This`).
+//! Every method defaults to calling the matching `walk_mut_*`
+//! function which visits that node's children.
+//!
+//! ```
+//! use resast::prelude::*;
+//! use resast::visit_mut::VisitorMut;
+//!
+//! struct ThisToSelf;
+//!
+//! impl<'a> VisitorMut<'a> for ThisToSelf {
+//!     fn visit_expr(&mut self, expr: &mut Expr<'a>) {
+//!         if let Expr::This = expr {
+//!             *expr = Expr::ident_from("self");
+//!         } else {
+//!             resast::visit_mut::walk_mut_expr(self, expr);
+//!         }
+//!     }
+//! }
+//! ```
+use crate::decl::*;
+use crate::expr::*;
+use crate::pat::*;
+use crate::stmt::*;
+use crate::*;
+
+/// A visitor that can modify every node in the AST in place
+pub trait VisitorMut<'a> {
+    fn visit_program(&mut self, program: &mut Program<'a>) {
+        walk_mut_program(self, program)
+    }
+    fn visit_program_part(&mut self, part: &mut ProgramPart<'a>) {
+        walk_mut_program_part(self, part)
+    }
+    fn visit_dir(&mut self, dir: &mut Dir<'a>) {
+        walk_mut_dir(self, dir)
+    }
+    fn visit_ident(&mut self, _ident: &mut Ident<'a>) {}
+    fn visit_private_id(&mut self, _id: &mut PrivateId<'a>) {}
+    fn visit_func(&mut self, func: &mut Func<'a>) {
+        walk_mut_func(self, func)
+    }
+    fn visit_func_arg(&mut self, arg: &mut FuncArg<'a>) {
+        walk_mut_func_arg(self, arg)
+    }
+    fn visit_func_body(&mut self, body: &mut FuncBody<'a>) {
+        walk_mut_func_body(self, body)
+    }
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        walk_mut_class(self, class)
+    }
+    fn visit_class_member(&mut self, member: &mut ClassMember<'a>) {
+        walk_mut_class_member(self, member)
+    }
+    fn visit_method_def(&mut self, method: &mut MethodDef<'a>) {
+        walk_mut_method_def(self, method)
+    }
+    fn visit_field_def(&mut self, field: &mut FieldDef<'a>) {
+        walk_mut_field_def(self, field)
+    }
+    fn visit_decorator(&mut self, decorator: &mut Decorator<'a>) {
+        walk_mut_decorator(self, decorator)
+    }
+    fn visit_decl(&mut self, decl: &mut Decl<'a>) {
+        walk_mut_decl(self, decl)
+    }
+    fn visit_var_decl(&mut self, decl: &mut VarDecl<'a>) {
+        walk_mut_var_decl(self, decl)
+    }
+    fn visit_mod_import(&mut self, import: &mut ModImport<'a>) {
+        walk_mut_mod_import(self, import)
+    }
+    fn visit_import_specifier(&mut self, spec: &mut ImportSpecifier<'a>) {
+        walk_mut_import_specifier(self, spec)
+    }
+    fn visit_import_attribute(&mut self, attr: &mut ImportAttribute<'a>) {
+        walk_mut_import_attribute(self, attr)
+    }
+    fn visit_module_export_name(&mut self, name: &mut ModuleExportName<'a>) {
+        walk_mut_module_export_name(self, name)
+    }
+    fn visit_mod_export(&mut self, export: &mut ModExport<'a>) {
+        walk_mut_mod_export(self, export)
+    }
+    fn visit_export_source(&mut self, source: &mut ExportSource<'a>) {
+        walk_mut_export_source(self, source)
+    }
+    fn visit_export_specifier(&mut self, spec: &mut ExportSpecifier<'a>) {
+        walk_mut_export_specifier(self, spec)
+    }
+    fn visit_stmt(&mut self, stmt: &mut Stmt<'a>) {
+        walk_mut_stmt(self, stmt)
+    }
+    fn visit_block_stmt(&mut self, block: &mut BlockStmt<'a>) {
+        walk_mut_block_stmt(self, block)
+    }
+    fn visit_with_stmt(&mut self, stmt: &mut WithStmt<'a>) {
+        walk_mut_with_stmt(self, stmt)
+    }
+    fn visit_labeled_stmt(&mut self, stmt: &mut LabeledStmt<'a>) {
+        walk_mut_labeled_stmt(self, stmt)
+    }
+    fn visit_if_stmt(&mut self, stmt: &mut IfStmt<'a>) {
+        walk_mut_if_stmt(self, stmt)
+    }
+    fn visit_switch_stmt(&mut self, stmt: &mut SwitchStmt<'a>) {
+        walk_mut_switch_stmt(self, stmt)
+    }
+    fn visit_switch_case(&mut self, case: &mut SwitchCase<'a>) {
+        walk_mut_switch_case(self, case)
+    }
+    fn visit_try_stmt(&mut self, stmt: &mut TryStmt<'a>) {
+        walk_mut_try_stmt(self, stmt)
+    }
+    fn visit_catch_clause(&mut self, clause: &mut CatchClause<'a>) {
+        walk_mut_catch_clause(self, clause)
+    }
+    fn visit_while_stmt(&mut self, stmt: &mut WhileStmt<'a>) {
+        walk_mut_while_stmt(self, stmt)
+    }
+    fn visit_do_while_stmt(&mut self, stmt: &mut DoWhileStmt<'a>) {
+        walk_mut_do_while_stmt(self, stmt)
+    }
+    fn visit_for_stmt(&mut self, stmt: &mut ForStmt<'a>) {
+        walk_mut_for_stmt(self, stmt)
+    }
+    fn visit_loop_init(&mut self, init: &mut LoopInit<'a>) {
+        walk_mut_loop_init(self, init)
+    }
+    fn visit_for_in_stmt(&mut self, stmt: &mut ForInStmt<'a>) {
+        walk_mut_for_in_stmt(self, stmt)
+    }
+    fn visit_for_of_stmt(&mut self, stmt: &mut ForOfStmt<'a>) {
+        walk_mut_for_of_stmt(self, stmt)
+    }
+    fn visit_loop_left(&mut self, left: &mut LoopLeft<'a>) {
+        walk_mut_loop_left(self, left)
+    }
+    fn visit_expr(&mut self, expr: &mut Expr<'a>) {
+        walk_mut_expr(self, expr)
+    }
+    fn visit_obj_prop(&mut self, prop: &mut ObjProp<'a>) {
+        walk_mut_obj_prop(self, prop)
+    }
+    fn visit_prop(&mut self, prop: &mut Prop<'a>) {
+        walk_mut_prop(self, prop)
+    }
+    fn visit_prop_key(&mut self, key: &mut PropKey<'a>) {
+        walk_mut_prop_key(self, key)
+    }
+    fn visit_prop_value(&mut self, value: &mut PropValue<'a>) {
+        walk_mut_prop_value(self, value)
+    }
+    fn visit_unary_expr(&mut self, expr: &mut UnaryExpr<'a>) {
+        walk_mut_unary_expr(self, expr)
+    }
+    fn visit_update_expr(&mut self, expr: &mut UpdateExpr<'a>) {
+        walk_mut_update_expr(self, expr)
+    }
+    fn visit_binary_expr(&mut self, expr: &mut BinaryExpr<'a>) {
+        walk_mut_binary_expr(self, expr)
+    }
+    fn visit_assign_expr(&mut self, expr: &mut AssignExpr<'a>) {
+        walk_mut_assign_expr(self, expr)
+    }
+    fn visit_assign_left(&mut self, left: &mut AssignLeft<'a>) {
+        walk_mut_assign_left(self, left)
+    }
+    fn visit_logical_expr(&mut self, expr: &mut LogicalExpr<'a>) {
+        walk_mut_logical_expr(self, expr)
+    }
+    fn visit_member_expr(&mut self, expr: &mut MemberExpr<'a>) {
+        walk_mut_member_expr(self, expr)
+    }
+    fn visit_member_prop(&mut self, prop: &mut MemberProp<'a>) {
+        walk_mut_member_prop(self, prop)
+    }
+    fn visit_conditional_expr(&mut self, expr: &mut ConditionalExpr<'a>) {
+        walk_mut_conditional_expr(self, expr)
+    }
+    fn visit_call_expr(&mut self, expr: &mut CallExpr<'a>) {
+        walk_mut_call_expr(self, expr)
+    }
+    fn visit_import_expr(&mut self, expr: &mut ImportExpr<'a>) {
+        walk_mut_import_expr(self, expr)
+    }
+    fn visit_new_expr(&mut self, expr: &mut NewExpr<'a>) {
+        walk_mut_new_expr(self, expr)
+    }
+    fn visit_arrow_func_expr(&mut self, expr: &mut ArrowFuncExpr<'a>) {
+        walk_mut_arrow_func_expr(self, expr)
+    }
+    fn visit_yield_expr(&mut self, expr: &mut YieldExpr<'a>) {
+        walk_mut_yield_expr(self, expr)
+    }
+    fn visit_tagged_template_expr(&mut self, expr: &mut TaggedTemplateExpr<'a>) {
+        walk_mut_tagged_template_expr(self, expr)
+    }
+    fn visit_template_lit(&mut self, lit: &mut TemplateLit<'a>) {
+        walk_mut_template_lit(self, lit)
+    }
+    fn visit_template_element(&mut self, _element: &mut TemplateElement<'a>) {}
+    fn visit_meta_prop(&mut self, meta: &mut MetaProp<'a>) {
+        walk_mut_meta_prop(self, meta)
+    }
+    fn visit_lit(&mut self, lit: &mut Lit<'a>) {
+        walk_mut_lit(self, lit)
+    }
+    fn visit_pat(&mut self, pat: &mut Pat<'a>) {
+        walk_mut_pat(self, pat)
+    }
+    fn visit_array_pat_part(&mut self, part: &mut ArrayPatPart<'a>) {
+        walk_mut_array_pat_part(self, part)
+    }
+    fn visit_obj_pat_part(&mut self, part: &mut ObjPatPart<'a>) {
+        walk_mut_obj_pat_part(self, part)
+    }
+    fn visit_assign_pat(&mut self, pat: &mut AssignPat<'a>) {
+        walk_mut_assign_pat(self, pat)
+    }
+}
+
+pub fn walk_mut_program<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, program: &mut Program<'a>) {
+    match program {
+        Program::Mod(parts, _) | Program::Script(parts, _) => {
+            for part in parts {
+                v.visit_program_part(part);
+            }
+        }
+    }
+}
+
+pub fn walk_mut_program_part<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, part: &mut ProgramPart<'a>) {
+    match part {
+        ProgramPart::Dir(dir) => v.visit_dir(dir),
+        ProgramPart::Decl(decl) => v.visit_decl(decl),
+        ProgramPart::Stmt(stmt) => v.visit_stmt(stmt),
+    }
+}
+
+pub fn walk_mut_dir<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, dir: &mut Dir<'a>) {
+    v.visit_lit(&mut dir.expr);
+}
+
+pub fn walk_mut_func<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, func: &mut Func<'a>) {
+    if let Some(id) = &mut func.id {
+        v.visit_ident(id);
+    }
+    for arg in &mut func.params {
+        v.visit_func_arg(arg);
+    }
+    v.visit_func_body(&mut func.body);
+}
+
+pub fn walk_mut_func_arg<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, arg: &mut FuncArg<'a>) {
+    match arg {
+        FuncArg::Expr(expr) => v.visit_expr(expr),
+        FuncArg::Pat(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_mut_func_body<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, body: &mut FuncBody<'a>) {
+    for part in &mut body.0 {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_mut_class<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, class: &mut Class<'a>) {
+    for decorator in &mut class.decorators {
+        v.visit_decorator(decorator);
+    }
+    if let Some(id) = &mut class.id {
+        v.visit_ident(id);
+    }
+    if let Some(super_class) = &mut class.super_class {
+        v.visit_expr(super_class);
+    }
+    for member in &mut class.body.0 {
+        v.visit_class_member(member);
+    }
+}
+
+pub fn walk_mut_class_member<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, member: &mut ClassMember<'a>) {
+    match member {
+        ClassMember::Method(method) => v.visit_method_def(method),
+        ClassMember::Field(field) => v.visit_field_def(field),
+        ClassMember::StaticBlock(block) => v.visit_block_stmt(block),
+    }
+}
+
+pub fn walk_mut_method_def<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, method: &mut MethodDef<'a>) {
+    for decorator in &mut method.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&mut method.key);
+    v.visit_func(&mut method.value);
+}
+
+pub fn walk_mut_field_def<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, field: &mut FieldDef<'a>) {
+    for decorator in &mut field.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&mut field.key);
+    if let Some(value) = &mut field.value {
+        v.visit_expr(value);
+    }
+}
+
+pub fn walk_mut_decorator<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, decorator: &mut Decorator<'a>) {
+    v.visit_expr(&mut decorator.expression);
+}
+
+pub fn walk_mut_decl<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, decl: &mut Decl<'a>) {
+    match decl {
+        Decl::Var(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+        Decl::Func(func) => v.visit_func(func),
+        Decl::Class(class) => v.visit_class(class),
+        Decl::Import(import) => v.visit_mod_import(import),
+        Decl::Export(export) => v.visit_mod_export(export),
+    }
+}
+
+pub fn walk_mut_var_decl<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, decl: &mut VarDecl<'a>) {
+    v.visit_pat(&mut decl.id);
+    if let Some(init) = &mut decl.init {
+        v.visit_expr(init);
+    }
+}
+
+pub fn walk_mut_mod_import<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, import: &mut ModImport<'a>) {
+    for spec in &mut import.specifiers {
+        v.visit_import_specifier(spec);
+    }
+    v.visit_lit(&mut import.source);
+    for attr in &mut import.attributes {
+        v.visit_import_attribute(attr);
+    }
+}
+
+pub fn walk_mut_import_specifier<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    spec: &mut ImportSpecifier<'a>,
+) {
+    match spec {
+        ImportSpecifier::Normal(normal) => {
+            v.visit_module_export_name(&mut normal.imported);
+            v.visit_ident(&mut normal.local);
+        }
+        ImportSpecifier::Default(ident) | ImportSpecifier::Namespace(ident) => {
+            v.visit_ident(ident)
+        }
+    }
+}
+
+pub fn walk_mut_import_attribute<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    attr: &mut ImportAttribute<'a>,
+) {
+    match &mut attr.key {
+        ImportAttributeKey::Ident(ident) => v.visit_ident(ident),
+        ImportAttributeKey::Lit(lit) => v.visit_lit(lit),
+    }
+    v.visit_lit(&mut attr.value);
+}
+
+pub fn walk_mut_module_export_name<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    name: &mut ModuleExportName<'a>,
+) {
+    match name {
+        ModuleExportName::Ident(ident) => v.visit_ident(ident),
+        ModuleExportName::Str(lit) => v.visit_lit(lit),
+    }
+}
+
+pub fn walk_mut_mod_export<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, export: &mut ModExport<'a>) {
+    match export {
+        ModExport::Default(DefaultExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Default(DefaultExportDecl::Expr(expr)) => v.visit_expr(expr),
+        ModExport::Named(NamedExportDecl::Decl(decl)) => v.visit_decl(decl),
+        ModExport::Named(NamedExportDecl::Specifier(specs, source)) => {
+            for spec in specs {
+                v.visit_export_specifier(spec);
+            }
+            if let Some(source) = source {
+                v.visit_export_source(source);
+            }
+        }
+        ModExport::All(alias, source) => {
+            if let Some(alias) = alias {
+                v.visit_module_export_name(alias);
+            }
+            v.visit_export_source(source);
+        }
+    }
+}
+
+pub fn walk_mut_export_source<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, source: &mut ExportSource<'a>) {
+    v.visit_lit(&mut source.source);
+    for attr in &mut source.attributes {
+        v.visit_import_attribute(attr);
+    }
+}
+
+pub fn walk_mut_export_specifier<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    spec: &mut ExportSpecifier<'a>,
+) {
+    v.visit_ident(&mut spec.local);
+    v.visit_module_export_name(&mut spec.exported);
+}
+
+pub fn walk_mut_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut Stmt<'a>) {
+    match stmt {
+        Stmt::Expr(expr) | Stmt::Throw(expr) => v.visit_expr(expr),
+        Stmt::Block(block) => v.visit_block_stmt(block),
+        Stmt::Empty | Stmt::Debugger => {}
+        Stmt::With(with) => v.visit_with_stmt(with),
+        Stmt::Return(arg) => {
+            if let Some(arg) = arg {
+                v.visit_expr(arg);
+            }
+        }
+        Stmt::Labeled(labeled) => v.visit_labeled_stmt(labeled),
+        Stmt::Break(label) | Stmt::Continue(label) => {
+            if let Some(label) = label {
+                v.visit_ident(label);
+            }
+        }
+        Stmt::If(stmt) => v.visit_if_stmt(stmt),
+        Stmt::Switch(stmt) => v.visit_switch_stmt(stmt),
+        Stmt::Try(stmt) => v.visit_try_stmt(stmt),
+        Stmt::While(stmt) => v.visit_while_stmt(stmt),
+        Stmt::DoWhile(stmt) => v.visit_do_while_stmt(stmt),
+        Stmt::For(stmt) => v.visit_for_stmt(stmt),
+        Stmt::ForIn(stmt) => v.visit_for_in_stmt(stmt),
+        Stmt::ForOf(stmt) => v.visit_for_of_stmt(stmt),
+        Stmt::Var(decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+    }
+}
+
+pub fn walk_mut_block_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, block: &mut BlockStmt<'a>) {
+    for part in &mut block.0 {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_mut_with_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut WithStmt<'a>) {
+    v.visit_expr(&mut stmt.object);
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_labeled_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut LabeledStmt<'a>) {
+    v.visit_ident(&mut stmt.label);
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_if_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut IfStmt<'a>) {
+    v.visit_expr(&mut stmt.test);
+    v.visit_stmt(&mut stmt.consequent);
+    if let Some(alt) = &mut stmt.alternate {
+        v.visit_stmt(alt);
+    }
+}
+
+pub fn walk_mut_switch_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut SwitchStmt<'a>) {
+    v.visit_expr(&mut stmt.discriminant);
+    for case in &mut stmt.cases {
+        v.visit_switch_case(case);
+    }
+}
+
+pub fn walk_mut_switch_case<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, case: &mut SwitchCase<'a>) {
+    if let Some(test) = &mut case.test {
+        v.visit_expr(test);
+    }
+    for part in &mut case.consequent {
+        v.visit_program_part(part);
+    }
+}
+
+pub fn walk_mut_try_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut TryStmt<'a>) {
+    v.visit_block_stmt(&mut stmt.block);
+    if let Some(handler) = &mut stmt.handler {
+        v.visit_catch_clause(handler);
+    }
+    if let Some(finalizer) = &mut stmt.finalizer {
+        v.visit_block_stmt(finalizer);
+    }
+}
+
+pub fn walk_mut_catch_clause<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, clause: &mut CatchClause<'a>) {
+    if let Some(param) = &mut clause.param {
+        v.visit_pat(param);
+    }
+    v.visit_block_stmt(&mut clause.body);
+}
+
+pub fn walk_mut_while_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut WhileStmt<'a>) {
+    v.visit_expr(&mut stmt.test);
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_do_while_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut DoWhileStmt<'a>) {
+    v.visit_stmt(&mut stmt.body);
+    v.visit_expr(&mut stmt.test);
+}
+
+pub fn walk_mut_for_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut ForStmt<'a>) {
+    if let Some(init) = &mut stmt.init {
+        v.visit_loop_init(init);
+    }
+    if let Some(test) = &mut stmt.test {
+        v.visit_expr(test);
+    }
+    if let Some(update) = &mut stmt.update {
+        v.visit_expr(update);
+    }
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_loop_init<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, init: &mut LoopInit<'a>) {
+    match init {
+        LoopInit::Variable(_, decls) => {
+            for decl in decls {
+                v.visit_var_decl(decl);
+            }
+        }
+        LoopInit::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_for_in_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut ForInStmt<'a>) {
+    v.visit_loop_left(&mut stmt.left);
+    v.visit_expr(&mut stmt.right);
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_for_of_stmt<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, stmt: &mut ForOfStmt<'a>) {
+    v.visit_loop_left(&mut stmt.left);
+    v.visit_expr(&mut stmt.right);
+    v.visit_stmt(&mut stmt.body);
+}
+
+pub fn walk_mut_loop_left<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, left: &mut LoopLeft<'a>) {
+    match left {
+        LoopLeft::Expr(expr) => v.visit_expr(expr),
+        LoopLeft::Variable(_, decl) => v.visit_var_decl(decl),
+        LoopLeft::Pat(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_mut_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut Expr<'a>) {
+    match expr {
+        Expr::Array(elements) => {
+            for el in elements.iter_mut().flatten() {
+                v.visit_expr(el);
+            }
+        }
+        Expr::ArrowFunc(arrow) => v.visit_arrow_func_expr(arrow),
+        Expr::ArrowParamPlaceHolder(args, _) => {
+            for arg in args {
+                v.visit_func_arg(arg);
+            }
+        }
+        Expr::Assign(assign) => v.visit_assign_expr(assign),
+        Expr::Await(expr) | Expr::OptionalChain(expr) | Expr::Spread(expr) => v.visit_expr(expr),
+        #[cfg(feature = "preserve-parens")]
+        Expr::Paren(expr) => v.visit_expr(expr),
+        Expr::Binary(bin) => v.visit_binary_expr(bin),
+        Expr::Class(class) => v.visit_class(class),
+        Expr::Call(call) => v.visit_call_expr(call),
+        Expr::Conditional(cond) => v.visit_conditional_expr(cond),
+        Expr::Func(func) => v.visit_func(func),
+        Expr::Ident(ident) => v.visit_ident(ident),
+        Expr::Import(import) => v.visit_import_expr(import),
+        Expr::Lit(lit) => v.visit_lit(lit),
+        Expr::Logical(logical) => v.visit_logical_expr(logical),
+        Expr::Member(member) => v.visit_member_expr(member),
+        Expr::MetaProp(meta) => v.visit_meta_prop(meta),
+        Expr::PrivateId(id) => v.visit_private_id(id),
+        Expr::New(new) => v.visit_new_expr(new),
+        Expr::Obj(props) => {
+            for prop in props {
+                v.visit_obj_prop(prop);
+            }
+        }
+        Expr::Sequence(exprs) => {
+            for expr in exprs {
+                v.visit_expr(expr);
+            }
+        }
+        Expr::Super | Expr::This => {}
+        Expr::TaggedTemplate(tagged) => v.visit_tagged_template_expr(tagged),
+        Expr::Unary(unary) => v.visit_unary_expr(unary),
+        Expr::Update(update) => v.visit_update_expr(update),
+        Expr::Yield(y) => v.visit_yield_expr(y),
+    }
+}
+
+pub fn walk_mut_obj_prop<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, prop: &mut ObjProp<'a>) {
+    match prop {
+        ObjProp::Prop(prop) => v.visit_prop(prop),
+        ObjProp::Spread(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_prop<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, prop: &mut Prop<'a>) {
+    for decorator in &mut prop.decorators {
+        v.visit_decorator(decorator);
+    }
+    v.visit_prop_key(&mut prop.key);
+    v.visit_prop_value(&mut prop.value);
+}
+
+pub fn walk_mut_prop_key<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, key: &mut PropKey<'a>) {
+    match key {
+        PropKey::Lit(lit) => v.visit_lit(lit),
+        PropKey::Expr(expr) => v.visit_expr(expr),
+        PropKey::Pat(pat) => v.visit_pat(pat),
+        PropKey::Private(id) => v.visit_private_id(id),
+    }
+}
+
+pub fn walk_mut_prop_value<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, value: &mut PropValue<'a>) {
+    match value {
+        PropValue::Expr(expr) => v.visit_expr(expr),
+        PropValue::Pat(pat) => v.visit_pat(pat),
+        PropValue::None => {}
+    }
+}
+
+pub fn walk_mut_unary_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut UnaryExpr<'a>) {
+    v.visit_expr(&mut expr.argument);
+}
+
+pub fn walk_mut_update_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut UpdateExpr<'a>) {
+    v.visit_expr(&mut expr.argument);
+}
+
+pub fn walk_mut_binary_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut BinaryExpr<'a>) {
+    v.visit_expr(&mut expr.left);
+    v.visit_expr(&mut expr.right);
+}
+
+pub fn walk_mut_assign_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut AssignExpr<'a>) {
+    v.visit_assign_left(&mut expr.left);
+    v.visit_expr(&mut expr.right);
+}
+
+pub fn walk_mut_assign_left<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, left: &mut AssignLeft<'a>) {
+    match left {
+        AssignLeft::Pat(pat) => v.visit_pat(pat),
+        AssignLeft::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_logical_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut LogicalExpr<'a>) {
+    v.visit_expr(&mut expr.left);
+    v.visit_expr(&mut expr.right);
+}
+
+pub fn walk_mut_member_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut MemberExpr<'a>) {
+    v.visit_expr(&mut expr.object);
+    v.visit_member_prop(&mut expr.property);
+}
+
+pub fn walk_mut_member_prop<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, prop: &mut MemberProp<'a>) {
+    match prop {
+        MemberProp::Ident(ident) => v.visit_ident(ident),
+        MemberProp::Private(id) => v.visit_private_id(id),
+        MemberProp::Computed(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_conditional_expr<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    expr: &mut ConditionalExpr<'a>,
+) {
+    v.visit_expr(&mut expr.test);
+    v.visit_expr(&mut expr.consequent);
+    v.visit_expr(&mut expr.alternate);
+}
+
+pub fn walk_mut_call_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut CallExpr<'a>) {
+    v.visit_expr(&mut expr.callee);
+    for arg in &mut expr.arguments {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_mut_import_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut ImportExpr<'a>) {
+    v.visit_expr(&mut expr.source);
+    if let Some(options) = &mut expr.options {
+        v.visit_expr(options);
+    }
+}
+
+pub fn walk_mut_new_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut NewExpr<'a>) {
+    v.visit_expr(&mut expr.callee);
+    for arg in expr.arguments.iter_mut().flatten() {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_mut_arrow_func_expr<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    expr: &mut ArrowFuncExpr<'a>,
+) {
+    if let Some(id) = &mut expr.id {
+        v.visit_ident(id);
+    }
+    for arg in &mut expr.params {
+        v.visit_func_arg(arg);
+    }
+    match &mut expr.body {
+        ArrowFuncBody::FuncBody(body) => v.visit_func_body(body),
+        ArrowFuncBody::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_yield_expr<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, expr: &mut YieldExpr<'a>) {
+    if let Some(arg) = &mut expr.argument {
+        v.visit_expr(arg);
+    }
+}
+
+pub fn walk_mut_tagged_template_expr<'a, V: VisitorMut<'a> + ?Sized>(
+    v: &mut V,
+    expr: &mut TaggedTemplateExpr<'a>,
+) {
+    v.visit_expr(&mut expr.tag);
+    v.visit_template_lit(&mut expr.quasi);
+}
+
+pub fn walk_mut_template_lit<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, lit: &mut TemplateLit<'a>) {
+    // the quasis and expressions alternate, starting and
+    // ending with a quasi
+    let mut exprs = lit.expressions.iter_mut();
+    for quasi in &mut lit.quasis {
+        v.visit_template_element(quasi);
+        if let Some(expr) = exprs.next() {
+            v.visit_expr(expr);
+        }
+    }
+    for expr in exprs {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn walk_mut_meta_prop<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, meta: &mut MetaProp<'a>) {
+    v.visit_ident(&mut meta.meta);
+    v.visit_ident(&mut meta.property);
+}
+
+pub fn walk_mut_lit<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, lit: &mut Lit<'a>) {
+    if let Lit::Template(template) = lit {
+        v.visit_template_lit(template);
+    }
+}
+
+pub fn walk_mut_pat<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, pat: &mut Pat<'a>) {
+    match pat {
+        Pat::Ident(ident) => v.visit_ident(ident),
+        Pat::Obj(parts) => {
+            for part in parts {
+                v.visit_obj_pat_part(part);
+            }
+        }
+        Pat::Array(parts) => {
+            for part in parts.iter_mut().flatten() {
+                v.visit_array_pat_part(part);
+            }
+        }
+        Pat::RestElement(pat) => v.visit_pat(pat),
+        Pat::Assign(assign) => v.visit_assign_pat(assign),
+    }
+}
+
+pub fn walk_mut_array_pat_part<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, part: &mut ArrayPatPart<'a>) {
+    match part {
+        ArrayPatPart::Pat(pat) => v.visit_pat(pat),
+        ArrayPatPart::Expr(expr) => v.visit_expr(expr),
+    }
+}
+
+pub fn walk_mut_obj_pat_part<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, part: &mut ObjPatPart<'a>) {
+    match part {
+        ObjPatPart::Assign(prop) => v.visit_prop(prop),
+        ObjPatPart::Rest(pat) => v.visit_pat(pat),
+    }
+}
+
+pub fn walk_mut_assign_pat<'a, V: VisitorMut<'a> + ?Sized>(v: &mut V, pat: &mut AssignPat<'a>) {
+    v.visit_pat(&mut pat.left);
+    v.visit_expr(&mut pat.right);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::borrow::Cow;
+
+    struct Doubler;
+
+    impl<'a> VisitorMut<'a> for Doubler {
+        fn visit_lit(&mut self, lit: &mut Lit<'a>) {
+            if let Lit::Number(number) = lit {
+                let value: f64 = number.without_separators().parse().unwrap();
+                *number = NumberLit(Cow::Owned((value * 2.0).to_string()));
+            }
+            walk_mut_lit(self, lit);
+        }
+    }
+
+    struct InlineConst;
+
+    impl<'a> VisitorMut<'a> for InlineConst {
+        fn visit_expr(&mut self, expr: &mut Expr<'a>) {
+            if *expr == Expr::ident_from("TWO") {
+                *expr = Expr::Lit(Lit::number_from("2"));
+            } else {
+                walk_mut_expr(self, expr);
+            }
+        }
+    }
+
+    fn add<'a>(left: Expr<'a>, right: Expr<'a>) -> Expr<'a> {
+        Expr::Binary(BinaryExpr {
+            operator: BinaryOp::Plus,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    fn expr_stmt(expr: Expr) -> Program {
+        Program::script(vec![ProgramPart::Stmt(Stmt::Expr(expr))])
+    }
+
+    #[test]
+    fn double_numbers() {
+        // [1, 2 + 1_000]
+        let mut program = expr_stmt(Expr::Array(vec![
+            Some(Expr::Lit(Lit::number_from("1"))),
+            None,
+            Some(add(
+                Expr::Lit(Lit::number_from("2")),
+                Expr::Lit(Lit::number_from("1_000")),
+            )),
+        ]));
+        Doubler.visit_program(&mut program);
+        assert_eq!(
+            program,
+            expr_stmt(Expr::Array(vec![
+                Some(Expr::Lit(Lit::number_from("2"))),
+                None,
+                Some(add(
+                    Expr::Lit(Lit::number_from("4")),
+                    Expr::Lit(Lit::number_from("2000")),
+                )),
+            ]))
+        );
+    }
+
+    #[test]
+    fn replace_subtree() {
+        let mut program = expr_stmt(add(Expr::ident_from("TWO"), Expr::ident_from("x")));
+        InlineConst.visit_program(&mut program);
+        assert_eq!(
+            program,
+            expr_stmt(add(
+                Expr::Lit(Lit::number_from("2")),
+                Expr::ident_from("x")
+            ))
+        );
+    }
+}
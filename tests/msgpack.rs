@@ -0,0 +1,90 @@
+#![cfg(all(feature = "msgpack", not(feature = "esprima")))]
+use resast::msgpack::{from_msgpack, to_msgpack};
+use resast::prelude::*;
+use std::borrow::Cow;
+
+#[test]
+fn program_round_trip() {
+    // import { a as b } from './a.js';
+    // function f(x, ...rest) {
+    //     let y = (x, new Date), z = `t${y}`;
+    //     return /re/g.test(z) ? 1.5 : "s";
+    // }
+    let template = TemplateLit {
+        quasis: vec![
+            TemplateElement {
+                tail: false,
+                cooked: Some(Cow::Borrowed("t")),
+                raw: Cow::Borrowed("`t${"),
+            },
+            TemplateElement {
+                tail: true,
+                cooked: Some(Cow::Borrowed("")),
+                raw: Cow::Borrowed("}`"),
+            },
+        ],
+        expressions: vec![Expr::ident_from("y")],
+    };
+    let test = CallExpr::new(
+        Expr::Member(MemberExpr::new(
+            Expr::Lit(Lit::RegEx(RegEx::from("re", "g"))),
+            MemberProp::ident_from("test"),
+        )),
+        vec![Expr::ident_from("z")],
+    );
+    let func = Func {
+        id: Some(Ident::from("f")),
+        params: vec![
+            FuncArg::Pat(Pat::ident_from("x")),
+            FuncArg::Pat(Pat::RestElement(Box::new(Pat::ident_from("rest")))),
+        ],
+        body: FuncBody(vec![
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Let,
+                vec![
+                    VarDecl {
+                        id: Pat::ident_from("y"),
+                        init: Some(Expr::Sequence(SequenceExpr::new(
+                            Expr::ident_from("x"),
+                            vec![Expr::New(NewExpr::without_parens(Expr::ident_from("Date")))],
+                        ))),
+                    },
+                    VarDecl {
+                        id: Pat::ident_from("z"),
+                        init: Some(Expr::Lit(Lit::Template(template))),
+                    },
+                ],
+            )),
+            ProgramPart::Stmt(Stmt::Return(Some(Expr::Conditional(ConditionalExpr::new(
+                Expr::Call(test),
+                Expr::Lit(Lit::number_from("1.5")),
+                Expr::Lit(Lit::double_string_from("s")),
+            ))))),
+        ]),
+        generator: false,
+        is_async: false,
+    };
+    let ast = Program::module(vec![
+        ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+            specifiers: vec![ImportSpecifier::Normal(NormalImportSpec {
+                local: Ident::from("b"),
+                imported: ModuleExportName::ident_from("a"),
+            })],
+            source: Lit::single_string_from("./a.js"),
+            attributes: vec![],
+            attributes_keyword: None,
+        }))),
+        ProgramPart::Decl(Decl::Func(func)),
+    ]);
+    let bytes = to_msgpack(&ast);
+    let back = from_msgpack(&bytes).expect("Failed to decode msgpack");
+    assert_eq!(ast, back);
+    let json = serde_json::to_vec(&ast).expect("Failed to serialize ast");
+    assert!(bytes.len() < json.len());
+}
+
+#[test]
+fn invalid_bytes() {
+    assert!(from_msgpack(&[0xc1]).is_err());
+    assert!(from_msgpack(&[]).is_err());
+}
@@ -0,0 +1,57 @@
+#![cfg(all(feature = "serde", not(feature = "esprima")))]
+use resast::prelude::*;
+use serde_json::{from_str, to_string};
+
+#[test]
+fn import_and_named_export() {
+    let ast = Program::module(vec![
+        ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+            specifiers: vec![
+                ImportSpecifier::Default(Ident::from("Thing")),
+                ImportSpecifier::Normal(NormalImportSpec {
+                    local: Ident::from("p"),
+                    imported: ModuleExportName::ident_from("People"),
+                }),
+            ],
+            source: Lit::single_string_from("./stuff.js"),
+            attributes: vec![],
+            attributes_keyword: None,
+        }))),
+        ProgramPart::Decl(Decl::Export(Box::new(ModExport::Named(
+            NamedExportDecl::Specifier(
+                vec![
+                    ExportSpecifier {
+                        local: Ident::from("p"),
+                        exported: ModuleExportName::ident_from("Persons"),
+                    },
+                    ExportSpecifier {
+                        local: Ident::from("p"),
+                        exported: ModuleExportName::str_from("string name"),
+                    },
+                ],
+                None,
+            ),
+        )))),
+    ]);
+    let json = to_string(&ast).expect("Failed to serialize ast");
+    let back: Program = from_str(&json).expect("Failed to deserialize ast");
+    assert_eq!(ast, back);
+}
+
+#[test]
+fn import_attributes() {
+    for keyword in &[AttributesKeyword::With, AttributesKeyword::Assert] {
+        let ast = Program::module(vec![ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+            specifiers: vec![ImportSpecifier::Default(Ident::from("data"))],
+            source: Lit::single_string_from("./data.json"),
+            attributes: vec![ImportAttribute {
+                key: ImportAttributeKey::Ident(Ident::from("type")),
+                value: Lit::single_string_from("json"),
+            }],
+            attributes_keyword: Some(*keyword),
+        })))]);
+        let json = to_string(&ast).expect("Failed to serialize ast");
+        let back: Program = from_str(&json).expect("Failed to deserialize ast");
+        assert_eq!(ast, back);
+    }
+}
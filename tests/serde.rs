@@ -16,7 +16,7 @@ use std::{
 };
 #[test]
 fn serde1() {
-    let ast = Program::Script(vec![
+    let ast = Program::script(vec![
         ProgramPart::Decl(
             Decl::Func(
                 Func {
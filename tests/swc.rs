@@ -0,0 +1,149 @@
+#![cfg(feature = "swc-interop")]
+use resast::prelude::*;
+use resast::swc_interop::{to_swc, SwcError};
+use std::borrow::Cow;
+
+fn emit(program: &Program) -> String {
+    let program = to_swc(program).expect("Failed to convert to swc");
+    swc_ecma_codegen::to_code(&program)
+}
+
+fn ident(name: &str) -> Expr<'_> {
+    Expr::ident_from(name)
+}
+
+fn binary<'a>(left: Expr<'a>, operator: BinaryOp, right: Expr<'a>) -> Expr<'a> {
+    Expr::Binary(BinaryExpr {
+        operator,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
+
+#[test]
+fn small_module() {
+    // import { a as b } from './a.js';
+    // export function f(x, ...rest) {
+    //     const y = (b + x) * 2, z = `t${y}`;
+    //     return rest?.[0] ?? z;
+    // }
+    // ({}.toString());
+    let template = TemplateLit {
+        quasis: vec![
+            TemplateElement {
+                tail: false,
+                cooked: Some(Cow::Borrowed("t")),
+                raw: Cow::Borrowed("`t${"),
+            },
+            TemplateElement {
+                tail: true,
+                cooked: Some(Cow::Borrowed("")),
+                raw: Cow::Borrowed("}`"),
+            },
+        ],
+        expressions: vec![ident("y")],
+    };
+    let mut first = MemberExpr::new(
+        ident("rest"),
+        MemberProp::Computed(Box::new(Expr::Lit(Lit::number_from("0")))),
+    );
+    first.optional = true;
+    let func = Func {
+        id: Some(Ident::from("f")),
+        params: vec![
+            FuncArg::Pat(Pat::ident_from("x")),
+            FuncArg::Pat(Pat::RestElement(Box::new(Pat::ident_from("rest")))),
+        ],
+        body: FuncBody(vec![
+            ProgramPart::Decl(Decl::Var(
+                VarKind::Const,
+                vec![
+                    VarDecl {
+                        id: Pat::ident_from("y"),
+                        init: Some(binary(
+                            binary(ident("b"), BinaryOp::Plus, ident("x")),
+                            BinaryOp::Times,
+                            Expr::Lit(Lit::number_from("2")),
+                        )),
+                    },
+                    VarDecl {
+                        id: Pat::ident_from("z"),
+                        init: Some(Expr::Lit(Lit::Template(template))),
+                    },
+                ],
+            )),
+            ProgramPart::Stmt(Stmt::Return(Some(Expr::Logical(LogicalExpr {
+                operator: LogicalOp::NullishCoalescing,
+                left: Box::new(Expr::OptionalChain(Box::new(Expr::Member(first)))),
+                right: Box::new(ident("z")),
+            })))),
+        ]),
+        generator: false,
+        is_async: false,
+    };
+    let to_string = CallExpr::new(
+        Expr::Member(MemberExpr::new(
+            Expr::Obj(vec![]),
+            MemberProp::ident_from("toString"),
+        )),
+        vec![],
+    );
+    let program = Program::module(vec![
+        ProgramPart::Decl(Decl::Import(Box::new(ModImport {
+            specifiers: vec![ImportSpecifier::Normal(NormalImportSpec {
+                local: Ident::from("b"),
+                imported: ModuleExportName::ident_from("a"),
+            })],
+            source: Lit::single_string_from("./a.js"),
+            attributes: vec![],
+            attributes_keyword: None,
+        }))),
+        ProgramPart::Decl(Decl::Export(Box::new(ModExport::Named(
+            NamedExportDecl::Decl(Decl::Func(func)),
+        )))),
+        ProgramPart::Stmt(Stmt::Expr(Expr::Call(to_string))),
+    ]);
+    assert_eq!(
+        emit(&program),
+        "import { a as b } from './a.js';
+export function f(x, ...rest) {
+    const y = (b + x) * 2, z = `t${y}`;
+    return rest?.[0] ?? z;
+}
+({}.toString());
+"
+    );
+}
+
+#[test]
+fn unsupported_nodes() {
+    let import = ModImport {
+        specifiers: vec![ImportSpecifier::Default(Ident::from("data"))],
+        source: Lit::single_string_from("./data.json"),
+        attributes: vec![ImportAttribute {
+            key: ImportAttributeKey::Ident(Ident::from("type")),
+            value: Lit::single_string_from("json"),
+        }],
+        attributes_keyword: Some(AttributesKeyword::Assert),
+    };
+    let program = Program::module(vec![ProgramPart::Decl(Decl::Import(Box::new(import)))]);
+    assert_eq!(
+        to_swc(&program).unwrap_err(),
+        SwcError::Unsupported("import assertions")
+    );
+    let program = Program::script(vec![ProgramPart::Stmt(Stmt::Expr(Expr::Spread(
+        Box::new(ident("a")),
+    )))]);
+    assert!(matches!(to_swc(&program), Err(SwcError::Invalid(_))));
+    let program = Program::script(vec![ProgramPart::Decl(Decl::Func(Func {
+        id: None,
+        params: vec![],
+        body: FuncBody(vec![]),
+        generator: false,
+        is_async: false,
+    }))]);
+    assert_eq!(
+        to_swc(&program).unwrap_err(),
+        SwcError::Decl(DeclError::AnonymousFunc)
+    );
+}